@@ -1,14 +1,17 @@
 //! Agreement management logic for the Chioma/Rental contract.
-use soroban_sdk::{Address, Env, String, Vec};
+use soroban_sdk::{Address, BytesN, Env, Error, String, Vec};
 
-use crate::errors::RentalError;
+use crate::agreement_index;
+use crate::arrears;
+use crate::errors::{into_rental, DocumentError, RentalError, TokenError};
 use crate::events;
 use crate::rate_limit;
-use crate::storage::DataKey;
+use crate::storage::{DataKey, TokenKey};
 use crate::types::{
     AgreementExtension, AgreementStatus, ExtensionHistory, ExtensionStatus, PaymentSplit,
-    RentAgreement,
+    RentAgreement, TokenMigration, TokenUsage, UnitOfAccount,
 };
+use crate::witness;
 
 const TTL_THRESHOLD: u32 = 500000;
 const TTL_BUMP: u32 = 500000;
@@ -105,6 +108,11 @@ fn create_agreement_internal(
         next_payment_due: input.terms.start_date,
         metadata_uri: input.metadata_uri,
         attributes: input.attributes,
+        co_tenants: Vec::new(env),
+        subtenant: None,
+        document_hash: None,
+        document_version: 0,
+        witnesses: Vec::new(env),
     };
 
     // Store agreement
@@ -117,6 +125,8 @@ fn create_agreement_internal(
         TTL_BUMP,
     );
 
+    agreement_index::index_agreement(env, &agreement);
+
     // Update counter
     let mut count: u32 = env
         .storage()
@@ -145,8 +155,14 @@ fn create_agreement_internal(
     Ok(())
 }
 
-/// Sign an agreement as the tenant
-pub fn sign_agreement(env: &Env, user: Address, agreement_id: String) -> Result<(), RentalError> {
+/// Sign an agreement as the tenant. `document_hash` is the document the
+/// tenant read and must match the agreement's anchored hash.
+pub fn sign_agreement(
+    env: &Env,
+    user: Address,
+    agreement_id: String,
+    document_hash: Option<BytesN<32>>,
+) -> Result<(), RentalError> {
     // Tenant MUST authorize signing
     user.require_auth();
 
@@ -154,11 +170,8 @@ pub fn sign_agreement(env: &Env, user: Address, agreement_id: String) -> Result<
     rate_limit::check_rate_limit(env, &user, "sign_agreement")?;
 
     // Retrieve the agreement
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     // Validate caller is the intended tenant
     if agreement.user != user {
@@ -176,8 +189,11 @@ pub fn sign_agreement(env: &Env, user: Address, agreement_id: String) -> Result<
         return Err(RentalError::Expired);
     }
 
+    crate::document::attest_on_sign(env, &agreement, &document_hash)
+        .map_err(|error| into_rental(env, error))?;
+
     // Update agreement status and record signing time; awaiting witness approval
-    agreement.status = AgreementStatus::PendingApproval;
+    agreement_index::set_status(env, &mut agreement, AgreementStatus::PendingApproval);
     agreement.signed_at = Some(current_time);
 
     // Save updated agreement
@@ -214,11 +230,8 @@ pub fn approve_agreement(
 ) -> Result<(), RentalError> {
     approver.require_auth();
 
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     // Only valid from PendingApproval state
     if agreement.status != AgreementStatus::PendingApproval {
@@ -231,9 +244,20 @@ pub fn approve_agreement(
         return Err(RentalError::Expired);
     }
 
-    // Permanently record witness and activate agreement
-    agreement.witness_id = Some(approver.clone());
-    agreement.status = AgreementStatus::Active;
+    let policy = witness::get_witness_policy(env, agreement_id.clone());
+    witness::check_witness(env, &agreement, &policy, &approver)
+        .map_err(|error| into_rental(env, error))?;
+    if agreement.witnesses.contains(&approver) {
+        return Err(RentalError::AlreadyApproved);
+    }
+
+    // Permanently record the witness; the final required one activates
+    agreement.witnesses.push_back(approver.clone());
+    let activated = agreement.witnesses.len() >= policy.required_witnesses;
+    if activated {
+        agreement.witness_id = Some(approver.clone());
+        agreement_index::set_status(env, &mut agreement, AgreementStatus::Active);
+    }
 
     env.storage()
         .persistent()
@@ -245,7 +269,16 @@ pub fn approve_agreement(
     );
     env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_BUMP);
 
-    events::agreement_approved(env, agreement_id, approver);
+    events::witness_recorded(
+        env,
+        agreement_id.clone(),
+        approver.clone(),
+        agreement.witnesses.len(),
+        policy.required_witnesses,
+    );
+    if activated {
+        events::agreement_approved(env, agreement_id, approver);
+    }
 
     Ok(())
 }
@@ -258,11 +291,8 @@ pub fn submit_agreement(
 ) -> Result<(), RentalError> {
     admin.require_auth();
 
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     if agreement.admin != admin {
         return Err(RentalError::Unauthorized);
@@ -272,7 +302,8 @@ pub fn submit_agreement(
         return Err(RentalError::InvalidState);
     }
 
-    agreement.status = AgreementStatus::Pending;
+    agreement_index::set_status(env, &mut agreement, AgreementStatus::Pending);
+    crate::document::attest_on_submit(env, &mut agreement);
 
     env.storage()
         .persistent()
@@ -296,11 +327,8 @@ pub fn cancel_agreement(
 ) -> Result<(), RentalError> {
     caller.require_auth();
 
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     // Only landlord can cancel
     if agreement.admin != caller {
@@ -315,7 +343,7 @@ pub fn cancel_agreement(
         return Err(RentalError::InvalidState);
     }
 
-    agreement.status = AgreementStatus::Cancelled;
+    agreement_index::set_status(env, &mut agreement, AgreementStatus::Cancelled);
 
     env.storage()
         .persistent()
@@ -333,9 +361,7 @@ pub fn cancel_agreement(
 
 /// Retrieve a rent agreement by its unique identifier
 pub fn get_agreement(env: &Env, agreement_id: String) -> Option<RentAgreement> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id))
+    crate::storage::get_agreement(env, &agreement_id)
 }
 
 /// Check whether a rent agreement exists for the given identifier
@@ -358,30 +384,20 @@ pub fn get_payment_split(
     agreement_id: String,
     month: u32,
 ) -> Result<PaymentSplit, RentalError> {
-    env.storage()
-        .persistent()
-        .get(&DataKey::PaymentRecord(agreement_id, month))
+    crate::storage::get_payment_split(env, &agreement_id, month)
         .ok_or(RentalError::AgreementNotFound)
 }
 
 /// Get all payments for an agreement
 pub fn get_payment_history(env: &Env, agreement_id: String) -> Vec<PaymentSplit> {
     let mut history = Vec::new(env);
-    let agreement: RentAgreement = match env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-    {
+    let agreement: RentAgreement = match crate::storage::get_agreement(env, &agreement_id) {
         Some(a) => a,
         None => return history,
     };
 
     for i in 1..=agreement.payment_count {
-        if let Some(payment) = env
-            .storage()
-            .persistent()
-            .get(&DataKey::PaymentRecord(agreement_id.clone(), i))
-        {
+        if let Some(payment) = crate::storage::get_payment_split(env, &agreement_id, i) {
             history.push_back(payment);
         }
     }
@@ -395,14 +411,16 @@ pub fn update_metadata(
     metadata_uri: String,
     attributes: Vec<crate::types::Attribute>,
 ) -> Result<(), RentalError> {
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     agreement.admin.require_auth();
 
+    // Once submitted, changes go through `propose_document_update`.
+    if agreement.status != AgreementStatus::Draft {
+        env.panic_with_error(DocumentError::ConsentRequired);
+    }
+
     agreement.metadata_uri = metadata_uri;
     agreement.attributes = attributes;
 
@@ -420,10 +438,23 @@ pub fn create_agreement_with_token(
 ) -> Result<String, RentalError> {
     input.user.require_auth();
 
-    // Check if token is supported
-    if !crate::multi_token::is_token_supported(env.clone(), input.payment_token.clone())? {
-        return Err(RentalError::TokenNotSupported);
-    }
+    create_token_agreement(env, input)
+}
+
+/// Create a token-aware agreement without requiring authorization; callers
+/// must have authorized the tenant already.
+pub(crate) fn create_token_agreement(
+    env: &Env,
+    input: crate::types::AgreementInput,
+) -> Result<String, RentalError> {
+    // Check the token is supported and may be used for this agreement's flows
+    crate::multi_token::require_token_usage(
+        env,
+        &input.payment_token,
+        true,
+        input.terms.security_deposit > 0,
+    )
+    .map_err(|error| into_rental(env, error))?;
 
     let agreement_id = input.agreement_id.clone();
 
@@ -432,11 +463,7 @@ pub fn create_agreement_with_token(
     // Store the token mapping explicitly if needed, but it's already in RentAgreement
     // Wait, create_agreement_internal already set the agreement.
     // We just need the extra DataKey::AgreementToken if the frontend relies on it.
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .unwrap();
+    let agreement: RentAgreement = crate::storage::get_agreement(env, &agreement_id).unwrap();
 
     env.storage().persistent().set(
         &DataKey::AgreementToken(agreement_id.clone()),
@@ -454,47 +481,150 @@ pub fn get_agreement_token(env: &Env, agreement_id: String) -> Result<Address, R
         .ok_or(RentalError::AgreementNotFound)
 }
 
+/// Create an agreement whose rent is priced in an off-chain unit of account.
+///
+/// `terms.monthly_rent` is read as minor units of `unit`; `payment_token`
+/// remains the default settlement token.
+pub fn create_agreement_with_unit(
+    env: &Env,
+    input: crate::types::AgreementInput,
+    unit: UnitOfAccount,
+) -> Result<String, RentalError> {
+    if unit.decimals > 18 {
+        return Err(RentalError::InvalidInput);
+    }
+    let agreement_id = create_agreement_with_token(env, input)?;
+    env.storage().persistent().set(
+        &DataKey::Token(TokenKey::AgreementUnit(agreement_id.clone())),
+        &unit,
+    );
+    Ok(agreement_id)
+}
+
+/// Get the unit of account for an agreement, if it is priced off-chain.
+pub fn get_agreement_unit(env: &Env, agreement_id: String) -> Option<UnitOfAccount> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Token(TokenKey::AgreementUnit(agreement_id)))
+}
+
+/// Amount of `token` currently needed to pay one month of rent.
+pub fn quote_rent_payment(env: &Env, agreement_id: String, token: Address) -> Result<i128, Error> {
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
+
+    match get_agreement_unit(env, agreement_id) {
+        Some(unit) => {
+            crate::multi_token::quote_unit_amount(env, &unit, &token, agreement.monthly_rent)
+                .map(|(amount, _)| amount)
+        }
+        None if token == agreement.payment_token => Ok(agreement.monthly_rent),
+        None => {
+            let rate = crate::multi_token::get_exchange_rate(
+                env.clone(),
+                agreement.payment_token.clone(),
+                token.clone(),
+            )?;
+            Ok(crate::multi_token::convert_at_rate(
+                env,
+                &agreement.payment_token,
+                &token,
+                agreement.monthly_rent,
+                rate,
+            )?)
+        }
+    }
+}
+
 /// Make a payment for an agreement using a specific token
+///
+/// For agreements priced in a unit of account, `amount` is the most the
+/// tenant is willing to spend: the rent is converted at the oracle rate and
+/// only the converted amount is transferred.
 pub fn make_payment_with_token(
     env: &Env,
     agreement_id: String,
     amount: i128,
     token: Address,
+) -> Result<(), RentalError> {
+    pay_rent(env, None, agreement_id, amount, token)
+}
+
+/// Pay rent on a sublet agreement from the subtenant's account. The tenant
+/// stays liable and can still pay through `make_payment_with_token`.
+pub fn make_subtenant_payment(
+    env: &Env,
+    agreement_id: String,
+    amount: i128,
+    token: Address,
+) -> Result<(), RentalError> {
+    let agreement =
+        get_agreement(env, agreement_id.clone()).ok_or(RentalError::AgreementNotFound)?;
+    let subtenant = agreement.subtenant.ok_or(RentalError::Unauthorized)?;
+    pay_rent(env, Some(subtenant), agreement_id, amount, token)
+}
+
+fn pay_rent(
+    env: &Env,
+    payer: Option<Address>,
+    agreement_id: String,
+    amount: i128,
+    token: Address,
 ) -> Result<(), RentalError> {
     // Single storage read – reuse `agreement` for all subsequent checks and
     // the final write-back, avoiding a second persistent-storage lookup.
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     if agreement.status != AgreementStatus::Active {
         return Err(RentalError::AgreementNotActive);
     }
 
-    agreement.user.require_auth();
-
-    // Skip the token-rate lookup entirely when the payment token already
-    // matches the agreement's base token – saves one persistent storage read.
-    let amount_in_base = if token != agreement.payment_token {
-        crate::multi_token::convert_amount(
-            env.clone(),
-            token.clone(),
-            agreement.payment_token.clone(),
-            amount,
-        )?
-    } else {
-        amount
-    };
+    let payer = payer.unwrap_or_else(|| agreement.user.clone());
+    payer.require_auth();
+
+    // Settle payment-plan deadlines that passed before this payment.
+    arrears::before_rent_paid(env, &agreement).map_err(|error| into_rental(env, error))?;
+
+    let (amount_paid, amount_in_base, conversion_rate) =
+        if let Some(unit) = get_agreement_unit(env, agreement_id.clone()) {
+            let (required, rate) =
+                crate::multi_token::quote_unit_amount(env, &unit, &token, agreement.monthly_rent)
+                    .map_err(|error| into_rental(env, error))?;
+            if required > amount {
+                env.panic_with_error(TokenError::SlippageExceeded);
+            }
+            (required, agreement.monthly_rent, rate)
+        } else if token != agreement.payment_token {
+            // Skip the token-rate lookup entirely when the payment token already
+            // matches the agreement's base token – saves one persistent storage read.
+            let rate = crate::multi_token::get_exchange_rate(
+                env.clone(),
+                token.clone(),
+                agreement.payment_token.clone(),
+            )?;
+            let converted = crate::multi_token::convert_at_rate(
+                env,
+                &token,
+                &agreement.payment_token,
+                amount,
+                rate,
+            )?;
+            (amount, converted, rate)
+        } else {
+            (amount, amount, crate::multi_token::RATE_SCALE)
+        };
 
     if amount_in_base < agreement.monthly_rent {
         return Err(RentalError::InsufficientPayment);
     }
 
+    crate::multi_token::consume_token_allowance(env, &token, TokenUsage::Rent, amount_paid)
+        .map_err(|error| into_rental(env, error))?;
+
     // Transfer tokens from tenant to contract (escrow)
     let client = soroban_sdk::token::Client::new(env, &token);
-    client.transfer(&agreement.user, env.current_contract_address(), &amount);
+    client.transfer(&payer, env.current_contract_address(), &amount_paid);
 
     // Update agreement state in the cached local variable
     agreement.total_rent_paid += amount_in_base;
@@ -505,7 +635,9 @@ pub fn make_payment_with_token(
         platform_amount: 0,
         token: token.clone(),
         payment_date: env.ledger().timestamp(),
-        payer: agreement.user.clone(),
+        payer,
+        amount_paid,
+        conversion_rate,
     };
 
     // Write payment record
@@ -520,11 +652,166 @@ pub fn make_payment_with_token(
         .persistent()
         .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
 
-    events::payment_made_with_token(env, agreement_id, token, amount);
+    events::payment_made_with_token(env, agreement_id, token, amount_paid);
+
+    arrears::on_rent_paid(env, &agreement).map_err(|error| into_rental(env, error))
+}
+
+/// Rent due for every period started so far, less rent already paid.
+///
+/// Rent falls due at the start of each 30-day period from `start_date`,
+/// at the agreement's current monthly rent.
+pub fn get_outstanding_rent(env: &Env, agreement_id: String) -> Result<i128, RentalError> {
+    let agreement = get_agreement(env, agreement_id).ok_or(RentalError::AgreementNotFound)?;
+    outstanding_rent(env, &agreement)
+}
+
+pub(crate) fn outstanding_rent(env: &Env, agreement: &RentAgreement) -> Result<i128, RentalError> {
+    outstanding_rent_at(agreement, env.ledger().timestamp())
+}
+
+/// Outstanding rent as of `now`, given the rent paid so far.
+pub(crate) fn outstanding_rent_at(
+    agreement: &RentAgreement,
+    now: u64,
+) -> Result<i128, RentalError> {
+    if now < agreement.start_date {
+        return Ok(0);
+    }
+    let periods = now
+        .min(agreement.end_date.saturating_sub(1))
+        .saturating_sub(agreement.start_date)
+        / SECONDS_PER_MONTH
+        + 1;
+    let due = agreement
+        .monthly_rent
+        .checked_mul(periods as i128)
+        .ok_or(RentalError::InvalidAmount)?;
+    Ok((due - agreement.total_rent_paid).max(0))
+}
+
+/// Whether the agreement's payment token has been disabled, so the parties
+/// must agree on a replacement before paying in it again.
+pub fn needs_token_migration(env: &Env, agreement_id: String) -> Result<bool, RentalError> {
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
+
+    Ok(!crate::multi_token::is_token_supported(
+        env.clone(),
+        agreement.payment_token,
+    )?)
+}
+
+/// Propose a replacement payment token for an agreement whose token was
+/// disabled. Either party may propose; the other must accept. A new proposal
+/// replaces any pending one.
+pub fn propose_token_migration(
+    env: &Env,
+    caller: Address,
+    agreement_id: String,
+    to_token: Address,
+    monthly_rent: i128,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
+
+    if caller != agreement.admin && caller != agreement.user {
+        return Err(RentalError::Unauthorized.into());
+    }
+    if matches!(
+        agreement.status,
+        AgreementStatus::Completed | AgreementStatus::Cancelled | AgreementStatus::Terminated
+    ) {
+        return Err(RentalError::InvalidState.into());
+    }
+    if crate::multi_token::is_token_supported(env.clone(), agreement.payment_token.clone())? {
+        return Err(TokenError::MigrationNotRequired.into());
+    }
+    // Rent priced in a unit of account is unaffected by the settlement token.
+    if monthly_rent <= 0
+        || (get_agreement_unit(env, agreement_id.clone()).is_some()
+            && monthly_rent != agreement.monthly_rent)
+    {
+        return Err(RentalError::InvalidAmount.into());
+    }
+    crate::multi_token::require_token_usage(env, &to_token, true, agreement.security_deposit > 0)?;
+
+    let migration = TokenMigration {
+        agreement_id: agreement_id.clone(),
+        from_token: agreement.payment_token.clone(),
+        to_token: to_token.clone(),
+        monthly_rent,
+        proposed_by: caller.clone(),
+        proposed_at: env.ledger().timestamp(),
+    };
+    env.storage().persistent().set(
+        &DataKey::Token(TokenKey::Migration(agreement_id.clone())),
+        &migration,
+    );
+
+    events::token_migration_proposed(env, agreement_id, agreement.payment_token, to_token, caller);
+    Ok(())
+}
+
+/// Accept the pending token migration. Must be called by the party that did
+/// not propose it.
+pub fn accept_token_migration(
+    env: &Env,
+    caller: Address,
+    agreement_id: String,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    let migration: TokenMigration = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Token(TokenKey::Migration(agreement_id.clone())))
+        .ok_or(TokenError::MigrationNotFound)?;
+    let mut agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
+
+    if (caller != agreement.admin && caller != agreement.user) || caller == migration.proposed_by {
+        return Err(RentalError::Unauthorized.into());
+    }
+    crate::multi_token::require_token_usage(
+        env,
+        &migration.to_token,
+        true,
+        agreement.security_deposit > 0,
+    )?;
+
+    agreement.payment_token = migration.to_token.clone();
+    agreement.monthly_rent = migration.monthly_rent;
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    env.storage().persistent().set(
+        &DataKey::AgreementToken(agreement_id.clone()),
+        &migration.to_token,
+    );
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Token(TokenKey::Migration(agreement_id.clone())));
 
+    events::token_migrated(
+        env,
+        agreement_id,
+        migration.from_token,
+        migration.to_token,
+        migration.monthly_rent,
+    );
     Ok(())
 }
 
+pub fn get_token_migration(env: &Env, agreement_id: String) -> Option<TokenMigration> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Token(TokenKey::Migration(agreement_id)))
+}
+
 /// Release escrow for an agreement
 pub fn release_escrow_with_token(
     env: &Env,
@@ -533,11 +820,8 @@ pub fn release_escrow_with_token(
 ) -> Result<(), RentalError> {
     // For simplicity, we assume escrow_id is the agreement_id
     let agreement_id = escrow_id.clone();
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     if is_escrow_frozen(env, escrow_id.clone()) {
         return Err(RentalError::InvalidState);
@@ -594,11 +878,8 @@ pub fn propose_extension(
 ) -> Result<String, RentalError> {
     caller.require_auth();
 
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     if caller != agreement.admin && caller != agreement.user {
         return Err(RentalError::Unauthorized);
@@ -672,11 +953,9 @@ pub fn accept_extension(
         return Err(RentalError::InvalidState);
     }
 
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(extension.original_agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &extension.original_agreement_id)
+            .ok_or(RentalError::AgreementNotFound)?;
 
     if caller == agreement.admin {
         extension.landlord_accepted = true;
@@ -712,11 +991,9 @@ pub fn reject_extension(
         .get(&DataKey::AgreementExtension(extension_id.clone()))
         .ok_or(RentalError::AgreementNotFound)?;
 
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(extension.original_agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &extension.original_agreement_id)
+            .ok_or(RentalError::AgreementNotFound)?;
 
     if caller != agreement.admin && caller != agreement.user {
         return Err(RentalError::Unauthorized);
@@ -752,11 +1029,9 @@ pub fn activate_extension(
         return Err(RentalError::InvalidState);
     }
 
-    let mut agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(extension.original_agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement: RentAgreement =
+        crate::storage::get_agreement(env, &extension.original_agreement_id)
+            .ok_or(RentalError::AgreementNotFound)?;
 
     if caller != agreement.admin {
         return Err(RentalError::Unauthorized);
@@ -795,11 +1070,9 @@ pub fn cancel_extension(
         .get(&DataKey::AgreementExtension(extension_id.clone()))
         .ok_or(RentalError::AgreementNotFound)?;
 
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(extension.original_agreement_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &extension.original_agreement_id)
+            .ok_or(RentalError::AgreementNotFound)?;
 
     if caller != agreement.admin && caller != agreement.user {
         return Err(RentalError::Unauthorized);
@@ -842,11 +1115,8 @@ pub fn get_extension_history(
 }
 
 pub fn get_current_agreement_end(env: &Env, agreement_id: String) -> Result<u64, RentalError> {
-    let agreement: RentAgreement = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Agreement(agreement_id))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
 
     Ok(agreement.end_date)
 }
@@ -0,0 +1,143 @@
+//! Secondary indexes over agreements by party and status.
+//!
+//! Each index is stored as a length plus one entry per position, with a
+//! reverse position key so entries can be swap-removed in O(1). Reads only
+//! touch the requested page, never the whole index.
+use soroban_sdk::{Env, String, Vec};
+
+use crate::errors::RentalError;
+use crate::storage::{DataKey, IndexKey};
+use crate::types::{AgreementIndex, AgreementPage, AgreementStatus, RentAgreement};
+
+/// Largest page returned by a single query.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+const TTL_THRESHOLD: u32 = 500000;
+const TTL_BUMP: u32 = 500000;
+
+fn len(env: &Env, index: &AgreementIndex) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Index(IndexKey::Len(index.clone())))
+        .unwrap_or(0)
+}
+
+fn set_len(env: &Env, index: &AgreementIndex, value: u32) {
+    let key = DataKey::Index(IndexKey::Len(index.clone()));
+    env.storage().persistent().set(&key, &value);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+fn set_entry(env: &Env, index: &AgreementIndex, position: u32, agreement_id: &String) {
+    let entry_key = DataKey::Index(IndexKey::Entry(index.clone(), position));
+    let position_key = DataKey::Index(IndexKey::Position(index.clone(), agreement_id.clone()));
+    env.storage().persistent().set(&entry_key, agreement_id);
+    env.storage().persistent().set(&position_key, &position);
+    env.storage()
+        .persistent()
+        .extend_ttl(&entry_key, TTL_THRESHOLD, TTL_BUMP);
+    env.storage()
+        .persistent()
+        .extend_ttl(&position_key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+/// Append `agreement_id` to `index` unless it is already present.
+pub(crate) fn insert(env: &Env, index: AgreementIndex, agreement_id: &String) {
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::Index(IndexKey::Position(
+            index.clone(),
+            agreement_id.clone(),
+        )))
+    {
+        return;
+    }
+    let position = len(env, &index);
+    set_entry(env, &index, position, agreement_id);
+    set_len(env, &index, position + 1);
+}
+
+/// Remove `agreement_id` from `index` by moving the last entry into its slot.
+pub(crate) fn remove(env: &Env, index: AgreementIndex, agreement_id: &String) {
+    let position_key = DataKey::Index(IndexKey::Position(index.clone(), agreement_id.clone()));
+    let Some(position) = env.storage().persistent().get::<_, u32>(&position_key) else {
+        return;
+    };
+    let last = len(env, &index) - 1;
+    let last_key = DataKey::Index(IndexKey::Entry(index.clone(), last));
+
+    if position != last {
+        let moved: String = env.storage().persistent().get(&last_key).unwrap();
+        set_entry(env, &index, position, &moved);
+    }
+    env.storage().persistent().remove(&last_key);
+    env.storage().persistent().remove(&position_key);
+    set_len(env, &index, last);
+}
+
+/// Index a newly created agreement under each of its parties and its status.
+pub(crate) fn index_agreement(env: &Env, agreement: &RentAgreement) {
+    let id = &agreement.agreement_id;
+    insert(env, AgreementIndex::Landlord(agreement.admin.clone()), id);
+    insert(env, AgreementIndex::Tenant(agreement.user.clone()), id);
+    if let Some(agent) = &agreement.agent {
+        insert(env, AgreementIndex::Agent(agent.clone()), id);
+    }
+    insert(env, AgreementIndex::Status(agreement.status.clone()), id);
+}
+
+/// Move an agreement between status indexes and update its status field.
+pub(crate) fn set_status(env: &Env, agreement: &mut RentAgreement, status: AgreementStatus) {
+    if agreement.status == status {
+        return;
+    }
+    remove(
+        env,
+        AgreementIndex::Status(agreement.status.clone()),
+        &agreement.agreement_id,
+    );
+    insert(
+        env,
+        AgreementIndex::Status(status.clone()),
+        &agreement.agreement_id,
+    );
+    agreement.status = status;
+}
+
+/// Read one page of `index`, starting at position `cursor`.
+///
+/// Removals swap the last entry into the freed slot, so a page walk that
+/// overlaps a removal may skip or repeat one agreement.
+pub fn get_page(
+    env: &Env,
+    index: AgreementIndex,
+    cursor: u32,
+    limit: u32,
+) -> Result<AgreementPage, RentalError> {
+    if limit == 0 || limit > MAX_PAGE_SIZE {
+        return Err(RentalError::InvalidInput);
+    }
+
+    let total = len(env, &index);
+    let end = cursor.saturating_add(limit).min(total);
+    let mut agreements = Vec::new(env);
+    for position in cursor..end {
+        let id: String = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Index(IndexKey::Entry(index.clone(), position)))
+            .ok_or(RentalError::InternalError)?;
+        if let Some(agreement) = crate::storage::get_agreement(env, &id) {
+            agreements.push_back(agreement);
+        }
+    }
+
+    Ok(AgreementPage {
+        agreements,
+        next_cursor: if end < total { Some(end) } else { None },
+        total,
+    })
+}
@@ -0,0 +1,409 @@
+//! Mid-lease amendments to active agreements.
+//!
+//! Either party proposes a set of changes with an effective date. The
+//! amendment is applied once the landlord, the primary tenant and any tenant
+//! being added have all accepted and the effective date has been reached.
+//! Every applied amendment appends a `TermsVersion` to the agreement's
+//! terms history.
+use soroban_sdk::{Address, Env, Error, String, Vec};
+
+use crate::agreement_index;
+use crate::errors::{AmendmentError, RentalError};
+use crate::events;
+use crate::storage::{AmendmentKey, DataKey};
+use crate::types::{
+    AgreementAmendment, AgreementIndex, AgreementStatus, AmendmentChange, AmendmentStatus,
+    RentAgreement, TermsVersion,
+};
+
+const TTL_THRESHOLD: u32 = 500000;
+const TTL_BUMP: u32 = 500000;
+
+fn load_agreement(env: &Env, agreement_id: &String) -> Result<RentAgreement, Error> {
+    crate::storage::get_agreement(env, agreement_id).ok_or(RentalError::AgreementNotFound.into())
+}
+
+fn save_amendment(env: &Env, amendment: &AgreementAmendment) {
+    let key = DataKey::Amendment(AmendmentKey::Amendment(
+        amendment.agreement_id.clone(),
+        amendment.amendment_id,
+    ));
+    env.storage().persistent().set(&key, amendment);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+fn is_tenant(agreement: &RentAgreement, address: &Address) -> bool {
+    agreement.user == *address || agreement.co_tenants.contains(address)
+}
+
+/// Check that every change can be applied to `agreement` as it stands now.
+fn validate_changes(
+    env: &Env,
+    agreement: &RentAgreement,
+    changes: &Vec<AmendmentChange>,
+) -> Result<(), Error> {
+    if changes.is_empty() {
+        return Err(RentalError::InvalidInput.into());
+    }
+    for change in changes.iter() {
+        match change {
+            AmendmentChange::MonthlyRent(rent) => {
+                if rent <= 0 {
+                    return Err(RentalError::InvalidAmount.into());
+                }
+            }
+            AmendmentChange::PaymentToken(token) => {
+                crate::multi_token::require_token_usage(
+                    env,
+                    &token,
+                    true,
+                    agreement.security_deposit > 0,
+                )?;
+            }
+            AmendmentChange::Agent(Some(agent)) => {
+                if agent == agreement.admin || is_tenant(agreement, &agent) {
+                    return Err(RentalError::InvalidInput.into());
+                }
+            }
+            AmendmentChange::Agent(None) | AmendmentChange::Document(..) => {}
+            AmendmentChange::AddTenant(tenant) => {
+                if tenant == agreement.admin
+                    || is_tenant(agreement, &tenant)
+                    || agreement.subtenant.as_ref() == Some(&tenant)
+                {
+                    return Err(RentalError::InvalidInput.into());
+                }
+            }
+            AmendmentChange::RemoveTenant(tenant) => {
+                if !agreement.co_tenants.contains(&tenant) {
+                    return Err(RentalError::InvalidInput.into());
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Propose an amendment to an active agreement. Returns its sequence number.
+pub fn propose_amendment(
+    env: &Env,
+    caller: Address,
+    agreement_id: String,
+    changes: Vec<AmendmentChange>,
+    effective_date: u64,
+) -> Result<u32, Error> {
+    caller.require_auth();
+
+    let agreement = load_agreement(env, &agreement_id)?;
+    if caller != agreement.admin && caller != agreement.user {
+        return Err(RentalError::Unauthorized.into());
+    }
+    if agreement.status != AgreementStatus::Active {
+        return Err(RentalError::AgreementNotActive.into());
+    }
+    if effective_date < env.ledger().timestamp() || effective_date > agreement.end_date {
+        return Err(RentalError::InvalidDate.into());
+    }
+    validate_changes(env, &agreement, &changes)?;
+
+    let mut required_approvals = Vec::new(env);
+    required_approvals.push_back(agreement.admin.clone());
+    required_approvals.push_back(agreement.user.clone());
+    for change in changes.iter() {
+        if let AmendmentChange::AddTenant(tenant) = change {
+            required_approvals.push_back(tenant);
+        }
+    }
+    let mut approvals = Vec::new(env);
+    approvals.push_back(caller.clone());
+
+    let count_key = DataKey::Amendment(AmendmentKey::Count(agreement_id.clone()));
+    let amendment_id: u32 = env.storage().persistent().get(&count_key).unwrap_or(0) + 1;
+    env.storage().persistent().set(&count_key, &amendment_id);
+
+    let amendment = AgreementAmendment {
+        agreement_id: agreement_id.clone(),
+        amendment_id,
+        changes,
+        effective_date,
+        proposed_by: caller.clone(),
+        required_approvals,
+        approvals,
+        status: AmendmentStatus::Proposed,
+        created_at: env.ledger().timestamp(),
+        last_reason: None,
+    };
+    save_amendment(env, &amendment);
+
+    events::amendment_proposed(env, agreement_id, amendment_id, caller, effective_date);
+
+    Ok(amendment_id)
+}
+
+/// Accept an amendment. Once every required party has accepted it is
+/// applied immediately if its effective date has passed.
+pub fn accept_amendment(
+    env: &Env,
+    caller: Address,
+    agreement_id: String,
+    amendment_id: u32,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    let mut amendment = get_amendment(env, agreement_id.clone(), amendment_id)?;
+    if amendment.status != AmendmentStatus::Proposed {
+        return Err(RentalError::InvalidState.into());
+    }
+    if !amendment.required_approvals.contains(&caller) {
+        return Err(RentalError::Unauthorized.into());
+    }
+    if amendment.approvals.contains(&caller) {
+        return Err(RentalError::AlreadyApproved.into());
+    }
+
+    amendment.approvals.push_back(caller.clone());
+    events::amendment_accepted(env, agreement_id.clone(), amendment_id, caller);
+
+    let fully_approved = amendment
+        .required_approvals
+        .iter()
+        .all(|party| amendment.approvals.contains(&party));
+    if fully_approved {
+        amendment.status = AmendmentStatus::Accepted;
+        if amendment.effective_date <= env.ledger().timestamp() {
+            return apply(env, amendment);
+        }
+    }
+
+    save_amendment(env, &amendment);
+    Ok(())
+}
+
+/// Reject a proposed amendment. Any required party may reject.
+pub fn reject_amendment(
+    env: &Env,
+    caller: Address,
+    agreement_id: String,
+    amendment_id: u32,
+    reason: String,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    let mut amendment = get_amendment(env, agreement_id.clone(), amendment_id)?;
+    if amendment.status != AmendmentStatus::Proposed {
+        return Err(RentalError::InvalidState.into());
+    }
+    if !amendment.required_approvals.contains(&caller) {
+        return Err(RentalError::Unauthorized.into());
+    }
+
+    amendment.status = AmendmentStatus::Rejected;
+    amendment.last_reason = Some(reason);
+    save_amendment(env, &amendment);
+
+    events::amendment_rejected(env, agreement_id, amendment_id);
+    Ok(())
+}
+
+/// Withdraw an amendment that has not been applied. Proposer only.
+pub fn cancel_amendment(
+    env: &Env,
+    caller: Address,
+    agreement_id: String,
+    amendment_id: u32,
+    reason: String,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    let mut amendment = get_amendment(env, agreement_id.clone(), amendment_id)?;
+    if caller != amendment.proposed_by {
+        return Err(RentalError::Unauthorized.into());
+    }
+    if amendment.status != AmendmentStatus::Proposed
+        && amendment.status != AmendmentStatus::Accepted
+    {
+        return Err(RentalError::InvalidState.into());
+    }
+
+    amendment.status = AmendmentStatus::Cancelled;
+    amendment.last_reason = Some(reason);
+    save_amendment(env, &amendment);
+
+    events::amendment_cancelled(env, agreement_id, amendment_id);
+    Ok(())
+}
+
+/// Apply an accepted amendment whose effective date has been reached.
+/// Permissionless, so a keeper or either party can trigger it.
+pub fn apply_amendment(env: &Env, agreement_id: String, amendment_id: u32) -> Result<(), Error> {
+    let amendment = get_amendment(env, agreement_id, amendment_id)?;
+    if amendment.status != AmendmentStatus::Accepted {
+        return Err(RentalError::InvalidState.into());
+    }
+    if amendment.effective_date > env.ledger().timestamp() {
+        return Err(AmendmentError::AmendmentNotEffective.into());
+    }
+    apply(env, amendment)
+}
+
+fn apply(env: &Env, mut amendment: AgreementAmendment) -> Result<(), Error> {
+    let agreement_id = amendment.agreement_id.clone();
+    let mut agreement = load_agreement(env, &agreement_id)?;
+    if agreement.status != AgreementStatus::Active {
+        return Err(RentalError::AgreementNotActive.into());
+    }
+    // Terms may have moved since the proposal (e.g. a token was disabled).
+    validate_changes(env, &agreement, &amendment.changes)?;
+
+    let mut version = terms_version_count(env, &agreement_id);
+    if version == 0 {
+        record_version(env, &agreement, 0, agreement.start_date, None);
+        version = 1;
+    }
+
+    for change in amendment.changes.iter() {
+        match change {
+            AmendmentChange::MonthlyRent(rent) => agreement.monthly_rent = rent,
+            AmendmentChange::PaymentToken(token) => {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::AgreementToken(agreement_id.clone()), &token);
+                agreement.payment_token = token;
+            }
+            AmendmentChange::Agent(agent) => {
+                if let Some(old) = agreement.agent.clone() {
+                    agreement_index::remove(env, AgreementIndex::Agent(old), &agreement_id);
+                }
+                if let Some(new) = agent.clone() {
+                    agreement_index::insert(env, AgreementIndex::Agent(new), &agreement_id);
+                }
+                agreement.agent = agent;
+            }
+            AmendmentChange::Document(document_hash, metadata_uri) => {
+                crate::document::apply_amended_document(
+                    env,
+                    &mut agreement,
+                    &amendment.proposed_by,
+                    document_hash,
+                    metadata_uri,
+                );
+            }
+            AmendmentChange::AddTenant(tenant) => {
+                agreement_index::insert(env, AgreementIndex::Tenant(tenant.clone()), &agreement_id);
+                agreement.co_tenants.push_back(tenant);
+            }
+            AmendmentChange::RemoveTenant(tenant) => {
+                agreement_index::remove(env, AgreementIndex::Tenant(tenant.clone()), &agreement_id);
+                if let Some(position) = agreement.co_tenants.first_index_of(&tenant) {
+                    agreement.co_tenants.remove(position);
+                }
+            }
+        }
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    env.storage().persistent().extend_ttl(
+        &DataKey::Agreement(agreement_id.clone()),
+        TTL_THRESHOLD,
+        TTL_BUMP,
+    );
+    record_version(
+        env,
+        &agreement,
+        version,
+        amendment.effective_date,
+        Some(amendment.amendment_id),
+    );
+
+    amendment.status = AmendmentStatus::Applied;
+    save_amendment(env, &amendment);
+
+    events::amendment_applied(env, agreement_id, amendment.amendment_id, version);
+    Ok(())
+}
+
+fn terms_version_count(env: &Env, agreement_id: &String) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Amendment(AmendmentKey::TermsVersionCount(
+            agreement_id.clone(),
+        )))
+        .unwrap_or(0)
+}
+
+fn record_version(
+    env: &Env,
+    agreement: &RentAgreement,
+    version: u32,
+    effective_from: u64,
+    amendment_id: Option<u32>,
+) {
+    let terms = TermsVersion {
+        version,
+        monthly_rent: agreement.monthly_rent,
+        payment_token: agreement.payment_token.clone(),
+        agent: agreement.agent.clone(),
+        metadata_uri: agreement.metadata_uri.clone(),
+        co_tenants: agreement.co_tenants.clone(),
+        effective_from,
+        amendment_id,
+    };
+    let key = DataKey::Amendment(AmendmentKey::TermsVersion(
+        agreement.agreement_id.clone(),
+        version,
+    ));
+    env.storage().persistent().set(&key, &terms);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    env.storage().persistent().set(
+        &DataKey::Amendment(AmendmentKey::TermsVersionCount(
+            agreement.agreement_id.clone(),
+        )),
+        &(version + 1),
+    );
+}
+
+pub fn get_amendment(
+    env: &Env,
+    agreement_id: String,
+    amendment_id: u32,
+) -> Result<AgreementAmendment, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Amendment(AmendmentKey::Amendment(
+            agreement_id,
+            amendment_id,
+        )))
+        .ok_or(AmendmentError::AmendmentNotFound.into())
+}
+
+pub fn get_amendment_count(env: &Env, agreement_id: String) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Amendment(AmendmentKey::Count(agreement_id)))
+        .unwrap_or(0)
+}
+
+/// Number of recorded terms versions (0 until the first amendment applies).
+pub fn get_terms_version_count(env: &Env, agreement_id: String) -> u32 {
+    terms_version_count(env, &agreement_id)
+}
+
+pub fn get_terms_version(
+    env: &Env,
+    agreement_id: String,
+    version: u32,
+) -> Result<TermsVersion, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Amendment(AmendmentKey::TermsVersion(
+            agreement_id,
+            version,
+        )))
+        .ok_or(AmendmentError::AmendmentNotFound.into())
+}
@@ -0,0 +1,339 @@
+//! Default process for tenants in sustained arrears.
+//!
+//! Once arrears reach the agreement's `ArrearsPolicy` triggers, the landlord
+//! may serve a default notice, which also charges any late fees. Paying off
+//! the arrears during the cure period cures the notice automatically. If the
+//! arrears are still outstanding when the cure period ends, the landlord may
+//! declare default: the agreement is terminated and a claim against the
+//! security deposit is recorded. An agreed payment plan suspends the notice
+//! for as long as it is honoured.
+use soroban_sdk::{Address, Env, Error, String};
+
+use crate::agreement::outstanding_rent;
+use crate::agreement_index;
+use crate::errors::{ArrearsError, PaymentPlanError, RentalError};
+use crate::events;
+use crate::payment_plan;
+use crate::storage::{ArrearsKey, DataKey};
+use crate::types::{
+    AgreementStatus, ArrearsPolicy, DefaultNotice, DefaultNoticeStatus, RentAgreement,
+};
+
+const TTL_THRESHOLD: u32 = 500000;
+const TTL_BUMP: u32 = 500000;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Policy applied to agreements the landlord has not configured.
+pub fn default_policy() -> ArrearsPolicy {
+    ArrearsPolicy {
+        missed_periods: 2,
+        arrears_threshold: 0,
+        cure_period: 14 * SECONDS_PER_DAY,
+        late_fee: 0,
+    }
+}
+
+fn load_agreement(env: &Env, agreement_id: &String) -> Result<RentAgreement, Error> {
+    crate::storage::get_agreement(env, agreement_id).ok_or(RentalError::AgreementNotFound.into())
+}
+
+fn load_landlord_agreement(
+    env: &Env,
+    landlord: &Address,
+    agreement_id: &String,
+) -> Result<RentAgreement, Error> {
+    landlord.require_auth();
+    let agreement = load_agreement(env, agreement_id)?;
+    if *landlord != agreement.admin {
+        return Err(RentalError::Unauthorized.into());
+    }
+    if agreement.status != AgreementStatus::Active {
+        return Err(RentalError::AgreementNotActive.into());
+    }
+    Ok(agreement)
+}
+
+fn save_notice(env: &Env, notice: &DefaultNotice) {
+    let key = DataKey::Arrears(ArrearsKey::DefaultNotice(notice.agreement_id.clone()));
+    env.storage().persistent().set(&key, notice);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+fn notice_in(
+    env: &Env,
+    agreement_id: &String,
+    statuses: &[DefaultNoticeStatus],
+) -> Option<DefaultNotice> {
+    get_default_notice(env, agreement_id.clone())
+        .ok()
+        .filter(|notice| statuses.contains(&notice.status))
+}
+
+/// Set the arrears policy of an agreement. Only the landlord may do so, and
+/// only before the tenant has signed.
+pub fn set_arrears_policy(
+    env: &Env,
+    agreement_id: String,
+    policy: ArrearsPolicy,
+) -> Result<(), Error> {
+    let agreement = load_agreement(env, &agreement_id)?;
+    agreement.admin.require_auth();
+
+    match agreement.status {
+        AgreementStatus::Draft | AgreementStatus::Pending => {}
+        _ => return Err(RentalError::InvalidState.into()),
+    }
+    if policy.arrears_threshold < 0
+        || (policy.missed_periods == 0 && policy.arrears_threshold == 0)
+        || policy.cure_period == 0
+        || policy.late_fee < 0
+    {
+        return Err(RentalError::InvalidConfig.into());
+    }
+
+    let key = DataKey::Arrears(ArrearsKey::Policy(agreement_id.clone()));
+    env.storage().persistent().set(&key, &policy);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+
+    events::arrears_policy_set(
+        env,
+        agreement_id,
+        policy.missed_periods,
+        policy.arrears_threshold,
+        policy.cure_period,
+    );
+    Ok(())
+}
+
+pub fn get_arrears_policy(env: &Env, agreement_id: String) -> ArrearsPolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Arrears(ArrearsKey::Policy(agreement_id)))
+        .unwrap_or_else(default_policy)
+}
+
+/// Latest default notice served on the agreement, whatever its status.
+pub fn get_default_notice(env: &Env, agreement_id: String) -> Result<DefaultNotice, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Arrears(ArrearsKey::DefaultNotice(agreement_id)))
+        .ok_or(ArrearsError::DefaultNoticeNotFound.into())
+}
+
+/// Late fees charged and not yet paid.
+pub fn get_late_fees(env: &Env, agreement_id: String) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Arrears(ArrearsKey::LateFees(agreement_id)))
+        .unwrap_or(0)
+}
+
+fn set_late_fees(env: &Env, agreement_id: &String, amount: i128) {
+    let key = DataKey::Arrears(ArrearsKey::LateFees(agreement_id.clone()));
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+/// Add `amount` to the late fees owed.
+pub(crate) fn add_late_fees(env: &Env, agreement_id: &String, amount: i128) {
+    if amount > 0 {
+        set_late_fees(
+            env,
+            agreement_id,
+            get_late_fees(env, agreement_id.clone()) + amount,
+        );
+    }
+}
+
+/// Clear the late fees owed and return what they were.
+pub(crate) fn take_late_fees(env: &Env, agreement_id: &String) -> i128 {
+    let owed = get_late_fees(env, agreement_id.clone());
+    if owed > 0 {
+        set_late_fees(env, agreement_id, 0);
+    }
+    owed
+}
+
+/// Pay late fees in the agreement's payment token.
+pub fn pay_late_fees(
+    env: &Env,
+    payer: Address,
+    agreement_id: String,
+    amount: i128,
+) -> Result<(), Error> {
+    payer.require_auth();
+    let agreement = load_agreement(env, &agreement_id)?;
+    let owed = get_late_fees(env, agreement_id.clone());
+    if amount <= 0 || amount > owed {
+        return Err(RentalError::InvalidAmount.into());
+    }
+
+    soroban_sdk::token::Client::new(env, &agreement.payment_token).transfer(
+        &payer,
+        env.current_contract_address(),
+        &amount,
+    );
+    set_late_fees(env, &agreement_id, owed - amount);
+
+    events::late_fees_paid(env, agreement_id, payer, amount);
+    Ok(())
+}
+
+/// Serve a default notice on a tenant whose arrears meet the policy.
+pub fn issue_default_notice(
+    env: &Env,
+    landlord: Address,
+    agreement_id: String,
+) -> Result<DefaultNotice, Error> {
+    let agreement = load_landlord_agreement(env, &landlord, &agreement_id)?;
+    if payment_plan::active_plan(env, &agreement_id).is_some() {
+        return Err(PaymentPlanError::PaymentPlanActive.into());
+    }
+    if notice_in(env, &agreement_id, &[DefaultNoticeStatus::Open]).is_some() {
+        return Err(ArrearsError::DefaultNoticeOpen.into());
+    }
+
+    let policy = get_arrears_policy(env, agreement_id.clone());
+    let arrears = outstanding_rent(env, &agreement)?;
+    let missed_periods = (arrears / agreement.monthly_rent) as u32;
+    let by_periods = policy.missed_periods > 0 && missed_periods >= policy.missed_periods;
+    let by_amount = policy.arrears_threshold > 0 && arrears >= policy.arrears_threshold;
+    if !by_periods && !by_amount {
+        return Err(ArrearsError::ArrearsBelowThreshold.into());
+    }
+
+    let now = env.ledger().timestamp();
+    let notice = DefaultNotice {
+        agreement_id: agreement_id.clone(),
+        arrears,
+        missed_periods,
+        issued_at: now,
+        cure_deadline: now + policy.cure_period,
+        status: DefaultNoticeStatus::Open,
+        deposit_claim: 0,
+    };
+    save_notice(env, &notice);
+
+    events::default_notice_issued(
+        env,
+        agreement_id.clone(),
+        agreement.user,
+        arrears,
+        notice.cure_deadline,
+    );
+
+    let fees = policy.late_fee * missed_periods as i128;
+    if fees > 0 {
+        add_late_fees(env, &agreement_id, fees);
+        events::late_fees_charged(env, agreement_id, fees);
+    }
+    Ok(notice)
+}
+
+/// Put the open notice on hold while a payment plan runs.
+pub(crate) fn suspend_notice(env: &Env, agreement_id: &String) {
+    if let Some(mut notice) = notice_in(env, agreement_id, &[DefaultNoticeStatus::Open]) {
+        notice.status = DefaultNoticeStatus::Suspended;
+        save_notice(env, &notice);
+    }
+}
+
+/// Reopen a suspended notice after a broken plan. The cure period was
+/// already granted, so default may be declared straight away.
+pub(crate) fn resume_notice(env: &Env, agreement: &RentAgreement) {
+    let Some(mut notice) = notice_in(
+        env,
+        &agreement.agreement_id,
+        &[DefaultNoticeStatus::Suspended],
+    ) else {
+        return;
+    };
+    notice.status = DefaultNoticeStatus::Open;
+    notice.cure_deadline = env.ledger().timestamp();
+    save_notice(env, &notice);
+
+    events::default_notice_issued(
+        env,
+        agreement.agreement_id.clone(),
+        agreement.user.clone(),
+        notice.arrears,
+        notice.cure_deadline,
+    );
+}
+
+/// Mark an open or suspended notice as cured.
+pub(crate) fn cure_notice(env: &Env, agreement: &RentAgreement) {
+    let Some(mut notice) = notice_in(
+        env,
+        &agreement.agreement_id,
+        &[DefaultNoticeStatus::Open, DefaultNoticeStatus::Suspended],
+    ) else {
+        return;
+    };
+    notice.status = DefaultNoticeStatus::Cured;
+    save_notice(env, &notice);
+    events::default_cured(env, agreement.agreement_id.clone(), agreement.user.clone());
+}
+
+/// Settle payment-plan deadlines before a rent payment is recorded, so a
+/// late payment never counts towards an instalment it missed.
+pub(crate) fn before_rent_paid(env: &Env, agreement: &RentAgreement) -> Result<(), Error> {
+    payment_plan::process_due(env, agreement)
+}
+
+/// Cure the notice and complete any plan once a payment has cleared the
+/// arrears.
+pub(crate) fn on_rent_paid(env: &Env, agreement: &RentAgreement) -> Result<(), Error> {
+    if outstanding_rent(env, agreement)? > 0 {
+        return Ok(());
+    }
+    payment_plan::complete_active(env, agreement);
+    cure_notice(env, agreement);
+    Ok(())
+}
+
+/// Declare default after the cure period: terminate the agreement and claim
+/// the arrears and unpaid late fees against the security deposit, up to the
+/// deposit amount.
+pub fn declare_default(
+    env: &Env,
+    landlord: Address,
+    agreement_id: String,
+) -> Result<DefaultNotice, Error> {
+    let mut agreement = load_landlord_agreement(env, &landlord, &agreement_id)?;
+    let mut notice = notice_in(env, &agreement_id, &[DefaultNoticeStatus::Open])
+        .ok_or(ArrearsError::DefaultNoticeNotFound)?;
+    if env.ledger().timestamp() < notice.cure_deadline {
+        return Err(ArrearsError::CurePeriodActive.into());
+    }
+    let arrears = outstanding_rent(env, &agreement)?;
+    if arrears == 0 {
+        return Err(ArrearsError::ArrearsBelowThreshold.into());
+    }
+
+    notice.arrears = arrears;
+    notice.status = DefaultNoticeStatus::Defaulted;
+    notice.deposit_claim =
+        (arrears + get_late_fees(env, agreement_id.clone())).min(agreement.security_deposit);
+    save_notice(env, &notice);
+
+    agreement_index::set_status(env, &mut agreement, AgreementStatus::Terminated);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+
+    events::agreement_defaulted(
+        env,
+        agreement_id,
+        agreement.user,
+        arrears,
+        notice.deposit_claim,
+    );
+    Ok(notice)
+}
@@ -0,0 +1,295 @@
+//! Lease assignment and subletting.
+//!
+//! The tenant nominates a replacement, the nominee accepts (paying a fresh
+//! deposit if one was asked for) and the landlord approves. An assignment
+//! swaps the tenant on the agreement; a sublet records a subtenant who pays
+//! rent while the tenant stays liable. The handover only completes once all
+//! rent due so far has been paid. A fresh deposit is handed to the landlord
+//! on completion, and the landlord refunds the outgoing tenant's deposit.
+use soroban_sdk::{token, Address, Env, Error, String};
+
+use crate::agreement::get_outstanding_rent;
+use crate::agreement_index;
+use crate::deposit_escrow::get_deposit_record;
+use crate::errors::{AssignmentError, RentalError};
+use crate::events;
+use crate::storage::DataKey;
+use crate::types::{
+    AgreementIndex, AgreementStatus, AssignmentMode, AssignmentStatus, DepositStatus,
+    LeaseAssignment, RentAgreement,
+};
+
+const TTL_THRESHOLD: u32 = 500000;
+const TTL_BUMP: u32 = 500000;
+
+fn load_agreement(env: &Env, agreement_id: &String) -> Result<RentAgreement, Error> {
+    crate::storage::get_agreement(env, agreement_id).ok_or(RentalError::AgreementNotFound.into())
+}
+
+fn save_agreement(env: &Env, agreement: &RentAgreement) {
+    let key = DataKey::Agreement(agreement.agreement_id.clone());
+    env.storage().persistent().set(&key, agreement);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+fn save_assignment(env: &Env, assignment: &LeaseAssignment) {
+    let key = DataKey::LeaseAssignment(assignment.agreement_id.clone());
+    env.storage().persistent().set(&key, assignment);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+fn is_party(agreement: &RentAgreement, address: &Address) -> bool {
+    agreement.admin == *address
+        || agreement.user == *address
+        || agreement.co_tenants.contains(address)
+        || agreement.subtenant.as_ref() == Some(address)
+        || agreement.agent.as_ref() == Some(address)
+}
+
+/// Nominate a new tenant or subtenant. Primary tenant only. A new
+/// nomination replaces one that has not been accepted yet.
+pub fn nominate_assignee(
+    env: &Env,
+    tenant: Address,
+    agreement_id: String,
+    assignee: Address,
+    mode: AssignmentMode,
+    new_deposit: i128,
+) -> Result<(), Error> {
+    tenant.require_auth();
+
+    let agreement = load_agreement(env, &agreement_id)?;
+    if tenant != agreement.user {
+        return Err(RentalError::Unauthorized.into());
+    }
+    if agreement.status != AgreementStatus::Active {
+        return Err(RentalError::AgreementNotActive.into());
+    }
+    if is_party(&agreement, &assignee) {
+        return Err(RentalError::InvalidInput.into());
+    }
+    // A sublet never replaces the deposit held against the tenant.
+    if new_deposit < 0 || (mode == AssignmentMode::Sublet && new_deposit != 0) {
+        return Err(RentalError::InvalidAmount.into());
+    }
+    // Only one sublet at a time, and a sublet must end before assigning.
+    if agreement.subtenant.is_some() {
+        return Err(RentalError::InvalidState.into());
+    }
+    if let Some(pending) = get_lease_assignment(env, agreement_id.clone()) {
+        if pending.status == AssignmentStatus::Accepted {
+            return Err(RentalError::InvalidState.into());
+        }
+    }
+
+    let assignment = LeaseAssignment {
+        agreement_id: agreement_id.clone(),
+        mode,
+        from_tenant: tenant.clone(),
+        to_tenant: assignee.clone(),
+        new_deposit,
+        deposit_token: agreement.payment_token.clone(),
+        status: AssignmentStatus::Nominated,
+        created_at: env.ledger().timestamp(),
+    };
+    save_assignment(env, &assignment);
+
+    events::assignment_nominated(env, agreement_id, mode, tenant, assignee);
+    Ok(())
+}
+
+/// Accept a nomination. Transfers the fresh deposit, if any, from the
+/// nominee into the contract.
+pub fn accept_assignment(env: &Env, assignee: Address, agreement_id: String) -> Result<(), Error> {
+    assignee.require_auth();
+
+    let mut assignment = pending_assignment(env, &agreement_id)?;
+    if assignee != assignment.to_tenant {
+        return Err(RentalError::Unauthorized.into());
+    }
+    if assignment.status != AssignmentStatus::Nominated {
+        return Err(RentalError::InvalidState.into());
+    }
+    let agreement = load_agreement(env, &agreement_id)?;
+    if agreement.status != AgreementStatus::Active {
+        return Err(RentalError::AgreementNotActive.into());
+    }
+
+    if assignment.new_deposit > 0 {
+        token::Client::new(env, &assignment.deposit_token).transfer(
+            &assignee,
+            env.current_contract_address(),
+            &assignment.new_deposit,
+        );
+    }
+
+    assignment.status = AssignmentStatus::Accepted;
+    save_assignment(env, &assignment);
+
+    events::assignment_accepted(env, agreement_id, assignee, assignment.new_deposit);
+    Ok(())
+}
+
+/// Approve an accepted nomination and complete the handover. Landlord only.
+pub fn approve_assignment(env: &Env, landlord: Address, agreement_id: String) -> Result<(), Error> {
+    landlord.require_auth();
+
+    let mut assignment = pending_assignment(env, &agreement_id)?;
+    let mut agreement = load_agreement(env, &agreement_id)?;
+    if landlord != agreement.admin {
+        return Err(RentalError::Unauthorized.into());
+    }
+    if assignment.status != AssignmentStatus::Accepted {
+        return Err(RentalError::InvalidState.into());
+    }
+    if agreement.status != AgreementStatus::Active {
+        return Err(RentalError::AgreementNotActive.into());
+    }
+    // The tenant may have changed through another flow since the nomination.
+    if agreement.user != assignment.from_tenant {
+        return Err(RentalError::InvalidState.into());
+    }
+    if get_outstanding_rent(env, agreement_id.clone())? > 0 {
+        return Err(AssignmentError::OutstandingBalance.into());
+    }
+    // A deposit still held by the escrow contract is refunded from there.
+    let replaces_deposit =
+        assignment.mode == AssignmentMode::Assignment && assignment.new_deposit > 0;
+    if replaces_deposit && deposit_in_escrow(env, &agreement_id) {
+        return Err(RentalError::InvalidState.into());
+    }
+
+    let to_tenant = assignment.to_tenant.clone();
+    agreement_index::insert(
+        env,
+        AgreementIndex::Tenant(to_tenant.clone()),
+        &agreement_id,
+    );
+    match assignment.mode {
+        AssignmentMode::Assignment => {
+            agreement_index::remove(
+                env,
+                AgreementIndex::Tenant(agreement.user.clone()),
+                &agreement_id,
+            );
+            agreement.user = to_tenant.clone();
+            if replaces_deposit {
+                swap_deposit(env, &landlord, &assignment, agreement.security_deposit);
+                agreement.security_deposit = assignment.new_deposit;
+            }
+        }
+        AssignmentMode::Sublet => agreement.subtenant = Some(to_tenant.clone()),
+    }
+    save_agreement(env, &agreement);
+
+    assignment.status = AssignmentStatus::Completed;
+    save_assignment(env, &assignment);
+
+    events::assignment_completed(
+        env,
+        agreement_id,
+        assignment.mode,
+        assignment.from_tenant,
+        to_tenant,
+    );
+    Ok(())
+}
+
+/// Cancel a nomination before it completes. The tenant, the nominee or the
+/// landlord may cancel; a deposit already paid is refunded to the nominee.
+pub fn cancel_assignment(env: &Env, caller: Address, agreement_id: String) -> Result<(), Error> {
+    caller.require_auth();
+
+    let mut assignment = pending_assignment(env, &agreement_id)?;
+    let agreement = load_agreement(env, &agreement_id)?;
+    if caller != assignment.from_tenant
+        && caller != assignment.to_tenant
+        && caller != agreement.admin
+    {
+        return Err(RentalError::Unauthorized.into());
+    }
+
+    if assignment.status == AssignmentStatus::Accepted && assignment.new_deposit > 0 {
+        token::Client::new(env, &assignment.deposit_token).transfer(
+            &env.current_contract_address(),
+            &assignment.to_tenant,
+            &assignment.new_deposit,
+        );
+    }
+
+    assignment.status = AssignmentStatus::Cancelled;
+    save_assignment(env, &assignment);
+
+    events::assignment_cancelled(env, agreement_id, caller);
+    Ok(())
+}
+
+/// End a sublet. The tenant or the landlord may end it.
+pub fn end_sublet(env: &Env, caller: Address, agreement_id: String) -> Result<(), Error> {
+    caller.require_auth();
+
+    let mut agreement = load_agreement(env, &agreement_id)?;
+    if caller != agreement.user && caller != agreement.admin {
+        return Err(RentalError::Unauthorized.into());
+    }
+    let subtenant = agreement
+        .subtenant
+        .take()
+        .ok_or(RentalError::InvalidState)?;
+
+    agreement_index::remove(
+        env,
+        AgreementIndex::Tenant(subtenant.clone()),
+        &agreement_id,
+    );
+    save_agreement(env, &agreement);
+
+    events::sublet_ended(env, agreement_id, subtenant);
+    Ok(())
+}
+
+/// Pay the assignee's fresh deposit out to the landlord, who holds the
+/// lease's deposit, and refund the outgoing tenant's old deposit from them.
+fn swap_deposit(env: &Env, landlord: &Address, assignment: &LeaseAssignment, old_deposit: i128) {
+    let token = token::Client::new(env, &assignment.deposit_token);
+    token.transfer(
+        &env.current_contract_address(),
+        landlord,
+        &assignment.new_deposit,
+    );
+    if old_deposit > 0 {
+        token.transfer(landlord, &assignment.from_tenant, &old_deposit);
+    }
+}
+
+fn deposit_in_escrow(env: &Env, agreement_id: &String) -> bool {
+    get_deposit_record(env, agreement_id.clone()).is_some_and(|record| {
+        !matches!(
+            record.status,
+            DepositStatus::Released | DepositStatus::Refunded
+        )
+    })
+}
+
+fn pending_assignment(env: &Env, agreement_id: &String) -> Result<LeaseAssignment, Error> {
+    match get_lease_assignment(env, agreement_id.clone()) {
+        Some(assignment)
+            if assignment.status == AssignmentStatus::Nominated
+                || assignment.status == AssignmentStatus::Accepted =>
+        {
+            Ok(assignment)
+        }
+        _ => Err(AssignmentError::AssignmentNotFound.into()),
+    }
+}
+
+/// Latest assignment for an agreement, including completed or cancelled ones.
+pub fn get_lease_assignment(env: &Env, agreement_id: String) -> Option<LeaseAssignment> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::LeaseAssignment(agreement_id))
+}
@@ -0,0 +1,95 @@
+//! Security deposits held by the escrow contract.
+//!
+//! The platform admin registers one escrow contract. Escrows created for an
+//! agreement report each status change back here, so the agreement always
+//! knows which escrow holds its deposit and whether it is held, disputed,
+//! released or refunded.
+use soroban_sdk::{Address, BytesN, Env, Error, String};
+
+use crate::errors::{DepositEscrowError, RentalError};
+use crate::events;
+use crate::storage::{DataKey, DepositEscrowKey};
+use crate::types::{DepositRecord, DepositStatus, DepositTerms};
+
+const TTL_THRESHOLD: u32 = 500000;
+const TTL_BUMP: u32 = 500000;
+
+pub fn set_escrow_contract(env: &Env, escrow_contract: Address) {
+    env.storage().instance().set(
+        &DataKey::DepositEscrow(DepositEscrowKey::Contract),
+        &escrow_contract,
+    );
+}
+
+pub fn get_escrow_contract(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::DepositEscrow(DepositEscrowKey::Contract))
+}
+
+pub fn get_deposit_record(env: &Env, agreement_id: String) -> Option<DepositRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DepositEscrow(DepositEscrowKey::Record(
+            agreement_id,
+        )))
+}
+
+/// The deposit terms an escrow is created against.
+pub fn get_deposit_terms(env: &Env, agreement_id: String) -> Option<DepositTerms> {
+    let agreement = crate::storage::get_agreement(env, &agreement_id)?;
+    Some(DepositTerms {
+        agreement_id,
+        landlord: agreement.admin,
+        tenant: agreement.user,
+        security_deposit: agreement.security_deposit,
+        payment_token: agreement.payment_token,
+        status: agreement.status,
+    })
+}
+
+/// Record the status of the escrow holding an agreement's deposit. Only the
+/// registered escrow contract may report, and a deposit that is still held
+/// cannot be taken over by another escrow.
+pub fn update_deposit_status(
+    env: &Env,
+    agreement_id: String,
+    escrow_id: BytesN<32>,
+    status: DepositStatus,
+) -> Result<(), Error> {
+    let escrow_contract =
+        get_escrow_contract(env).ok_or(DepositEscrowError::EscrowContractNotSet)?;
+    escrow_contract.require_auth();
+
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::Agreement(agreement_id.clone()))
+    {
+        return Err(RentalError::AgreementNotFound.into());
+    }
+
+    if let Some(record) = get_deposit_record(env, agreement_id.clone()) {
+        let settled = matches!(
+            record.status,
+            DepositStatus::Released | DepositStatus::Refunded
+        );
+        if record.escrow_id != escrow_id && !settled {
+            return Err(RentalError::InvalidState.into());
+        }
+    }
+
+    let record = DepositRecord {
+        escrow_id: escrow_id.clone(),
+        status: status.clone(),
+        updated_at: env.ledger().timestamp(),
+    };
+    let key = DataKey::DepositEscrow(DepositEscrowKey::Record(agreement_id.clone()));
+    env.storage().persistent().set(&key, &record);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+
+    events::deposit_status_updated(env, agreement_id, escrow_id, status);
+    Ok(())
+}
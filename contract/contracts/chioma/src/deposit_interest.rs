@@ -70,10 +70,7 @@ pub fn set_deposit_interest_config(
         .has(&DataKey::DepositInterest(agreement_id.clone()))
     {
         // Read principal from the agreement.
-        let agreement = env
-            .storage()
-            .persistent()
-            .get::<DataKey, crate::types::RentAgreement>(&DataKey::Agreement(agreement_id.clone()))
+        let agreement = crate::storage::get_agreement(&env, &agreement_id)
             .ok_or(RentalError::AgreementNotFound)?;
 
         let principal = agreement.security_deposit;
@@ -271,11 +268,8 @@ pub fn distribute_interest(env: Env, escrow_id: String) -> Result<(), RentalErro
         return Ok(());
     }
 
-    let agreement = env
-        .storage()
-        .persistent()
-        .get::<DataKey, crate::types::RentAgreement>(&DataKey::Agreement(escrow_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement =
+        crate::storage::get_agreement(&env, &escrow_id).ok_or(RentalError::AgreementNotFound)?;
 
     let (user_share, admin_share) = match config.interest_recipient {
         InterestRecipient::Tenant => (total, 0_i128),
@@ -0,0 +1,296 @@
+//! Lease document anchoring and attestation.
+//!
+//! While an agreement is a draft the landlord may anchor and re-anchor the
+//! document hash freely. Submitting records the landlord's attestation to
+//! the current hash and signing records the tenant's. From then on any
+//! change is a new `DocumentVersion` that only takes effect once both
+//! parties have attested to its exact hash.
+use soroban_sdk::{Address, BytesN, Env, Error, String, Vec};
+
+use crate::errors::{DocumentError, RentalError};
+use crate::events;
+use crate::storage::{DataKey, DocumentKey};
+use crate::types::{AgreementStatus, Attribute, DocumentVersion, RentAgreement};
+
+const TTL_THRESHOLD: u32 = 500000;
+const TTL_BUMP: u32 = 500000;
+
+fn load_agreement(env: &Env, agreement_id: &String) -> Result<RentAgreement, Error> {
+    crate::storage::get_agreement(env, agreement_id).ok_or(RentalError::AgreementNotFound.into())
+}
+
+fn save_version(env: &Env, agreement_id: &String, version: &DocumentVersion) {
+    let key = DataKey::Document(DocumentKey::Version(agreement_id.clone(), version.version));
+    env.storage().persistent().set(&key, version);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+fn append_version(env: &Env, agreement_id: &String, version: &DocumentVersion) {
+    save_version(env, agreement_id, version);
+    env.storage().persistent().set(
+        &DataKey::Document(DocumentKey::VersionCount(agreement_id.clone())),
+        &version.version,
+    );
+}
+
+/// Append a version attested by `proposer` and return its number.
+fn push_version(
+    env: &Env,
+    agreement: &RentAgreement,
+    proposer: &Address,
+    document_hash: BytesN<32>,
+    metadata_uri: String,
+    attributes: Vec<Attribute>,
+) -> u32 {
+    let now = env.ledger().timestamp();
+    let version = get_document_version_count(env, agreement.agreement_id.clone()) + 1;
+    let is_landlord = *proposer == agreement.admin;
+    let entry = DocumentVersion {
+        version,
+        document_hash: document_hash.clone(),
+        metadata_uri,
+        attributes,
+        proposed_by: proposer.clone(),
+        proposed_at: now,
+        landlord_signed_at: if is_landlord { Some(now) } else { None },
+        tenant_signed_at: if is_landlord { None } else { Some(now) },
+    };
+    append_version(env, &agreement.agreement_id, &entry);
+
+    events::document_proposed(
+        env,
+        agreement.agreement_id.clone(),
+        version,
+        document_hash.clone(),
+        proposer.clone(),
+    );
+    events::document_attested(
+        env,
+        agreement.agreement_id.clone(),
+        version,
+        document_hash,
+        proposer.clone(),
+    );
+    version
+}
+
+/// Set the document hash, URI and attributes of a draft agreement.
+pub fn anchor_document(
+    env: &Env,
+    agreement_id: String,
+    document_hash: BytesN<32>,
+    metadata_uri: String,
+    attributes: Vec<Attribute>,
+) -> Result<(), Error> {
+    let mut agreement = load_agreement(env, &agreement_id)?;
+    agreement.admin.require_auth();
+
+    if agreement.status != AgreementStatus::Draft {
+        return Err(DocumentError::ConsentRequired.into());
+    }
+
+    agreement.document_hash = Some(document_hash.clone());
+    agreement.metadata_uri = metadata_uri;
+    agreement.attributes = attributes;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+
+    events::document_anchored(env, agreement_id, document_hash, agreement.admin);
+    Ok(())
+}
+
+/// Record the landlord's attestation to the anchored document on submission.
+pub(crate) fn attest_on_submit(env: &Env, agreement: &mut RentAgreement) {
+    if let Some(hash) = agreement.document_hash.clone() {
+        agreement.document_version = push_version(
+            env,
+            agreement,
+            &agreement.admin.clone(),
+            hash,
+            agreement.metadata_uri.clone(),
+            agreement.attributes.clone(),
+        );
+    }
+}
+
+/// Record the tenant's attestation to the current document on signing.
+/// `document_hash` is the hash the tenant signed; it must be the one in force.
+pub(crate) fn attest_on_sign(
+    env: &Env,
+    agreement: &RentAgreement,
+    document_hash: &Option<BytesN<32>>,
+) -> Result<(), Error> {
+    if agreement.document_hash != *document_hash {
+        return Err(DocumentError::DocumentHashMismatch.into());
+    }
+    if agreement.document_version == 0 {
+        return Ok(());
+    }
+    let mut version = get_document_version(
+        env,
+        agreement.agreement_id.clone(),
+        agreement.document_version,
+    )?;
+    if version.tenant_signed_at.is_some() {
+        return Ok(());
+    }
+    version.tenant_signed_at = Some(env.ledger().timestamp());
+    save_version(env, &agreement.agreement_id, &version);
+    events::document_attested(
+        env,
+        agreement.agreement_id.clone(),
+        version.version,
+        version.document_hash,
+        agreement.user.clone(),
+    );
+    Ok(())
+}
+
+/// Record a document changed by an applied amendment. Every required party
+/// accepted the amendment, so the version is attested by both and takes
+/// effect at once. The caller saves `agreement`.
+pub(crate) fn apply_amended_document(
+    env: &Env,
+    agreement: &mut RentAgreement,
+    proposer: &Address,
+    document_hash: BytesN<32>,
+    metadata_uri: String,
+) {
+    let now = env.ledger().timestamp();
+    let entry = DocumentVersion {
+        version: get_document_version_count(env, agreement.agreement_id.clone()) + 1,
+        document_hash: document_hash.clone(),
+        metadata_uri: metadata_uri.clone(),
+        attributes: agreement.attributes.clone(),
+        proposed_by: proposer.clone(),
+        proposed_at: now,
+        landlord_signed_at: Some(now),
+        tenant_signed_at: Some(now),
+    };
+    append_version(env, &agreement.agreement_id, &entry);
+
+    events::document_proposed(
+        env,
+        agreement.agreement_id.clone(),
+        entry.version,
+        document_hash.clone(),
+        proposer.clone(),
+    );
+    for party in [agreement.admin.clone(), agreement.user.clone()] {
+        events::document_attested(
+            env,
+            agreement.agreement_id.clone(),
+            entry.version,
+            document_hash.clone(),
+            party,
+        );
+    }
+
+    agreement.document_hash = Some(document_hash);
+    agreement.document_version = entry.version;
+    agreement.metadata_uri = metadata_uri;
+}
+
+/// Propose a new document for a submitted agreement. The proposer's
+/// attestation is recorded; the change applies once the other party attests.
+/// A newer proposal supersedes one that is still pending.
+pub fn propose_document_update(
+    env: &Env,
+    caller: Address,
+    agreement_id: String,
+    document_hash: BytesN<32>,
+    metadata_uri: String,
+    attributes: Vec<Attribute>,
+) -> Result<u32, Error> {
+    caller.require_auth();
+
+    let agreement = load_agreement(env, &agreement_id)?;
+    if caller != agreement.admin && caller != agreement.user {
+        return Err(RentalError::Unauthorized.into());
+    }
+    match agreement.status {
+        AgreementStatus::Pending | AgreementStatus::PendingApproval | AgreementStatus::Active => {}
+        _ => return Err(RentalError::InvalidState.into()),
+    }
+
+    Ok(push_version(
+        env,
+        &agreement,
+        &caller,
+        document_hash,
+        metadata_uri,
+        attributes,
+    ))
+}
+
+/// Attest to a pending document version. `document_hash` must match the
+/// version exactly, so a party only ever consents to the document they saw.
+pub fn attest_document(
+    env: &Env,
+    caller: Address,
+    agreement_id: String,
+    version: u32,
+    document_hash: BytesN<32>,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    let mut agreement = load_agreement(env, &agreement_id)?;
+    let mut entry = get_document_version(env, agreement_id.clone(), version)?;
+    if version != get_document_version_count(env, agreement_id.clone())
+        || version <= agreement.document_version
+    {
+        return Err(RentalError::InvalidState.into());
+    }
+    if entry.document_hash != document_hash {
+        return Err(DocumentError::DocumentHashMismatch.into());
+    }
+
+    let now = env.ledger().timestamp();
+    let slot = if caller == agreement.admin {
+        &mut entry.landlord_signed_at
+    } else if caller == agreement.user {
+        &mut entry.tenant_signed_at
+    } else {
+        return Err(RentalError::Unauthorized.into());
+    };
+    if slot.is_some() {
+        return Err(RentalError::AlreadyApproved.into());
+    }
+    *slot = Some(now);
+    save_version(env, &agreement_id, &entry);
+    events::document_attested(env, agreement_id.clone(), version, document_hash, caller);
+
+    // Both parties have now attested (the proposer did when proposing).
+    agreement.document_hash = Some(entry.document_hash);
+    agreement.document_version = version;
+    agreement.metadata_uri = entry.metadata_uri;
+    agreement.attributes = entry.attributes;
+    env.storage()
+        .persistent()
+        .set(&DataKey::Agreement(agreement_id), &agreement);
+    Ok(())
+}
+
+pub fn get_document_version(
+    env: &Env,
+    agreement_id: String,
+    version: u32,
+) -> Result<DocumentVersion, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Document(DocumentKey::Version(
+            agreement_id,
+            version,
+        )))
+        .ok_or(DocumentError::DocumentVersionNotFound.into())
+}
+
+pub fn get_document_version_count(env: &Env, agreement_id: String) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Document(DocumentKey::VersionCount(agreement_id)))
+        .unwrap_or(0)
+}
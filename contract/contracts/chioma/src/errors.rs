@@ -169,6 +169,145 @@ impl RentalError {
     }
 }
 
+/// Errors raised while resolving exchange rates from the price oracle.
+///
+/// `RentalError` is at the 50-case limit of a contract error enum, so each
+/// feature module gets its own enum and code range.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum OracleError {
+    /// The oracle quote or stored rate is older than `max_age`.
+    RateStale = 1200,
+    /// The oracle rate moved more than `max_deviation_bps` from the last
+    /// accepted rate.
+    RateDeviationTooLarge = 1201,
+    /// No oracle is configured, or it returned no usable quote.
+    OracleUnavailable = 1202,
+}
+
+/// Errors raised by payment token checks and token migrations.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TokenError {
+    /// Settling the rent would cost more than the payer's maximum.
+    SlippageExceeded = 1300,
+    /// The token's policy does not allow this kind of payment.
+    TokenUsageNotAllowed = 1301,
+    /// The amount is below the token's `min_amount`.
+    AmountBelowTokenMinimum = 1302,
+    /// The amount is above the token's `max_amount`.
+    AmountAboveTokenMaximum = 1303,
+    /// The payment would exceed the token's daily cap.
+    TokenDailyCapExceeded = 1304,
+    /// The agreement's payment token is still enabled.
+    MigrationNotRequired = 1305,
+    /// No token migration is pending for the agreement.
+    MigrationNotFound = 1306,
+}
+
+/// Errors raised by lease amendments.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AmendmentError {
+    /// No amendment or terms version with that number.
+    AmendmentNotFound = 1400,
+    /// The amendment's effective date has not been reached.
+    AmendmentNotEffective = 1401,
+}
+
+/// Errors raised by lease assignment and subletting.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum AssignmentError {
+    /// No assignment is pending for the agreement.
+    AssignmentNotFound = 1500,
+    /// Rent is still outstanding, so the lease cannot be handed over.
+    OutstandingBalance = 1501,
+}
+
+/// Errors raised when accepting or revoking a signed lease offer.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum OfferError {
+    /// The offer's `expires_at` has passed.
+    OfferExpired = 1600,
+    /// The offer's nonce was already accepted or revoked.
+    OfferNonceUsed = 1601,
+    /// The landlord has not registered an offer signing key.
+    SigningKeyNotFound = 1602,
+}
+
+/// Errors raised by lease document anchoring and attestation.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DocumentError {
+    /// The agreement is past draft; changes need both parties' attestation.
+    ConsentRequired = 1700,
+    /// The attested hash differs from the proposed document.
+    DocumentHashMismatch = 1701,
+    /// No document version with that number.
+    DocumentVersionNotFound = 1702,
+}
+
+/// Errors raised by witness policies.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum WitnessError {
+    /// The address may not witness the agreement under its policy.
+    WitnessNotAllowed = 1800,
+}
+
+/// Errors raised by default notices and default termination.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ArrearsError {
+    /// Arrears do not meet the agreement's arrears policy.
+    ArrearsBelowThreshold = 1900,
+    /// No open default notice for the agreement.
+    DefaultNoticeNotFound = 1901,
+    /// A default notice is already open for the agreement.
+    DefaultNoticeOpen = 1902,
+    /// The notice's cure period has not ended.
+    CurePeriodActive = 1903,
+}
+
+/// Errors raised by arrears payment plans.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PaymentPlanError {
+    /// No payment plan in the required state for the agreement.
+    PaymentPlanNotFound = 2000,
+    /// A payment plan is already active for the agreement.
+    PaymentPlanActive = 2001,
+}
+
+/// Errors raised by deposit status reports from the escrow contract.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DepositEscrowError {
+    /// No escrow contract is registered for security deposits.
+    EscrowContractNotSet = 2100,
+}
+
+/// Return a feature error through a function declared with `RentalError`.
+///
+/// Feature modules return `soroban_sdk::Error` so that their own error
+/// enums and `RentalError` can share one `?`. A `RentalError` passes through
+/// unchanged; any other error aborts the invocation with that error.
+pub fn into_rental(env: &Env, error: soroban_sdk::Error) -> RentalError {
+    RentalError::try_from(error).unwrap_or_else(|error| env.panic_with_error(error))
+}
+
 pub fn log_error(
     env: &Env,
     error: RentalError,
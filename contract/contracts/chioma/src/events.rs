@@ -1,5 +1,6 @@
+use crate::types::{AssignmentMode, DepositStatus, TokenUsage};
 use crate::Config;
-use soroban_sdk::{contractevent, Address, Env, String};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String, Symbol};
 
 /// Event emitted when the contract is initialized
 /// Topics: ["initialized", admin: Address]
@@ -223,6 +224,44 @@ pub struct ExchangeRateUpdated {
     pub rate: i128,
 }
 
+#[contractevent]
+pub struct UnitRateUpdated {
+    pub currency: Symbol,
+    pub token: Address,
+    pub rate: i128,
+}
+
+#[contractevent]
+pub struct RateSourceUpdated {
+    pub oracle: Option<Address>,
+    pub base_token: Option<Address>,
+    pub max_age: u64,
+    pub max_deviation_bps: u32,
+}
+
+#[contractevent]
+pub struct TokenPolicyUpdated {
+    pub token: Address,
+    pub usage: TokenUsage,
+    pub daily_cap: i128,
+}
+
+#[contractevent]
+pub struct TokenMigrationProposed {
+    pub agreement_id: String,
+    pub from_token: Address,
+    pub to_token: Address,
+    pub proposed_by: Address,
+}
+
+#[contractevent]
+pub struct TokenMigrated {
+    pub agreement_id: String,
+    pub from_token: Address,
+    pub to_token: Address,
+    pub monthly_rent: i128,
+}
+
 #[contractevent]
 pub struct PaymentMadeWithToken {
     pub agreement_id: String,
@@ -254,6 +293,72 @@ pub(crate) fn exchange_rate_updated(env: &Env, from_token: Address, to_token: Ad
     .publish(env);
 }
 
+pub(crate) fn unit_rate_updated(env: &Env, currency: Symbol, token: Address, rate: i128) {
+    UnitRateUpdated {
+        currency,
+        token,
+        rate,
+    }
+    .publish(env);
+}
+
+pub(crate) fn rate_source_updated(
+    env: &Env,
+    oracle: Option<Address>,
+    base_token: Option<Address>,
+    max_age: u64,
+    max_deviation_bps: u32,
+) {
+    RateSourceUpdated {
+        oracle,
+        base_token,
+        max_age,
+        max_deviation_bps,
+    }
+    .publish(env);
+}
+
+pub(crate) fn token_policy_updated(env: &Env, token: Address, usage: TokenUsage, daily_cap: i128) {
+    TokenPolicyUpdated {
+        token,
+        usage,
+        daily_cap,
+    }
+    .publish(env);
+}
+
+pub(crate) fn token_migration_proposed(
+    env: &Env,
+    agreement_id: String,
+    from_token: Address,
+    to_token: Address,
+    proposed_by: Address,
+) {
+    TokenMigrationProposed {
+        agreement_id,
+        from_token,
+        to_token,
+        proposed_by,
+    }
+    .publish(env);
+}
+
+pub(crate) fn token_migrated(
+    env: &Env,
+    agreement_id: String,
+    from_token: Address,
+    to_token: Address,
+    monthly_rent: i128,
+) {
+    TokenMigrated {
+        agreement_id,
+        from_token,
+        to_token,
+        monthly_rent,
+    }
+    .publish(env);
+}
+
 pub(crate) fn payment_made_with_token(
     env: &Env,
     agreement_id: String,
@@ -682,6 +787,650 @@ pub(crate) fn extension_cancelled(env: &Env, extension_id: String) {
     ExtensionCancelled { extension_id }.publish(env);
 }
 
+// ─── Agreement Amendment Events ─────────────────────────────────────────────
+
+#[contractevent(topics = ["amendment_proposed"])]
+pub struct AmendmentProposed {
+    #[topic]
+    pub agreement_id: String,
+    pub amendment_id: u32,
+    pub proposed_by: Address,
+    pub effective_date: u64,
+}
+
+#[contractevent(topics = ["amendment_accepted"])]
+pub struct AmendmentAccepted {
+    #[topic]
+    pub agreement_id: String,
+    pub amendment_id: u32,
+    pub accepted_by: Address,
+}
+
+#[contractevent(topics = ["amendment_rejected"])]
+pub struct AmendmentRejected {
+    #[topic]
+    pub agreement_id: String,
+    pub amendment_id: u32,
+}
+
+#[contractevent(topics = ["amendment_cancelled"])]
+pub struct AmendmentCancelled {
+    #[topic]
+    pub agreement_id: String,
+    pub amendment_id: u32,
+}
+
+#[contractevent(topics = ["amendment_applied"])]
+pub struct AmendmentApplied {
+    #[topic]
+    pub agreement_id: String,
+    pub amendment_id: u32,
+    pub version: u32,
+}
+
+pub(crate) fn amendment_proposed(
+    env: &Env,
+    agreement_id: String,
+    amendment_id: u32,
+    proposed_by: Address,
+    effective_date: u64,
+) {
+    AmendmentProposed {
+        agreement_id,
+        amendment_id,
+        proposed_by,
+        effective_date,
+    }
+    .publish(env);
+}
+
+pub(crate) fn amendment_accepted(
+    env: &Env,
+    agreement_id: String,
+    amendment_id: u32,
+    accepted_by: Address,
+) {
+    AmendmentAccepted {
+        agreement_id,
+        amendment_id,
+        accepted_by,
+    }
+    .publish(env);
+}
+
+pub(crate) fn amendment_rejected(env: &Env, agreement_id: String, amendment_id: u32) {
+    AmendmentRejected {
+        agreement_id,
+        amendment_id,
+    }
+    .publish(env);
+}
+
+pub(crate) fn amendment_cancelled(env: &Env, agreement_id: String, amendment_id: u32) {
+    AmendmentCancelled {
+        agreement_id,
+        amendment_id,
+    }
+    .publish(env);
+}
+
+pub(crate) fn amendment_applied(env: &Env, agreement_id: String, amendment_id: u32, version: u32) {
+    AmendmentApplied {
+        agreement_id,
+        amendment_id,
+        version,
+    }
+    .publish(env);
+}
+
+// ─── Lease Document Events ──────────────────────────────────────────────────
+
+#[contractevent(topics = ["document_proposed"])]
+pub struct DocumentProposed {
+    #[topic]
+    pub agreement_id: String,
+    pub version: u32,
+    pub document_hash: BytesN<32>,
+    pub proposed_by: Address,
+}
+
+#[contractevent(topics = ["document_attested"])]
+pub struct DocumentAttested {
+    #[topic]
+    pub agreement_id: String,
+    pub version: u32,
+    pub document_hash: BytesN<32>,
+    pub signer: Address,
+}
+
+#[contractevent(topics = ["document_anchored"])]
+pub struct DocumentAnchored {
+    #[topic]
+    pub agreement_id: String,
+    pub document_hash: BytesN<32>,
+    pub landlord: Address,
+}
+
+pub(crate) fn document_anchored(
+    env: &Env,
+    agreement_id: String,
+    document_hash: BytesN<32>,
+    landlord: Address,
+) {
+    DocumentAnchored {
+        agreement_id,
+        document_hash,
+        landlord,
+    }
+    .publish(env);
+}
+
+pub(crate) fn document_proposed(
+    env: &Env,
+    agreement_id: String,
+    version: u32,
+    document_hash: BytesN<32>,
+    proposed_by: Address,
+) {
+    DocumentProposed {
+        agreement_id,
+        version,
+        document_hash,
+        proposed_by,
+    }
+    .publish(env);
+}
+
+pub(crate) fn document_attested(
+    env: &Env,
+    agreement_id: String,
+    version: u32,
+    document_hash: BytesN<32>,
+    signer: Address,
+) {
+    DocumentAttested {
+        agreement_id,
+        version,
+        document_hash,
+        signer,
+    }
+    .publish(env);
+}
+
+// ─── Lease Assignment Events ────────────────────────────────────────────────
+
+#[contractevent(topics = ["assignment_nominated"])]
+pub struct AssignmentNominated {
+    #[topic]
+    pub agreement_id: String,
+    pub mode: AssignmentMode,
+    pub from_tenant: Address,
+    pub to_tenant: Address,
+}
+
+#[contractevent(topics = ["assignment_accepted"])]
+pub struct AssignmentAccepted {
+    #[topic]
+    pub agreement_id: String,
+    pub to_tenant: Address,
+    pub deposit: i128,
+}
+
+#[contractevent(topics = ["assignment_completed"])]
+pub struct AssignmentCompleted {
+    #[topic]
+    pub agreement_id: String,
+    pub mode: AssignmentMode,
+    pub from_tenant: Address,
+    pub to_tenant: Address,
+}
+
+#[contractevent(topics = ["assignment_cancelled"])]
+pub struct AssignmentCancelled {
+    #[topic]
+    pub agreement_id: String,
+    pub cancelled_by: Address,
+}
+
+#[contractevent(topics = ["sublet_ended"])]
+pub struct SubletEnded {
+    #[topic]
+    pub agreement_id: String,
+    pub subtenant: Address,
+}
+
+pub(crate) fn assignment_nominated(
+    env: &Env,
+    agreement_id: String,
+    mode: AssignmentMode,
+    from_tenant: Address,
+    to_tenant: Address,
+) {
+    AssignmentNominated {
+        agreement_id,
+        mode,
+        from_tenant,
+        to_tenant,
+    }
+    .publish(env);
+}
+
+pub(crate) fn assignment_accepted(
+    env: &Env,
+    agreement_id: String,
+    to_tenant: Address,
+    deposit: i128,
+) {
+    AssignmentAccepted {
+        agreement_id,
+        to_tenant,
+        deposit,
+    }
+    .publish(env);
+}
+
+pub(crate) fn assignment_completed(
+    env: &Env,
+    agreement_id: String,
+    mode: AssignmentMode,
+    from_tenant: Address,
+    to_tenant: Address,
+) {
+    AssignmentCompleted {
+        agreement_id,
+        mode,
+        from_tenant,
+        to_tenant,
+    }
+    .publish(env);
+}
+
+pub(crate) fn assignment_cancelled(env: &Env, agreement_id: String, cancelled_by: Address) {
+    AssignmentCancelled {
+        agreement_id,
+        cancelled_by,
+    }
+    .publish(env);
+}
+
+pub(crate) fn sublet_ended(env: &Env, agreement_id: String, subtenant: Address) {
+    SubletEnded {
+        agreement_id,
+        subtenant,
+    }
+    .publish(env);
+}
+
+// ─── Signed Offer Events ────────────────────────────────────────────────────
+
+#[contractevent(topics = ["offer_key_set"])]
+pub struct OfferKeySet {
+    #[topic]
+    pub landlord: Address,
+    pub public_key: BytesN<32>,
+}
+
+#[contractevent(topics = ["offer_accepted"])]
+pub struct OfferAccepted {
+    #[topic]
+    pub agreement_id: String,
+    pub landlord: Address,
+    pub tenant: Address,
+    pub nonce: u64,
+}
+
+#[contractevent(topics = ["offer_revoked"])]
+pub struct OfferRevoked {
+    #[topic]
+    pub landlord: Address,
+    pub nonce: u64,
+}
+
+pub(crate) fn offer_key_set(env: &Env, landlord: Address, public_key: BytesN<32>) {
+    OfferKeySet {
+        landlord,
+        public_key,
+    }
+    .publish(env);
+}
+
+pub(crate) fn offer_accepted(
+    env: &Env,
+    agreement_id: String,
+    landlord: Address,
+    tenant: Address,
+    nonce: u64,
+) {
+    OfferAccepted {
+        agreement_id,
+        landlord,
+        tenant,
+        nonce,
+    }
+    .publish(env);
+}
+
+pub(crate) fn offer_revoked(env: &Env, landlord: Address, nonce: u64) {
+    OfferRevoked { landlord, nonce }.publish(env);
+}
+
+// ─── Witness Events ─────────────────────────────────────────────────────────
+
+#[contractevent(topics = ["witness_policy_set"])]
+pub struct WitnessPolicySet {
+    #[topic]
+    pub agreement_id: String,
+    pub required_witnesses: u32,
+}
+
+#[contractevent(topics = ["witness_recorded"])]
+pub struct WitnessRecorded {
+    #[topic]
+    pub agreement_id: String,
+    pub witness: Address,
+    pub witness_count: u32,
+    pub required_witnesses: u32,
+}
+
+#[contractevent(topics = ["notary_added"])]
+pub struct NotaryAdded {
+    #[topic]
+    pub notary: Address,
+}
+
+#[contractevent(topics = ["notary_removed"])]
+pub struct NotaryRemoved {
+    #[topic]
+    pub notary: Address,
+}
+
+#[contractevent(topics = ["profile_contract_set"])]
+pub struct ProfileContractSet {
+    #[topic]
+    pub profile_contract: Address,
+}
+
+pub(crate) fn witness_policy_set(env: &Env, agreement_id: String, required_witnesses: u32) {
+    WitnessPolicySet {
+        agreement_id,
+        required_witnesses,
+    }
+    .publish(env);
+}
+
+pub(crate) fn witness_recorded(
+    env: &Env,
+    agreement_id: String,
+    witness: Address,
+    witness_count: u32,
+    required_witnesses: u32,
+) {
+    WitnessRecorded {
+        agreement_id,
+        witness,
+        witness_count,
+        required_witnesses,
+    }
+    .publish(env);
+}
+
+pub(crate) fn notary_added(env: &Env, notary: Address) {
+    NotaryAdded { notary }.publish(env);
+}
+
+pub(crate) fn notary_removed(env: &Env, notary: Address) {
+    NotaryRemoved { notary }.publish(env);
+}
+
+pub(crate) fn profile_contract_set(env: &Env, profile_contract: Address) {
+    ProfileContractSet { profile_contract }.publish(env);
+}
+
+// ─── Deposit Escrow Events ──────────────────────────────────────────────────
+
+#[contractevent(topics = ["deposit_status_updated"])]
+pub struct DepositStatusUpdated {
+    #[topic]
+    pub agreement_id: String,
+    pub escrow_id: BytesN<32>,
+    pub status: DepositStatus,
+}
+
+pub(crate) fn deposit_status_updated(
+    env: &Env,
+    agreement_id: String,
+    escrow_id: BytesN<32>,
+    status: DepositStatus,
+) {
+    DepositStatusUpdated {
+        agreement_id,
+        escrow_id,
+        status,
+    }
+    .publish(env);
+}
+
+// ─── Arrears Events ─────────────────────────────────────────────────────────
+
+#[contractevent(topics = ["arrears_policy_set"])]
+pub struct ArrearsPolicySet {
+    #[topic]
+    pub agreement_id: String,
+    pub missed_periods: u32,
+    pub arrears_threshold: i128,
+    pub cure_period: u64,
+}
+
+#[contractevent(topics = ["default_notice_issued"])]
+pub struct DefaultNoticeIssued {
+    #[topic]
+    pub agreement_id: String,
+    pub tenant: Address,
+    pub arrears: i128,
+    pub cure_deadline: u64,
+}
+
+#[contractevent(topics = ["default_cured"])]
+pub struct DefaultCured {
+    #[topic]
+    pub agreement_id: String,
+    pub tenant: Address,
+}
+
+#[contractevent(topics = ["agreement_defaulted"])]
+pub struct AgreementDefaulted {
+    #[topic]
+    pub agreement_id: String,
+    pub tenant: Address,
+    pub arrears: i128,
+    pub deposit_claim: i128,
+}
+
+pub(crate) fn arrears_policy_set(
+    env: &Env,
+    agreement_id: String,
+    missed_periods: u32,
+    arrears_threshold: i128,
+    cure_period: u64,
+) {
+    ArrearsPolicySet {
+        agreement_id,
+        missed_periods,
+        arrears_threshold,
+        cure_period,
+    }
+    .publish(env);
+}
+
+pub(crate) fn default_notice_issued(
+    env: &Env,
+    agreement_id: String,
+    tenant: Address,
+    arrears: i128,
+    cure_deadline: u64,
+) {
+    DefaultNoticeIssued {
+        agreement_id,
+        tenant,
+        arrears,
+        cure_deadline,
+    }
+    .publish(env);
+}
+
+pub(crate) fn default_cured(env: &Env, agreement_id: String, tenant: Address) {
+    DefaultCured {
+        agreement_id,
+        tenant,
+    }
+    .publish(env);
+}
+
+pub(crate) fn agreement_defaulted(
+    env: &Env,
+    agreement_id: String,
+    tenant: Address,
+    arrears: i128,
+    deposit_claim: i128,
+) {
+    AgreementDefaulted {
+        agreement_id,
+        tenant,
+        arrears,
+        deposit_claim,
+    }
+    .publish(env);
+}
+
+// ─── Payment Plan Events ────────────────────────────────────────────────────
+
+#[contractevent(topics = ["late_fees_charged"])]
+pub struct LateFeesCharged {
+    #[topic]
+    pub agreement_id: String,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["late_fees_paid"])]
+pub struct LateFeesPaid {
+    #[topic]
+    pub agreement_id: String,
+    pub payer: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["payment_plan_proposed"])]
+pub struct PaymentPlanProposed {
+    #[topic]
+    pub agreement_id: String,
+    pub proposed_by: Address,
+    pub instalments: u32,
+    pub waive_late_fees: bool,
+}
+
+#[contractevent(topics = ["payment_plan_accepted"])]
+pub struct PaymentPlanAccepted {
+    #[topic]
+    pub agreement_id: String,
+    pub arrears: i128,
+    pub instalments: u32,
+    pub instalment_amount: i128,
+}
+
+#[contractevent(topics = ["plan_instalment_met"])]
+pub struct PlanInstalmentMet {
+    #[topic]
+    pub agreement_id: String,
+    pub instalment: u32,
+}
+
+#[contractevent(topics = ["plan_instalment_missed"])]
+pub struct PlanInstalmentMissed {
+    #[topic]
+    pub agreement_id: String,
+    pub instalment: u32,
+}
+
+#[contractevent(topics = ["payment_plan_completed"])]
+pub struct PaymentPlanCompleted {
+    #[topic]
+    pub agreement_id: String,
+    pub waived_late_fees: i128,
+}
+
+pub(crate) fn late_fees_charged(env: &Env, agreement_id: String, amount: i128) {
+    LateFeesCharged {
+        agreement_id,
+        amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn late_fees_paid(env: &Env, agreement_id: String, payer: Address, amount: i128) {
+    LateFeesPaid {
+        agreement_id,
+        payer,
+        amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn payment_plan_proposed(
+    env: &Env,
+    agreement_id: String,
+    proposed_by: Address,
+    instalments: u32,
+    waive_late_fees: bool,
+) {
+    PaymentPlanProposed {
+        agreement_id,
+        proposed_by,
+        instalments,
+        waive_late_fees,
+    }
+    .publish(env);
+}
+
+pub(crate) fn payment_plan_accepted(
+    env: &Env,
+    agreement_id: String,
+    arrears: i128,
+    instalments: u32,
+    instalment_amount: i128,
+) {
+    PaymentPlanAccepted {
+        agreement_id,
+        arrears,
+        instalments,
+        instalment_amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn plan_instalment_met(env: &Env, agreement_id: String, instalment: u32) {
+    PlanInstalmentMet {
+        agreement_id,
+        instalment,
+    }
+    .publish(env);
+}
+
+pub(crate) fn plan_instalment_missed(env: &Env, agreement_id: String, instalment: u32) {
+    PlanInstalmentMissed {
+        agreement_id,
+        instalment,
+    }
+    .publish(env);
+}
+
+pub(crate) fn payment_plan_completed(env: &Env, agreement_id: String, waived_late_fees: i128) {
+    PaymentPlanCompleted {
+        agreement_id,
+        waived_late_fees,
+    }
+    .publish(env);
+}
+
 // ─── Contract Upgrade Events ────────────────────────────────────────────────
 
 #[contractevent(topics = ["upgrade_proposed"])]
@@ -0,0 +1,143 @@
+//! Lease offers signed off-chain by the landlord.
+//!
+//! A landlord registers an ed25519 public key once. They can then sign any
+//! number of `LeaseOffer`s off-chain, and the tenant creates, submits and
+//! signs the agreement in one transaction by presenting the offer with the
+//! landlord's signature. Each offer carries a nonce that can be used once
+//! and an expiry after which it is refused.
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Error, String};
+
+use crate::agreement::create_token_agreement;
+use crate::agreement_index;
+use crate::errors::{OfferError, RentalError};
+use crate::events;
+use crate::rate_limit;
+use crate::storage::{DataKey, OfferKey};
+use crate::types::{AgreementStatus, LeaseOffer, RentAgreement};
+
+/// Prefix for signed payloads, so an offer signature is never valid for
+/// any other message format.
+const OFFER_DOMAIN: &[u8] = b"chioma.lease_offer.v1";
+
+const TTL_THRESHOLD: u32 = 500000;
+const TTL_BUMP: u32 = 500000;
+
+/// Register or rotate the key the landlord signs offers with. Offers signed
+/// with a previous key stop verifying.
+pub fn set_offer_signing_key(
+    env: &Env,
+    landlord: Address,
+    public_key: BytesN<32>,
+) -> Result<(), Error> {
+    landlord.require_auth();
+
+    let key = DataKey::Offer(OfferKey::SigningKey(landlord.clone()));
+    env.storage().persistent().set(&key, &public_key);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+
+    events::offer_key_set(env, landlord, public_key);
+    Ok(())
+}
+
+pub fn get_offer_signing_key(env: &Env, landlord: Address) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Offer(OfferKey::SigningKey(landlord)))
+}
+
+/// Hash the landlord signs: sha256 of the domain prefix, this contract's
+/// address and the XDR-encoded offer.
+pub fn offer_hash(env: &Env, offer: &LeaseOffer) -> BytesN<32> {
+    let mut payload = Bytes::from_slice(env, OFFER_DOMAIN);
+    payload.append(&env.current_contract_address().to_xdr(env));
+    payload.append(&offer.clone().to_xdr(env));
+    env.crypto().sha256(&payload).to_bytes()
+}
+
+pub fn is_offer_nonce_used(env: &Env, landlord: Address, nonce: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Offer(OfferKey::Nonce(landlord, nonce)))
+}
+
+fn consume_nonce(env: &Env, landlord: &Address, nonce: u64) -> Result<(), Error> {
+    let key = DataKey::Offer(OfferKey::Nonce(landlord.clone(), nonce));
+    if env.storage().persistent().has(&key) {
+        return Err(OfferError::OfferNonceUsed.into());
+    }
+    env.storage().persistent().set(&key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    Ok(())
+}
+
+/// Invalidate an offer that has been handed out but not yet accepted.
+pub fn revoke_offer(env: &Env, landlord: Address, nonce: u64) -> Result<(), Error> {
+    landlord.require_auth();
+    consume_nonce(env, &landlord, nonce)?;
+    events::offer_revoked(env, landlord, nonce);
+    Ok(())
+}
+
+/// Create an agreement from a landlord-signed offer and sign it as the
+/// tenant. The agreement lands in `PendingApproval`, awaiting the witness.
+///
+/// An invalid signature traps inside `ed25519_verify` and aborts the call.
+pub fn accept_signed_offer(
+    env: &Env,
+    tenant: Address,
+    offer: LeaseOffer,
+    signature: BytesN<64>,
+) -> Result<String, Error> {
+    tenant.require_auth();
+    rate_limit::check_rate_limit(env, &tenant, "create_agreement")?;
+
+    if offer.input.user != tenant {
+        return Err(RentalError::NotTenant.into());
+    }
+    let now = env.ledger().timestamp();
+    if now > offer.expires_at {
+        return Err(OfferError::OfferExpired.into());
+    }
+    let landlord = offer.input.admin.clone();
+    if is_offer_nonce_used(env, landlord.clone(), offer.nonce) {
+        return Err(OfferError::OfferNonceUsed.into());
+    }
+    let public_key =
+        get_offer_signing_key(env, landlord.clone()).ok_or(OfferError::SigningKeyNotFound)?;
+
+    let message: Bytes = offer_hash(env, &offer).into();
+    env.crypto()
+        .ed25519_verify(&public_key, &message, &signature);
+
+    consume_nonce(env, &landlord, offer.nonce)?;
+    let agreement_id = create_token_agreement(env, offer.input)?;
+
+    let key = DataKey::Agreement(agreement_id.clone());
+    let mut agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::InternalError)?;
+    // The landlord signed the offer and the tenant authorized it, so each
+    // has attested to its document hash.
+    agreement.document_hash = offer.document_hash.clone();
+    crate::document::attest_on_submit(env, &mut agreement);
+    crate::document::attest_on_sign(env, &agreement, &offer.document_hash)?;
+    agreement_index::set_status(env, &mut agreement, AgreementStatus::PendingApproval);
+    agreement.signed_at = Some(now);
+    env.storage().persistent().set(&key, &agreement);
+
+    // Same events as the three-step flow, so indexers see no difference.
+    events::agreement_submitted(env, agreement_id.clone(), landlord.clone(), tenant.clone());
+    events::agreement_signed(
+        env,
+        agreement_id.clone(),
+        tenant.clone(),
+        landlord.clone(),
+        now,
+    );
+    events::offer_accepted(env, agreement_id.clone(), landlord, tenant, offer.nonce);
+
+    Ok(agreement_id)
+}
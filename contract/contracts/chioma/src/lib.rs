@@ -7,20 +7,30 @@
 //! @notice On-chain rental agreement lifecycle: create, sign, submit, cancel, and query agreements.
 //! Optimized for gas efficiency and security.
 
-use soroban_sdk::{contract, contractimpl, Address, Bytes, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, String, Symbol, Vec};
 
 mod agreement;
+mod agreement_index;
+mod amendment;
+mod arrears;
+mod assignment;
+mod deposit_escrow;
 mod deposit_interest;
+mod document;
 mod errors;
 mod events;
 mod gas_optimization;
+mod lease_offer;
 mod multi_sig;
 mod multi_token;
+mod oracle;
+mod payment_plan;
 mod rate_limit;
 mod royalties;
 mod storage;
 mod timelock;
 mod types;
+mod witness;
 
 #[cfg(test)]
 mod tests;
@@ -28,6 +38,42 @@ mod tests;
 #[cfg(test)]
 mod tests_multi_token;
 
+#[cfg(test)]
+mod tests_oracle;
+
+#[cfg(test)]
+mod tests_unit_of_account;
+
+#[cfg(test)]
+mod tests_token_policy;
+
+#[cfg(test)]
+mod tests_agreement_index;
+
+#[cfg(test)]
+mod tests_amendment;
+
+#[cfg(test)]
+mod tests_assignment;
+
+#[cfg(test)]
+mod tests_lease_offer;
+
+#[cfg(test)]
+mod tests_document;
+
+#[cfg(test)]
+mod tests_witness;
+
+#[cfg(test)]
+mod tests_arrears;
+
+#[cfg(test)]
+mod tests_payment_plan;
+
+#[cfg(test)]
+mod tests_deposit_escrow;
+
 #[cfg(test)]
 mod tests_deposit_interest;
 
@@ -60,32 +106,45 @@ mod tests_lease_negotiation;
 
 pub use agreement::{
     accept_extension, activate_extension, approve_agreement, cancel_agreement, cancel_extension,
-    create_agreement, create_agreement_with_token, get_agreement, get_agreement_count,
-    get_agreement_token, get_current_agreement_end, get_extension, get_extension_history,
-    get_payment_history, get_payment_split, has_agreement, is_escrow_frozen,
-    make_payment_with_token, propose_extension, reject_extension, release_escrow_with_token,
+    create_agreement, create_agreement_with_token, create_agreement_with_unit, get_agreement,
+    get_agreement_count, get_agreement_token, get_agreement_unit, get_current_agreement_end,
+    get_extension, get_extension_history, get_payment_history, get_payment_split,
+    get_token_migration, has_agreement, is_escrow_frozen, make_payment_with_token,
+    propose_extension, quote_rent_payment, reject_extension, release_escrow_with_token,
     set_escrow_frozen, sign_agreement, submit_agreement, update_metadata,
     validate_agreement_params,
 };
-pub use errors::RentalError;
+pub use agreement_index::MAX_PAGE_SIZE;
+pub use errors::{
+    AmendmentError, ArrearsError, AssignmentError, DepositEscrowError, DocumentError, OfferError,
+    OracleError, PaymentPlanError, RentalError, TokenError, WitnessError,
+};
 pub use gas_optimization::{
     estimate_gas_cost, get_gas_metrics, optimize_operation, GasMetrics, OperationType,
     OptimizationSuggestion,
 };
 pub use multi_token::{
     add_supported_token, convert_amount, get_exchange_rate, get_supported_tokens,
-    is_token_supported, remove_supported_token, set_exchange_rate,
+    is_token_supported, remove_supported_token, set_exchange_rate, set_rate_source,
+    set_token_policy, set_unit_rate, sync_exchange_rate, sync_unit_rate, RATE_SCALE,
 };
+pub use oracle::PriceOracleClient;
 pub use storage::DataKey;
 pub use types::{
-    ActionType, AdminProposal, AgreementExtension, AgreementInput, AgreementStatus, AgreementTerms,
-    AgreementWithToken, Attribute, CompoundingFrequency, Config, ContractState,
-    ContractUpgradeProposal, ContractVersion, DepositInterest, DepositInterestConfig, ErrorContext,
-    ExtensionHistory, ExtensionStatus, InterestAccrual, InterestRecipient, MultiSigConfig,
-    PauseState, PaymentSplit, RateLimitConfig, RateLimitReason, RentAgreement, RoyaltyConfig,
-    RoyaltyPayment, SupportedToken, TimelockAction, TimelockActionType, TokenExchangeRate,
-    UserCallCount, VersionStatus,
+    ActionType, AdminProposal, AgreementAmendment, AgreementExtension, AgreementIndex,
+    AgreementInput, AgreementPage, AgreementStatus, AgreementTerms, AgreementWithToken,
+    AmendmentChange, AmendmentStatus, ArrearsPolicy, AssignmentMode, AssignmentStatus, Attribute,
+    CompoundingFrequency, Config, ContractState, ContractUpgradeProposal, ContractVersion,
+    DefaultNotice, DefaultNoticeStatus, DepositInterest, DepositInterestConfig, DepositRecord,
+    DepositStatus, DepositTerms, DocumentVersion, ErrorContext, ExtensionHistory, ExtensionStatus,
+    InterestAccrual, InterestRecipient, LeaseAssignment, LeaseOffer, MultiSigConfig, OracleAsset,
+    PauseState, PaymentPlan, PaymentPlanStatus, PaymentSplit, PriceData, RateLimitConfig,
+    RateLimitReason, RateSourceConfig, RentAgreement, RoyaltyConfig, RoyaltyPayment,
+    SupportedToken, TermsVersion, TimelockAction, TimelockActionType, TokenExchangeRate,
+    TokenMigration, TokenPolicy, TokenUsage, UnitOfAccount, UserCallCount, VersionStatus,
+    WitnessPolicy,
 };
+pub use witness::UserProfileClient;
 
 /// Chioma rental agreement contract.
 ///
@@ -600,6 +659,27 @@ impl Contract {
         multi_token::is_token_supported(env, token_address)
     }
 
+    /// Set usage and daily volume limits for a supported token. Admin only.
+    pub fn set_token_policy(
+        env: Env,
+        token_address: Address,
+        policy: TokenPolicy,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env)?;
+        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
+        state.admin.require_auth();
+
+        multi_token::set_token_policy(env, token_address, policy)
+    }
+
+    pub fn get_token_policy(env: Env, token_address: Address) -> TokenPolicy {
+        multi_token::get_token_policy(&env, &token_address)
+    }
+
+    pub fn get_token_daily_volume(env: Env, token_address: Address) -> i128 {
+        multi_token::get_token_daily_volume(&env, &token_address)
+    }
+
     // --- Exchange Rate Functions ---
 
     pub fn set_exchange_rate(
@@ -637,6 +717,56 @@ impl Contract {
         Ok(())
     }
 
+    /// Configure the oracle, base token and rate guards used to resolve
+    /// exchange rates. Admin only.
+    pub fn set_rate_source(env: Env, config: RateSourceConfig) -> Result<(), RentalError> {
+        Self::check_paused(&env)?;
+        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
+        state.admin.require_auth();
+
+        multi_token::set_rate_source(env, config)
+    }
+
+    pub fn get_rate_source(env: Env) -> Option<RateSourceConfig> {
+        multi_token::get_rate_source(&env)
+    }
+
+    /// Store the current oracle rate for a pair as the new reference rate.
+    /// Permissionless so a keeper can track gradual price moves.
+    pub fn sync_exchange_rate(
+        env: Env,
+        from_token: Address,
+        to_token: Address,
+    ) -> Result<i128, soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        multi_token::sync_exchange_rate(env, from_token, to_token)
+    }
+
+    /// Reset the reference rate for an off-chain currency by hand.
+    pub fn set_unit_rate(
+        env: Env,
+        currency: Symbol,
+        token: Address,
+        rate: i128,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env)?;
+        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
+        state.admin.require_auth();
+
+        multi_token::set_unit_rate(env, currency, token, rate)
+    }
+
+    /// Store the current oracle rate for an off-chain currency as the new
+    /// reference rate. Permissionless, like `sync_exchange_rate`.
+    pub fn sync_unit_rate(
+        env: Env,
+        currency: Symbol,
+        token: Address,
+    ) -> Result<i128, soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        multi_token::sync_unit_rate(env, currency, token)
+    }
+
     pub fn convert_amount(
         env: Env,
         from_token: Address,
@@ -660,6 +790,30 @@ impl Contract {
         agreement::get_agreement_token(&env, agreement_id)
     }
 
+    /// Create an agreement whose rent is priced in an off-chain currency and
+    /// settled in supported tokens at the oracle rate.
+    pub fn create_agreement_with_unit(
+        env: Env,
+        input: crate::types::AgreementInput,
+        unit: UnitOfAccount,
+    ) -> Result<String, RentalError> {
+        Self::check_paused(&env)?;
+        agreement::create_agreement_with_unit(&env, input, unit)
+    }
+
+    pub fn get_agreement_unit(env: Env, agreement_id: String) -> Option<UnitOfAccount> {
+        agreement::get_agreement_unit(&env, agreement_id)
+    }
+
+    /// Amount of `token` currently needed to pay one month of rent.
+    pub fn quote_rent_payment(
+        env: Env,
+        agreement_id: String,
+        token: Address,
+    ) -> Result<i128, soroban_sdk::Error> {
+        agreement::quote_rent_payment(&env, agreement_id, token)
+    }
+
     // --- Payment Functions with Token ---
 
     pub fn make_payment_with_token(
@@ -672,6 +826,52 @@ impl Contract {
         agreement::make_payment_with_token(&env, agreement_id, amount, token)
     }
 
+    /// Pay rent on a sublet agreement from the subtenant's account.
+    pub fn make_subtenant_payment(
+        env: Env,
+        agreement_id: String,
+        amount: i128,
+        token: Address,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env)?;
+        agreement::make_subtenant_payment(&env, agreement_id, amount, token)
+    }
+
+    /// Rent due for every period started so far, less rent already paid.
+    pub fn get_outstanding_rent(env: Env, agreement_id: String) -> Result<i128, RentalError> {
+        agreement::get_outstanding_rent(&env, agreement_id)
+    }
+
+    pub fn needs_token_migration(env: Env, agreement_id: String) -> Result<bool, RentalError> {
+        agreement::needs_token_migration(&env, agreement_id)
+    }
+
+    /// Propose a new payment token for an agreement whose token was disabled.
+    pub fn propose_token_migration(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        to_token: Address,
+        monthly_rent: i128,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        agreement::propose_token_migration(&env, caller, agreement_id, to_token, monthly_rent)
+    }
+
+    /// Accept the pending token migration as the non-proposing party.
+    pub fn accept_token_migration(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        agreement::accept_token_migration(&env, caller, agreement_id)
+    }
+
+    pub fn get_token_migration(env: Env, agreement_id: String) -> Option<TokenMigration> {
+        agreement::get_token_migration(&env, agreement_id)
+    }
+
     pub fn release_escrow_with_token(
         env: Env,
         escrow_id: String,
@@ -760,14 +960,35 @@ impl Contract {
         agreement_id: String,
     ) -> Result<(), RentalError> {
         Self::check_paused(&env)?;
-        agreement::sign_agreement(&env, user, agreement_id)
+        agreement::sign_agreement(&env, user, agreement_id, None)
+    }
+
+    /// Sign an agreement that has an anchored lease document.
+    ///
+    /// @notice Like `sign_agreement`, but binds the tenant's signature to the
+    ///         document they read. Fails unless `document_hash` matches the
+    ///         agreement's anchored hash.
+    /// @param env The Soroban environment.
+    /// @param user Address of the tenant signing (must authorize).
+    /// @param agreement_id Identifier of the agreement to sign.
+    /// @param document_hash Document hash the tenant signed.
+    /// @return Ok(()) on success.
+    pub fn sign_agreement_with_document(
+        env: Env,
+        user: Address,
+        agreement_id: String,
+        document_hash: BytesN<32>,
+    ) -> Result<(), RentalError> {
+        Self::check_paused(&env)?;
+        agreement::sign_agreement(&env, user, agreement_id, Some(document_hash))
     }
 
     /// Approve a pending agreement as a witness (PendingApproval → Active).
     ///
-    /// @notice Admin or designated agent witnesses the agreement, permanently recording
-    ///         their ID and activating the escrow. Payouts cannot be triggered before
-    ///         this call succeeds.
+    /// @notice A witness allowed by the agreement's witness policy approves the
+    ///         agreement and is permanently recorded on it. Once the policy's required
+    ///         number of distinct witnesses have approved, the agreement and its escrow
+    ///         become Active. Payouts cannot be triggered before then.
     /// @param env The Soroban environment.
     /// @param approver Address of the approving witness (must authorize).
     /// @param agreement_id Identifier of the agreement to approve.
@@ -888,6 +1109,188 @@ impl Contract {
         agreement::get_current_agreement_end(&env, agreement_id)
     }
 
+    // --- Amendment Functions ---
+
+    /// Propose mid-lease changes to an active agreement, effective from
+    /// `effective_date`. Landlord or primary tenant only.
+    pub fn propose_amendment(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        changes: Vec<AmendmentChange>,
+        effective_date: u64,
+    ) -> Result<u32, soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        amendment::propose_amendment(&env, caller, agreement_id, changes, effective_date)
+    }
+
+    pub fn accept_amendment(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        amendment_id: u32,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        amendment::accept_amendment(&env, caller, agreement_id, amendment_id)
+    }
+
+    pub fn reject_amendment(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        amendment_id: u32,
+        reason: String,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        amendment::reject_amendment(&env, caller, agreement_id, amendment_id, reason)
+    }
+
+    pub fn cancel_amendment(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        amendment_id: u32,
+        reason: String,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        amendment::cancel_amendment(&env, caller, agreement_id, amendment_id, reason)
+    }
+
+    /// Apply an accepted amendment once its effective date is reached.
+    pub fn apply_amendment(
+        env: Env,
+        agreement_id: String,
+        amendment_id: u32,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        amendment::apply_amendment(&env, agreement_id, amendment_id)
+    }
+
+    pub fn get_amendment(
+        env: Env,
+        agreement_id: String,
+        amendment_id: u32,
+    ) -> Result<AgreementAmendment, soroban_sdk::Error> {
+        amendment::get_amendment(&env, agreement_id, amendment_id)
+    }
+
+    pub fn get_amendment_count(env: Env, agreement_id: String) -> u32 {
+        amendment::get_amendment_count(&env, agreement_id)
+    }
+
+    pub fn get_terms_version_count(env: Env, agreement_id: String) -> u32 {
+        amendment::get_terms_version_count(&env, agreement_id)
+    }
+
+    pub fn get_terms_version(
+        env: Env,
+        agreement_id: String,
+        version: u32,
+    ) -> Result<TermsVersion, soroban_sdk::Error> {
+        amendment::get_terms_version(&env, agreement_id, version)
+    }
+
+    // --- Assignment Functions ---
+
+    /// Nominate a replacement tenant (`Assignment`) or a subtenant
+    /// (`Sublet`). Primary tenant only.
+    pub fn nominate_assignee(
+        env: Env,
+        tenant: Address,
+        agreement_id: String,
+        assignee: Address,
+        mode: AssignmentMode,
+        new_deposit: i128,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        assignment::nominate_assignee(&env, tenant, agreement_id, assignee, mode, new_deposit)
+    }
+
+    pub fn accept_assignment(
+        env: Env,
+        assignee: Address,
+        agreement_id: String,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        assignment::accept_assignment(&env, assignee, agreement_id)
+    }
+
+    /// Complete an accepted assignment or sublet. Landlord only; fails while
+    /// rent is outstanding.
+    pub fn approve_assignment(
+        env: Env,
+        landlord: Address,
+        agreement_id: String,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        assignment::approve_assignment(&env, landlord, agreement_id)
+    }
+
+    pub fn cancel_assignment(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        assignment::cancel_assignment(&env, caller, agreement_id)
+    }
+
+    pub fn end_sublet(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        assignment::end_sublet(&env, caller, agreement_id)
+    }
+
+    pub fn get_lease_assignment(env: Env, agreement_id: String) -> Option<LeaseAssignment> {
+        assignment::get_lease_assignment(&env, agreement_id)
+    }
+
+    // --- Signed Offer Functions ---
+
+    /// Register or rotate the ed25519 key the landlord signs offers with.
+    pub fn set_offer_signing_key(
+        env: Env,
+        landlord: Address,
+        public_key: BytesN<32>,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        lease_offer::set_offer_signing_key(&env, landlord, public_key)
+    }
+
+    pub fn get_offer_signing_key(env: Env, landlord: Address) -> Option<BytesN<32>> {
+        lease_offer::get_offer_signing_key(&env, landlord)
+    }
+
+    /// Hash the landlord must sign for `offer`.
+    pub fn get_offer_hash(env: Env, offer: LeaseOffer) -> BytesN<32> {
+        lease_offer::offer_hash(&env, &offer)
+    }
+
+    /// Create, submit and sign an agreement in one call from a
+    /// landlord-signed offer. Returns the agreement ID.
+    pub fn accept_signed_offer(
+        env: Env,
+        tenant: Address,
+        offer: LeaseOffer,
+        signature: BytesN<64>,
+    ) -> Result<String, soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        lease_offer::accept_signed_offer(&env, tenant, offer, signature)
+    }
+
+    /// Invalidate an unaccepted offer by burning its nonce.
+    pub fn revoke_offer(env: Env, landlord: Address, nonce: u64) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        lease_offer::revoke_offer(&env, landlord, nonce)
+    }
+
+    pub fn is_offer_nonce_used(env: Env, landlord: Address, nonce: u64) -> bool {
+        lease_offer::is_offer_nonce_used(&env, landlord, nonce)
+    }
+
     /// Retrieve details of a rental agreement.
     ///
     /// @notice Returns full agreement data (parties, amounts, dates, status) by ID.
@@ -917,6 +1320,70 @@ impl Contract {
         agreement::get_agreement_count(&env)
     }
 
+    /// List agreements where `landlord` is the landlord (`admin`).
+    ///
+    /// @notice Paginated; pass the returned `next_cursor` to fetch the next page.
+    /// @param landlord Landlord address.
+    /// @param cursor Position to start from (0 for the first page).
+    /// @param limit Page size, at most `MAX_PAGE_SIZE`.
+    /// @return One page of agreements.
+    pub fn get_agreements_by_landlord(
+        env: Env,
+        landlord: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<AgreementPage, RentalError> {
+        agreement_index::get_page(&env, AgreementIndex::Landlord(landlord), cursor, limit)
+    }
+
+    /// List agreements where `tenant` is the tenant (`user`).
+    ///
+    /// @notice Paginated; pass the returned `next_cursor` to fetch the next page.
+    /// @param tenant Tenant address.
+    /// @param cursor Position to start from (0 for the first page).
+    /// @param limit Page size, at most `MAX_PAGE_SIZE`.
+    /// @return One page of agreements.
+    pub fn get_agreements_by_tenant(
+        env: Env,
+        tenant: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<AgreementPage, RentalError> {
+        agreement_index::get_page(&env, AgreementIndex::Tenant(tenant), cursor, limit)
+    }
+
+    /// List agreements brokered by `agent`.
+    ///
+    /// @notice Paginated; pass the returned `next_cursor` to fetch the next page.
+    /// @param agent Agent address.
+    /// @param cursor Position to start from (0 for the first page).
+    /// @param limit Page size, at most `MAX_PAGE_SIZE`.
+    /// @return One page of agreements.
+    pub fn get_agreements_by_agent(
+        env: Env,
+        agent: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<AgreementPage, RentalError> {
+        agreement_index::get_page(&env, AgreementIndex::Agent(agent), cursor, limit)
+    }
+
+    /// List agreements currently in `status`.
+    ///
+    /// @notice Paginated; the index is updated on every status transition.
+    /// @param status Agreement status to filter by.
+    /// @param cursor Position to start from (0 for the first page).
+    /// @param limit Page size, at most `MAX_PAGE_SIZE`.
+    /// @return One page of agreements.
+    pub fn get_agreements_by_status(
+        env: Env,
+        status: AgreementStatus,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<AgreementPage, RentalError> {
+        agreement_index::get_page(&env, AgreementIndex::Status(status), cursor, limit)
+    }
+
     /// Get the payment split details for a specific month of an agreement.
     ///
     /// @notice Returns landlord, tenant, and agent amounts for a given month from payment history.
@@ -937,7 +1404,8 @@ impl Contract {
         agreement::get_payment_history(&env, agreement_id)
     }
 
-    /// Update metadata for an agreement.
+    /// Update metadata for a draft agreement. Submitted agreements change
+    /// their document through `propose_document_update`.
     pub fn update_metadata(
         env: Env,
         agreement_id: String,
@@ -948,6 +1416,253 @@ impl Contract {
         agreement::update_metadata(&env, agreement_id, metadata_uri, attributes)
     }
 
+    /// Anchor the lease document's content hash on a draft agreement.
+    pub fn anchor_document(
+        env: Env,
+        agreement_id: String,
+        document_hash: BytesN<32>,
+        metadata_uri: String,
+        attributes: Vec<Attribute>,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        document::anchor_document(&env, agreement_id, document_hash, metadata_uri, attributes)
+    }
+
+    /// Propose a new lease document; applies once the other party attests.
+    pub fn propose_document_update(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        document_hash: BytesN<32>,
+        metadata_uri: String,
+        attributes: Vec<Attribute>,
+    ) -> Result<u32, soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        document::propose_document_update(
+            &env,
+            caller,
+            agreement_id,
+            document_hash,
+            metadata_uri,
+            attributes,
+        )
+    }
+
+    /// Attest to the exact hash of a pending document version.
+    pub fn attest_document(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        version: u32,
+        document_hash: BytesN<32>,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        document::attest_document(&env, caller, agreement_id, version, document_hash)
+    }
+
+    pub fn get_document_version(
+        env: Env,
+        agreement_id: String,
+        version: u32,
+    ) -> Result<DocumentVersion, soroban_sdk::Error> {
+        document::get_document_version(&env, agreement_id, version)
+    }
+
+    pub fn get_document_version_count(env: Env, agreement_id: String) -> u32 {
+        document::get_document_version_count(&env, agreement_id)
+    }
+
+    // ─── Witness Functions ────────────────────────────────────────────────────
+
+    /// Set who may witness an agreement and how many witnesses activate it.
+    /// Landlord only, before the tenant signs.
+    pub fn set_witness_policy(
+        env: Env,
+        agreement_id: String,
+        policy: WitnessPolicy,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        witness::set_witness_policy(&env, agreement_id, policy)
+    }
+
+    pub fn get_witness_policy(env: Env, agreement_id: String) -> WitnessPolicy {
+        witness::get_witness_policy(&env, agreement_id)
+    }
+
+    /// Register a notary who may witness agreements. Admin only.
+    pub fn add_notary(env: Env, notary: Address) -> Result<(), RentalError> {
+        Self::check_paused(&env)?;
+        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
+        state.admin.require_auth();
+        witness::add_notary(&env, notary);
+        Ok(())
+    }
+
+    /// Deregister a notary. Admin only.
+    pub fn remove_notary(env: Env, notary: Address) -> Result<(), RentalError> {
+        Self::check_paused(&env)?;
+        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
+        state.admin.require_auth();
+        witness::remove_notary(&env, notary);
+        Ok(())
+    }
+
+    pub fn is_notary(env: Env, account: Address) -> bool {
+        witness::is_notary(&env, account)
+    }
+
+    /// Set the user profile contract consulted for verified-profile witnesses.
+    /// Admin only.
+    pub fn set_profile_contract(env: Env, profile_contract: Address) -> Result<(), RentalError> {
+        Self::check_paused(&env)?;
+        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
+        state.admin.require_auth();
+        witness::set_profile_contract(&env, profile_contract);
+        Ok(())
+    }
+
+    pub fn get_profile_contract(env: Env) -> Option<Address> {
+        witness::get_profile_contract(&env)
+    }
+
+    // ─── Arrears Functions ────────────────────────────────────────────────────
+
+    /// Set when a default notice may be served and the cure period that
+    /// follows. Landlord only, before the tenant signs.
+    pub fn set_arrears_policy(
+        env: Env,
+        agreement_id: String,
+        policy: ArrearsPolicy,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        arrears::set_arrears_policy(&env, agreement_id, policy)
+    }
+
+    pub fn get_arrears_policy(env: Env, agreement_id: String) -> ArrearsPolicy {
+        arrears::get_arrears_policy(&env, agreement_id)
+    }
+
+    /// Serve a default notice on a tenant whose arrears meet the policy.
+    pub fn issue_default_notice(
+        env: Env,
+        landlord: Address,
+        agreement_id: String,
+    ) -> Result<DefaultNotice, soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        arrears::issue_default_notice(&env, landlord, agreement_id)
+    }
+
+    /// Terminate an agreement whose cure period has lapsed with arrears
+    /// outstanding, recording the claim against the security deposit.
+    pub fn declare_default(
+        env: Env,
+        landlord: Address,
+        agreement_id: String,
+    ) -> Result<DefaultNotice, soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        arrears::declare_default(&env, landlord, agreement_id)
+    }
+
+    pub fn get_default_notice(
+        env: Env,
+        agreement_id: String,
+    ) -> Result<DefaultNotice, soroban_sdk::Error> {
+        arrears::get_default_notice(&env, agreement_id)
+    }
+
+    /// Pay late fees in the agreement's payment token.
+    pub fn pay_late_fees(
+        env: Env,
+        payer: Address,
+        agreement_id: String,
+        amount: i128,
+    ) -> Result<(), soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        arrears::pay_late_fees(&env, payer, agreement_id, amount)
+    }
+
+    pub fn get_late_fees(env: Env, agreement_id: String) -> i128 {
+        arrears::get_late_fees(&env, agreement_id)
+    }
+
+    // ─── Payment Plan Functions ───────────────────────────────────────────────
+
+    /// Propose repaying the current arrears in monthly instalments on top of
+    /// rent, optionally holding late fees while the plan is honoured.
+    pub fn propose_payment_plan(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        instalments: u32,
+        waive_late_fees: bool,
+    ) -> Result<PaymentPlan, soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        payment_plan::propose_payment_plan(&env, caller, agreement_id, instalments, waive_late_fees)
+    }
+
+    /// Accept the other party's payment plan proposal.
+    pub fn accept_payment_plan(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+    ) -> Result<PaymentPlan, soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        payment_plan::accept_payment_plan(&env, caller, agreement_id)
+    }
+
+    /// Check instalments whose deadlines have passed. Callable by anyone.
+    pub fn check_payment_plan(
+        env: Env,
+        agreement_id: String,
+    ) -> Result<PaymentPlan, soroban_sdk::Error> {
+        Self::check_paused(&env)?;
+        payment_plan::check_payment_plan(&env, agreement_id)
+    }
+
+    pub fn get_payment_plan(
+        env: Env,
+        agreement_id: String,
+    ) -> Result<PaymentPlan, soroban_sdk::Error> {
+        payment_plan::get_payment_plan(&env, agreement_id)
+    }
+
+    // ─── Deposit Escrow Functions ─────────────────────────────────────────────
+
+    /// Register the escrow contract allowed to report deposit status. Admin only.
+    pub fn set_escrow_contract(env: Env, escrow_contract: Address) -> Result<(), RentalError> {
+        Self::check_paused(&env)?;
+        let state = Self::get_state(env.clone()).ok_or(RentalError::InvalidState)?;
+        state.admin.require_auth();
+        deposit_escrow::set_escrow_contract(&env, escrow_contract);
+        Ok(())
+    }
+
+    pub fn get_escrow_contract(env: Env) -> Option<Address> {
+        deposit_escrow::get_escrow_contract(&env)
+    }
+
+    /// Record the status of the escrow holding an agreement's deposit.
+    /// Registered escrow contract only. Not blocked by a pause, so escrows can
+    /// always settle.
+    pub fn update_deposit_status(
+        env: Env,
+        agreement_id: String,
+        escrow_id: BytesN<32>,
+        status: DepositStatus,
+    ) -> Result<(), soroban_sdk::Error> {
+        deposit_escrow::update_deposit_status(&env, agreement_id, escrow_id, status)
+    }
+
+    pub fn get_deposit_record(env: Env, agreement_id: String) -> Option<DepositRecord> {
+        deposit_escrow::get_deposit_record(&env, agreement_id)
+    }
+
+    /// Parties, deposit amount, token and status of an agreement, for the
+    /// escrow holding its deposit.
+    pub fn get_deposit_terms(env: Env, agreement_id: String) -> Option<DepositTerms> {
+        deposit_escrow::get_deposit_terms(&env, agreement_id)
+    }
+
     // ─── Deposit Interest Functions ───────────────────────────────────────────
 
     /// Set the interest configuration for a security deposit.
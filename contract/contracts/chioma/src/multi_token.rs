@@ -1,8 +1,19 @@
-use crate::errors::RentalError;
+use crate::errors::{into_rental, OracleError, RentalError, TokenError};
 use crate::events;
-use crate::storage::DataKey;
-use crate::types::{SupportedToken, TokenExchangeRate};
-use soroban_sdk::{Address, Env, String, Vec};
+use crate::oracle;
+use crate::storage::{DataKey, TokenKey};
+use crate::types::{
+    OracleAsset, RateSourceConfig, SupportedToken, TokenExchangeRate, TokenPolicy, TokenUsage,
+    UnitOfAccount,
+};
+use soroban_sdk::{Address, Env, Error, String, Symbol, Vec};
+
+/// Fixed-point scale of exchange rates (1.0 = 10^18).
+pub const RATE_SCALE: i128 = 1_000_000_000_000_000_000;
+pub(crate) const RATE_DECIMALS: u32 = 18;
+const BPS_DENOMINATOR: i128 = 10_000;
+const SECONDS_PER_DAY: u64 = 86_400;
+const DAILY_VOLUME_TTL: u32 = 17_280 * 2; // ~2 days of ledgers
 
 pub fn add_supported_token(
     env: Env,
@@ -90,12 +101,137 @@ pub fn is_token_supported(env: Env, token_address: Address) -> Result<bool, Rent
     }
 }
 
+pub fn set_token_policy(
+    env: Env,
+    token_address: Address,
+    policy: TokenPolicy,
+) -> Result<(), RentalError> {
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::SupportedToken(token_address.clone()))
+    {
+        return Err(RentalError::TokenNotSupported);
+    }
+    if policy.daily_cap < 0 {
+        return Err(RentalError::InvalidAmount);
+    }
+    env.storage().persistent().set(
+        &DataKey::Token(TokenKey::Policy(token_address.clone())),
+        &policy,
+    );
+
+    events::token_policy_updated(&env, token_address, policy.usage, policy.daily_cap);
+    Ok(())
+}
+
+/// Policy for a token. Tokens without an explicit policy may be used for
+/// rent and deposits with no daily cap.
+pub fn get_token_policy(env: &Env, token_address: &Address) -> TokenPolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Token(TokenKey::Policy(token_address.clone())))
+        .unwrap_or(TokenPolicy {
+            usage: TokenUsage::RentAndDeposit,
+            daily_cap: 0,
+        })
+}
+
+fn allows(policy: &TokenPolicy, usage: TokenUsage) -> bool {
+    policy.usage == TokenUsage::RentAndDeposit || policy.usage == usage
+}
+
+/// Check that an enabled token may back an agreement for the given flows.
+pub(crate) fn require_token_usage(
+    env: &Env,
+    token_address: &Address,
+    rent: bool,
+    deposit: bool,
+) -> Result<(), Error> {
+    if !is_token_supported(env.clone(), token_address.clone())? {
+        return Err(RentalError::TokenNotSupported.into());
+    }
+    let policy = get_token_policy(env, token_address);
+    if (rent && !allows(&policy, TokenUsage::Rent))
+        || (deposit && !allows(&policy, TokenUsage::Deposit))
+    {
+        return Err(TokenError::TokenUsageNotAllowed.into());
+    }
+    Ok(())
+}
+
+/// Validate a single transfer of `amount` in `token_address` against the
+/// token's status, usage, per-payment bounds and daily cap, then count it
+/// toward today's volume.
+pub(crate) fn consume_token_allowance(
+    env: &Env,
+    token_address: &Address,
+    usage: TokenUsage,
+    amount: i128,
+) -> Result<(), Error> {
+    let token: SupportedToken = env
+        .storage()
+        .persistent()
+        .get(&DataKey::SupportedToken(token_address.clone()))
+        .ok_or(RentalError::TokenNotSupported)?;
+    if !token.enabled {
+        return Err(RentalError::TokenNotSupported.into());
+    }
+
+    let policy = get_token_policy(env, token_address);
+    if !allows(&policy, usage) {
+        return Err(TokenError::TokenUsageNotAllowed.into());
+    }
+    if amount < token.min_amount {
+        return Err(TokenError::AmountBelowTokenMinimum.into());
+    }
+    if token.max_amount > 0 && amount > token.max_amount {
+        return Err(TokenError::AmountAboveTokenMaximum.into());
+    }
+
+    let key = DataKey::Token(TokenKey::DailyVolume(
+        token_address.clone(),
+        env.ledger().timestamp() / SECONDS_PER_DAY,
+    ));
+    let volume: i128 = env.storage().temporary().get(&key).unwrap_or(0);
+    let volume = volume
+        .checked_add(amount)
+        .ok_or(RentalError::ConversionError)?;
+    if policy.daily_cap > 0 && volume > policy.daily_cap {
+        return Err(TokenError::TokenDailyCapExceeded.into());
+    }
+    env.storage().temporary().set(&key, &volume);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, DAILY_VOLUME_TTL, DAILY_VOLUME_TTL);
+    Ok(())
+}
+
+/// Volume of `token_address` accepted so far in the current UTC day.
+pub fn get_token_daily_volume(env: &Env, token_address: &Address) -> i128 {
+    env.storage()
+        .temporary()
+        .get(&DataKey::Token(TokenKey::DailyVolume(
+            token_address.clone(),
+            env.ledger().timestamp() / SECONDS_PER_DAY,
+        )))
+        .unwrap_or(0)
+}
+
 pub fn set_exchange_rate(
     env: Env,
     from_token: Address,
     to_token: Address,
     rate: i128,
 ) -> Result<(), RentalError> {
+    if rate <= 0 || from_token == to_token {
+        return Err(RentalError::InvalidAmount);
+    }
+    store_rate(&env, from_token, to_token, rate);
+    Ok(())
+}
+
+fn store_rate(env: &Env, from_token: Address, to_token: Address, rate: i128) {
     let key = DataKey::ExchangeRate(from_token.clone(), to_token.clone());
     let exchange_rate = TokenExchangeRate {
         from_token: from_token.clone(),
@@ -106,31 +242,205 @@ pub fn set_exchange_rate(
 
     env.storage().persistent().set(&key, &exchange_rate);
 
-    events::exchange_rate_updated(&env, from_token, to_token, rate);
+    events::exchange_rate_updated(env, from_token, to_token, rate);
+}
+
+pub fn set_rate_source(env: Env, config: RateSourceConfig) -> Result<(), RentalError> {
+    if config.max_deviation_bps as i128 > BPS_DENOMINATOR {
+        return Err(RentalError::InvalidConfig);
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::Token(TokenKey::RateSource), &config);
+
+    events::rate_source_updated(
+        &env,
+        config.oracle,
+        config.base_token,
+        config.max_age,
+        config.max_deviation_bps,
+    );
     Ok(())
 }
 
+pub fn get_rate_source(env: &Env) -> Option<RateSourceConfig> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Token(TokenKey::RateSource))
+}
+
+/// Resolve the rate for a pair.
+///
+/// Order of precedence: oracle quotes for both tokens (guarded against the
+/// last accepted rate), then a stored direct rate, its inverse, and finally
+/// a cross rate through the configured base token.
 pub fn get_exchange_rate(
     env: Env,
     from_token: Address,
     to_token: Address,
 ) -> Result<i128, RentalError> {
+    resolve_rate(&env, from_token, to_token).map_err(|error| into_rental(&env, error))
+}
+
+fn resolve_rate(env: &Env, from_token: Address, to_token: Address) -> Result<i128, Error> {
     if from_token == to_token {
-        return Ok(1_000_000_000_000_000_000); // 1.0 scaled by 10^18
+        return Ok(RATE_SCALE);
     }
 
-    let key = DataKey::ExchangeRate(from_token, to_token);
-    if let Some(rate_struct) = env
-        .storage()
-        .persistent()
-        .get::<DataKey, TokenExchangeRate>(&key)
+    let config = get_rate_source(env);
+    if let Some(config) = &config {
+        if let Some(rate) = oracle_rate(env, config, &from_token, &to_token)? {
+            let reference = stored_rate(env, &from_token, &to_token).map(|r| r.rate);
+            check_deviation(config, reference, rate)?;
+            return Ok(rate);
+        }
+    }
+
+    if let Some(rate) = stored_pair_rate(env, config.as_ref(), &from_token, &to_token)? {
+        return Ok(rate);
+    }
+
+    if let Some(base) = config.as_ref().and_then(|c| c.base_token.clone()) {
+        if base != from_token && base != to_token {
+            let first = stored_pair_rate(env, config.as_ref(), &from_token, &base)?;
+            let second = stored_pair_rate(env, config.as_ref(), &base, &to_token)?;
+            if let (Some(first), Some(second)) = (first, second) {
+                return Ok(first
+                    .checked_mul(second)
+                    .map(|r| r / RATE_SCALE)
+                    .ok_or(RentalError::ConversionError)?);
+            }
+        }
+    }
+
+    Err(RentalError::RateNotFound.into())
+}
+
+/// Pull the current oracle rate for a pair and store it as the new reference
+/// rate. The same staleness and deviation guards as `get_exchange_rate` apply.
+pub fn sync_exchange_rate(env: Env, from_token: Address, to_token: Address) -> Result<i128, Error> {
+    let config = get_rate_source(&env).ok_or(OracleError::OracleUnavailable)?;
+    let rate =
+        oracle_rate(&env, &config, &from_token, &to_token)?.ok_or(RentalError::RateNotFound)?;
+    let reference = stored_rate(&env, &from_token, &to_token).map(|r| r.rate);
+    check_deviation(&config, reference, rate)?;
+
+    store_rate(&env, from_token, to_token, rate);
+    Ok(rate)
+}
+
+fn oracle_rate(
+    env: &Env,
+    config: &RateSourceConfig,
+    from_token: &Address,
+    to_token: &Address,
+) -> Result<Option<i128>, Error> {
+    let Some(oracle) = &config.oracle else {
+        return Ok(None);
+    };
+    let from_price = oracle::price_of(
+        env,
+        oracle,
+        config,
+        &OracleAsset::Stellar(from_token.clone()),
+    )?;
+    let to_price = oracle::price_of(env, oracle, config, &OracleAsset::Stellar(to_token.clone()))?;
+
+    match (from_price, to_price) {
+        (Some(from_price), Some(to_price)) => price_ratio(from_price, to_price).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// `from_price / to_price` scaled by 10^18. Prices that rescale to zero, or
+/// a ratio that rounds to zero, are rejected rather than divided by.
+fn price_ratio(from_price: i128, to_price: i128) -> Result<i128, Error> {
+    if from_price <= 0 || to_price <= 0 {
+        return Err(RentalError::ConversionError.into());
+    }
+    match from_price
+        .checked_mul(RATE_SCALE)
+        .and_then(|r| r.checked_div(to_price))
     {
-        Ok(rate_struct.rate)
+        Some(rate) if rate > 0 => Ok(rate),
+        _ => Err(RentalError::ConversionError.into()),
+    }
+}
+
+/// Reject an oracle rate that moved too far from the last accepted rate.
+fn check_deviation(
+    config: &RateSourceConfig,
+    reference: Option<i128>,
+    rate: i128,
+) -> Result<(), Error> {
+    if config.max_deviation_bps == 0 {
+        return Ok(());
+    }
+    let Some(reference) = reference else {
+        return Ok(());
+    };
+
+    let deviation = (rate - reference)
+        .abs()
+        .checked_mul(BPS_DENOMINATOR)
+        .and_then(|d| d.checked_div(reference))
+        .ok_or(RentalError::ConversionError)?;
+    if deviation > config.max_deviation_bps as i128 {
+        return Err(OracleError::RateDeviationTooLarge.into());
+    }
+    Ok(())
+}
+
+fn stored_rate(env: &Env, from_token: &Address, to_token: &Address) -> Option<TokenExchangeRate> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ExchangeRate(from_token.clone(), to_token.clone()))
+}
+
+/// Stored rate for a pair, taken directly or inverted from the reverse pair.
+fn stored_pair_rate(
+    env: &Env,
+    config: Option<&RateSourceConfig>,
+    from_token: &Address,
+    to_token: &Address,
+) -> Result<Option<i128>, Error> {
+    let (record, inverse) = match stored_rate(env, from_token, to_token) {
+        Some(record) => (record, false),
+        None => match stored_rate(env, to_token, from_token) {
+            Some(record) => (record, true),
+            None => return Ok(None),
+        },
+    };
+
+    let max_age = config.map(|c| c.max_age).unwrap_or(0);
+    if max_age > 0 && env.ledger().timestamp() > record.updated_at + max_age {
+        return Err(OracleError::RateStale.into());
+    }
+
+    if inverse {
+        RATE_SCALE
+            .checked_mul(RATE_SCALE)
+            .and_then(|r| r.checked_div(record.rate))
+            .filter(|rate| *rate > 0)
+            .map(Some)
+            .ok_or(RentalError::ConversionError.into())
     } else {
-        Err(RentalError::RateNotFound)
+        Ok(Some(record.rate))
     }
 }
 
+pub(crate) fn token_decimals(env: &Env, token: &Address) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get::<DataKey, SupportedToken>(&DataKey::SupportedToken(token.clone()))
+        .map(|t| t.decimals)
+}
+
+/// Convert `amount` of `from_token` into `to_token` units.
+///
+/// Rates are quoted per whole token, so the result is rescaled between the
+/// two tokens' registered decimals. Unregistered tokens are treated as
+/// sharing the same precision.
 pub fn convert_amount(
     env: Env,
     from_token: Address,
@@ -141,11 +451,132 @@ pub fn convert_amount(
         return Ok(amount);
     }
 
-    let rate = get_exchange_rate(env, from_token, to_token)?;
+    let rate = get_exchange_rate(env.clone(), from_token.clone(), to_token.clone())?;
+    convert_at_rate(&env, &from_token, &to_token, amount, rate)
+}
+
+/// Apply an already resolved `rate` to `amount`, normalizing decimals.
+pub(crate) fn convert_at_rate(
+    env: &Env,
+    from_token: &Address,
+    to_token: &Address,
+    amount: i128,
+    rate: i128,
+) -> Result<i128, RentalError> {
     // amount * rate / 10^18
     let converted = amount
         .checked_mul(rate)
         .ok_or(RentalError::ConversionError)?
-        / 1_000_000_000_000_000_000;
-    Ok(converted)
+        / RATE_SCALE;
+
+    match (
+        token_decimals(env, from_token),
+        token_decimals(env, to_token),
+    ) {
+        (Some(from_decimals), Some(to_decimals)) => {
+            oracle::rescale(converted, from_decimals, to_decimals)
+        }
+        _ => Ok(converted),
+    }
+}
+
+/// Oracle rate from one whole unit of `currency` to one whole `token`,
+/// scaled by 10^18, guarded against the last accepted unit rate.
+/// Off-chain currencies have no stored fallback.
+pub fn get_unit_rate(env: &Env, currency: &Symbol, token: &Address) -> Result<i128, Error> {
+    let config = get_rate_source(env).ok_or(OracleError::OracleUnavailable)?;
+    let rate = oracle_unit_rate(env, &config, currency, token)?;
+    check_deviation(&config, stored_unit_rate(env, currency, token), rate)?;
+    Ok(rate)
+}
+
+/// Pull the current oracle rate for a currency and store it as the new
+/// reference rate, under the same guards as `get_unit_rate`.
+pub fn sync_unit_rate(env: Env, currency: Symbol, token: Address) -> Result<i128, Error> {
+    let rate = get_unit_rate(&env, &currency, &token)?;
+    store_unit_rate(&env, currency, token, rate);
+    Ok(rate)
+}
+
+pub fn set_unit_rate(
+    env: Env,
+    currency: Symbol,
+    token: Address,
+    rate: i128,
+) -> Result<(), RentalError> {
+    if rate <= 0 {
+        return Err(RentalError::InvalidAmount);
+    }
+    store_unit_rate(&env, currency, token, rate);
+    Ok(())
+}
+
+fn store_unit_rate(env: &Env, currency: Symbol, token: Address, rate: i128) {
+    env.storage().persistent().set(
+        &DataKey::Token(TokenKey::UnitRate(currency.clone(), token.clone())),
+        &rate,
+    );
+    events::unit_rate_updated(env, currency, token, rate);
+}
+
+fn stored_unit_rate(env: &Env, currency: &Symbol, token: &Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Token(TokenKey::UnitRate(
+            currency.clone(),
+            token.clone(),
+        )))
+}
+
+fn oracle_unit_rate(
+    env: &Env,
+    config: &RateSourceConfig,
+    currency: &Symbol,
+    token: &Address,
+) -> Result<i128, Error> {
+    let oracle = config
+        .oracle
+        .clone()
+        .ok_or(OracleError::OracleUnavailable)?;
+
+    let unit_price = oracle::price_of(env, &oracle, config, &OracleAsset::Other(currency.clone()))?
+        .ok_or(RentalError::RateNotFound)?;
+    let token_price = oracle::price_of(env, &oracle, config, &OracleAsset::Stellar(token.clone()))?
+        .ok_or(RentalError::RateNotFound)?;
+
+    price_ratio(unit_price, token_price)
+}
+
+/// Token amount needed to settle `amount` minor units of `unit`, rounded up
+/// in the payee's favour. Returns `(token_amount, rate)`, where `rate` is
+/// quoted from `token` to `unit` like every other recorded conversion rate.
+pub fn quote_unit_amount(
+    env: &Env,
+    unit: &UnitOfAccount,
+    token: &Address,
+    amount: i128,
+) -> Result<(i128, i128), Error> {
+    let token_decimals = token_decimals(env, token).ok_or(RentalError::TokenNotSupported)?;
+    let rate = get_unit_rate(env, &unit.code, token)?;
+
+    // amount [unit minor] * rate * 10^token_decimals / (10^18 * 10^unit_decimals)
+    let numerator = oracle::rescale(
+        amount
+            .checked_mul(rate)
+            .ok_or(RentalError::ConversionError)?,
+        unit.decimals,
+        unit.decimals.max(token_decimals),
+    )?;
+    let denominator = oracle::rescale(
+        RATE_SCALE,
+        token_decimals,
+        unit.decimals.max(token_decimals),
+    )?;
+    let token_amount = (numerator + denominator - 1) / denominator;
+    let token_rate = RATE_SCALE
+        .checked_mul(RATE_SCALE)
+        .and_then(|r| r.checked_div(rate))
+        .filter(|r| *r > 0)
+        .ok_or(RentalError::ConversionError)?;
+    Ok((token_amount, token_rate))
 }
@@ -0,0 +1,76 @@
+use crate::errors::{OracleError, RentalError};
+use crate::multi_token::{RATE_DECIMALS, RATE_SCALE};
+use crate::types::{OracleAsset, PriceData, RateSourceConfig};
+use soroban_sdk::{contractclient, Address, Env, Error};
+
+/// Subset of the SEP-40 price feed interface used for exchange rates.
+#[allow(dead_code)]
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn lastprice(env: Env, asset: OracleAsset) -> Option<PriceData>;
+    fn decimals(env: Env) -> u32;
+}
+
+/// Fetch the oracle price of `asset`, scaled to `RATE_SCALE`.
+///
+/// Returns `Ok(None)` when the oracle has no quote for the asset so the
+/// caller can fall back to stored rates. The configured base token and
+/// quote currency are always priced at exactly one unit.
+pub(crate) fn price_of(
+    env: &Env,
+    oracle: &Address,
+    config: &RateSourceConfig,
+    asset: &OracleAsset,
+) -> Result<Option<i128>, Error> {
+    let is_unit = match asset {
+        OracleAsset::Stellar(token) => config.base_token.as_ref() == Some(token),
+        OracleAsset::Other(code) => config.quote_currency.as_ref() == Some(code),
+    };
+    if is_unit {
+        return Ok(Some(RATE_SCALE));
+    }
+
+    let client = PriceOracleClient::new(env, oracle);
+    let quote = match client.try_lastprice(asset) {
+        Ok(Ok(quote)) => quote,
+        _ => return Err(OracleError::OracleUnavailable.into()),
+    };
+    let Some(quote) = quote else {
+        return Ok(None);
+    };
+
+    if quote.price <= 0 {
+        return Err(OracleError::OracleUnavailable.into());
+    }
+    if config.max_age > 0 && env.ledger().timestamp() > quote.timestamp + config.max_age {
+        return Err(OracleError::RateStale.into());
+    }
+
+    let decimals = match client.try_decimals() {
+        Ok(Ok(decimals)) => decimals,
+        _ => return Err(OracleError::OracleUnavailable.into()),
+    };
+    Ok(rescale(quote.price, decimals, RATE_DECIMALS).map(Some)?)
+}
+
+/// Move `value` from `from_decimals` to `to_decimals` fixed-point precision.
+pub(crate) fn rescale(
+    value: i128,
+    from_decimals: u32,
+    to_decimals: u32,
+) -> Result<i128, RentalError> {
+    if from_decimals == to_decimals {
+        return Ok(value);
+    }
+    let diff = from_decimals.abs_diff(to_decimals);
+    let factor = 10_i128
+        .checked_pow(diff)
+        .ok_or(RentalError::ConversionError)?;
+    if to_decimals > from_decimals {
+        value
+            .checked_mul(factor)
+            .ok_or(RentalError::ConversionError)
+    } else {
+        Ok(value / factor)
+    }
+}
@@ -0,0 +1,204 @@
+//! Payment plans for tenants in arrears.
+//!
+//! Either party proposes repaying the current arrears in a number of monthly
+//! instalments on top of regular rent; the other party accepts. While the
+//! plan is honoured any open default notice is suspended and, if agreed,
+//! late fees are held. Each instalment deadline is checked against the rent
+//! actually paid by then. A miss breaks the plan, reinstates held fees and
+//! resumes the default process.
+use soroban_sdk::{Address, Env, Error, String};
+
+use crate::agreement::{outstanding_rent, outstanding_rent_at};
+use crate::arrears;
+use crate::errors::{ArrearsError, PaymentPlanError, RentalError};
+use crate::events;
+use crate::storage::{ArrearsKey, DataKey};
+use crate::types::{AgreementStatus, PaymentPlan, PaymentPlanStatus, RentAgreement};
+
+/// Upper bound on the number of instalments in a plan.
+pub const MAX_PLAN_INSTALMENTS: u32 = 24;
+
+const TTL_THRESHOLD: u32 = 500000;
+const TTL_BUMP: u32 = 500000;
+const SECONDS_PER_MONTH: u64 = 30 * 24 * 60 * 60;
+
+fn load_party_agreement(
+    env: &Env,
+    caller: &Address,
+    agreement_id: &String,
+) -> Result<RentAgreement, Error> {
+    caller.require_auth();
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, agreement_id).ok_or(RentalError::AgreementNotFound)?;
+    if *caller != agreement.admin && *caller != agreement.user {
+        return Err(RentalError::Unauthorized.into());
+    }
+    if agreement.status != AgreementStatus::Active {
+        return Err(RentalError::AgreementNotActive.into());
+    }
+    Ok(agreement)
+}
+
+fn save_plan(env: &Env, plan: &PaymentPlan) {
+    let key = DataKey::Arrears(ArrearsKey::PaymentPlan(plan.agreement_id.clone()));
+    env.storage().persistent().set(&key, plan);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+fn instalment_amount(arrears: i128, instalments: u32) -> i128 {
+    let count = instalments as i128;
+    (arrears + count - 1) / count
+}
+
+/// Latest payment plan for the agreement, whatever its status.
+pub fn get_payment_plan(env: &Env, agreement_id: String) -> Result<PaymentPlan, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Arrears(ArrearsKey::PaymentPlan(agreement_id)))
+        .ok_or(PaymentPlanError::PaymentPlanNotFound.into())
+}
+
+pub(crate) fn active_plan(env: &Env, agreement_id: &String) -> Option<PaymentPlan> {
+    get_payment_plan(env, agreement_id.clone())
+        .ok()
+        .filter(|plan| plan.status == PaymentPlanStatus::Active)
+}
+
+/// Propose repaying the current arrears in `instalments` monthly
+/// instalments. A new proposal replaces one that has not been accepted.
+pub fn propose_payment_plan(
+    env: &Env,
+    caller: Address,
+    agreement_id: String,
+    instalments: u32,
+    waive_late_fees: bool,
+) -> Result<PaymentPlan, Error> {
+    let agreement = load_party_agreement(env, &caller, &agreement_id)?;
+    if instalments == 0 || instalments > MAX_PLAN_INSTALMENTS {
+        return Err(RentalError::InvalidConfig.into());
+    }
+    if active_plan(env, &agreement_id).is_some() {
+        return Err(PaymentPlanError::PaymentPlanActive.into());
+    }
+    let arrears = outstanding_rent(env, &agreement)?;
+    if arrears == 0 {
+        return Err(ArrearsError::ArrearsBelowThreshold.into());
+    }
+
+    let plan = PaymentPlan {
+        agreement_id: agreement_id.clone(),
+        proposed_by: caller.clone(),
+        arrears,
+        instalments,
+        instalment_amount: instalment_amount(arrears, instalments),
+        waive_late_fees,
+        waived_late_fees: 0,
+        instalments_met: 0,
+        started_at: 0,
+        status: PaymentPlanStatus::Proposed,
+    };
+    save_plan(env, &plan);
+
+    events::payment_plan_proposed(env, agreement_id, caller, instalments, waive_late_fees);
+    Ok(plan)
+}
+
+/// Accept the other party's proposal. The arrears are fixed at this point
+/// and the first instalment falls due one period later.
+pub fn accept_payment_plan(
+    env: &Env,
+    caller: Address,
+    agreement_id: String,
+) -> Result<PaymentPlan, Error> {
+    let agreement = load_party_agreement(env, &caller, &agreement_id)?;
+    let mut plan = get_payment_plan(env, agreement_id.clone())?;
+    if plan.status != PaymentPlanStatus::Proposed {
+        return Err(PaymentPlanError::PaymentPlanNotFound.into());
+    }
+    if caller == plan.proposed_by {
+        return Err(RentalError::Unauthorized.into());
+    }
+    let arrears = outstanding_rent(env, &agreement)?;
+    if arrears == 0 {
+        return Err(ArrearsError::ArrearsBelowThreshold.into());
+    }
+
+    plan.arrears = arrears;
+    plan.instalment_amount = instalment_amount(arrears, plan.instalments);
+    plan.started_at = env.ledger().timestamp();
+    plan.status = PaymentPlanStatus::Active;
+    if plan.waive_late_fees {
+        plan.waived_late_fees = arrears::take_late_fees(env, &agreement_id);
+    }
+    arrears::suspend_notice(env, &agreement_id);
+    save_plan(env, &plan);
+
+    events::payment_plan_accepted(
+        env,
+        agreement_id,
+        arrears,
+        plan.instalments,
+        plan.instalment_amount,
+    );
+    Ok(plan)
+}
+
+/// Check every instalment whose deadline has passed. Anyone may call this;
+/// rent payments do so automatically before they are recorded.
+pub fn check_payment_plan(env: &Env, agreement_id: String) -> Result<PaymentPlan, Error> {
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
+    process_due(env, &agreement)?;
+    get_payment_plan(env, agreement_id)
+}
+
+/// Instalment `n` is met when, just before its deadline, the outstanding
+/// rent is no more than the arrears left after `n` instalments.
+pub(crate) fn process_due(env: &Env, agreement: &RentAgreement) -> Result<(), Error> {
+    let Some(mut plan) = active_plan(env, &agreement.agreement_id) else {
+        return Ok(());
+    };
+    let now = env.ledger().timestamp();
+
+    while plan.instalments_met < plan.instalments {
+        let n = plan.instalments_met + 1;
+        let due = plan.started_at + n as u64 * SECONDS_PER_MONTH;
+        if now < due {
+            break;
+        }
+        let remaining = (plan.arrears - plan.instalment_amount * n as i128).max(0);
+        if outstanding_rent_at(agreement, due - 1)? > remaining {
+            plan.status = PaymentPlanStatus::Broken;
+            save_plan(env, &plan);
+            events::plan_instalment_missed(env, agreement.agreement_id.clone(), n);
+            arrears::add_late_fees(env, &agreement.agreement_id, plan.waived_late_fees);
+            arrears::resume_notice(env, agreement);
+            return Ok(());
+        }
+        plan.instalments_met = n;
+        events::plan_instalment_met(env, agreement.agreement_id.clone(), n);
+    }
+
+    if plan.instalments_met == plan.instalments {
+        complete(env, agreement, &mut plan);
+    }
+    save_plan(env, &plan);
+    Ok(())
+}
+
+/// Complete the active plan, if any, after the arrears were paid off early.
+pub(crate) fn complete_active(env: &Env, agreement: &RentAgreement) {
+    if let Some(mut plan) = active_plan(env, &agreement.agreement_id) {
+        complete(env, agreement, &mut plan);
+        save_plan(env, &plan);
+    }
+}
+
+/// Held late fees stay forgiven and the suspended notice is cured.
+fn complete(env: &Env, agreement: &RentAgreement, plan: &mut PaymentPlan) {
+    plan.status = PaymentPlanStatus::Completed;
+    events::payment_plan_completed(env, agreement.agreement_id.clone(), plan.waived_late_fees);
+    arrears::cure_notice(env, agreement);
+}
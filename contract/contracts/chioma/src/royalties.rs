@@ -22,11 +22,8 @@ pub fn set_royalty(
     }
 
     // Read agreement to verify landlord (creator)
-    let agreement = env
-        .storage()
-        .persistent()
-        .get::<DataKey, crate::types::RentAgreement>(&DataKey::Agreement(token_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let agreement =
+        crate::storage::get_agreement(&env, &token_id).ok_or(RentalError::AgreementNotFound)?;
 
     agreement.admin.require_auth();
 
@@ -78,11 +75,8 @@ pub fn transfer_with_royalty(
         return Err(RentalError::InvalidAmount);
     }
 
-    let mut agreement = env
-        .storage()
-        .persistent()
-        .get::<DataKey, crate::types::RentAgreement>(&DataKey::Agreement(token_id.clone()))
-        .ok_or(RentalError::AgreementNotFound)?;
+    let mut agreement =
+        crate::storage::get_agreement(&env, &token_id).ok_or(RentalError::AgreementNotFound)?;
 
     let current_landlord = agreement.admin.clone();
     current_landlord.require_auth();
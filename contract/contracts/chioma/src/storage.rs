@@ -1,16 +1,21 @@
-use soroban_sdk::{contracttype, String};
+use soroban_sdk::{contracttype, Env, Map, String, Symbol, TryFromVal, Val};
+
+use crate::types::{PaymentSplit, PaymentSplitV1, RentAgreement, RentAgreementV1};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     Agreement(String),
     AgreementCount,
+    // Agreement secondary indexes
+    Index(IndexKey),
     State,
     PauseState,
     Initialized,
     SupportedToken(soroban_sdk::Address),
     SupportedTokens,
     ExchangeRate(soroban_sdk::Address, soroban_sdk::Address),
+    Token(TokenKey),
     AgreementToken(String),
     DepositInterestConfig(String),
     DepositInterest(String),
@@ -36,10 +41,134 @@ pub enum DataKey {
     VersionHistory,
     AgreementExtension(String),
     ExtensionHistory(String),
+    Amendment(AmendmentKey),
+    Document(DocumentKey),
+    LeaseAssignment(String),
+    Offer(OfferKey),
+    Witness(WitnessKey),
+    Arrears(ArrearsKey),
     EscrowFrozen(String),
+    DepositEscrow(DepositEscrowKey),
     UpgradeProposal(String),
     UpgradeProposalCount,
     ActiveUpgradeProposals,
     // Gas optimisation metrics (keyed by operation name string)
     GasMetrics(String),
 }
+
+/// Agreement secondary index keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IndexKey {
+    Len(crate::types::AgreementIndex),
+    Entry(crate::types::AgreementIndex, u32),
+    Position(crate::types::AgreementIndex, String),
+}
+
+/// Payment token policy and rate source keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TokenKey {
+    RateSource,
+    Policy(soroban_sdk::Address),
+    DailyVolume(soroban_sdk::Address, u64), // (token, day index)
+    Migration(String),
+    AgreementUnit(String),
+    UnitRate(soroban_sdk::Symbol, soroban_sdk::Address), // (currency, token)
+}
+
+/// Amendment and terms history keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AmendmentKey {
+    Amendment(String, u32),
+    Count(String),
+    TermsVersion(String, u32),
+    TermsVersionCount(String),
+}
+
+/// Lease document version keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DocumentKey {
+    Version(String, u32),
+    VersionCount(String),
+}
+
+/// Signed lease offer keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OfferKey {
+    SigningKey(soroban_sdk::Address),
+    Nonce(soroban_sdk::Address, u64),
+}
+
+/// Witness policy and notary keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WitnessKey {
+    Policy(String),
+    Notary(soroban_sdk::Address),
+    ProfileContract,
+}
+
+/// Arrears and default notice keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArrearsKey {
+    Policy(String),
+    DefaultNotice(String),
+    LateFees(String),
+    PaymentPlan(String),
+}
+
+/// Deposit escrow keys.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DepositEscrowKey {
+    /// Escrow contract allowed to report deposit status (instance storage).
+    Contract,
+    /// Escrow holding an agreement's deposit and its last status.
+    Record(String),
+}
+
+/// Load an agreement. Agreements stored before co-tenants existed are
+/// upgraded here and rewritten in the current layout on their next save.
+pub fn get_agreement(env: &Env, agreement_id: &String) -> Option<RentAgreement> {
+    let raw = env
+        .storage()
+        .persistent()
+        .get::<_, Val>(&DataKey::Agreement(agreement_id.clone()))?;
+    let fields = Map::<Symbol, Val>::try_from_val(env, &raw).ok()?;
+    if fields.contains_key(Symbol::new(env, "co_tenants")) {
+        RentAgreement::try_from_val(env, &raw).ok()
+    } else {
+        RentAgreementV1::try_from_val(env, &raw)
+            .ok()
+            .map(|old| old.upgrade(env))
+    }
+}
+
+/// Load the split recorded for an agreement's `payment_number`th payment,
+/// upgrading records written before transferred amounts were kept.
+pub fn get_payment_split(
+    env: &Env,
+    agreement_id: &String,
+    payment_number: u32,
+) -> Option<PaymentSplit> {
+    let raw = env
+        .storage()
+        .persistent()
+        .get::<_, Val>(&DataKey::PaymentRecord(
+            agreement_id.clone(),
+            payment_number,
+        ))?;
+    let fields = Map::<Symbol, Val>::try_from_val(env, &raw).ok()?;
+    if fields.contains_key(Symbol::new(env, "amount_paid")) {
+        PaymentSplit::try_from_val(env, &raw).ok()
+    } else {
+        PaymentSplitV1::try_from_val(env, &raw)
+            .ok()
+            .map(PaymentSplit::from)
+    }
+}
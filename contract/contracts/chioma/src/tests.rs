@@ -987,6 +987,15 @@ fn test_cancel_agreement_invalid_state() {
     env.mock_all_auths();
 
     let client = create_contract(&env);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
 
@@ -996,7 +1005,7 @@ fn test_cancel_agreement_invalid_state() {
     client.sign_agreement(&tenant, &String::from_str(&env, agreement_id));
 
     // Agreement is now PendingApproval; must be approved before cancel is invalid
-    client.approve_agreement(&landlord, &String::from_str(&env, agreement_id));
+    client.approve_agreement(&admin, &String::from_str(&env, agreement_id));
 
     // Status is now Active
 
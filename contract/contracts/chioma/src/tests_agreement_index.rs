@@ -0,0 +1,173 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+fn setup(env: &Env) -> (ContractClient<'_>, Address) {
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(env),
+            paused: false,
+        },
+    );
+    (client, admin)
+}
+
+fn create(
+    env: &Env,
+    client: &ContractClient,
+    id: &str,
+    landlord: &Address,
+    tenant: &Address,
+    agent: Option<Address>,
+) -> String {
+    let agreement_id = String::from_str(env, id);
+    client.create_agreement(&AgreementInput {
+        agreement_id: agreement_id.clone(),
+        user: tenant.clone(),
+        admin: landlord.clone(),
+        agent,
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 2_000,
+            start_date: 100,
+            end_date: 1_000_000,
+            agent_commission_rate: 0,
+        },
+        payment_token: Address::generate(env),
+        metadata_uri: String::from_str(env, ""),
+        attributes: Vec::new(env),
+    });
+    agreement_id
+}
+
+fn ids(env: &Env, page: &AgreementPage) -> Vec<String> {
+    let mut out = Vec::new(env);
+    for agreement in page.agreements.iter() {
+        out.push_back(agreement.agreement_id);
+    }
+    out
+}
+
+fn expected(env: &Env, ids: &[&str]) -> Vec<String> {
+    let mut out = Vec::new(env);
+    for id in ids {
+        out.push_back(String::from_str(env, id));
+    }
+    out
+}
+
+#[test]
+fn test_agreements_by_party() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let landlord = Address::generate(&env);
+    let other_landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    create(&env, &client, "A1", &landlord, &tenant, Some(agent.clone()));
+    create(
+        &env,
+        &client,
+        "A2",
+        &landlord,
+        &Address::generate(&env),
+        None,
+    );
+    create(&env, &client, "A3", &other_landlord, &tenant, None);
+
+    let by_landlord = client.get_agreements_by_landlord(&landlord, &0, &10);
+    assert_eq!(by_landlord.total, 2);
+    assert_eq!(ids(&env, &by_landlord), expected(&env, &["A1", "A2"]));
+    assert_eq!(by_landlord.next_cursor, None);
+
+    let by_tenant = client.get_agreements_by_tenant(&tenant, &0, &10);
+    assert_eq!(ids(&env, &by_tenant), expected(&env, &["A1", "A3"]));
+
+    let by_agent = client.get_agreements_by_agent(&agent, &0, &10);
+    assert_eq!(ids(&env, &by_agent), expected(&env, &["A1"]));
+
+    let nobody = client.get_agreements_by_tenant(&Address::generate(&env), &0, &10);
+    assert_eq!(nobody.total, 0);
+    assert!(nobody.agreements.is_empty());
+}
+
+#[test]
+fn test_pagination() {
+    let env = Env::default();
+    let (client, _) = setup(&env);
+    let landlord = Address::generate(&env);
+    for id in ["P1", "P2", "P3", "P4", "P5"] {
+        create(&env, &client, id, &landlord, &Address::generate(&env), None);
+    }
+
+    let first = client.get_agreements_by_landlord(&landlord, &0, &2);
+    assert_eq!(ids(&env, &first), expected(&env, &["P1", "P2"]));
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.get_agreements_by_landlord(&landlord, &2, &2);
+    assert_eq!(ids(&env, &second), expected(&env, &["P3", "P4"]));
+
+    let last = client.get_agreements_by_landlord(&landlord, &4, &2);
+    assert_eq!(ids(&env, &last), expected(&env, &["P5"]));
+    assert_eq!(last.next_cursor, None);
+    assert_eq!(last.total, 5);
+
+    assert_eq!(
+        client.try_get_agreements_by_landlord(&landlord, &0, &0),
+        Err(Ok(RentalError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_get_agreements_by_landlord(&landlord, &0, &(MAX_PAGE_SIZE + 1)),
+        Err(Ok(RentalError::InvalidInput))
+    );
+}
+
+#[test]
+fn test_status_index_follows_transitions() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+
+    let s1 = create(&env, &client, "S1", &landlord, &tenant, None);
+    let s2 = create(&env, &client, "S2", &landlord, &tenant, None);
+    create(&env, &client, "S3", &landlord, &tenant, None);
+    assert_eq!(
+        client
+            .get_agreements_by_status(&AgreementStatus::Draft, &0, &10)
+            .total,
+        3
+    );
+
+    client.submit_agreement(&landlord, &s1);
+    client.sign_agreement(&tenant, &s1);
+    client.approve_agreement(&admin, &s1);
+    client.cancel_agreement(&landlord, &s2);
+
+    let drafts = client.get_agreements_by_status(&AgreementStatus::Draft, &0, &10);
+    assert_eq!(ids(&env, &drafts), expected(&env, &["S3"]));
+    let active = client.get_agreements_by_status(&AgreementStatus::Active, &0, &10);
+    assert_eq!(ids(&env, &active), expected(&env, &["S1"]));
+    assert_eq!(
+        active.agreements.get(0).unwrap().status,
+        AgreementStatus::Active
+    );
+    let cancelled = client.get_agreements_by_status(&AgreementStatus::Cancelled, &0, &10);
+    assert_eq!(ids(&env, &cancelled), expected(&env, &["S2"]));
+    assert_eq!(
+        client
+            .get_agreements_by_status(&AgreementStatus::PendingApproval, &0, &10)
+            .total,
+        0
+    );
+}
@@ -0,0 +1,405 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String,
+};
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    landlord: Address,
+    tenant: Address,
+    agreement_id: String,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let agreement_id = String::from_str(&env, "AMEND1");
+    client.create_agreement(&AgreementInput {
+        agreement_id: agreement_id.clone(),
+        user: tenant.clone(),
+        admin: landlord.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 2_000,
+            start_date: 1_000,
+            end_date: 10_000_000,
+            agent_commission_rate: 0,
+        },
+        payment_token: Address::generate(&env),
+        metadata_uri: String::from_str(&env, "ipfs://v1"),
+        attributes: Vec::new(&env),
+    });
+    client.submit_agreement(&landlord, &agreement_id);
+    client.sign_agreement(&tenant, &agreement_id);
+    client.approve_agreement(&admin, &agreement_id);
+
+    Setup {
+        env,
+        client,
+        landlord,
+        tenant,
+        agreement_id,
+    }
+}
+
+#[test]
+fn test_amendment_applies_immediately_and_records_versions() {
+    let s = setup();
+    let changes = vec![
+        &s.env,
+        AmendmentChange::MonthlyRent(1_200),
+        AmendmentChange::Document(
+            BytesN::from_array(&s.env, &[2; 32]),
+            String::from_str(&s.env, "ipfs://v2"),
+        ),
+    ];
+    let id = s
+        .client
+        .propose_amendment(&s.tenant, &s.agreement_id, &changes, &1_000);
+    assert_eq!(id, 1);
+    assert_eq!(
+        s.client.get_amendment(&s.agreement_id, &id).status,
+        AmendmentStatus::Proposed
+    );
+
+    s.client.accept_amendment(&s.landlord, &s.agreement_id, &id);
+
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.monthly_rent, 1_200);
+    assert_eq!(
+        agreement.metadata_uri,
+        String::from_str(&s.env, "ipfs://v2")
+    );
+    assert_eq!(
+        s.client.get_amendment(&s.agreement_id, &id).status,
+        AmendmentStatus::Applied
+    );
+
+    assert_eq!(s.client.get_terms_version_count(&s.agreement_id), 2);
+    let original = s.client.get_terms_version(&s.agreement_id, &0);
+    assert_eq!(original.monthly_rent, 1_000);
+    assert_eq!(original.amendment_id, None);
+    let amended = s.client.get_terms_version(&s.agreement_id, &1);
+    assert_eq!(amended.monthly_rent, 1_200);
+    assert_eq!(amended.amendment_id, Some(id));
+    assert_eq!(amended.effective_from, 1_000);
+}
+
+#[test]
+fn test_document_amendment_records_document_version() {
+    let s = setup();
+    let document_hash = BytesN::from_array(&s.env, &[7; 32]);
+    let changes = vec![
+        &s.env,
+        AmendmentChange::Document(
+            document_hash.clone(),
+            String::from_str(&s.env, "ipfs://addendum"),
+        ),
+    ];
+    let id = s
+        .client
+        .propose_amendment(&s.landlord, &s.agreement_id, &changes, &1_000);
+    s.client.accept_amendment(&s.tenant, &s.agreement_id, &id);
+
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.document_hash, Some(document_hash.clone()));
+    assert_eq!(
+        agreement.metadata_uri,
+        String::from_str(&s.env, "ipfs://addendum")
+    );
+    let count = s.client.get_document_version_count(&s.agreement_id);
+    assert_eq!(agreement.document_version, count);
+
+    let version = s.client.get_document_version(&s.agreement_id, &count);
+    assert_eq!(version.document_hash, document_hash);
+    assert_eq!(version.proposed_by, s.landlord);
+    assert_eq!(version.landlord_signed_at, Some(1_000));
+    assert_eq!(version.tenant_signed_at, Some(1_000));
+}
+
+#[test]
+fn test_future_amendment_waits_for_effective_date() {
+    let s = setup();
+    let changes = vec![&s.env, AmendmentChange::MonthlyRent(900)];
+    let id = s
+        .client
+        .propose_amendment(&s.landlord, &s.agreement_id, &changes, &50_000);
+    s.client.accept_amendment(&s.tenant, &s.agreement_id, &id);
+
+    assert_eq!(
+        s.client.get_amendment(&s.agreement_id, &id).status,
+        AmendmentStatus::Accepted
+    );
+    assert_eq!(
+        s.client
+            .get_agreement(&s.agreement_id)
+            .unwrap()
+            .monthly_rent,
+        1_000
+    );
+    assert_eq!(
+        s.client.try_apply_amendment(&s.agreement_id, &id),
+        Err(Ok(AmendmentError::AmendmentNotEffective.into()))
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp = 50_000);
+    s.client.apply_amendment(&s.agreement_id, &id);
+    assert_eq!(
+        s.client
+            .get_agreement(&s.agreement_id)
+            .unwrap()
+            .monthly_rent,
+        900
+    );
+    assert_eq!(
+        s.client.try_apply_amendment(&s.agreement_id, &id),
+        Err(Ok(RentalError::InvalidState.into()))
+    );
+}
+
+#[test]
+fn test_add_and_remove_tenant() {
+    let s = setup();
+    let roommate = Address::generate(&s.env);
+    let add = vec![&s.env, AmendmentChange::AddTenant(roommate.clone())];
+    let id = s
+        .client
+        .propose_amendment(&s.tenant, &s.agreement_id, &add, &1_000);
+
+    // The incoming tenant must consent as well.
+    s.client.accept_amendment(&s.landlord, &s.agreement_id, &id);
+    assert_eq!(
+        s.client.get_amendment(&s.agreement_id, &id).status,
+        AmendmentStatus::Proposed
+    );
+    s.client.accept_amendment(&roommate, &s.agreement_id, &id);
+
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.co_tenants, vec![&s.env, roommate.clone()]);
+    assert_eq!(
+        s.client.get_agreements_by_tenant(&roommate, &0, &10).total,
+        1
+    );
+
+    let remove = vec![&s.env, AmendmentChange::RemoveTenant(roommate.clone())];
+    let id = s
+        .client
+        .propose_amendment(&s.landlord, &s.agreement_id, &remove, &1_000);
+    s.client.accept_amendment(&s.tenant, &s.agreement_id, &id);
+
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert!(agreement.co_tenants.is_empty());
+    assert_eq!(
+        s.client.get_agreements_by_tenant(&roommate, &0, &10).total,
+        0
+    );
+    assert_eq!(s.client.get_terms_version_count(&s.agreement_id), 3);
+}
+
+#[test]
+fn test_agent_change_updates_index() {
+    let s = setup();
+    let agent = Address::generate(&s.env);
+    let changes = vec![&s.env, AmendmentChange::Agent(Some(agent.clone()))];
+    let id = s
+        .client
+        .propose_amendment(&s.landlord, &s.agreement_id, &changes, &1_000);
+    s.client.accept_amendment(&s.tenant, &s.agreement_id, &id);
+
+    assert_eq!(
+        s.client.get_agreement(&s.agreement_id).unwrap().agent,
+        Some(agent.clone())
+    );
+    assert_eq!(s.client.get_agreements_by_agent(&agent, &0, &10).total, 1);
+}
+
+#[test]
+fn test_invalid_amendments_rejected() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+    let rent = vec![&s.env, AmendmentChange::MonthlyRent(1_100)];
+
+    assert_eq!(
+        s.client
+            .try_propose_amendment(&outsider, &s.agreement_id, &rent, &1_000),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_propose_amendment(&s.tenant, &s.agreement_id, &Vec::new(&s.env), &1_000),
+        Err(Ok(RentalError::InvalidInput.into()))
+    );
+    assert_eq!(
+        s.client.try_propose_amendment(
+            &s.tenant,
+            &s.agreement_id,
+            &vec![&s.env, AmendmentChange::MonthlyRent(0)],
+            &1_000
+        ),
+        Err(Ok(RentalError::InvalidAmount.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_propose_amendment(&s.tenant, &s.agreement_id, &rent, &999),
+        Err(Ok(RentalError::InvalidDate.into()))
+    );
+    assert_eq!(
+        s.client.try_propose_amendment(
+            &s.tenant,
+            &s.agreement_id,
+            &vec![
+                &s.env,
+                AmendmentChange::PaymentToken(Address::generate(&s.env))
+            ],
+            &1_000
+        ),
+        Err(Ok(RentalError::TokenNotSupported.into()))
+    );
+    assert_eq!(
+        s.client.try_propose_amendment(
+            &s.tenant,
+            &s.agreement_id,
+            &vec![&s.env, AmendmentChange::RemoveTenant(outsider.clone())],
+            &1_000
+        ),
+        Err(Ok(RentalError::InvalidInput.into()))
+    );
+}
+
+#[test]
+fn test_reject_and_cancel() {
+    let s = setup();
+    let rent = vec![&s.env, AmendmentChange::MonthlyRent(1_100)];
+
+    let first = s
+        .client
+        .propose_amendment(&s.tenant, &s.agreement_id, &rent, &1_000);
+    assert_eq!(
+        s.client
+            .try_accept_amendment(&s.tenant, &s.agreement_id, &first),
+        Err(Ok(RentalError::AlreadyApproved.into()))
+    );
+    s.client.reject_amendment(
+        &s.landlord,
+        &s.agreement_id,
+        &first,
+        &String::from_str(&s.env, "too low"),
+    );
+    assert_eq!(
+        s.client.get_amendment(&s.agreement_id, &first).status,
+        AmendmentStatus::Rejected
+    );
+    assert_eq!(
+        s.client
+            .try_accept_amendment(&s.landlord, &s.agreement_id, &first),
+        Err(Ok(RentalError::InvalidState.into()))
+    );
+
+    let second = s
+        .client
+        .propose_amendment(&s.tenant, &s.agreement_id, &rent, &1_000);
+    assert_eq!(
+        s.client.try_cancel_amendment(
+            &s.landlord,
+            &s.agreement_id,
+            &second,
+            &String::from_str(&s.env, "")
+        ),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+    s.client.cancel_amendment(
+        &s.tenant,
+        &s.agreement_id,
+        &second,
+        &String::from_str(&s.env, "changed my mind"),
+    );
+    assert_eq!(
+        s.client.get_amendment(&s.agreement_id, &second).status,
+        AmendmentStatus::Cancelled
+    );
+    assert_eq!(s.client.get_amendment_count(&s.agreement_id), 2);
+    assert_eq!(
+        s.client
+            .get_agreement(&s.agreement_id)
+            .unwrap()
+            .monthly_rent,
+        1_000
+    );
+}
+
+#[test]
+fn test_agreement_stored_before_co_tenants_is_upgraded() {
+    let s = setup();
+    let agreement_id = String::from_str(&s.env, "LEGACY1");
+    let witness = Address::generate(&s.env);
+    let legacy = types::RentAgreementV1 {
+        agreement_id: agreement_id.clone(),
+        admin: s.landlord.clone(),
+        user: s.tenant.clone(),
+        agent: None,
+        monthly_rent: 1_000,
+        security_deposit: 2_000,
+        start_date: 1_000,
+        end_date: 10_000_000,
+        agent_commission_rate: 0,
+        status: AgreementStatus::Active,
+        total_rent_paid: 0,
+        payment_count: 0,
+        signed_at: Some(1_000),
+        witness_id: Some(witness.clone()),
+        payment_token: Address::generate(&s.env),
+        next_payment_due: 1_000,
+        metadata_uri: String::from_str(&s.env, "ipfs://v1"),
+        attributes: Vec::new(&s.env),
+    };
+    s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .set(&storage::DataKey::Agreement(agreement_id.clone()), &legacy);
+    });
+
+    let agreement = s.client.get_agreement(&agreement_id).unwrap();
+    assert_eq!(agreement.co_tenants.len(), 0);
+    assert_eq!(agreement.subtenant, None);
+    assert_eq!(agreement.document_hash, None);
+    assert_eq!(agreement.document_version, 0);
+    assert_eq!(agreement.witnesses, vec![&s.env, witness]);
+    assert_eq!(agreement.monthly_rent, 1_000);
+
+    // Applying an amendment saves the agreement in the current layout
+    let changes = vec![&s.env, AmendmentChange::MonthlyRent(1_200)];
+    let id = s
+        .client
+        .propose_amendment(&s.tenant, &agreement_id, &changes, &1_000);
+    s.client.accept_amendment(&s.landlord, &agreement_id, &id);
+    s.env.as_contract(&s.client.address, || {
+        let stored: RentAgreement = s
+            .env
+            .storage()
+            .persistent()
+            .get(&storage::DataKey::Agreement(agreement_id.clone()))
+            .unwrap();
+        assert_eq!(stored.monthly_rent, 1_200);
+        assert_eq!(stored.co_tenants.len(), 0);
+    });
+}
@@ -0,0 +1,228 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, Env, String,
+};
+
+const MONTH: u64 = 30 * 24 * 60 * 60;
+const DAY: u64 = 24 * 60 * 60;
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    landlord: Address,
+    token: Address,
+    agreement_id: String,
+}
+
+fn setup<'a>(policy: Option<ArrearsPolicy>) -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_supported_token(&token, &String::from_str(&env, "USDC"), &6, &1, &0);
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&tenant, &100_000);
+
+    let agreement_id = client.create_agreement_with_token(&AgreementInput {
+        agreement_id: String::from_str(&env, "ARREARS1"),
+        user: tenant.clone(),
+        admin: landlord.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 2_000,
+            start_date: 1_000,
+            end_date: 1_000 + 12 * MONTH,
+            agent_commission_rate: 0,
+        },
+        payment_token: token.clone(),
+        metadata_uri: String::from_str(&env, ""),
+        attributes: Vec::new(&env),
+    });
+    if let Some(policy) = policy {
+        client.set_arrears_policy(&agreement_id, &policy);
+    }
+    client.submit_agreement(&landlord, &agreement_id);
+    client.sign_agreement(&tenant, &agreement_id);
+    client.approve_agreement(&admin, &agreement_id);
+
+    Setup {
+        env,
+        client,
+        landlord,
+        token,
+        agreement_id,
+    }
+}
+
+fn advance_to(s: &Setup, timestamp: u64) {
+    s.env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+#[test]
+fn test_payment_cures_notice() {
+    let s = setup(None);
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.token);
+
+    // One missed period is below the default trigger of two.
+    advance_to(&s, 1_000 + MONTH);
+    assert_eq!(
+        s.client
+            .try_issue_default_notice(&s.landlord, &s.agreement_id),
+        Err(Ok(ArrearsError::ArrearsBelowThreshold.into()))
+    );
+
+    advance_to(&s, 1_000 + 2 * MONTH);
+    let notice = s.client.issue_default_notice(&s.landlord, &s.agreement_id);
+    assert_eq!(notice.arrears, 2_000);
+    assert_eq!(notice.missed_periods, 2);
+    assert_eq!(notice.cure_deadline, 1_000 + 2 * MONTH + 14 * DAY);
+    assert_eq!(
+        s.client
+            .try_issue_default_notice(&s.landlord, &s.agreement_id),
+        Err(Ok(ArrearsError::DefaultNoticeOpen.into()))
+    );
+    assert_eq!(
+        s.client.try_declare_default(&s.landlord, &s.agreement_id),
+        Err(Ok(ArrearsError::CurePeriodActive.into()))
+    );
+
+    // A partial payment leaves the notice open; clearing the arrears cures it.
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.token);
+    assert_eq!(
+        s.client.get_default_notice(&s.agreement_id).status,
+        DefaultNoticeStatus::Open
+    );
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.token);
+    assert_eq!(
+        s.client.get_default_notice(&s.agreement_id).status,
+        DefaultNoticeStatus::Cured
+    );
+
+    advance_to(&s, 1_000 + 2 * MONTH + 15 * DAY);
+    assert_eq!(
+        s.client.try_declare_default(&s.landlord, &s.agreement_id),
+        Err(Ok(ArrearsError::DefaultNoticeNotFound.into()))
+    );
+}
+
+#[test]
+fn test_default_terminates_and_claims_deposit() {
+    let s = setup(Some(ArrearsPolicy {
+        missed_periods: 0,
+        arrears_threshold: 3_000,
+        cure_period: 7 * DAY,
+        late_fee: 0,
+    }));
+
+    advance_to(&s, 1_000 + 2 * MONTH);
+    assert_eq!(
+        s.client
+            .try_issue_default_notice(&Address::generate(&s.env), &s.agreement_id),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+    s.client.issue_default_notice(&s.landlord, &s.agreement_id);
+
+    advance_to(&s, 1_000 + 2 * MONTH + 7 * DAY);
+    let notice = s.client.declare_default(&s.landlord, &s.agreement_id);
+    assert_eq!(notice.status, DefaultNoticeStatus::Defaulted);
+    assert_eq!(notice.arrears, 3_000);
+    // The claim is capped at the security deposit.
+    assert_eq!(notice.deposit_claim, 2_000);
+    assert_eq!(
+        s.client.get_agreement(&s.agreement_id).unwrap().status,
+        AgreementStatus::Terminated
+    );
+    assert_eq!(
+        s.client
+            .try_make_payment_with_token(&s.agreement_id, &1_000, &s.token),
+        Err(Ok(RentalError::AgreementNotActive))
+    );
+}
+
+#[test]
+fn test_arrears_policy_validation() {
+    let s = setup(None);
+    assert_eq!(
+        s.client.get_arrears_policy(&s.agreement_id).missed_periods,
+        2
+    );
+    assert_eq!(
+        s.client.try_set_arrears_policy(
+            &s.agreement_id,
+            &ArrearsPolicy {
+                missed_periods: 1,
+                arrears_threshold: 0,
+                cure_period: DAY,
+                late_fee: 0,
+            }
+        ),
+        Err(Ok(RentalError::InvalidState.into()))
+    );
+
+    let env = &s.env;
+    let draft = String::from_str(env, "ARREARS2");
+    s.client.create_agreement(&AgreementInput {
+        agreement_id: draft.clone(),
+        user: Address::generate(env),
+        admin: s.landlord.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 0,
+            start_date: 1_000,
+            end_date: 1_000 + MONTH,
+            agent_commission_rate: 0,
+        },
+        payment_token: s.token.clone(),
+        metadata_uri: String::from_str(env, ""),
+        attributes: Vec::new(env),
+    });
+    for policy in [
+        ArrearsPolicy {
+            missed_periods: 0,
+            arrears_threshold: 0,
+            cure_period: DAY,
+            late_fee: 0,
+        },
+        ArrearsPolicy {
+            missed_periods: 1,
+            arrears_threshold: -1,
+            cure_period: DAY,
+            late_fee: 0,
+        },
+        ArrearsPolicy {
+            missed_periods: 1,
+            arrears_threshold: 0,
+            cure_period: 0,
+            late_fee: 0,
+        },
+    ] {
+        assert_eq!(
+            s.client.try_set_arrears_policy(&draft, &policy),
+            Err(Ok(RentalError::InvalidConfig.into()))
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, String,
+};
+
+const MONTH: u64 = 30 * 24 * 60 * 60;
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    landlord: Address,
+    tenant: Address,
+    token: Address,
+    agreement_id: String,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_supported_token(&token, &String::from_str(&env, "USDC"), &6, &1, &0);
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&tenant, &100_000);
+
+    let agreement_id = client.create_agreement_with_token(&AgreementInput {
+        agreement_id: String::from_str(&env, "LEASE1"),
+        user: tenant.clone(),
+        admin: landlord.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 2_000,
+            start_date: 1_000,
+            end_date: 1_000 + 12 * MONTH,
+            agent_commission_rate: 0,
+        },
+        payment_token: token.clone(),
+        metadata_uri: String::from_str(&env, ""),
+        attributes: Vec::new(&env),
+    });
+    client.submit_agreement(&landlord, &agreement_id);
+    client.sign_agreement(&tenant, &agreement_id);
+    client.approve_agreement(&admin, &agreement_id);
+
+    Setup {
+        env,
+        client,
+        landlord,
+        tenant,
+        token,
+        agreement_id,
+    }
+}
+
+fn funded(s: &Setup, amount: i128) -> Address {
+    let address = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token).mint(&address, &amount);
+    address
+}
+
+#[test]
+fn test_outstanding_rent_follows_periods() {
+    let s = setup();
+    assert_eq!(s.client.get_outstanding_rent(&s.agreement_id), 1_000);
+
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.token);
+    assert_eq!(s.client.get_outstanding_rent(&s.agreement_id), 0);
+
+    s.env
+        .ledger()
+        .with_mut(|li| li.timestamp = 1_000 + 2 * MONTH);
+    assert_eq!(s.client.get_outstanding_rent(&s.agreement_id), 2_000);
+
+    // No rent accrues beyond the end of the lease.
+    s.env
+        .ledger()
+        .with_mut(|li| li.timestamp = 1_000 + 20 * MONTH);
+    assert_eq!(s.client.get_outstanding_rent(&s.agreement_id), 11_000);
+}
+
+#[test]
+fn test_assignment_replaces_tenant() {
+    let s = setup();
+    let newcomer = funded(&s, 10_000);
+    let token = TokenClient::new(&s.env, &s.token);
+
+    s.client.nominate_assignee(
+        &s.tenant,
+        &s.agreement_id,
+        &newcomer,
+        &AssignmentMode::Assignment,
+        &2_500,
+    );
+    s.client.accept_assignment(&newcomer, &s.agreement_id);
+    assert_eq!(token.balance(&newcomer), 7_500);
+    assert_eq!(
+        s.client
+            .get_lease_assignment(&s.agreement_id)
+            .unwrap()
+            .status,
+        AssignmentStatus::Accepted
+    );
+
+    // The current month's rent has not been paid yet.
+    assert_eq!(
+        s.client
+            .try_approve_assignment(&s.landlord, &s.agreement_id),
+        Err(Ok(AssignmentError::OutstandingBalance.into()))
+    );
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.token);
+    assert_eq!(
+        s.client.try_approve_assignment(&s.tenant, &s.agreement_id),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+    s.client.approve_assignment(&s.landlord, &s.agreement_id);
+
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.user, newcomer);
+    assert_eq!(agreement.security_deposit, 2_500);
+    assert_eq!(agreement.subtenant, None);
+    assert_eq!(
+        s.client.get_agreements_by_tenant(&s.tenant, &0, &10).total,
+        0
+    );
+    assert_eq!(
+        s.client.get_agreements_by_tenant(&newcomer, &0, &10).total,
+        1
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp = 1_000 + MONTH);
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.token);
+    assert_eq!(token.balance(&newcomer), 6_500);
+}
+
+#[test]
+fn test_sublet_keeps_tenant_liable() {
+    let s = setup();
+    let subtenant = funded(&s, 5_000);
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.token);
+
+    assert_eq!(
+        s.client.try_nominate_assignee(
+            &s.tenant,
+            &s.agreement_id,
+            &subtenant,
+            &AssignmentMode::Sublet,
+            &500
+        ),
+        Err(Ok(RentalError::InvalidAmount.into()))
+    );
+    s.client.nominate_assignee(
+        &s.tenant,
+        &s.agreement_id,
+        &subtenant,
+        &AssignmentMode::Sublet,
+        &0,
+    );
+    s.client.accept_assignment(&subtenant, &s.agreement_id);
+    s.client.approve_assignment(&s.landlord, &s.agreement_id);
+
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.user, s.tenant);
+    assert_eq!(agreement.subtenant, Some(subtenant.clone()));
+    assert_eq!(
+        s.client.get_agreements_by_tenant(&s.tenant, &0, &10).total,
+        1
+    );
+    assert_eq!(
+        s.client.get_agreements_by_tenant(&subtenant, &0, &10).total,
+        1
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp = 1_000 + MONTH);
+    s.client
+        .make_subtenant_payment(&s.agreement_id, &1_000, &s.token);
+    assert_eq!(
+        TokenClient::new(&s.env, &s.token).balance(&subtenant),
+        4_000
+    );
+    assert_eq!(
+        s.client.get_payment_split(&s.agreement_id, &2).payer,
+        subtenant
+    );
+
+    // Only one sublet at a time.
+    assert_eq!(
+        s.client.try_nominate_assignee(
+            &s.tenant,
+            &s.agreement_id,
+            &Address::generate(&s.env),
+            &AssignmentMode::Assignment,
+            &0
+        ),
+        Err(Ok(RentalError::InvalidState.into()))
+    );
+
+    s.client.end_sublet(&s.landlord, &s.agreement_id);
+    assert_eq!(
+        s.client.get_agreement(&s.agreement_id).unwrap().subtenant,
+        None
+    );
+    assert_eq!(
+        s.client.get_agreements_by_tenant(&subtenant, &0, &10).total,
+        0
+    );
+    assert_eq!(
+        s.client
+            .try_make_subtenant_payment(&s.agreement_id, &1_000, &s.token),
+        Err(Ok(RentalError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_cancel_refunds_deposit() {
+    let s = setup();
+    let newcomer = funded(&s, 3_000);
+    let outsider = Address::generate(&s.env);
+
+    assert_eq!(
+        s.client.try_nominate_assignee(
+            &newcomer,
+            &s.agreement_id,
+            &outsider,
+            &AssignmentMode::Assignment,
+            &0
+        ),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+    assert_eq!(
+        s.client.try_nominate_assignee(
+            &s.tenant,
+            &s.agreement_id,
+            &s.landlord,
+            &AssignmentMode::Assignment,
+            &0
+        ),
+        Err(Ok(RentalError::InvalidInput.into()))
+    );
+
+    s.client.nominate_assignee(
+        &s.tenant,
+        &s.agreement_id,
+        &newcomer,
+        &AssignmentMode::Assignment,
+        &2_000,
+    );
+    assert_eq!(
+        s.client.try_accept_assignment(&outsider, &s.agreement_id),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+    s.client.accept_assignment(&newcomer, &s.agreement_id);
+    assert_eq!(
+        s.client.try_cancel_assignment(&outsider, &s.agreement_id),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+
+    s.client.cancel_assignment(&s.landlord, &s.agreement_id);
+    assert_eq!(TokenClient::new(&s.env, &s.token).balance(&newcomer), 3_000);
+    assert_eq!(
+        s.client
+            .get_lease_assignment(&s.agreement_id)
+            .unwrap()
+            .status,
+        AssignmentStatus::Cancelled
+    );
+    assert_eq!(
+        s.client
+            .try_approve_assignment(&s.landlord, &s.agreement_id),
+        Err(Ok(AssignmentError::AssignmentNotFound.into()))
+    );
+    assert_eq!(
+        s.client.get_agreement(&s.agreement_id).unwrap().user,
+        s.tenant
+    );
+}
+
+#[test]
+fn test_assignment_swaps_deposit() {
+    let s = setup();
+    let newcomer = funded(&s, 10_000);
+    let token = TokenClient::new(&s.env, &s.token);
+
+    s.client.nominate_assignee(
+        &s.tenant,
+        &s.agreement_id,
+        &newcomer,
+        &AssignmentMode::Assignment,
+        &2_500,
+    );
+    s.client.accept_assignment(&newcomer, &s.agreement_id);
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.token);
+
+    let tenant_before = token.balance(&s.tenant);
+    let landlord_before = token.balance(&s.landlord);
+    let held_before = token.balance(&s.client.address);
+    s.client.approve_assignment(&s.landlord, &s.agreement_id);
+
+    // The landlord takes the fresh deposit and refunds the old one.
+    assert_eq!(token.balance(&s.tenant) - tenant_before, 2_000);
+    assert_eq!(token.balance(&s.landlord) - landlord_before, 500);
+    assert_eq!(held_before - token.balance(&s.client.address), 2_500);
+}
+
+#[test]
+fn test_assignment_refused_while_deposit_in_escrow() {
+    let s = setup();
+    let newcomer = funded(&s, 10_000);
+    let escrow_id = BytesN::from_array(&s.env, &[7; 32]);
+    s.client.set_escrow_contract(&Address::generate(&s.env));
+    s.client
+        .update_deposit_status(&s.agreement_id, &escrow_id, &DepositStatus::Held);
+
+    s.client.nominate_assignee(
+        &s.tenant,
+        &s.agreement_id,
+        &newcomer,
+        &AssignmentMode::Assignment,
+        &2_500,
+    );
+    s.client.accept_assignment(&newcomer, &s.agreement_id);
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.token);
+    assert_eq!(
+        s.client
+            .try_approve_assignment(&s.landlord, &s.agreement_id),
+        Err(Ok(RentalError::InvalidState.into()))
+    );
+
+    s.client
+        .update_deposit_status(&s.agreement_id, &escrow_id, &DepositStatus::Refunded);
+    s.client.approve_assignment(&s.landlord, &s.agreement_id);
+    assert_eq!(
+        s.client.get_agreement(&s.agreement_id).unwrap().user,
+        newcomer
+    );
+}
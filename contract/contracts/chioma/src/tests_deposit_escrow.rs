@@ -0,0 +1,138 @@
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String};
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    agreement_id: String,
+}
+
+/// A draft agreement with a 5_000 deposit and no escrow contract registered.
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    client.initialize(
+        &Address::generate(&env),
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_supported_token(&token, &String::from_str(&env, "USDC"), &6, &1, &0);
+
+    let agreement_id = client.create_agreement_with_token(&AgreementInput {
+        agreement_id: String::from_str(&env, "DEPOSIT1"),
+        user: Address::generate(&env),
+        admin: Address::generate(&env),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 5_000,
+            start_date: 1_000,
+            end_date: 2_000_000,
+            agent_commission_rate: 0,
+        },
+        payment_token: token,
+        metadata_uri: String::from_str(&env, ""),
+        attributes: Vec::new(&env),
+    });
+
+    Setup {
+        env,
+        client,
+        agreement_id,
+    }
+}
+
+fn escrow_id(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+#[test]
+fn test_deposit_status_follows_escrow() {
+    let s = setup();
+    let first = escrow_id(&s.env, 1);
+    assert_eq!(
+        s.client
+            .try_update_deposit_status(&s.agreement_id, &first, &DepositStatus::Pending),
+        Err(Ok(DepositEscrowError::EscrowContractNotSet.into()))
+    );
+
+    let escrow_contract = Address::generate(&s.env);
+    s.client.set_escrow_contract(&escrow_contract);
+    assert_eq!(s.client.get_escrow_contract(), Some(escrow_contract));
+    assert_eq!(s.client.get_deposit_record(&s.agreement_id), None);
+
+    s.client
+        .update_deposit_status(&s.agreement_id, &first, &DepositStatus::Pending);
+    s.client
+        .update_deposit_status(&s.agreement_id, &first, &DepositStatus::Held);
+    let record = s.client.get_deposit_record(&s.agreement_id).unwrap();
+    assert_eq!(record.escrow_id, first);
+    assert_eq!(record.status, DepositStatus::Held);
+}
+
+#[test]
+fn test_held_deposit_cannot_move_escrow() {
+    let s = setup();
+    s.client.set_escrow_contract(&Address::generate(&s.env));
+    let first = escrow_id(&s.env, 1);
+    let second = escrow_id(&s.env, 2);
+
+    s.client
+        .update_deposit_status(&s.agreement_id, &first, &DepositStatus::Held);
+    assert_eq!(
+        s.client
+            .try_update_deposit_status(&s.agreement_id, &second, &DepositStatus::Pending),
+        Err(Ok(RentalError::InvalidState.into()))
+    );
+    assert_eq!(
+        s.client.try_update_deposit_status(
+            &String::from_str(&s.env, "MISSING"),
+            &first,
+            &DepositStatus::Pending
+        ),
+        Err(Ok(RentalError::AgreementNotFound.into()))
+    );
+
+    // Once the first deposit is refunded a new escrow may take over.
+    s.client
+        .update_deposit_status(&s.agreement_id, &first, &DepositStatus::Refunded);
+    s.client
+        .update_deposit_status(&s.agreement_id, &second, &DepositStatus::Pending);
+    assert_eq!(
+        s.client
+            .get_deposit_record(&s.agreement_id)
+            .unwrap()
+            .escrow_id,
+        second
+    );
+}
+
+#[test]
+fn test_deposit_terms_mirror_agreement() {
+    let s = setup();
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+
+    let terms = s.client.get_deposit_terms(&s.agreement_id).unwrap();
+    assert_eq!(terms.agreement_id, s.agreement_id);
+    assert_eq!(terms.landlord, agreement.admin);
+    assert_eq!(terms.tenant, agreement.user);
+    assert_eq!(terms.security_deposit, 5_000);
+    assert_eq!(terms.payment_token, agreement.payment_token);
+    assert_eq!(terms.status, AgreementStatus::Draft);
+
+    assert_eq!(
+        s.client
+            .get_deposit_terms(&String::from_str(&s.env, "MISSING")),
+        None
+    );
+}
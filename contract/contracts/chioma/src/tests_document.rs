@@ -0,0 +1,273 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    Address, BytesN, Env, String,
+};
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    admin: Address,
+    landlord: Address,
+    tenant: Address,
+    agreement_id: String,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let agreement_id = String::from_str(&env, "DOC1");
+    client.create_agreement(&AgreementInput {
+        agreement_id: agreement_id.clone(),
+        user: tenant.clone(),
+        admin: landlord.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 2_000,
+            start_date: 1_000,
+            end_date: 1_000_000,
+            agent_commission_rate: 0,
+        },
+        payment_token: Address::generate(&env),
+        metadata_uri: String::from_str(&env, ""),
+        attributes: Vec::new(&env),
+    });
+
+    Setup {
+        env,
+        client,
+        admin,
+        landlord,
+        tenant,
+        agreement_id,
+    }
+}
+
+fn hash(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+fn uri(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+#[test]
+fn test_signatures_bind_to_anchored_hash() {
+    let s = setup();
+    s.client.anchor_document(
+        &s.agreement_id,
+        &hash(&s.env, 1),
+        &uri(&s.env, "ipfs://lease-v1"),
+        &Vec::new(&s.env),
+    );
+    s.client.submit_agreement(&s.landlord, &s.agreement_id);
+
+    // The document is locked once submitted.
+    assert_eq!(
+        s.client.try_update_metadata(
+            &s.agreement_id,
+            &uri(&s.env, "ipfs://swapped"),
+            &Vec::new(&s.env)
+        ),
+        Err(Err(DocumentError::ConsentRequired.into()))
+    );
+    assert_eq!(
+        s.client.try_anchor_document(
+            &s.agreement_id,
+            &hash(&s.env, 2),
+            &uri(&s.env, "ipfs://swapped"),
+            &Vec::new(&s.env)
+        ),
+        Err(Ok(DocumentError::ConsentRequired.into()))
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp = 1_500);
+    s.client
+        .sign_agreement_with_document(&s.tenant, &s.agreement_id, &hash(&s.env, 1));
+
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.document_hash, Some(hash(&s.env, 1)));
+    assert_eq!(agreement.document_version, 1);
+
+    let v1 = s.client.get_document_version(&s.agreement_id, &1);
+    assert_eq!(v1.document_hash, hash(&s.env, 1));
+    assert_eq!(v1.landlord_signed_at, Some(1_000));
+    assert_eq!(v1.tenant_signed_at, Some(1_500));
+}
+
+#[test]
+fn test_anchor_document_emits_event() {
+    let s = setup();
+    s.client.anchor_document(
+        &s.agreement_id,
+        &hash(&s.env, 1),
+        &uri(&s.env, "ipfs://lease-v1"),
+        &Vec::new(&s.env),
+    );
+
+    let events = s.env.events().all();
+    let event = events.last().unwrap();
+    assert_eq!(event.0, s.client.address);
+    // Topics: event name + agreement_id
+    assert_eq!(event.1.len(), 2);
+}
+
+#[test]
+fn test_sign_rejects_a_different_document_hash() {
+    let s = setup();
+    s.client.anchor_document(
+        &s.agreement_id,
+        &hash(&s.env, 1),
+        &uri(&s.env, "ipfs://lease-v1"),
+        &Vec::new(&s.env),
+    );
+    s.client.submit_agreement(&s.landlord, &s.agreement_id);
+
+    assert_eq!(
+        s.client
+            .try_sign_agreement_with_document(&s.tenant, &s.agreement_id, &hash(&s.env, 2)),
+        Err(Err(DocumentError::DocumentHashMismatch.into()))
+    );
+    // Signing without a hash cannot bypass the anchored document.
+    assert_eq!(
+        s.client.try_sign_agreement(&s.tenant, &s.agreement_id),
+        Err(Err(DocumentError::DocumentHashMismatch.into()))
+    );
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.status, AgreementStatus::Pending);
+    assert_eq!(
+        s.client
+            .get_document_version(&s.agreement_id, &1)
+            .tenant_signed_at,
+        None
+    );
+}
+
+#[test]
+fn test_document_update_needs_both_parties() {
+    let s = setup();
+    s.client.anchor_document(
+        &s.agreement_id,
+        &hash(&s.env, 1),
+        &uri(&s.env, "ipfs://lease-v1"),
+        &Vec::new(&s.env),
+    );
+    s.client.submit_agreement(&s.landlord, &s.agreement_id);
+    s.client
+        .sign_agreement_with_document(&s.tenant, &s.agreement_id, &hash(&s.env, 1));
+    s.client.approve_agreement(&s.admin, &s.agreement_id);
+
+    let version = s.client.propose_document_update(
+        &s.landlord,
+        &s.agreement_id,
+        &hash(&s.env, 2),
+        &uri(&s.env, "ipfs://lease-v2"),
+        &Vec::new(&s.env),
+    );
+    assert_eq!(version, 2);
+    // Nothing changes until the tenant attests.
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.document_hash, Some(hash(&s.env, 1)));
+    assert_eq!(agreement.metadata_uri, uri(&s.env, "ipfs://lease-v1"));
+
+    assert_eq!(
+        s.client
+            .try_attest_document(&s.tenant, &s.agreement_id, &version, &hash(&s.env, 3)),
+        Err(Ok(DocumentError::DocumentHashMismatch.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_attest_document(&s.landlord, &s.agreement_id, &version, &hash(&s.env, 2)),
+        Err(Ok(RentalError::AlreadyApproved.into()))
+    );
+    assert_eq!(
+        s.client.try_attest_document(
+            &Address::generate(&s.env),
+            &s.agreement_id,
+            &version,
+            &hash(&s.env, 2)
+        ),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+
+    s.client
+        .attest_document(&s.tenant, &s.agreement_id, &version, &hash(&s.env, 2));
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.document_hash, Some(hash(&s.env, 2)));
+    assert_eq!(agreement.document_version, 2);
+    assert_eq!(agreement.metadata_uri, uri(&s.env, "ipfs://lease-v2"));
+
+    // The full history stays queryable.
+    assert_eq!(s.client.get_document_version_count(&s.agreement_id), 2);
+    assert_eq!(
+        s.client
+            .get_document_version(&s.agreement_id, &1)
+            .document_hash,
+        hash(&s.env, 1)
+    );
+    assert_eq!(
+        s.client
+            .try_attest_document(&s.tenant, &s.agreement_id, &1, &hash(&s.env, 1)),
+        Err(Ok(RentalError::InvalidState.into()))
+    );
+}
+
+#[test]
+fn test_superseded_proposal_cannot_be_attested() {
+    let s = setup();
+    s.client.submit_agreement(&s.landlord, &s.agreement_id);
+
+    let first = s.client.propose_document_update(
+        &s.landlord,
+        &s.agreement_id,
+        &hash(&s.env, 4),
+        &uri(&s.env, "ipfs://a"),
+        &Vec::new(&s.env),
+    );
+    let second = s.client.propose_document_update(
+        &s.tenant,
+        &s.agreement_id,
+        &hash(&s.env, 5),
+        &uri(&s.env, "ipfs://b"),
+        &Vec::new(&s.env),
+    );
+    assert_eq!(
+        s.client
+            .try_attest_document(&s.tenant, &s.agreement_id, &first, &hash(&s.env, 4)),
+        Err(Ok(RentalError::InvalidState.into()))
+    );
+    s.client
+        .attest_document(&s.landlord, &s.agreement_id, &second, &hash(&s.env, 5));
+
+    // Signing binds the tenant to the document in force.
+    s.client
+        .sign_agreement_with_document(&s.tenant, &s.agreement_id, &hash(&s.env, 5));
+    assert_eq!(
+        s.client
+            .get_agreement(&s.agreement_id)
+            .unwrap()
+            .document_version,
+        second
+    );
+    assert_eq!(
+        s.client.try_get_document_version(&s.agreement_id, &9),
+        Err(Ok(DocumentError::DocumentVersionNotFound.into()))
+    );
+}
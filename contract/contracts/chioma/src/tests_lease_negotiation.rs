@@ -87,7 +87,7 @@ fn create_active_agreement(
 #[test]
 fn lease_extension_landlord_proposes_tenant_accepts_landlord_activates() {
     let env = Env::default();
-    let (client, _system_admin) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -100,7 +100,7 @@ fn lease_extension_landlord_proposes_tenant_accepts_landlord_activates() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let before = client.get_agreement(&agreement_id).unwrap();
@@ -152,7 +152,7 @@ fn lease_extension_landlord_proposes_tenant_accepts_landlord_activates() {
 #[test]
 fn lease_extension_tenant_proposes_landlord_accepts_landlord_activates() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
 
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
@@ -165,7 +165,7 @@ fn lease_extension_tenant_proposes_landlord_accepts_landlord_activates() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let before_end = client.get_agreement(&agreement_id).unwrap().end_date;
@@ -192,7 +192,7 @@ fn lease_extension_tenant_proposes_landlord_accepts_landlord_activates() {
 #[test]
 fn propose_extension_zero_months_returns_invalid_input() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let agreement_id_str = "LEASE_EXT_BAD_MONTHS";
@@ -203,7 +203,7 @@ fn propose_extension_zero_months_returns_invalid_input() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let res = client.try_propose_extension(&landlord, &agreement_id, &0u32, &None, &None);
@@ -213,7 +213,7 @@ fn propose_extension_zero_months_returns_invalid_input() {
 #[test]
 fn propose_extension_stranger_unauthorized() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let stranger = Address::generate(&env);
@@ -225,7 +225,7 @@ fn propose_extension_stranger_unauthorized() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let res = client.try_propose_extension(&stranger, &agreement_id, &1u32, &None, &None);
@@ -246,7 +246,7 @@ fn propose_extension_agreement_not_found() {
 #[test]
 fn accept_extension_stranger_unauthorized() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let stranger = Address::generate(&env);
@@ -258,7 +258,7 @@ fn accept_extension_stranger_unauthorized() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let extension_id = client.propose_extension(&landlord, &agreement_id, &1u32, &None, &None);
@@ -270,7 +270,7 @@ fn accept_extension_stranger_unauthorized() {
 #[test]
 fn accept_extension_after_fully_accepted_is_invalid_state() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let agreement_id_str = "LEASE_EXT_DOUBLE_ACC";
@@ -281,7 +281,7 @@ fn accept_extension_after_fully_accepted_is_invalid_state() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let extension_id = client.propose_extension(&landlord, &agreement_id, &1u32, &None, &None);
@@ -294,7 +294,7 @@ fn accept_extension_after_fully_accepted_is_invalid_state() {
 #[test]
 fn activate_extension_before_accepted_invalid_state() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let agreement_id_str = "LEASE_EXT_ACT_EARLY";
@@ -305,7 +305,7 @@ fn activate_extension_before_accepted_invalid_state() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let extension_id = client.propose_extension(&landlord, &agreement_id, &1u32, &None, &None);
@@ -317,7 +317,7 @@ fn activate_extension_before_accepted_invalid_state() {
 #[test]
 fn activate_extension_non_landlord_unauthorized() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let agreement_id_str = "LEASE_EXT_ACT_NON_ADMIN";
@@ -328,7 +328,7 @@ fn activate_extension_non_landlord_unauthorized() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let extension_id = client.propose_extension(&landlord, &agreement_id, &1u32, &None, &None);
@@ -341,7 +341,7 @@ fn activate_extension_non_landlord_unauthorized() {
 #[test]
 fn reject_extension_sets_status_and_reason() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let agreement_id_str = "LEASE_EXT_REJECT";
@@ -352,7 +352,7 @@ fn reject_extension_sets_status_and_reason() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let extension_id = client.propose_extension(&landlord, &agreement_id, &2u32, &None, &None);
@@ -371,7 +371,7 @@ fn reject_extension_sets_status_and_reason() {
 #[test]
 fn cancel_extension_after_accept_prevents_activation() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let agreement_id_str = "LEASE_EXT_CANCEL_POST_ACC";
@@ -382,7 +382,7 @@ fn cancel_extension_after_accept_prevents_activation() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let extension_id = client.propose_extension(&landlord, &agreement_id, &1u32, &None, &None);
@@ -404,7 +404,7 @@ fn cancel_extension_after_accept_prevents_activation() {
 #[test]
 fn cancel_extension_twice_second_call_invalid_state() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let agreement_id_str = "LEASE_EXT_CANCEL_TWICE";
@@ -415,7 +415,7 @@ fn cancel_extension_twice_second_call_invalid_state() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let extension_id = client.propose_extension(&landlord, &agreement_id, &1u32, &None, &None);
@@ -438,7 +438,7 @@ fn get_extension_not_found() {
 #[test]
 fn get_extension_history_not_found_before_any_proposal() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let agreement_id_str = "LEASE_EXT_NO_HIST";
@@ -449,7 +449,7 @@ fn get_extension_history_not_found_before_any_proposal() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     let res = client.try_get_extension_history(&agreement_id);
@@ -459,7 +459,7 @@ fn get_extension_history_not_found_before_any_proposal() {
 #[test]
 fn propose_extension_while_paused_returns_contract_paused() {
     let env = Env::default();
-    let (client, _) = setup_initialized_client(&env);
+    let (client, system_admin) = setup_initialized_client(&env);
     let tenant = Address::generate(&env);
     let landlord = Address::generate(&env);
     let agreement_id_str = "LEASE_EXT_PAUSED";
@@ -470,7 +470,7 @@ fn propose_extension_while_paused_returns_contract_paused() {
         agreement_id_str,
         &tenant,
         &landlord,
-        &landlord,
+        &system_admin,
     );
 
     client.pause(&String::from_str(&env, "maintenance"));
@@ -0,0 +1,235 @@
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    Address, BytesN, Env, IntoVal, String,
+};
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    admin: Address,
+    landlord: Address,
+    tenant: Address,
+    token: Address,
+    key: SigningKey,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_supported_token(&token, &String::from_str(&env, "USDC"), &6, &1, &0);
+
+    let landlord = Address::generate(&env);
+    let key = SigningKey::from_bytes(&[7; 32]);
+    client.set_offer_signing_key(&landlord, &public_key(&env, &key));
+
+    Setup {
+        tenant: Address::generate(&env),
+        env,
+        client,
+        admin,
+        landlord,
+        token,
+        key,
+    }
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn offer(s: &Setup, id: &str, nonce: u64) -> LeaseOffer {
+    LeaseOffer {
+        input: AgreementInput {
+            agreement_id: String::from_str(&s.env, id),
+            user: s.tenant.clone(),
+            admin: s.landlord.clone(),
+            agent: None,
+            terms: AgreementTerms {
+                monthly_rent: 1_000,
+                security_deposit: 2_000,
+                start_date: 1_000,
+                end_date: 1_000_000,
+                agent_commission_rate: 0,
+            },
+            payment_token: s.token.clone(),
+            metadata_uri: String::from_str(&s.env, ""),
+            attributes: Vec::new(&s.env),
+        },
+        document_hash: None,
+        nonce,
+        expires_at: 5_000,
+    }
+}
+
+fn sign(s: &Setup, key: &SigningKey, offer: &LeaseOffer) -> BytesN<64> {
+    sign_hash(s, key, &s.client.get_offer_hash(offer))
+}
+
+fn sign_hash(s: &Setup, key: &SigningKey, hash: &BytesN<32>) -> BytesN<64> {
+    BytesN::from_array(&s.env, &key.sign(&hash.to_array()).to_bytes())
+}
+
+type AcceptResult = Result<
+    Result<String, soroban_sdk::ConversionError>,
+    Result<soroban_sdk::Error, soroban_sdk::InvokeError>,
+>;
+
+/// Accept an offer with only `tenant`'s authorization mocked, so a hidden
+/// landlord `require_auth` would fail the call.
+fn accept(s: &Setup, tenant: &Address, offer: &LeaseOffer, signature: &BytesN<64>) -> AcceptResult {
+    s.client
+        .mock_auths(&[MockAuth {
+            address: tenant,
+            invoke: &MockAuthInvoke {
+                contract: &s.client.address,
+                fn_name: "accept_signed_offer",
+                args: (tenant.clone(), offer.clone(), signature.clone()).into_val(&s.env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_accept_signed_offer(tenant, offer, signature)
+}
+
+#[test]
+fn test_signed_offer_creates_signed_agreement() {
+    let s = setup();
+    let offer = offer(&s, "OFFER1", 1);
+    let signature = sign(&s, &s.key, &offer);
+
+    let agreement_id = accept(&s, &s.tenant, &offer, &signature).unwrap().unwrap();
+    let agreement = s.client.get_agreement(&agreement_id).unwrap();
+    assert_eq!(agreement.status, AgreementStatus::PendingApproval);
+    assert_eq!(agreement.signed_at, Some(1_000));
+    assert_eq!(s.client.get_agreement_token(&agreement_id), s.token);
+    assert!(s.client.is_offer_nonce_used(&s.landlord, &1));
+
+    s.client.approve_agreement(&s.admin, &agreement_id);
+    assert_eq!(
+        s.client.get_agreement(&agreement_id).unwrap().status,
+        AgreementStatus::Active
+    );
+
+    // Replaying the same signed offer is refused.
+    assert_eq!(
+        accept(&s, &s.tenant, &offer, &signature),
+        Err(Ok(OfferError::OfferNonceUsed.into()))
+    );
+}
+
+#[test]
+fn test_signed_offer_attests_document_hash() {
+    let s = setup();
+    let document_hash = BytesN::from_array(&s.env, &[9; 32]);
+    let mut offer = offer(&s, "OFFER_DOC", 7);
+    offer.document_hash = Some(document_hash.clone());
+    let signature = sign(&s, &s.key, &offer);
+
+    let agreement_id = accept(&s, &s.tenant, &offer, &signature).unwrap().unwrap();
+    let agreement = s.client.get_agreement(&agreement_id).unwrap();
+    assert_eq!(agreement.document_hash, Some(document_hash.clone()));
+    assert_eq!(agreement.document_version, 1);
+
+    let version = s.client.get_document_version(&agreement_id, &1);
+    assert_eq!(version.document_hash, document_hash);
+    assert_eq!(version.landlord_signed_at, Some(1_000));
+    assert_eq!(version.tenant_signed_at, Some(1_000));
+}
+
+#[test]
+fn test_offer_checks() {
+    let s = setup();
+
+    let other = offer(&s, "OFFER2", 2);
+    let signature = sign(&s, &s.key, &other);
+    assert_eq!(
+        accept(&s, &Address::generate(&s.env), &other, &signature),
+        Err(Ok(RentalError::NotTenant.into()))
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp = 5_001);
+    assert_eq!(
+        accept(&s, &s.tenant, &other, &signature),
+        Err(Ok(OfferError::OfferExpired.into()))
+    );
+    s.env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    s.client.revoke_offer(&s.landlord, &2);
+    assert_eq!(
+        accept(&s, &s.tenant, &other, &signature),
+        Err(Ok(OfferError::OfferNonceUsed.into()))
+    );
+
+    let mut unknown = offer(&s, "OFFER3", 3);
+    unknown.input.admin = Address::generate(&s.env);
+    let signature = sign(&s, &s.key, &unknown);
+    assert_eq!(
+        accept(&s, &s.tenant, &unknown, &signature),
+        Err(Ok(OfferError::SigningKeyNotFound.into()))
+    );
+}
+
+#[test]
+fn test_invalid_signature_rejected() {
+    let s = setup();
+    let original = offer(&s, "OFFER4", 4);
+    let signature = sign(&s, &s.key, &original);
+
+    let mut tampered = original.clone();
+    tampered.input.terms.monthly_rent = 1;
+    assert!(accept(&s, &s.tenant, &tampered, &signature).is_err());
+
+    // Rotating the key invalidates signatures made with the old one.
+    let rotated = SigningKey::from_bytes(&[9; 32]);
+    s.client
+        .set_offer_signing_key(&s.landlord, &public_key(&s.env, &rotated));
+    assert!(accept(&s, &s.tenant, &original, &signature).is_err());
+    assert!(!s.client.is_offer_nonce_used(&s.landlord, &4));
+
+    let signature = sign(&s, &rotated, &original);
+    accept(&s, &s.tenant, &original, &signature)
+        .unwrap()
+        .unwrap();
+}
+
+#[test]
+fn test_malformed_signature_rejected() {
+    let s = setup();
+    let offer = offer(&s, "OFFER5", 5);
+
+    let garbage = BytesN::from_array(&s.env, &[1; 64]);
+    assert!(accept(&s, &s.tenant, &offer, &garbage).is_err());
+    assert!(!s.client.is_offer_nonce_used(&s.landlord, &5));
+}
+
+#[test]
+fn test_signature_for_other_contract_rejected() {
+    let s = setup();
+    let offer = offer(&s, "OFFER6", 6);
+
+    // The same offer hashed for another deployment of the contract.
+    let other = ContractClient::new(&s.env, &s.env.register(Contract, ()));
+    let other_hash = other.get_offer_hash(&offer);
+    assert_ne!(other_hash, s.client.get_offer_hash(&offer));
+
+    let signature = sign_hash(&s, &s.key, &other_hash);
+    assert!(accept(&s, &s.tenant, &offer, &signature).is_err());
+    assert!(!s.client.is_offer_nonce_used(&s.landlord, &6));
+}
@@ -0,0 +1,298 @@
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    Address, Env, String, Symbol,
+};
+
+/// Minimal SEP-40 feed with 14 decimals, as used by Reflector.
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_price(env: Env, token: Address, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&token, &PriceData { price, timestamp });
+    }
+
+    pub fn set_fiat_price(env: Env, code: Symbol, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&code, &PriceData { price, timestamp });
+    }
+
+    pub fn lastprice(env: Env, asset: OracleAsset) -> Option<PriceData> {
+        match asset {
+            OracleAsset::Stellar(token) => env.storage().instance().get(&token),
+            OracleAsset::Other(code) => env.storage().instance().get(&code),
+        }
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        14
+    }
+}
+
+pub(crate) const ORACLE_ONE: i128 = 100_000_000_000_000; // 1.0 at 14 decimals
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    oracle: MockOracleClient<'a>,
+    token_a: Address,
+    token_b: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+
+    let oracle_id = env.register(MockOracle, ());
+    let oracle = MockOracleClient::new(&env, &oracle_id);
+
+    Setup {
+        token_a: Address::generate(&env),
+        token_b: Address::generate(&env),
+        env,
+        client,
+        oracle,
+    }
+}
+
+fn oracle_config(s: &Setup, max_age: u64, max_deviation_bps: u32) -> RateSourceConfig {
+    RateSourceConfig {
+        oracle: Some(s.oracle.address.clone()),
+        base_token: None,
+        quote_currency: None,
+        max_age,
+        max_deviation_bps,
+    }
+}
+
+#[test]
+fn test_oracle_cross_rate() {
+    let s = setup();
+    s.client.set_rate_source(&oracle_config(&s, 0, 0));
+    s.oracle
+        .set_price(&s.token_a, &(ORACLE_ONE * 11 / 10), &10_000);
+    s.oracle.set_price(&s.token_b, &(ORACLE_ONE / 2), &10_000);
+
+    let rate = s.client.get_exchange_rate(&s.token_a, &s.token_b);
+    assert_eq!(rate, 2_200_000_000_000_000_000);
+    assert_eq!(s.client.convert_amount(&s.token_a, &s.token_b, &1000), 2200);
+}
+
+#[test]
+fn test_oracle_base_token_is_priced_at_one() {
+    let s = setup();
+    let mut config = oracle_config(&s, 0, 0);
+    config.base_token = Some(s.token_b.clone());
+    s.client.set_rate_source(&config);
+    s.oracle.set_price(&s.token_a, &(ORACLE_ONE * 3), &10_000);
+
+    assert_eq!(
+        s.client.get_exchange_rate(&s.token_a, &s.token_b),
+        3 * RATE_SCALE
+    );
+    assert_eq!(
+        s.client.get_exchange_rate(&s.token_b, &s.token_a),
+        RATE_SCALE / 3
+    );
+}
+
+#[test]
+fn test_stale_oracle_quote_rejected() {
+    let s = setup();
+    s.client.set_rate_source(&oracle_config(&s, 600, 0));
+    s.oracle.set_price(&s.token_a, &ORACLE_ONE, &9_000);
+    s.oracle.set_price(&s.token_b, &ORACLE_ONE, &10_000);
+
+    assert_eq!(
+        s.client.try_get_exchange_rate(&s.token_a, &s.token_b),
+        Err(Err(OracleError::RateStale.into()))
+    );
+}
+
+#[test]
+fn test_oracle_deviation_guard_and_sync() {
+    let s = setup();
+    s.client.set_rate_source(&oracle_config(&s, 0, 500));
+    s.oracle.set_price(&s.token_a, &ORACLE_ONE, &10_000);
+    s.oracle.set_price(&s.token_b, &ORACLE_ONE, &10_000);
+
+    // No reference yet: the first quote is accepted and stored.
+    assert_eq!(
+        s.client.sync_exchange_rate(&s.token_a, &s.token_b),
+        RATE_SCALE
+    );
+
+    // A 4% move stays within the 5% threshold.
+    s.oracle
+        .set_price(&s.token_a, &(ORACLE_ONE * 104 / 100), &10_000);
+    assert_eq!(
+        s.client.sync_exchange_rate(&s.token_a, &s.token_b),
+        RATE_SCALE * 104 / 100
+    );
+
+    // A further 20% jump is rejected for both reads and syncs.
+    s.oracle
+        .set_price(&s.token_a, &(ORACLE_ONE * 125 / 100), &10_000);
+    assert_eq!(
+        s.client.try_get_exchange_rate(&s.token_a, &s.token_b),
+        Err(Err(OracleError::RateDeviationTooLarge.into()))
+    );
+    assert_eq!(
+        s.client.try_sync_exchange_rate(&s.token_a, &s.token_b),
+        Err(Ok(OracleError::RateDeviationTooLarge.into()))
+    );
+
+    // The admin can reset the reference rate by hand.
+    s.client
+        .set_exchange_rate(&s.token_a, &s.token_b, &(RATE_SCALE * 125 / 100));
+    assert_eq!(
+        s.client.get_exchange_rate(&s.token_a, &s.token_b),
+        RATE_SCALE * 125 / 100
+    );
+}
+
+#[test]
+fn test_missing_oracle_quote_falls_back_to_stored_rate() {
+    let s = setup();
+    s.client.set_rate_source(&oracle_config(&s, 0, 0));
+    s.oracle.set_price(&s.token_a, &ORACLE_ONE, &10_000);
+    s.client
+        .set_exchange_rate(&s.token_a, &s.token_b, &(2 * RATE_SCALE));
+
+    assert_eq!(
+        s.client.get_exchange_rate(&s.token_a, &s.token_b),
+        2 * RATE_SCALE
+    );
+}
+
+#[test]
+fn test_inverse_and_cross_rates_from_stored_rates() {
+    let s = setup();
+    let base = Address::generate(&s.env);
+    s.client.set_rate_source(&RateSourceConfig {
+        oracle: None,
+        base_token: Some(base.clone()),
+        quote_currency: None,
+        max_age: 0,
+        max_deviation_bps: 0,
+    });
+    s.client
+        .set_exchange_rate(&s.token_a, &base, &(4 * RATE_SCALE));
+    s.client
+        .set_exchange_rate(&s.token_b, &base, &(2 * RATE_SCALE));
+
+    // Inverse of a stored pair.
+    assert_eq!(
+        s.client.get_exchange_rate(&base, &s.token_a),
+        RATE_SCALE / 4
+    );
+    // A -> base -> B, using the inverse of the B/base rate.
+    assert_eq!(
+        s.client.get_exchange_rate(&s.token_a, &s.token_b),
+        2 * RATE_SCALE
+    );
+}
+
+#[test]
+fn test_stored_rate_max_age() {
+    let s = setup();
+    s.client.set_rate_source(&RateSourceConfig {
+        oracle: None,
+        base_token: None,
+        quote_currency: None,
+        max_age: 3_600,
+        max_deviation_bps: 0,
+    });
+    s.client
+        .set_exchange_rate(&s.token_a, &s.token_b, &RATE_SCALE);
+    assert_eq!(
+        s.client.get_exchange_rate(&s.token_a, &s.token_b),
+        RATE_SCALE
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp = 10_000 + 3_601);
+    assert_eq!(
+        s.client.try_get_exchange_rate(&s.token_a, &s.token_b),
+        Err(Err(OracleError::RateStale.into()))
+    );
+}
+
+#[test]
+fn test_convert_amount_normalizes_decimals() {
+    let s = setup();
+    s.client.add_supported_token(
+        &s.token_a,
+        &String::from_str(&s.env, "XLM"),
+        &7,
+        &1,
+        &i128::MAX,
+    );
+    s.client.add_supported_token(
+        &s.token_b,
+        &String::from_str(&s.env, "USDC"),
+        &6,
+        &1,
+        &i128::MAX,
+    );
+    // 1 XLM = 0.5 USDC
+    s.client
+        .set_exchange_rate(&s.token_a, &s.token_b, &(RATE_SCALE / 2));
+
+    // 10 XLM (7 decimals) -> 5 USDC (6 decimals)
+    assert_eq!(
+        s.client
+            .convert_amount(&s.token_a, &s.token_b, &100_000_000),
+        5_000_000
+    );
+    // 5 USDC -> 10 XLM via the inverse rate
+    assert_eq!(
+        s.client.convert_amount(&s.token_b, &s.token_a, &5_000_000),
+        100_000_000
+    );
+}
+
+#[test]
+fn test_invalid_rate_rejected() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_set_exchange_rate(&s.token_a, &s.token_b, &0),
+        Err(Ok(RentalError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_oracle_rate_rounding_to_zero_rejected() {
+    let s = setup();
+    s.client.set_rate_source(&oracle_config(&s, 0, 0));
+    s.oracle.set_price(&s.token_a, &1, &10_000);
+    s.oracle
+        .set_price(&s.token_b, &(ORACLE_ONE * 1_000_000), &10_000);
+
+    assert_eq!(
+        s.client.try_get_exchange_rate(&s.token_a, &s.token_b),
+        Err(Ok(RentalError::ConversionError))
+    );
+    assert_eq!(
+        s.client.try_sync_exchange_rate(&s.token_a, &s.token_b),
+        Err(Ok(RentalError::ConversionError.into()))
+    );
+}
@@ -0,0 +1,218 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, Env, String,
+};
+
+const MONTH: u64 = 30 * 24 * 60 * 60;
+const DAY: u64 = 24 * 60 * 60;
+/// Two periods in: one rent paid, two periods (2_000) in arrears.
+const NOTICE_AT: u64 = 1_000 + 2 * MONTH;
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    landlord: Address,
+    tenant: Address,
+    token: Address,
+    agreement_id: String,
+}
+
+/// Active lease at 1_000/month with a 100 late fee per missed period and
+/// the tenant two periods behind with a notice served.
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_supported_token(&token, &String::from_str(&env, "USDC"), &6, &1, &0);
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&tenant, &100_000);
+
+    let agreement_id = client.create_agreement_with_token(&AgreementInput {
+        agreement_id: String::from_str(&env, "PLAN1"),
+        user: tenant.clone(),
+        admin: landlord.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 5_000,
+            start_date: 1_000,
+            end_date: 1_000 + 12 * MONTH,
+            agent_commission_rate: 0,
+        },
+        payment_token: token.clone(),
+        metadata_uri: String::from_str(&env, ""),
+        attributes: Vec::new(&env),
+    });
+    client.set_arrears_policy(
+        &agreement_id,
+        &ArrearsPolicy {
+            missed_periods: 2,
+            arrears_threshold: 0,
+            cure_period: 7 * DAY,
+            late_fee: 100,
+        },
+    );
+    client.submit_agreement(&landlord, &agreement_id);
+    client.sign_agreement(&tenant, &agreement_id);
+    client.approve_agreement(&admin, &agreement_id);
+
+    client.make_payment_with_token(&agreement_id, &1_000, &token);
+    env.ledger().with_mut(|li| li.timestamp = NOTICE_AT);
+    client.issue_default_notice(&landlord, &agreement_id);
+
+    Setup {
+        env,
+        client,
+        landlord,
+        tenant,
+        token,
+        agreement_id,
+    }
+}
+
+fn advance_to(s: &Setup, timestamp: u64) {
+    s.env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+fn pay(s: &Setup, amount: i128) {
+    s.client
+        .make_payment_with_token(&s.agreement_id, &amount, &s.token);
+}
+
+fn notice_status(s: &Setup) -> DefaultNoticeStatus {
+    s.client.get_default_notice(&s.agreement_id).status
+}
+
+#[test]
+fn test_honoured_plan_cures_and_forgives_fees() {
+    let s = setup();
+    assert_eq!(s.client.get_late_fees(&s.agreement_id), 200);
+
+    s.client
+        .propose_payment_plan(&s.landlord, &s.agreement_id, &2, &true);
+    assert_eq!(
+        s.client
+            .try_accept_payment_plan(&s.landlord, &s.agreement_id),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+    let plan = s.client.accept_payment_plan(&s.tenant, &s.agreement_id);
+    assert_eq!(plan.arrears, 2_000);
+    assert_eq!(plan.instalment_amount, 1_000);
+    assert_eq!(plan.waived_late_fees, 200);
+    assert_eq!(s.client.get_late_fees(&s.agreement_id), 0);
+    assert_eq!(notice_status(&s), DefaultNoticeStatus::Suspended);
+
+    // The default process is on hold while the plan runs.
+    assert_eq!(
+        s.client
+            .try_issue_default_notice(&s.landlord, &s.agreement_id),
+        Err(Ok(PaymentPlanError::PaymentPlanActive.into()))
+    );
+    assert_eq!(
+        s.client.try_declare_default(&s.landlord, &s.agreement_id),
+        Err(Ok(ArrearsError::DefaultNoticeNotFound.into()))
+    );
+
+    // First instalment before its deadline, then rent for the new period.
+    advance_to(&s, NOTICE_AT + 10 * DAY);
+    pay(&s, 1_000);
+    advance_to(&s, NOTICE_AT + MONTH);
+    pay(&s, 1_000);
+    let plan = s.client.get_payment_plan(&s.agreement_id);
+    assert_eq!(plan.instalments_met, 1);
+    assert_eq!(plan.status, PaymentPlanStatus::Active);
+
+    // Paying the last instalment early clears the arrears and ends the plan.
+    advance_to(&s, NOTICE_AT + MONTH + DAY);
+    pay(&s, 1_000);
+    assert_eq!(
+        s.client.get_payment_plan(&s.agreement_id).status,
+        PaymentPlanStatus::Completed
+    );
+    assert_eq!(notice_status(&s), DefaultNoticeStatus::Cured);
+    assert_eq!(s.client.get_late_fees(&s.agreement_id), 0);
+}
+
+#[test]
+fn test_missed_instalment_resumes_default() {
+    let s = setup();
+    s.client
+        .propose_payment_plan(&s.tenant, &s.agreement_id, &2, &true);
+    s.client.accept_payment_plan(&s.landlord, &s.agreement_id);
+
+    // Paying after the deadline does not count towards the instalment.
+    advance_to(&s, NOTICE_AT + MONTH);
+    pay(&s, 2_000);
+    assert_eq!(
+        s.client.get_payment_plan(&s.agreement_id).status,
+        PaymentPlanStatus::Broken
+    );
+    assert_eq!(s.client.get_late_fees(&s.agreement_id), 200);
+    assert_eq!(notice_status(&s), DefaultNoticeStatus::Open);
+
+    // The cure period was already granted, so default is immediate.
+    let notice = s.client.declare_default(&s.landlord, &s.agreement_id);
+    assert_eq!(notice.arrears, 1_000);
+    assert_eq!(notice.deposit_claim, 1_200);
+}
+
+#[test]
+fn test_plan_checks_and_late_fee_payment() {
+    let s = setup();
+    assert_eq!(
+        s.client
+            .try_propose_payment_plan(&s.tenant, &s.agreement_id, &0, &false),
+        Err(Ok(RentalError::InvalidConfig.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_propose_payment_plan(&Address::generate(&s.env), &s.agreement_id, &3, &false),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_accept_payment_plan(&s.landlord, &s.agreement_id),
+        Err(Ok(PaymentPlanError::PaymentPlanNotFound.into()))
+    );
+
+    // Without a waiver the fees stay payable while the plan runs.
+    s.client
+        .propose_payment_plan(&s.tenant, &s.agreement_id, &3, &false);
+    let plan = s.client.accept_payment_plan(&s.landlord, &s.agreement_id);
+    assert_eq!(plan.instalment_amount, 667);
+    assert_eq!(s.client.get_late_fees(&s.agreement_id), 200);
+    assert_eq!(
+        s.client.try_pay_late_fees(&s.tenant, &s.agreement_id, &201),
+        Err(Ok(RentalError::InvalidAmount.into()))
+    );
+    s.client.pay_late_fees(&s.tenant, &s.agreement_id, &50);
+    assert_eq!(s.client.get_late_fees(&s.agreement_id), 150);
+
+    // No arrears, no plan.
+    pay(&s, 2_000);
+    assert_eq!(
+        s.client
+            .try_propose_payment_plan(&s.tenant, &s.agreement_id, &2, &false),
+        Err(Ok(ArrearsError::ArrearsBelowThreshold.into()))
+    );
+}
@@ -0,0 +1,254 @@
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::StellarAssetClient,
+    Address, Env, String,
+};
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    landlord: Address,
+    tenant: Address,
+    usdc: Address,
+    eurt: Address,
+    agreement_id: String,
+}
+
+fn new_token(env: &Env, client: &ContractClient, symbol: &str, tenant: &Address) -> Address {
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    client.add_supported_token(&token, &String::from_str(env, symbol), &6, &10, &5_000);
+    StellarAssetClient::new(env, &token).mint(tenant, &1_000_000);
+    token
+}
+
+fn agreement_input(
+    env: &Env,
+    id: &str,
+    tenant: &Address,
+    landlord: &Address,
+    token: &Address,
+) -> AgreementInput {
+    AgreementInput {
+        agreement_id: String::from_str(env, id),
+        user: tenant.clone(),
+        admin: landlord.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 2_000,
+            start_date: 100,
+            end_date: 1_000_000,
+            agent_commission_rate: 0,
+        },
+        payment_token: token.clone(),
+        metadata_uri: String::from_str(env, ""),
+        attributes: Vec::new(env),
+    }
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 100);
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let usdc = new_token(&env, &client, "USDC", &tenant);
+    let eurt = new_token(&env, &client, "EURT", &tenant);
+    client.set_exchange_rate(&eurt, &usdc, &1_000_000_000_000_000_000);
+
+    let agreement_id = client
+        .create_agreement_with_token(&agreement_input(&env, "POLICY1", &tenant, &landlord, &usdc));
+    client.submit_agreement(&landlord, &agreement_id);
+    client.sign_agreement(&tenant, &agreement_id);
+    client.approve_agreement(&admin, &agreement_id);
+
+    Setup {
+        env,
+        client,
+        landlord,
+        tenant,
+        usdc,
+        eurt,
+        agreement_id,
+    }
+}
+
+#[test]
+fn test_payment_outside_token_bounds_rejected() {
+    let s = setup();
+    assert_eq!(
+        s.client
+            .try_make_payment_with_token(&s.agreement_id, &5_001, &s.usdc),
+        Err(Err(TokenError::AmountAboveTokenMaximum.into()))
+    );
+
+    s.client.add_supported_token(
+        &s.eurt,
+        &String::from_str(&s.env, "EURT"),
+        &6,
+        &2_000,
+        &5_000,
+    );
+    assert_eq!(
+        s.client
+            .try_make_payment_with_token(&s.agreement_id, &1_000, &s.eurt),
+        Err(Err(TokenError::AmountBelowTokenMinimum.into()))
+    );
+}
+
+#[test]
+fn test_payment_token_must_be_supported() {
+    let s = setup();
+    let other = s
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&s.env))
+        .address();
+    s.client
+        .set_exchange_rate(&other, &s.usdc, &1_000_000_000_000_000_000);
+
+    assert_eq!(
+        s.client
+            .try_make_payment_with_token(&s.agreement_id, &1_000, &other),
+        Err(Ok(RentalError::TokenNotSupported))
+    );
+}
+
+#[test]
+fn test_daily_cap_resets_next_day() {
+    let s = setup();
+    s.client.set_token_policy(
+        &s.usdc,
+        &TokenPolicy {
+            usage: TokenUsage::RentAndDeposit,
+            daily_cap: 1_500,
+        },
+    );
+
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.usdc);
+    assert_eq!(s.client.get_token_daily_volume(&s.usdc), 1_000);
+    assert_eq!(
+        s.client
+            .try_make_payment_with_token(&s.agreement_id, &1_000, &s.usdc),
+        Err(Err(TokenError::TokenDailyCapExceeded.into()))
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp = 100 + 86_400);
+    assert_eq!(s.client.get_token_daily_volume(&s.usdc), 0);
+    s.client
+        .make_payment_with_token(&s.agreement_id, &1_000, &s.usdc);
+}
+
+#[test]
+fn test_token_usage_policy() {
+    let s = setup();
+    s.client.set_token_policy(
+        &s.eurt,
+        &TokenPolicy {
+            usage: TokenUsage::Deposit,
+            daily_cap: 0,
+        },
+    );
+
+    assert_eq!(
+        s.client
+            .try_make_payment_with_token(&s.agreement_id, &1_000, &s.eurt),
+        Err(Err(TokenError::TokenUsageNotAllowed.into()))
+    );
+    assert_eq!(
+        s.client.try_create_agreement_with_token(&agreement_input(
+            &s.env,
+            "POLICY2",
+            &s.tenant,
+            &s.landlord,
+            &s.eurt
+        )),
+        Err(Err(TokenError::TokenUsageNotAllowed.into()))
+    );
+
+    // A rent-only token cannot back an agreement that carries a deposit.
+    s.client.set_token_policy(
+        &s.eurt,
+        &TokenPolicy {
+            usage: TokenUsage::Rent,
+            daily_cap: 0,
+        },
+    );
+    assert_eq!(
+        s.client.try_create_agreement_with_token(&agreement_input(
+            &s.env,
+            "POLICY3",
+            &s.tenant,
+            &s.landlord,
+            &s.eurt
+        )),
+        Err(Err(TokenError::TokenUsageNotAllowed.into()))
+    );
+}
+
+#[test]
+fn test_disabled_token_migration() {
+    let s = setup();
+    assert!(!s.client.needs_token_migration(&s.agreement_id));
+    assert_eq!(
+        s.client
+            .try_propose_token_migration(&s.tenant, &s.agreement_id, &s.eurt, &900),
+        Err(Ok(TokenError::MigrationNotRequired.into()))
+    );
+
+    s.client.remove_supported_token(&s.usdc);
+    assert!(s.client.needs_token_migration(&s.agreement_id));
+    assert_eq!(
+        s.client
+            .try_make_payment_with_token(&s.agreement_id, &1_000, &s.usdc),
+        Err(Ok(RentalError::TokenNotSupported))
+    );
+    assert_eq!(
+        s.client
+            .try_accept_token_migration(&s.landlord, &s.agreement_id),
+        Err(Ok(TokenError::MigrationNotFound.into()))
+    );
+
+    s.client
+        .propose_token_migration(&s.tenant, &s.agreement_id, &s.eurt, &900);
+    assert_eq!(
+        s.client
+            .try_accept_token_migration(&s.tenant, &s.agreement_id),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+    let outsider = Address::generate(&s.env);
+    assert_eq!(
+        s.client
+            .try_accept_token_migration(&outsider, &s.agreement_id),
+        Err(Ok(RentalError::Unauthorized.into()))
+    );
+
+    s.client
+        .accept_token_migration(&s.landlord, &s.agreement_id);
+
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.payment_token, s.eurt);
+    assert_eq!(agreement.monthly_rent, 900);
+    assert_eq!(s.client.get_agreement_token(&s.agreement_id), s.eurt);
+    assert!(s.client.get_token_migration(&s.agreement_id).is_none());
+    assert!(!s.client.needs_token_migration(&s.agreement_id));
+
+    s.client
+        .make_payment_with_token(&s.agreement_id, &900, &s.eurt);
+}
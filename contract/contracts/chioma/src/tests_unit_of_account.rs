@@ -0,0 +1,260 @@
+use super::*;
+use crate::tests_oracle::{MockOracle, MockOracleClient, ORACLE_ONE};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
+    Address, Env, String, Symbol,
+};
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    oracle: MockOracleClient<'a>,
+    tenant: Address,
+    usdc: Address,
+    xlm: Address,
+    agreement_id: String,
+}
+
+/// Agreement priced at 1,500.00 USD, settled in USDC (6 decimals) or
+/// XLM (7 decimals). The oracle quotes in USD: USDC = 1.00, XLM = 0.10.
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 100);
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+
+    let usdc = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let xlm = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.add_supported_token(&usdc, &String::from_str(&env, "USDC"), &6, &1, &i128::MAX);
+    client.add_supported_token(&xlm, &String::from_str(&env, "XLM"), &7, &1, &i128::MAX);
+
+    let oracle_id = env.register(MockOracle, ());
+    let oracle = MockOracleClient::new(&env, &oracle_id);
+    oracle.set_price(&usdc, &ORACLE_ONE, &100);
+    oracle.set_price(&xlm, &(ORACLE_ONE / 10), &100);
+    client.set_rate_source(&RateSourceConfig {
+        oracle: Some(oracle_id),
+        base_token: None,
+        quote_currency: Some(Symbol::new(&env, "USD")),
+        max_age: 600,
+        max_deviation_bps: 0,
+    });
+
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let agreement_id = client.create_agreement_with_unit(
+        &AgreementInput {
+            agreement_id: String::from_str(&env, "FIAT1"),
+            user: tenant.clone(),
+            admin: landlord.clone(),
+            agent: None,
+            terms: AgreementTerms {
+                monthly_rent: 150_000,
+                security_deposit: 300_000,
+                start_date: 100,
+                end_date: 1_000_000,
+                agent_commission_rate: 0,
+            },
+            payment_token: usdc.clone(),
+            metadata_uri: String::from_str(&env, ""),
+            attributes: Vec::new(&env),
+        },
+        &UnitOfAccount {
+            code: Symbol::new(&env, "USD"),
+            decimals: 2,
+        },
+    );
+    client.submit_agreement(&landlord, &agreement_id);
+    client.sign_agreement(&tenant, &agreement_id);
+    client.approve_agreement(&admin, &agreement_id);
+
+    StellarAssetClient::new(&env, &usdc).mint(&tenant, &10_000_000_000);
+    StellarAssetClient::new(&env, &xlm).mint(&tenant, &1_000_000_000_000);
+
+    Setup {
+        env,
+        client,
+        oracle,
+        tenant,
+        usdc,
+        xlm,
+        agreement_id,
+    }
+}
+
+#[test]
+fn test_unit_of_account_is_stored() {
+    let s = setup();
+    let unit = s.client.get_agreement_unit(&s.agreement_id).unwrap();
+    assert_eq!(unit.code, Symbol::new(&s.env, "USD"));
+    assert_eq!(unit.decimals, 2);
+}
+
+#[test]
+fn test_quote_rent_payment_per_token() {
+    let s = setup();
+    assert_eq!(
+        s.client.quote_rent_payment(&s.agreement_id, &s.usdc),
+        1_500_000_000
+    );
+    assert_eq!(
+        s.client.quote_rent_payment(&s.agreement_id, &s.xlm),
+        150_000_000_000
+    );
+}
+
+#[test]
+fn test_pay_fiat_rent_charges_converted_amount_only() {
+    let s = setup();
+    let xlm = TokenClient::new(&s.env, &s.xlm);
+    let before = xlm.balance(&s.tenant);
+
+    // Tenant allows up to 1% slippage over the 15,000 XLM quote.
+    s.client
+        .make_payment_with_token(&s.agreement_id, &151_500_000_000, &s.xlm);
+
+    assert_eq!(before - xlm.balance(&s.tenant), 150_000_000_000);
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.total_rent_paid, 150_000);
+
+    let split = s.client.get_payment_split(&s.agreement_id, &1);
+    assert_eq!(split.token, s.xlm);
+    assert_eq!(split.admin_amount, 150_000);
+    assert_eq!(split.amount_paid, 150_000_000_000);
+    // One XLM is worth 0.10 USD.
+    assert_eq!(split.conversion_rate, RATE_SCALE / 10);
+}
+
+#[test]
+fn test_pay_fiat_rent_rejects_slippage() {
+    let s = setup();
+    // XLM drops to 0.09 USD after the tenant's quote.
+    s.oracle.set_price(&s.xlm, &(ORACLE_ONE * 9 / 100), &100);
+
+    assert_eq!(
+        s.client
+            .try_make_payment_with_token(&s.agreement_id, &151_500_000_000, &s.xlm),
+        Err(Err(TokenError::SlippageExceeded.into()))
+    );
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.payment_count, 0);
+}
+
+#[test]
+fn test_pay_fiat_rent_rejects_stale_quote() {
+    let s = setup();
+    s.env.ledger().with_mut(|li| li.timestamp = 100 + 601);
+
+    assert_eq!(
+        s.client
+            .try_make_payment_with_token(&s.agreement_id, &1_500_000_000, &s.usdc),
+        Err(Err(OracleError::RateStale.into()))
+    );
+}
+
+#[test]
+fn test_token_denominated_payment_records_rate() {
+    let s = setup();
+    let landlord = Address::generate(&s.env);
+    let admin = s.client.get_state().unwrap().admin;
+    let agreement_id = s.client.create_agreement_with_token(&AgreementInput {
+        agreement_id: String::from_str(&s.env, "TOKEN1"),
+        user: s.tenant.clone(),
+        admin: landlord.clone(),
+        agent: None,
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 0,
+            start_date: 100,
+            end_date: 1_000_000,
+            agent_commission_rate: 0,
+        },
+        payment_token: s.usdc.clone(),
+        metadata_uri: String::from_str(&s.env, ""),
+        attributes: Vec::new(&s.env),
+    });
+    s.client.submit_agreement(&landlord, &agreement_id);
+    s.client.sign_agreement(&s.tenant, &agreement_id);
+    s.client.approve_agreement(&admin, &agreement_id);
+
+    s.client
+        .make_payment_with_token(&agreement_id, &1_000, &s.usdc);
+
+    let split = s.client.get_payment_split(&agreement_id, &1);
+    assert_eq!(split.amount_paid, 1_000);
+    assert_eq!(split.conversion_rate, RATE_SCALE);
+}
+
+#[test]
+fn test_payment_split_stored_before_amount_paid_is_upgraded() {
+    let s = setup();
+    let legacy = types::PaymentSplitV1 {
+        admin_amount: 900,
+        platform_amount: 100,
+        token: s.usdc.clone(),
+        payment_date: 100,
+        payer: s.tenant.clone(),
+    };
+    s.env.as_contract(&s.client.address, || {
+        s.env.storage().persistent().set(
+            &storage::DataKey::PaymentRecord(s.agreement_id.clone(), 1),
+            &legacy,
+        );
+    });
+
+    let split = s.client.get_payment_split(&s.agreement_id, &1);
+    assert_eq!(split.amount_paid, 1000);
+    assert_eq!(split.conversion_rate, RATE_SCALE);
+    assert_eq!(split.payer, s.tenant);
+}
+
+#[test]
+fn test_unit_rate_deviation_guard_and_sync() {
+    let s = setup();
+    let mut config = s.client.get_rate_source().unwrap();
+    config.max_deviation_bps = 500;
+    s.client.set_rate_source(&config);
+    let usd = Symbol::new(&s.env, "USD");
+
+    // No reference yet: the first quote is accepted and stored.
+    assert_eq!(s.client.sync_unit_rate(&usd, &s.xlm), 10 * RATE_SCALE);
+
+    // XLM halving against USD doubles the unit rate, far past 5%.
+    s.oracle.set_price(&s.xlm, &(ORACLE_ONE / 20), &100);
+    assert_eq!(
+        s.client.try_quote_rent_payment(&s.agreement_id, &s.xlm),
+        Err(Ok(OracleError::RateDeviationTooLarge.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_make_payment_with_token(&s.agreement_id, &300_000_000_000, &s.xlm),
+        Err(Err(OracleError::RateDeviationTooLarge.into()))
+    );
+    assert_eq!(
+        s.client.try_sync_unit_rate(&usd, &s.xlm),
+        Err(Ok(OracleError::RateDeviationTooLarge.into()))
+    );
+
+    // The admin can reset the reference rate by hand.
+    s.client.set_unit_rate(&usd, &s.xlm, &(20 * RATE_SCALE));
+    assert_eq!(
+        s.client.quote_rent_payment(&s.agreement_id, &s.xlm),
+        300_000_000_000
+    );
+}
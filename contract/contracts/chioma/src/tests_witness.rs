@@ -0,0 +1,234 @@
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, String,
+};
+
+/// Stand-in for the user profile contract's verification lookup.
+#[contract]
+pub struct MockProfiles;
+
+#[contractimpl]
+impl MockProfiles {
+    pub fn verify(env: Env, account_id: Address) {
+        env.storage().instance().set(&account_id, &true);
+    }
+
+    pub fn is_verified(env: Env, account_id: Address) -> bool {
+        env.storage().instance().has(&account_id)
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    client: ContractClient<'a>,
+    admin: Address,
+    landlord: Address,
+    tenant: Address,
+    agent: Address,
+    agreement_id: String,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let contract_id = env.register(Contract, ());
+    let client = ContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(
+        &admin,
+        &Config {
+            fee_bps: 100,
+            fee_collector: Address::generate(&env),
+            paused: false,
+        },
+    );
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let agreement_id = String::from_str(&env, "WIT1");
+    client.create_agreement(&AgreementInput {
+        agreement_id: agreement_id.clone(),
+        user: tenant.clone(),
+        admin: landlord.clone(),
+        agent: Some(agent.clone()),
+        terms: AgreementTerms {
+            monthly_rent: 1_000,
+            security_deposit: 2_000,
+            start_date: 1_000,
+            end_date: 1_000_000,
+            agent_commission_rate: 0,
+        },
+        payment_token: Address::generate(&env),
+        metadata_uri: String::from_str(&env, ""),
+        attributes: Vec::new(&env),
+    });
+
+    Setup {
+        env,
+        client,
+        admin,
+        landlord,
+        tenant,
+        agent,
+        agreement_id,
+    }
+}
+
+fn sign(s: &Setup) {
+    s.client.submit_agreement(&s.landlord, &s.agreement_id);
+    s.client.sign_agreement(&s.tenant, &s.agreement_id);
+}
+
+fn status(s: &Setup) -> AgreementStatus {
+    s.client.get_agreement(&s.agreement_id).unwrap().status
+}
+
+#[test]
+fn test_default_policy_rejects_parties_and_strangers() {
+    let s = setup();
+    sign(&s);
+
+    for witness in [
+        s.landlord.clone(),
+        s.tenant.clone(),
+        Address::generate(&s.env),
+    ] {
+        assert_eq!(
+            s.client.try_approve_agreement(&witness, &s.agreement_id),
+            Err(Err(WitnessError::WitnessNotAllowed.into()))
+        );
+    }
+
+    s.client.approve_agreement(&s.admin, &s.agreement_id);
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.status, AgreementStatus::Active);
+    assert_eq!(agreement.witness_id, Some(s.admin.clone()));
+    assert_eq!(
+        agreement.witnesses,
+        Vec::from_array(&s.env, [s.admin.clone()])
+    );
+}
+
+#[test]
+fn test_two_of_agent_and_notaries() {
+    let s = setup();
+    s.client.set_witness_policy(
+        &s.agreement_id,
+        &WitnessPolicy {
+            allow_agent: true,
+            allow_notaries: true,
+            allow_verified_profiles: false,
+            required_witnesses: 2,
+        },
+    );
+    sign(&s);
+    assert_eq!(
+        s.client.try_set_witness_policy(
+            &s.agreement_id,
+            &s.client.get_witness_policy(&s.agreement_id)
+        ),
+        Err(Ok(RentalError::InvalidState.into()))
+    );
+
+    s.client.approve_agreement(&s.agent, &s.agreement_id);
+    assert_eq!(status(&s), AgreementStatus::PendingApproval);
+    assert_eq!(
+        s.client.try_approve_agreement(&s.agent, &s.agreement_id),
+        Err(Ok(RentalError::AlreadyApproved))
+    );
+
+    let notary = Address::generate(&s.env);
+    s.client.add_notary(&notary);
+    s.client.remove_notary(&notary);
+    assert!(!s.client.is_notary(&notary));
+    assert_eq!(
+        s.client.try_approve_agreement(&notary, &s.agreement_id),
+        Err(Err(WitnessError::WitnessNotAllowed.into()))
+    );
+
+    s.client.add_notary(&notary);
+    s.client.approve_agreement(&notary, &s.agreement_id);
+    let agreement = s.client.get_agreement(&s.agreement_id).unwrap();
+    assert_eq!(agreement.status, AgreementStatus::Active);
+    assert_eq!(agreement.witness_id, Some(notary.clone()));
+    assert_eq!(
+        agreement.witnesses,
+        Vec::from_array(&s.env, [s.agent.clone(), notary])
+    );
+}
+
+#[test]
+fn test_verified_profile_witness() {
+    let s = setup();
+    let invalid = [
+        WitnessPolicy {
+            allow_agent: false,
+            allow_notaries: false,
+            allow_verified_profiles: false,
+            required_witnesses: 1,
+        },
+        WitnessPolicy {
+            allow_agent: true,
+            allow_notaries: false,
+            allow_verified_profiles: false,
+            required_witnesses: 0,
+        },
+    ];
+    for policy in invalid {
+        assert_eq!(
+            s.client.try_set_witness_policy(&s.agreement_id, &policy),
+            Err(Ok(RentalError::InvalidConfig.into()))
+        );
+    }
+
+    s.client.set_witness_policy(
+        &s.agreement_id,
+        &WitnessPolicy {
+            allow_agent: false,
+            allow_notaries: false,
+            allow_verified_profiles: true,
+            required_witnesses: 1,
+        },
+    );
+    sign(&s);
+
+    let verified = Address::generate(&s.env);
+    // No profile contract configured yet: nobody counts as verified.
+    assert_eq!(
+        s.client.try_approve_agreement(&verified, &s.agreement_id),
+        Err(Err(WitnessError::WitnessNotAllowed.into()))
+    );
+
+    let profiles = MockProfilesClient::new(&s.env, &s.env.register(MockProfiles, ()));
+    s.client.set_profile_contract(&profiles.address);
+    profiles.verify(&verified);
+    profiles.verify(&s.tenant);
+
+    for witness in [s.admin.clone(), s.agent.clone(), s.tenant.clone()] {
+        assert_eq!(
+            s.client.try_approve_agreement(&witness, &s.agreement_id),
+            Err(Err(WitnessError::WitnessNotAllowed.into()))
+        );
+    }
+    s.client.approve_agreement(&verified, &s.agreement_id);
+    assert_eq!(status(&s), AgreementStatus::Active);
+}
+
+#[test]
+fn test_set_profile_contract_emits_event() {
+    let s = setup();
+    let profiles = Address::generate(&s.env);
+    s.client.set_profile_contract(&profiles);
+
+    let events = s.env.events().all();
+    let event = events.last().unwrap();
+    assert_eq!(event.0, s.client.address);
+    // Topics: event name + profile contract
+    assert_eq!(event.1.len(), 2);
+    assert_eq!(s.client.get_profile_contract(), Some(profiles));
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Bytes, String, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, String, Symbol, Vec};
 
 // ─── Timelock Types ───────────────────────────────────────────────────────────
 
@@ -73,6 +73,251 @@ pub struct ExtensionHistory {
     pub total_extensions: u32,
 }
 
+// ─── Amendment Types ──────────────────────────────────────────────────────────
+
+/// A single change carried by an amendment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AmendmentChange {
+    MonthlyRent(i128),
+    PaymentToken(Address),
+    Agent(Option<Address>),
+    /// New document hash and metadata URI, recorded as a document version.
+    Document(BytesN<32>, String),
+    AddTenant(Address),
+    RemoveTenant(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AmendmentStatus {
+    Proposed,
+    Accepted,
+    Applied,
+    Rejected,
+    Cancelled,
+}
+
+/// Mid-lease change to an active agreement's terms.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AgreementAmendment {
+    pub agreement_id: String,
+    pub amendment_id: u32,
+    pub changes: Vec<AmendmentChange>,
+    pub effective_date: u64,
+    pub proposed_by: Address,
+    /// Landlord, primary tenant and any tenant being added.
+    pub required_approvals: Vec<Address>,
+    pub approvals: Vec<Address>,
+    pub status: AmendmentStatus,
+    pub created_at: u64,
+    pub last_reason: Option<String>,
+}
+
+/// Snapshot of amendable terms. Version 0 holds the terms in force before
+/// the first amendment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TermsVersion {
+    pub version: u32,
+    pub monthly_rent: i128,
+    pub payment_token: Address,
+    pub agent: Option<Address>,
+    pub metadata_uri: String,
+    pub co_tenants: Vec<Address>,
+    pub effective_from: u64,
+    pub amendment_id: Option<u32>,
+}
+
+// ─── Document Types ───────────────────────────────────────────────────────────
+
+/// One version of the lease document and the parties' attestations to it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DocumentVersion {
+    pub version: u32,
+    pub document_hash: BytesN<32>,
+    pub metadata_uri: String,
+    pub attributes: Vec<Attribute>,
+    pub proposed_by: Address,
+    pub proposed_at: u64,
+    pub landlord_signed_at: Option<u64>,
+    pub tenant_signed_at: Option<u64>,
+}
+
+// ─── Witness Types ────────────────────────────────────────────────────────────
+
+/// Who may witness an agreement and how many witnesses activate it. The
+/// landlord, tenants and subtenant can never witness their own lease.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WitnessPolicy {
+    /// The agreement's agent may witness.
+    pub allow_agent: bool,
+    /// Platform-registered notaries (and the platform admin) may witness.
+    pub allow_notaries: bool,
+    /// Accounts verified in the configured user profile contract may witness.
+    pub allow_verified_profiles: bool,
+    /// Distinct witnesses needed before the agreement becomes Active.
+    pub required_witnesses: u32,
+}
+
+// ─── Deposit Escrow Types ─────────────────────────────────────────────────────
+
+/// State of an agreement's security deposit, as reported by the escrow
+/// contract holding it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DepositStatus {
+    /// Escrow created, deposit not yet paid in full.
+    Pending,
+    /// Deposit paid and held in escrow.
+    Held,
+    /// Deposit is under dispute.
+    Disputed,
+    /// Deposit paid out by the escrow.
+    Released,
+    /// Deposit returned to the tenant.
+    Refunded,
+}
+
+/// The escrow holding an agreement's security deposit and its last status.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositRecord {
+    pub escrow_id: BytesN<32>,
+    pub status: DepositStatus,
+    pub updated_at: u64,
+}
+
+/// The parts of an agreement an escrow checks its deposit against. Kept
+/// narrow so the escrow does not depend on the full `RentAgreement` layout.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositTerms {
+    pub agreement_id: String,
+    pub landlord: Address,
+    pub tenant: Address,
+    pub security_deposit: i128,
+    pub payment_token: Address,
+    pub status: AgreementStatus,
+}
+
+// ─── Arrears Types ────────────────────────────────────────────────────────────
+
+/// When a landlord may serve a default notice and how long the tenant then
+/// has to cure. A notice is allowed once either trigger is met.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArrearsPolicy {
+    /// Whole periods of unpaid rent that allow a notice; 0 disables this trigger.
+    pub missed_periods: u32,
+    /// Outstanding rent that allows a notice; 0 disables this trigger.
+    pub arrears_threshold: i128,
+    /// Seconds between the notice and the earliest default declaration.
+    pub cure_period: u64,
+    /// Fee charged per missed period when a notice is served; 0 charges none.
+    pub late_fee: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DefaultNoticeStatus {
+    /// Served; the tenant can still cure.
+    Open,
+    /// On hold while an agreed payment plan is honoured.
+    Suspended,
+    /// Arrears were paid off before default was declared.
+    Cured,
+    /// The landlord declared default and the agreement was terminated.
+    Defaulted,
+}
+
+/// Notice of default served on a tenant in arrears.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DefaultNotice {
+    pub agreement_id: String,
+    /// Outstanding rent when the notice was served.
+    pub arrears: i128,
+    /// Whole periods of unpaid rent when the notice was served.
+    pub missed_periods: u32,
+    pub issued_at: u64,
+    pub cure_deadline: u64,
+    pub status: DefaultNoticeStatus,
+    /// Amount claimed against the security deposit on default.
+    pub deposit_claim: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PaymentPlanStatus {
+    Proposed,
+    Active,
+    Completed,
+    /// An instalment was missed; the default process resumed.
+    Broken,
+}
+
+/// Schedule for paying down arrears in instalments on top of regular rent.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentPlan {
+    pub agreement_id: String,
+    pub proposed_by: Address,
+    /// Arrears being repaid, fixed when the plan is accepted.
+    pub arrears: i128,
+    pub instalments: u32,
+    /// Amount due per instalment; the last one may be smaller.
+    pub instalment_amount: i128,
+    /// Late fees are held while the plan runs and forgiven on completion.
+    pub waive_late_fees: bool,
+    /// Late fees held under the waiver, reinstated if the plan breaks.
+    pub waived_late_fees: i128,
+    pub instalments_met: u32,
+    /// Acceptance time; instalment `n` falls due `n` periods later.
+    pub started_at: u64,
+    pub status: PaymentPlanStatus,
+}
+
+// ─── Assignment Types ─────────────────────────────────────────────────────────
+
+/// How a nominee takes over from the tenant.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AssignmentMode {
+    /// The nominee replaces the tenant, who is released from the lease.
+    Assignment,
+    /// The nominee occupies and pays; the tenant stays liable.
+    Sublet,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AssignmentStatus {
+    Nominated,
+    Accepted,
+    Completed,
+    Cancelled,
+}
+
+/// Handover of an agreement from its tenant to a nominee.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaseAssignment {
+    pub agreement_id: String,
+    pub mode: AssignmentMode,
+    pub from_tenant: Address,
+    pub to_tenant: Address,
+    /// Fresh deposit paid by the nominee on acceptance; 0 keeps the existing one.
+    pub new_deposit: i128,
+    /// Token the fresh deposit is paid and refunded in.
+    pub deposit_token: Address,
+    pub status: AssignmentStatus,
+    pub created_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ContractUpgradeProposal {
@@ -157,6 +402,99 @@ pub struct RentAgreement {
     pub next_payment_due: u64,
     pub metadata_uri: String,
     pub attributes: Vec<Attribute>,
+    /// Additional tenants added by amendment; `user` stays the primary tenant.
+    pub co_tenants: Vec<Address>,
+    /// Occupant paying rent under a sublet; `user` remains liable.
+    pub subtenant: Option<Address>,
+    /// Content hash of the lease document both parties consented to.
+    pub document_hash: Option<BytesN<32>>,
+    /// Version of `document_hash` in the document history; 0 before the first.
+    pub document_version: u32,
+    /// Everyone who has witnessed the agreement, in approval order.
+    pub witnesses: Vec<Address>,
+}
+
+/// Agreement layout stored before co-tenants were added. Only read back
+/// from storage, and upgraded to `RentAgreement` on load.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RentAgreementV1 {
+    pub agreement_id: String,
+    pub admin: Address,
+    pub user: Address,
+    pub agent: Option<Address>,
+    pub monthly_rent: i128,
+    pub security_deposit: i128,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub agent_commission_rate: u32,
+    pub status: AgreementStatus,
+    pub total_rent_paid: i128,
+    pub payment_count: u32,
+    pub signed_at: Option<u64>,
+    pub witness_id: Option<Address>,
+    pub payment_token: Address,
+    pub next_payment_due: u64,
+    pub metadata_uri: String,
+    pub attributes: Vec<Attribute>,
+}
+
+impl RentAgreementV1 {
+    /// An agreement stored before amendments has no co-tenants, subtenant
+    /// or anchored document. Its single witness, if any, becomes the first
+    /// entry of `witnesses`.
+    pub fn upgrade(self, env: &Env) -> RentAgreement {
+        let mut witnesses = Vec::new(env);
+        if let Some(witness) = &self.witness_id {
+            witnesses.push_back(witness.clone());
+        }
+        RentAgreement {
+            agreement_id: self.agreement_id,
+            admin: self.admin,
+            user: self.user,
+            agent: self.agent,
+            monthly_rent: self.monthly_rent,
+            security_deposit: self.security_deposit,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            agent_commission_rate: self.agent_commission_rate,
+            status: self.status,
+            total_rent_paid: self.total_rent_paid,
+            payment_count: self.payment_count,
+            signed_at: self.signed_at,
+            witness_id: self.witness_id,
+            payment_token: self.payment_token,
+            next_payment_due: self.next_payment_due,
+            metadata_uri: self.metadata_uri,
+            attributes: self.attributes,
+            co_tenants: Vec::new(env),
+            subtenant: None,
+            document_hash: None,
+            document_version: 0,
+            witnesses,
+        }
+    }
+}
+
+/// Secondary index an agreement can be listed under.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AgreementIndex {
+    Landlord(Address),
+    Tenant(Address),
+    Agent(Address),
+    Status(AgreementStatus),
+}
+
+/// One page of an agreement index query.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AgreementPage {
+    pub agreements: Vec<RentAgreement>,
+    /// Cursor for the next page, or `None` when this page is the last.
+    pub next_cursor: Option<u32>,
+    /// Number of agreements in the index.
+    pub total: u32,
 }
 
 #[contracttype]
@@ -167,6 +505,40 @@ pub struct PaymentSplit {
     pub token: Address,
     pub payment_date: u64,
     pub payer: Address,
+    /// Amount of `token` actually transferred by the payer.
+    pub amount_paid: i128,
+    /// Rate from `token` to the agreement's denomination (its payment token
+    /// or unit of account), scaled by 10^18.
+    pub conversion_rate: i128,
+}
+
+/// Payment split layout stored before transferred amounts and conversion
+/// rates were recorded. Only read back from storage, and upgraded to
+/// `PaymentSplit` on load.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentSplitV1 {
+    pub admin_amount: i128,
+    pub platform_amount: i128,
+    pub token: Address,
+    pub payment_date: u64,
+    pub payer: Address,
+}
+
+impl From<PaymentSplitV1> for PaymentSplit {
+    /// The transferred amount was not kept, so the split is taken as paid
+    /// one-to-one in the agreement's denomination.
+    fn from(old: PaymentSplitV1) -> Self {
+        PaymentSplit {
+            amount_paid: old.admin_amount + old.platform_amount,
+            conversion_rate: crate::multi_token::RATE_SCALE,
+            admin_amount: old.admin_amount,
+            platform_amount: old.platform_amount,
+            token: old.token,
+            payment_date: old.payment_date,
+            payer: old.payer,
+        }
+    }
 }
 
 #[contracttype]
@@ -205,6 +577,38 @@ pub struct SupportedToken {
     pub max_amount: i128,
 }
 
+/// Flows a supported token may be used for.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TokenUsage {
+    Rent,
+    Deposit,
+    RentAndDeposit,
+}
+
+/// Admin policy applied on top of `SupportedToken` limits.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenPolicy {
+    pub usage: TokenUsage,
+    /// Maximum volume of the token accepted per UTC day (0 = uncapped).
+    pub daily_cap: i128,
+}
+
+/// Pending switch of an agreement's `payment_token` after its token was
+/// disabled. Applied once the other party accepts.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMigration {
+    pub agreement_id: String,
+    pub from_token: Address,
+    pub to_token: Address,
+    /// Monthly rent expressed in `to_token` units.
+    pub monthly_rent: i128,
+    pub proposed_by: Address,
+    pub proposed_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AgreementWithToken {
@@ -223,6 +627,49 @@ pub struct TokenExchangeRate {
     pub updated_at: u64,
 }
 
+/// Asset identifier used by SEP-40 price oracles.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OracleAsset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// Price quote returned by a SEP-40 `lastprice` call.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Off-chain currency an agreement's rent is priced in, such as USD or NGN.
+///
+/// `monthly_rent` is expressed in minor units of `code` at `decimals`
+/// precision and settled in tokens at the oracle rate on each payment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitOfAccount {
+    pub code: Symbol,
+    pub decimals: u32,
+}
+
+/// Where exchange rates come from and the guards applied to them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateSourceConfig {
+    /// SEP-40 oracle queried before falling back to stored rates.
+    pub oracle: Option<Address>,
+    /// Pivot token for cross rates; priced at exactly 1.0 by the oracle.
+    pub base_token: Option<Address>,
+    /// Currency the oracle quotes prices in (e.g. USD); priced at exactly 1.0.
+    pub quote_currency: Option<Symbol>,
+    /// Maximum age of an oracle quote or stored rate, in seconds (0 = unchecked).
+    pub max_age: u64,
+    /// Maximum move of an oracle rate against the last accepted rate (0 = unchecked).
+    pub max_deviation_bps: u32,
+}
+
 // ─── Security Deposit Interest ────────────────────────────────────────────────
 
 /// How often interest compounds.
@@ -359,6 +806,18 @@ pub struct AgreementInput {
     pub attributes: Vec<Attribute>,
 }
 
+/// Agreement offer signed off-chain by the landlord (`input.admin`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaseOffer {
+    pub input: AgreementInput,
+    /// Lease document both parties attest to on acceptance.
+    pub document_hash: Option<BytesN<32>>,
+    /// Single-use per landlord.
+    pub nonce: u64,
+    pub expires_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum VersionStatus {
@@ -0,0 +1,159 @@
+//! Witness policies for agreement approval.
+//!
+//! The landlord chooses, per agreement, which kinds of witness may approve
+//! it and how many distinct witnesses are needed before it becomes Active.
+//! Agreements without a policy accept one witness that is the agent, a
+//! registered notary or the platform admin. Parties to the lease are never
+//! accepted as witnesses.
+use soroban_sdk::{contractclient, Address, Env, Error, String};
+
+use crate::errors::{RentalError, WitnessError};
+use crate::events;
+use crate::storage::{DataKey, WitnessKey};
+use crate::types::{AgreementStatus, ContractState, RentAgreement, WitnessPolicy};
+
+/// Upper bound on `required_witnesses`.
+pub const MAX_WITNESSES: u32 = 10;
+
+const TTL_THRESHOLD: u32 = 500000;
+const TTL_BUMP: u32 = 500000;
+
+/// Subset of the user profile contract used to check verification.
+#[allow(dead_code)]
+#[contractclient(name = "UserProfileClient")]
+pub trait UserProfile {
+    fn is_verified(env: Env, account_id: Address) -> bool;
+}
+
+/// Policy applied to agreements the landlord has not configured.
+pub fn default_policy() -> WitnessPolicy {
+    WitnessPolicy {
+        allow_agent: true,
+        allow_notaries: true,
+        allow_verified_profiles: false,
+        required_witnesses: 1,
+    }
+}
+
+/// Set the witness policy of an agreement. Only the landlord may do so, and
+/// only before the tenant has signed.
+pub fn set_witness_policy(
+    env: &Env,
+    agreement_id: String,
+    policy: WitnessPolicy,
+) -> Result<(), Error> {
+    let agreement: RentAgreement =
+        crate::storage::get_agreement(env, &agreement_id).ok_or(RentalError::AgreementNotFound)?;
+    agreement.admin.require_auth();
+
+    match agreement.status {
+        AgreementStatus::Draft | AgreementStatus::Pending => {}
+        _ => return Err(RentalError::InvalidState.into()),
+    }
+    if policy.required_witnesses == 0 || policy.required_witnesses > MAX_WITNESSES {
+        return Err(RentalError::InvalidConfig.into());
+    }
+    if !policy.allow_agent && !policy.allow_notaries && !policy.allow_verified_profiles {
+        return Err(RentalError::InvalidConfig.into());
+    }
+
+    let key = DataKey::Witness(WitnessKey::Policy(agreement_id.clone()));
+    env.storage().persistent().set(&key, &policy);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+
+    events::witness_policy_set(env, agreement_id, policy.required_witnesses);
+    Ok(())
+}
+
+pub fn get_witness_policy(env: &Env, agreement_id: String) -> WitnessPolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Witness(WitnessKey::Policy(agreement_id)))
+        .unwrap_or_else(default_policy)
+}
+
+pub fn add_notary(env: &Env, notary: Address) {
+    let key = DataKey::Witness(WitnessKey::Notary(notary.clone()));
+    env.storage().persistent().set(&key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    events::notary_added(env, notary);
+}
+
+pub fn remove_notary(env: &Env, notary: Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Witness(WitnessKey::Notary(notary.clone())));
+    events::notary_removed(env, notary);
+}
+
+pub fn is_notary(env: &Env, account: Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Witness(WitnessKey::Notary(account)))
+}
+
+pub fn set_profile_contract(env: &Env, profile_contract: Address) {
+    env.storage().instance().set(
+        &DataKey::Witness(WitnessKey::ProfileContract),
+        &profile_contract,
+    );
+    events::profile_contract_set(env, profile_contract);
+}
+
+pub fn get_profile_contract(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Witness(WitnessKey::ProfileContract))
+}
+
+fn is_party(agreement: &RentAgreement, account: &Address) -> bool {
+    *account == agreement.admin
+        || *account == agreement.user
+        || agreement.co_tenants.contains(account)
+        || agreement.subtenant.as_ref() == Some(account)
+}
+
+fn is_platform_admin(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .instance()
+        .get::<_, ContractState>(&DataKey::State)
+        .is_some_and(|state| state.admin == *account)
+}
+
+/// A profile contract that is missing or fails to answer verifies no one.
+fn is_verified_profile(env: &Env, account: &Address) -> bool {
+    let Some(profile_contract) = get_profile_contract(env) else {
+        return false;
+    };
+    matches!(
+        UserProfileClient::new(env, &profile_contract).try_is_verified(account),
+        Ok(Ok(true))
+    )
+}
+
+/// Check that `witness` may witness `agreement` under `policy`.
+pub(crate) fn check_witness(
+    env: &Env,
+    agreement: &RentAgreement,
+    policy: &WitnessPolicy,
+    witness: &Address,
+) -> Result<(), Error> {
+    if is_party(agreement, witness) {
+        return Err(WitnessError::WitnessNotAllowed.into());
+    }
+    if policy.allow_agent && agreement.agent.as_ref() == Some(witness) {
+        return Ok(());
+    }
+    if policy.allow_notaries && (is_notary(env, witness.clone()) || is_platform_admin(env, witness))
+    {
+        return Ok(());
+    }
+    if policy.allow_verified_profiles && is_verified_profile(env, witness) {
+        return Ok(());
+    }
+    Err(WitnessError::WitnessNotAllowed.into())
+}
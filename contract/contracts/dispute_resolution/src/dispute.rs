@@ -1,12 +1,13 @@
-use soroban_sdk::{contracttype, Address, Env, Map, String};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, Map, String};
 
 use crate::errors::DisputeError;
 use crate::events;
 use crate::rate_limit;
-use crate::storage::DataKey;
+use crate::storage::{self, DataKey};
 use crate::types::{
     AppealStatus, AppealVote, Arbiter, ArbiterStats, ContractState, Dispute, DisputeAppeal,
-    DisputeOutcome, TimeoutConfig, Vote, VotingWeight, WeightedDisputeVotes, WeightedVote,
+    DisputeOutcome, DisputePhase, Evidence, TimeoutConfig, Vote, VoteCommitment, VotingWeight,
+    WeightedDisputeVotes, WeightedVote,
 };
 
 const APPEAL_WINDOW_SECONDS: u64 = 7 * 24 * 60 * 60;
@@ -15,16 +16,28 @@ const APPEAL_FEE: i128 = 100;
 const DEFAULT_ESCROW_TIMEOUT_DAYS: u64 = 14;
 const DEFAULT_DISPUTE_TIMEOUT_DAYS: u64 = 30;
 const DEFAULT_PAYMENT_TIMEOUT_DAYS: u64 = 7;
+const DEFAULT_EVIDENCE_PERIOD_DAYS: u64 = 7;
+const DEFAULT_RESPONSE_PERIOD_DAYS: u64 = 3;
+const DEFAULT_VOTING_PERIOD_DAYS: u64 = 7;
+/// Commit-reveal voting is off unless the admin configures a reveal window.
+const DEFAULT_REVEAL_PERIOD_DAYS: u64 = 0;
+/// Rating points an arbiter loses for each commitment never revealed.
+const UNREVEALED_COMMIT_PENALTY: u32 = 10;
+const SECONDS_PER_DAY: u64 = 86_400;
+/// Maximum evidence and response submissions stored per dispute.
+pub const MAX_EVIDENCE_PER_DISPUTE: u32 = 50;
 
 pub fn get_timeout_config(env: &Env) -> TimeoutConfig {
-    env.storage()
-        .instance()
-        .get(&DataKey::TimeoutConfig)
-        .unwrap_or(TimeoutConfig {
-            escrow_timeout_days: DEFAULT_ESCROW_TIMEOUT_DAYS,
-            dispute_timeout_days: DEFAULT_DISPUTE_TIMEOUT_DAYS,
-            payment_timeout_days: DEFAULT_PAYMENT_TIMEOUT_DAYS,
-        })
+    let defaults = TimeoutConfig {
+        escrow_timeout_days: DEFAULT_ESCROW_TIMEOUT_DAYS,
+        dispute_timeout_days: DEFAULT_DISPUTE_TIMEOUT_DAYS,
+        payment_timeout_days: DEFAULT_PAYMENT_TIMEOUT_DAYS,
+        evidence_period_days: DEFAULT_EVIDENCE_PERIOD_DAYS,
+        response_period_days: DEFAULT_RESPONSE_PERIOD_DAYS,
+        voting_period_days: DEFAULT_VOTING_PERIOD_DAYS,
+        reveal_period_days: DEFAULT_REVEAL_PERIOD_DAYS,
+    };
+    storage::get_timeout_config(env, defaults.clone()).unwrap_or(defaults)
 }
 
 pub fn set_timeout_config(
@@ -46,10 +59,34 @@ pub fn set_timeout_config(
     if config.escrow_timeout_days == 0
         || config.dispute_timeout_days == 0
         || config.payment_timeout_days == 0
+        || config.evidence_period_days == 0
+        || config.response_period_days == 0
+        || config.voting_period_days == 0
     {
         return Err(DisputeError::InvalidTimeoutConfig);
     }
 
+    // A dispute must not time out before its voting and reveal windows close
+    let phases_days = config
+        .evidence_period_days
+        .saturating_add(config.response_period_days)
+        .saturating_add(config.voting_period_days)
+        .saturating_add(config.reveal_period_days);
+    if config.dispute_timeout_days < phases_days {
+        return Err(DisputeError::InvalidTimeoutConfig);
+    }
+
+    // Open disputes keep the deadlines they were raised with, so the new
+    // timeout must not cut any of them short either.
+    let timeout_seconds = config.dispute_timeout_days.saturating_mul(SECONDS_PER_DAY);
+    for agreement_id in storage::get_open_disputes(env).iter() {
+        if let Some(dispute) = storage::get_dispute(env, &DataKey::Dispute(agreement_id)) {
+            if dispute.raised_at.saturating_add(timeout_seconds) < dispute.reveal_deadline {
+                return Err(DisputeError::InvalidTimeoutConfig);
+            }
+        }
+    }
+
     env.storage()
         .instance()
         .set(&DataKey::TimeoutConfig, &config);
@@ -196,10 +233,28 @@ pub fn raise_dispute(
         return Err(DisputeError::Unauthorized);
     }
 
+    let config = get_timeout_config(env);
+    let raised_at = env.ledger().timestamp();
+    let evidence_deadline =
+        raised_at.saturating_add(config.evidence_period_days.saturating_mul(SECONDS_PER_DAY));
+    let response_deadline = evidence_deadline
+        .saturating_add(config.response_period_days.saturating_mul(SECONDS_PER_DAY));
+    let voting_deadline =
+        response_deadline.saturating_add(config.voting_period_days.saturating_mul(SECONDS_PER_DAY));
+    let reveal_deadline =
+        voting_deadline.saturating_add(config.reveal_period_days.saturating_mul(SECONDS_PER_DAY));
+
     let dispute = Dispute {
         agreement_id: agreement_id.clone(),
         details_hash: details_hash.clone(),
-        raised_at: env.ledger().timestamp(),
+        landlord: agreement.landlord,
+        tenant: agreement.tenant,
+        raised_at,
+        evidence_deadline,
+        response_deadline,
+        voting_deadline,
+        commit_reveal: config.reveal_period_days > 0,
+        reveal_deadline,
         resolved: false,
         resolved_at: None,
         votes_favor_landlord: 0,
@@ -209,12 +264,123 @@ pub fn raise_dispute(
 
     env.storage().persistent().set(&key, &dispute);
     env.storage().persistent().extend_ttl(&key, 500000, 500000);
+    storage::add_open_dispute(env, agreement_id.clone());
 
     events::dispute_raised(env, agreement_id, details_hash);
 
     Ok(())
 }
 
+/// Submit evidence during the evidence window (tenant or landlord).
+pub fn submit_evidence(
+    env: &Env,
+    submitter: Address,
+    agreement_id: String,
+    evidence_hash: String,
+    description: String,
+) -> Result<(), DisputeError> {
+    add_evidence(
+        env,
+        submitter,
+        agreement_id,
+        evidence_hash,
+        description,
+        DisputePhase::Evidence,
+    )
+}
+
+/// Respond to the submitted evidence during the response window (tenant or
+/// landlord).
+pub fn submit_response(
+    env: &Env,
+    submitter: Address,
+    agreement_id: String,
+    evidence_hash: String,
+    description: String,
+) -> Result<(), DisputeError> {
+    add_evidence(
+        env,
+        submitter,
+        agreement_id,
+        evidence_hash,
+        description,
+        DisputePhase::Response,
+    )
+}
+
+/// Record a submission by a party, provided the dispute is in `phase`.
+fn add_evidence(
+    env: &Env,
+    submitter: Address,
+    agreement_id: String,
+    evidence_hash: String,
+    description: String,
+    phase: DisputePhase,
+) -> Result<(), DisputeError> {
+    if !env.storage().persistent().has(&DataKey::Initialized) {
+        return Err(DisputeError::NotInitialized);
+    }
+
+    submitter.require_auth();
+
+    // Rate limiting check
+    rate_limit::check_rate_limit(env, &submitter, "submit_evidence")?;
+
+    if evidence_hash.is_empty() {
+        return Err(DisputeError::InvalidDetailsHash);
+    }
+
+    let dispute = storage::get_dispute(env, &DataKey::Dispute(agreement_id.clone()))
+        .ok_or(DisputeError::DisputeNotFound)?;
+
+    if submitter != dispute.tenant && submitter != dispute.landlord {
+        return Err(DisputeError::Unauthorized);
+    }
+
+    if dispute.phase(env.ledger().timestamp()) != phase {
+        return Err(DisputeError::InvalidDisputePhase);
+    }
+
+    let key = DataKey::Evidence(agreement_id.clone());
+    let mut evidence: soroban_sdk::Vec<Evidence> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(soroban_sdk::Vec::new(env));
+    if evidence.len() >= MAX_EVIDENCE_PER_DISPUTE {
+        return Err(DisputeError::EvidenceLimitReached);
+    }
+
+    evidence.push_back(Evidence {
+        submitter: submitter.clone(),
+        evidence_hash: evidence_hash.clone(),
+        description,
+        phase: phase.clone(),
+        submitted_at: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&key, &evidence);
+    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+
+    events::evidence_submitted(env, agreement_id, submitter, evidence_hash, phase);
+
+    Ok(())
+}
+
+/// Return all evidence and responses submitted for a dispute, oldest first.
+pub fn get_evidence(env: &Env, agreement_id: String) -> soroban_sdk::Vec<Evidence> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Evidence(agreement_id))
+        .unwrap_or(soroban_sdk::Vec::new(env))
+}
+
+/// Return the current phase of a dispute.
+pub fn get_dispute_phase(env: &Env, agreement_id: String) -> Result<DisputePhase, DisputeError> {
+    let dispute = storage::get_dispute(env, &DataKey::Dispute(agreement_id))
+        .ok_or(DisputeError::DisputeNotFound)?;
+    Ok(dispute.phase(env.ledger().timestamp()))
+}
+
 pub fn vote_on_dispute(
     env: &Env,
     arbiter: Address,
@@ -242,16 +408,26 @@ pub fn vote_on_dispute(
     }
 
     let dispute_key = DataKey::Dispute(agreement_id.clone());
-    let mut dispute: Dispute = env
-        .storage()
-        .persistent()
-        .get(&dispute_key)
-        .ok_or(DisputeError::DisputeNotFound)?;
+    let mut dispute =
+        storage::get_dispute(env, &dispute_key).ok_or(DisputeError::DisputeNotFound)?;
 
     if dispute.resolved {
         return Err(DisputeError::DisputeAlreadyResolved);
     }
 
+    if dispute.commit_reveal {
+        return Err(DisputeError::VotingModeMismatch);
+    }
+
+    // Voting opens once the response window closes and stays open until the
+    // dispute is resolved, so a dispute short of votes is never stranded.
+    if matches!(
+        dispute.phase(env.ledger().timestamp()),
+        DisputePhase::Evidence | DisputePhase::Response
+    ) {
+        return Err(DisputeError::InvalidDisputePhase);
+    }
+
     let vote_key = DataKey::Vote(agreement_id.clone(), arbiter.clone());
     if env.storage().persistent().has(&vote_key) {
         return Err(DisputeError::AlreadyVoted);
@@ -294,16 +470,15 @@ pub fn resolve_dispute(env: &Env, agreement_id: String) -> Result<DisputeOutcome
         .ok_or(DisputeError::NotInitialized)?;
 
     let dispute_key = DataKey::Dispute(agreement_id.clone());
-    let mut dispute: Dispute = env
-        .storage()
-        .persistent()
-        .get(&dispute_key)
-        .ok_or(DisputeError::DisputeNotFound)?;
+    let mut dispute =
+        storage::get_dispute(env, &dispute_key).ok_or(DisputeError::DisputeNotFound)?;
 
     if dispute.resolved {
         return Err(DisputeError::DisputeAlreadyResolved);
     }
 
+    close_reveal(env, &agreement_id, &mut dispute)?;
+
     let total_votes = dispute.votes_favor_landlord + dispute.votes_favor_tenant;
 
     if total_votes < state.min_votes_required {
@@ -317,6 +492,7 @@ pub fn resolve_dispute(env: &Env, agreement_id: String) -> Result<DisputeOutcome
     env.storage()
         .persistent()
         .extend_ttl(&dispute_key, 500000, 500000);
+    storage::remove_open_dispute(env, &dispute.agreement_id);
 
     let outcome = if dispute.votes_favor_landlord > dispute.votes_favor_tenant {
         DisputeOutcome::FavorLandlord
@@ -340,30 +516,35 @@ pub fn resolve_dispute_on_timeout(
     agreement_id: String,
 ) -> Result<DisputeOutcome, DisputeError> {
     let dispute_key = DataKey::Dispute(agreement_id.clone());
-    let mut dispute: Dispute = env
-        .storage()
-        .persistent()
-        .get(&dispute_key)
-        .ok_or(DisputeError::DisputeNotFound)?;
+    let mut dispute =
+        storage::get_dispute(env, &dispute_key).ok_or(DisputeError::DisputeNotFound)?;
 
     if dispute.resolved {
         return Err(DisputeError::DisputeAlreadyResolved);
     }
 
     let timeout_days = get_timeout_config(env).dispute_timeout_days;
-    let timeout_seconds = timeout_days.saturating_mul(86_400);
-    let deadline = dispute.raised_at.saturating_add(timeout_seconds);
+    let timeout_seconds = timeout_days.saturating_mul(SECONDS_PER_DAY);
+    // Never cut a commit-reveal dispute off before its reveal window closes,
+    // even if the timeout has since been shortened.
+    let deadline = dispute
+        .raised_at
+        .saturating_add(timeout_seconds)
+        .max(dispute.reveal_deadline);
     let now = env.ledger().timestamp();
     if now <= deadline {
         return Err(DisputeError::TimeoutNotReached);
     }
 
+    close_reveal(env, &agreement_id, &mut dispute)?;
+
     dispute.resolved = true;
     dispute.resolved_at = Some(now);
     env.storage().persistent().set(&dispute_key, &dispute);
     env.storage()
         .persistent()
         .extend_ttl(&dispute_key, 500000, 500000);
+    storage::remove_open_dispute(env, &dispute.agreement_id);
 
     let outcome = if dispute.votes_favor_landlord > dispute.votes_favor_tenant {
         DisputeOutcome::FavorLandlord
@@ -383,8 +564,7 @@ pub fn resolve_dispute_on_timeout(
 }
 
 pub fn get_dispute(env: &Env, agreement_id: String) -> Option<Dispute> {
-    let key = DataKey::Dispute(agreement_id);
-    env.storage().persistent().get(&key)
+    storage::get_dispute(env, &DataKey::Dispute(agreement_id))
 }
 
 pub fn get_arbiter(env: &Env, arbiter: Address) -> Option<Arbiter> {
@@ -419,11 +599,7 @@ pub fn create_appeal(
     }
 
     let dispute_key = DataKey::Dispute(dispute_id.clone());
-    let dispute: Dispute = env
-        .storage()
-        .persistent()
-        .get(&dispute_key)
-        .ok_or(DisputeError::DisputeNotFound)?;
+    let dispute = storage::get_dispute(env, &dispute_key).ok_or(DisputeError::DisputeNotFound)?;
 
     if !dispute.resolved {
         return Err(DisputeError::InvalidAgreementState);
@@ -626,10 +802,7 @@ pub fn resolve_appeal(env: &Env, appeal_id: String) -> Result<(), DisputeError>
         DisputeOutcome::FavorTenant
     };
 
-    let dispute: Dispute = env
-        .storage()
-        .persistent()
-        .get(&DataKey::Dispute(appeal.dispute_id.clone()))
+    let dispute = storage::get_dispute(env, &DataKey::Dispute(appeal.dispute_id.clone()))
         .ok_or(DisputeError::DisputeNotFound)?;
 
     let original_outcome = dispute
@@ -737,6 +910,7 @@ pub fn set_arbiter_stats(
     let stats = ArbiterStats {
         rating,
         disputes_resolved,
+        missed_reveals: get_arbiter_stats(env, arbiter.clone()).missed_reveals,
     };
     let key = DataKey::ArbiterStats(arbiter.clone());
     env.storage().persistent().set(&key, &stats);
@@ -745,6 +919,15 @@ pub fn set_arbiter_stats(
     Ok(())
 }
 
+/// Return an arbiter's stats, defaulting to a rating of 50 with no history.
+pub fn get_arbiter_stats(env: &Env, arbiter: Address) -> ArbiterStats {
+    storage::get_arbiter_stats(env, &DataKey::ArbiterStats(arbiter)).unwrap_or(ArbiterStats {
+        rating: 50,
+        disputes_resolved: 0,
+        missed_reveals: 0,
+    })
+}
+
 /// Compute the voting weight for an arbiter.
 ///
 /// Formula (integer arithmetic, scale ×100):
@@ -764,14 +947,7 @@ pub fn calculate_voting_weight(env: &Env, arbiter: Address) -> Result<u32, Dispu
         return Err(DisputeError::ArbiterNotFound);
     }
 
-    let stats: ArbiterStats = env
-        .storage()
-        .persistent()
-        .get(&DataKey::ArbiterStats(arbiter))
-        .unwrap_or(ArbiterStats {
-            rating: 50,
-            disputes_resolved: 0,
-        });
+    let stats = get_arbiter_stats(env, arbiter);
 
     let rating_mult = stats.rating * 2; // 0–200
     let exp_mult = if stats.disputes_resolved * 2 < 200 {
@@ -799,14 +975,7 @@ pub fn get_voting_weight(env: &Env, arbiter: Address) -> Result<VotingWeight, Di
         return Err(DisputeError::ArbiterNotFound);
     }
 
-    let stats: ArbiterStats = env
-        .storage()
-        .persistent()
-        .get(&DataKey::ArbiterStats(arbiter.clone()))
-        .unwrap_or(ArbiterStats {
-            rating: 50,
-            disputes_resolved: 0,
-        });
+    let stats = get_arbiter_stats(env, arbiter.clone());
 
     let rating_mult = stats.rating * 2;
     let exp_mult = if stats.disputes_resolved * 2 < 200 {
@@ -854,16 +1023,25 @@ pub fn vote_on_dispute_weighted(
     }
 
     let dispute_key = DataKey::Dispute(dispute_id.clone());
-    let dispute: Dispute = env
-        .storage()
-        .persistent()
-        .get(&dispute_key)
-        .ok_or(DisputeError::DisputeNotFound)?;
+    let dispute = storage::get_dispute(env, &dispute_key).ok_or(DisputeError::DisputeNotFound)?;
 
     if dispute.resolved {
         return Err(DisputeError::DisputeAlreadyResolved);
     }
 
+    if dispute.commit_reveal {
+        return Err(DisputeError::VotingModeMismatch);
+    }
+
+    // Voting opens once the response window closes and stays open until the
+    // dispute is resolved, so a dispute short of votes is never stranded.
+    if matches!(
+        dispute.phase(env.ledger().timestamp()),
+        DisputePhase::Evidence | DisputePhase::Response
+    ) {
+        return Err(DisputeError::InvalidDisputePhase);
+    }
+
     let wvote_key = DataKey::WeightedVote(dispute_id.clone(), arbiter.clone());
     if env.storage().persistent().has(&wvote_key) {
         return Err(DisputeError::AlreadyVoted);
@@ -927,16 +1105,15 @@ pub fn resolve_dispute_weighted(
         .ok_or(DisputeError::NotInitialized)?;
 
     let dispute_key = DataKey::Dispute(dispute_id.clone());
-    let mut dispute: Dispute = env
-        .storage()
-        .persistent()
-        .get(&dispute_key)
-        .ok_or(DisputeError::DisputeNotFound)?;
+    let mut dispute =
+        storage::get_dispute(env, &dispute_key).ok_or(DisputeError::DisputeNotFound)?;
 
     if dispute.resolved {
         return Err(DisputeError::DisputeAlreadyResolved);
     }
 
+    close_reveal(env, &dispute_id, &mut dispute)?;
+
     let wdisp_key = DataKey::WeightedDisputeVotes(dispute_id.clone());
     let wdisp: WeightedDisputeVotes =
         env.storage()
@@ -978,6 +1155,7 @@ pub fn resolve_dispute_weighted(
     env.storage()
         .persistent()
         .extend_ttl(&dispute_key, 500000, 500000);
+    storage::remove_open_dispute(env, &dispute.agreement_id);
 
     events::dispute_resolved_by_weight(env, dispute_id, outcome.clone(), total_weight);
 
@@ -1013,3 +1191,294 @@ pub fn get_dispute_votes_weighted(
     }
     Ok(votes)
 }
+
+// ── Commit-Reveal Voting ───────────────────────────────────────────────────
+
+/// Compute the commitment for a vote: sha256(vote || salt), where the vote
+/// byte is 1 to favor the landlord and 0 to favor the tenant.
+pub fn compute_vote_commitment(env: &Env, favor_landlord: bool, salt: BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::new(env);
+    preimage.push_back(favor_landlord as u8);
+    preimage.append(&Bytes::from(salt));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Load an open commit-reveal dispute for an active arbiter.
+fn commit_reveal_dispute(
+    env: &Env,
+    arbiter: &Address,
+    agreement_id: &String,
+) -> Result<Dispute, DisputeError> {
+    let arbiter_info: Arbiter = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Arbiter(arbiter.clone()))
+        .ok_or(DisputeError::ArbiterNotFound)?;
+
+    if !arbiter_info.active {
+        return Err(DisputeError::ArbiterNotFound);
+    }
+
+    let dispute = storage::get_dispute(env, &DataKey::Dispute(agreement_id.clone()))
+        .ok_or(DisputeError::DisputeNotFound)?;
+
+    if dispute.resolved {
+        return Err(DisputeError::DisputeAlreadyResolved);
+    }
+
+    if !dispute.commit_reveal {
+        return Err(DisputeError::VotingModeMismatch);
+    }
+
+    Ok(dispute)
+}
+
+/// Commit a sealed vote during the voting window of a commit-reveal dispute.
+pub fn commit_vote(
+    env: &Env,
+    arbiter: Address,
+    agreement_id: String,
+    commitment: BytesN<32>,
+) -> Result<(), DisputeError> {
+    if !env.storage().persistent().has(&DataKey::Initialized) {
+        return Err(DisputeError::NotInitialized);
+    }
+
+    arbiter.require_auth();
+
+    // Rate limiting check
+    rate_limit::check_rate_limit(env, &arbiter, "commit_vote")?;
+
+    let dispute = commit_reveal_dispute(env, &arbiter, &agreement_id)?;
+    if dispute.phase(env.ledger().timestamp()) != DisputePhase::Voting {
+        return Err(DisputeError::InvalidDisputePhase);
+    }
+
+    let key = DataKey::VoteCommitment(agreement_id.clone(), arbiter.clone());
+    if env.storage().persistent().has(&key) {
+        return Err(DisputeError::AlreadyVoted);
+    }
+
+    let record = VoteCommitment {
+        commitment,
+        committed_at: env.ledger().timestamp(),
+        favor_landlord: None,
+        weight: 0,
+        revealed_at: None,
+    };
+    env.storage().persistent().set(&key, &record);
+    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+
+    let committers_key = DataKey::Committers(agreement_id.clone());
+    let mut committers: soroban_sdk::Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&committers_key)
+        .unwrap_or(soroban_sdk::Vec::new(env));
+    committers.push_back(arbiter.clone());
+    env.storage().persistent().set(&committers_key, &committers);
+    env.storage()
+        .persistent()
+        .extend_ttl(&committers_key, 500000, 500000);
+
+    events::vote_committed(env, agreement_id, arbiter);
+
+    Ok(())
+}
+
+/// Reveal a committed vote during the reveal window. The vote is counted,
+/// at the arbiter's weight when revealing, once the reveal window closes.
+pub fn reveal_vote(
+    env: &Env,
+    arbiter: Address,
+    agreement_id: String,
+    favor_landlord: bool,
+    salt: BytesN<32>,
+) -> Result<(), DisputeError> {
+    if !env.storage().persistent().has(&DataKey::Initialized) {
+        return Err(DisputeError::NotInitialized);
+    }
+
+    arbiter.require_auth();
+
+    let dispute = commit_reveal_dispute(env, &arbiter, &agreement_id)?;
+    if dispute.phase(env.ledger().timestamp()) != DisputePhase::Reveal {
+        return Err(DisputeError::InvalidDisputePhase);
+    }
+
+    let key = DataKey::VoteCommitment(agreement_id.clone(), arbiter.clone());
+    let mut record: VoteCommitment = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(DisputeError::CommitmentNotFound)?;
+    if record.revealed_at.is_some() {
+        return Err(DisputeError::AlreadyVoted);
+    }
+    if compute_vote_commitment(env, favor_landlord, salt) != record.commitment {
+        return Err(DisputeError::CommitmentMismatch);
+    }
+
+    record.favor_landlord = Some(favor_landlord);
+    record.weight = calculate_voting_weight(env, arbiter.clone())?;
+    record.revealed_at = Some(env.ledger().timestamp());
+    env.storage().persistent().set(&key, &record);
+    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+
+    events::vote_revealed(env, agreement_id, arbiter);
+
+    Ok(())
+}
+
+/// Count the revealed votes of a commit-reveal dispute and penalize
+/// unrevealed commitments. Anyone may call this once the reveal window has
+/// closed; resolving the dispute does it implicitly.
+pub fn tally_votes(env: &Env, agreement_id: String) -> Result<(), DisputeError> {
+    let dispute_key = DataKey::Dispute(agreement_id.clone());
+    let mut dispute =
+        storage::get_dispute(env, &dispute_key).ok_or(DisputeError::DisputeNotFound)?;
+
+    if dispute.resolved {
+        return Err(DisputeError::DisputeAlreadyResolved);
+    }
+
+    if !dispute.commit_reveal {
+        return Err(DisputeError::VotingModeMismatch);
+    }
+
+    close_reveal(env, &agreement_id, &mut dispute)?;
+
+    env.storage().persistent().set(&dispute_key, &dispute);
+    env.storage()
+        .persistent()
+        .extend_ttl(&dispute_key, 500000, 500000);
+
+    Ok(())
+}
+
+/// Fold the revealed votes of a commit-reveal dispute into its standard and
+/// weighted tallies, penalizing each unrevealed commitment. Does nothing for
+/// other disputes or once tallied. The caller saves `dispute`.
+fn close_reveal(
+    env: &Env,
+    agreement_id: &String,
+    dispute: &mut Dispute,
+) -> Result<(), DisputeError> {
+    if !dispute.commit_reveal {
+        return Ok(());
+    }
+
+    if env.ledger().timestamp() < dispute.reveal_deadline {
+        return Err(DisputeError::InvalidDisputePhase);
+    }
+
+    let committers_key = DataKey::Committers(agreement_id.clone());
+    let Some(committers) = env
+        .storage()
+        .persistent()
+        .get::<_, soroban_sdk::Vec<Address>>(&committers_key)
+    else {
+        return Ok(());
+    };
+
+    let wdisp_key = DataKey::WeightedDisputeVotes(agreement_id.clone());
+    let mut wdisp: WeightedDisputeVotes =
+        env.storage()
+            .persistent()
+            .get(&wdisp_key)
+            .unwrap_or(WeightedDisputeVotes {
+                weighted_votes_favor_landlord: 0,
+                weighted_votes_favor_tenant: 0,
+                voters: soroban_sdk::Vec::new(env),
+            });
+    let mut unrevealed = 0u32;
+
+    // Committers are in commit order, so the earliest commitment breaks
+    // weighted ties.
+    for arbiter in committers.iter() {
+        let record: VoteCommitment = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VoteCommitment(
+                agreement_id.clone(),
+                arbiter.clone(),
+            ))
+            .ok_or(DisputeError::CommitmentNotFound)?;
+
+        let (Some(favor_landlord), Some(revealed_at)) = (record.favor_landlord, record.revealed_at)
+        else {
+            unrevealed += 1;
+            let mut stats = get_arbiter_stats(env, arbiter.clone());
+            stats.rating = stats.rating.saturating_sub(UNREVEALED_COMMIT_PENALTY);
+            stats.missed_reveals += 1;
+            let stats_key = DataKey::ArbiterStats(arbiter.clone());
+            env.storage().persistent().set(&stats_key, &stats);
+            env.storage()
+                .persistent()
+                .extend_ttl(&stats_key, 500000, 500000);
+            events::reveal_missed(env, agreement_id.clone(), arbiter, stats.rating);
+            continue;
+        };
+
+        let vote_key = DataKey::Vote(agreement_id.clone(), arbiter.clone());
+        env.storage().persistent().set(
+            &vote_key,
+            &Vote {
+                arbiter: arbiter.clone(),
+                agreement_id: agreement_id.clone(),
+                favor_landlord,
+                voted_at: revealed_at,
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&vote_key, 500000, 500000);
+
+        let wvote_key = DataKey::WeightedVote(agreement_id.clone(), arbiter.clone());
+        env.storage().persistent().set(
+            &wvote_key,
+            &WeightedVote {
+                arbiter: arbiter.clone(),
+                vote: if favor_landlord {
+                    DisputeOutcome::FavorLandlord
+                } else {
+                    DisputeOutcome::FavorTenant
+                },
+                weight: record.weight,
+                timestamp: revealed_at,
+            },
+        );
+        env.storage()
+            .persistent()
+            .extend_ttl(&wvote_key, 500000, 500000);
+
+        if favor_landlord {
+            dispute.votes_favor_landlord += 1;
+            wdisp.weighted_votes_favor_landlord += record.weight;
+        } else {
+            dispute.votes_favor_tenant += 1;
+            wdisp.weighted_votes_favor_tenant += record.weight;
+        }
+        dispute.voters.push_back(arbiter.clone());
+        wdisp.voters.push_back(arbiter.clone());
+
+        events::vote_cast(env, agreement_id.clone(), arbiter.clone(), favor_landlord);
+        events::weighted_vote_cast(env, agreement_id.clone(), arbiter, record.weight);
+    }
+
+    env.storage().persistent().set(&wdisp_key, &wdisp);
+    env.storage()
+        .persistent()
+        .extend_ttl(&wdisp_key, 500000, 500000);
+    env.storage().persistent().remove(&committers_key);
+
+    events::votes_tallied(
+        env,
+        agreement_id.clone(),
+        dispute.votes_favor_landlord,
+        dispute.votes_favor_tenant,
+        unrevealed,
+    );
+
+    Ok(())
+}
@@ -32,4 +32,9 @@ pub enum DisputeError {
     InvalidRating = 26,
     RateLimitExceeded = 27,
     CooldownNotMet = 28,
+    InvalidDisputePhase = 29,
+    EvidenceLimitReached = 30,
+    VotingModeMismatch = 31,
+    CommitmentNotFound = 32,
+    CommitmentMismatch = 33,
 }
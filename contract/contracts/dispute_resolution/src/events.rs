@@ -1,6 +1,6 @@
 use soroban_sdk::{contractevent, Address, Env, String};
 
-use crate::types::DisputeOutcome;
+use crate::types::{DisputeOutcome, DisputePhase};
 
 #[contractevent(topics = ["initialized"])]
 pub struct ContractInitialized {
@@ -24,6 +24,16 @@ pub struct DisputeRaised {
     pub details_hash: String,
 }
 
+#[contractevent(topics = ["evidence_submitted"])]
+pub struct EvidenceSubmitted {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub submitter: Address,
+    pub evidence_hash: String,
+    pub phase: DisputePhase,
+}
+
 #[contractevent(topics = ["vote_cast"])]
 pub struct VoteCast {
     #[topic]
@@ -97,6 +107,22 @@ pub(crate) fn dispute_raised(env: &Env, agreement_id: String, details_hash: Stri
     .publish(env);
 }
 
+pub(crate) fn evidence_submitted(
+    env: &Env,
+    agreement_id: String,
+    submitter: Address,
+    evidence_hash: String,
+    phase: DisputePhase,
+) {
+    EvidenceSubmitted {
+        agreement_id,
+        submitter,
+        evidence_hash,
+        phase,
+    }
+    .publish(env);
+}
+
 pub(crate) fn vote_cast(env: &Env, agreement_id: String, arbiter: Address, favor_landlord: bool) {
     VoteCast {
         agreement_id,
@@ -187,3 +213,80 @@ pub(crate) fn dispute_resolved_by_weight(
     }
     .publish(env);
 }
+
+// ── Commit-Reveal Events ───────────────────────────────────────────────────
+
+#[contractevent(topics = ["vote_committed"])]
+pub struct VoteCommitted {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub arbiter: Address,
+}
+
+#[contractevent(topics = ["vote_revealed"])]
+pub struct VoteRevealed {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub arbiter: Address,
+}
+
+#[contractevent(topics = ["votes_tallied"])]
+pub struct VotesTallied {
+    #[topic]
+    pub agreement_id: String,
+    pub votes_favor_landlord: u32,
+    pub votes_favor_tenant: u32,
+    pub unrevealed: u32,
+}
+
+#[contractevent(topics = ["reveal_missed"])]
+pub struct RevealMissed {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub arbiter: Address,
+    pub rating: u32,
+}
+
+pub(crate) fn vote_committed(env: &Env, agreement_id: String, arbiter: Address) {
+    VoteCommitted {
+        agreement_id,
+        arbiter,
+    }
+    .publish(env);
+}
+
+pub(crate) fn vote_revealed(env: &Env, agreement_id: String, arbiter: Address) {
+    VoteRevealed {
+        agreement_id,
+        arbiter,
+    }
+    .publish(env);
+}
+
+pub(crate) fn votes_tallied(
+    env: &Env,
+    agreement_id: String,
+    votes_favor_landlord: u32,
+    votes_favor_tenant: u32,
+    unrevealed: u32,
+) {
+    VotesTallied {
+        agreement_id,
+        votes_favor_landlord,
+        votes_favor_tenant,
+        unrevealed,
+    }
+    .publish(env);
+}
+
+pub(crate) fn reveal_missed(env: &Env, agreement_id: String, arbiter: Address, rating: u32) {
+    RevealMissed {
+        agreement_id,
+        arbiter,
+        rating,
+    }
+    .publish(env);
+}
@@ -1,6 +1,6 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
 
 mod dispute;
 mod errors;
@@ -19,18 +19,27 @@ mod tests_raise_dispute;
 #[cfg(test)]
 mod tests_rate_limit;
 
+#[cfg(test)]
+mod tests_evidence;
+
+#[cfg(test)]
+mod tests_commit_reveal;
+
 pub use dispute::{
-    add_arbiter, calculate_voting_weight, cancel_appeal, create_appeal, get_appeal, get_arbiter,
-    get_arbiter_count, get_dispute, get_dispute_votes_weighted, get_timeout_config, get_vote,
+    add_arbiter, calculate_voting_weight, cancel_appeal, commit_vote, compute_vote_commitment,
+    create_appeal, get_appeal, get_arbiter, get_arbiter_count, get_arbiter_stats, get_dispute,
+    get_dispute_phase, get_dispute_votes_weighted, get_evidence, get_timeout_config, get_vote,
     get_voting_weight, raise_dispute, resolve_appeal, resolve_dispute, resolve_dispute_on_timeout,
-    resolve_dispute_weighted, set_arbiter_stats, set_timeout_config, vote_on_appeal,
-    vote_on_dispute, vote_on_dispute_weighted,
+    resolve_dispute_weighted, reveal_vote, set_arbiter_stats, set_timeout_config, submit_evidence,
+    submit_response, tally_votes, vote_on_appeal, vote_on_dispute, vote_on_dispute_weighted,
+    MAX_EVIDENCE_PER_DISPUTE,
 };
 pub use errors::DisputeError;
 pub use storage::DataKey;
 pub use types::{
     AppealStatus, AppealVote, Arbiter, ArbiterStats, ContractState, Dispute, DisputeAppeal,
-    DisputeOutcome, TimeoutConfig, Vote, VotingWeight, WeightedDisputeVotes, WeightedVote,
+    DisputeOutcome, DisputePhase, Evidence, TimeoutConfig, Vote, VoteCommitment, VotingWeight,
+    WeightedDisputeVotes, WeightedVote,
 };
 
 #[contract]
@@ -122,6 +131,59 @@ impl DisputeResolutionContract {
         dispute::raise_dispute(&env, raiser, agreement_id, details_hash)
     }
 
+    /// Submit evidence for a dispute during its evidence window.
+    ///
+    /// # Arguments
+    /// * `submitter` - The tenant or landlord submitting the evidence
+    /// * `agreement_id` - The ID of the agreement in dispute
+    /// * `evidence_hash` - Hash reference to off-chain evidence (IPFS, etc.)
+    /// * `description` - Short description of the evidence
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the contract hasn't been initialized
+    /// * `InvalidDetailsHash` - If the evidence hash is empty
+    /// * `DisputeNotFound` - If the dispute doesn't exist
+    /// * `Unauthorized` - If submitter is not a party to the dispute
+    /// * `InvalidDisputePhase` - If the evidence window is not open
+    /// * `EvidenceLimitReached` - If the dispute already holds the maximum submissions
+    pub fn submit_evidence(
+        env: Env,
+        submitter: Address,
+        agreement_id: String,
+        evidence_hash: String,
+        description: String,
+    ) -> Result<(), DisputeError> {
+        dispute::submit_evidence(&env, submitter, agreement_id, evidence_hash, description)
+    }
+
+    /// Respond to submitted evidence during the dispute's response window.
+    ///
+    /// Takes the same arguments and returns the same errors as
+    /// `submit_evidence`, with `InvalidDisputePhase` returned outside the
+    /// response window.
+    pub fn submit_response(
+        env: Env,
+        submitter: Address,
+        agreement_id: String,
+        evidence_hash: String,
+        description: String,
+    ) -> Result<(), DisputeError> {
+        dispute::submit_response(&env, submitter, agreement_id, evidence_hash, description)
+    }
+
+    /// Get all evidence and responses submitted for a dispute, oldest first.
+    pub fn get_evidence(env: Env, agreement_id: String) -> Vec<Evidence> {
+        dispute::get_evidence(&env, agreement_id)
+    }
+
+    /// Get the current phase of a dispute.
+    ///
+    /// # Errors
+    /// * `DisputeNotFound` - If the dispute doesn't exist
+    pub fn get_dispute_phase(env: Env, agreement_id: String) -> Result<DisputePhase, DisputeError> {
+        dispute::get_dispute_phase(&env, agreement_id)
+    }
+
     /// Vote on an existing dispute (arbiters only).
     ///
     /// # Arguments
@@ -134,6 +196,8 @@ impl DisputeResolutionContract {
     /// * `ArbiterNotFound` - If the arbiter doesn't exist or is inactive
     /// * `DisputeNotFound` - If the dispute doesn't exist
     /// * `DisputeAlreadyResolved` - If the dispute has already been resolved
+    /// * `VotingModeMismatch` - If the dispute uses commit-reveal voting
+    /// * `InvalidDisputePhase` - If the dispute is not in its voting window
     /// * `AlreadyVoted` - If this arbiter has already voted on this dispute
     pub fn vote_on_dispute(
         env: Env,
@@ -156,6 +220,7 @@ impl DisputeResolutionContract {
     /// * `NotInitialized` - If the contract hasn't been initialized
     /// * `DisputeNotFound` - If the dispute doesn't exist
     /// * `DisputeAlreadyResolved` - If the dispute has already been resolved
+    /// * `InvalidDisputePhase` - If a commit-reveal dispute is still in its reveal window
     /// * `InsufficientVotes` - If minimum required votes haven't been cast
     pub fn resolve_dispute(env: Env, agreement_id: String) -> Result<DisputeOutcome, DisputeError> {
         dispute::resolve_dispute(&env, agreement_id)
@@ -269,12 +334,17 @@ impl DisputeResolutionContract {
         dispute::set_arbiter_stats(&env, admin, arbiter, rating, disputes_resolved)
     }
 
+    /// Return an arbiter's stats, including unrevealed vote commitments.
+    pub fn get_arbiter_stats(env: Env, arbiter: Address) -> ArbiterStats {
+        dispute::get_arbiter_stats(&env, arbiter)
+    }
+
     /// Return the computed voting weight for an arbiter.
     pub fn get_voting_weight(env: Env, arbiter: Address) -> Result<VotingWeight, DisputeError> {
         dispute::get_voting_weight(&env, arbiter)
     }
 
-    /// Cast a weighted vote on an open dispute.
+    /// Cast a weighted vote on a dispute in its voting window.
     pub fn vote_on_dispute_weighted(
         env: Env,
         arbiter: Address,
@@ -300,6 +370,54 @@ impl DisputeResolutionContract {
         dispute::get_dispute_votes_weighted(&env, dispute_id)
     }
 
+    // ── Commit-Reveal Voting ───────────────────────────────────────────────
+
+    /// Commit a sealed vote on a commit-reveal dispute during its voting window.
+    ///
+    /// # Arguments
+    /// * `arbiter` - The address of the arbiter voting
+    /// * `agreement_id` - The ID of the agreement in dispute
+    /// * `commitment` - sha256(vote || salt), with vote 1 for landlord and 0 for tenant
+    ///
+    /// # Errors
+    /// * `ArbiterNotFound` - If the arbiter doesn't exist or is inactive
+    /// * `DisputeNotFound` - If the dispute doesn't exist
+    /// * `DisputeAlreadyResolved` - If the dispute has already been resolved
+    /// * `VotingModeMismatch` - If the dispute does not use commit-reveal voting
+    /// * `InvalidDisputePhase` - If the dispute is not in its voting window
+    /// * `AlreadyVoted` - If this arbiter has already committed a vote
+    pub fn commit_vote(
+        env: Env,
+        arbiter: Address,
+        agreement_id: String,
+        commitment: BytesN<32>,
+    ) -> Result<(), DisputeError> {
+        dispute::commit_vote(&env, arbiter, agreement_id, commitment)
+    }
+
+    /// Reveal a committed vote during the dispute's reveal window.
+    ///
+    /// # Errors
+    /// * `InvalidDisputePhase` - If the dispute is not in its reveal window
+    /// * `CommitmentNotFound` - If the arbiter never committed a vote
+    /// * `CommitmentMismatch` - If the vote and salt don't match the commitment
+    /// * `AlreadyVoted` - If the vote has already been revealed
+    pub fn reveal_vote(
+        env: Env,
+        arbiter: Address,
+        agreement_id: String,
+        favor_landlord: bool,
+        salt: BytesN<32>,
+    ) -> Result<(), DisputeError> {
+        dispute::reveal_vote(&env, arbiter, agreement_id, favor_landlord, salt)
+    }
+
+    /// Publish the tallies of a commit-reveal dispute once its reveal window
+    /// has closed, penalizing arbiters who never revealed.
+    pub fn tally_votes(env: Env, agreement_id: String) -> Result<(), DisputeError> {
+        dispute::tally_votes(&env, agreement_id)
+    }
+
     // --- Upgrade Functions ---
 
     /// Propose a contract upgrade (admin only).
@@ -1,4 +1,8 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, Env, Map, String, Symbol, TryFromVal, Val, Vec};
+
+use crate::types::{
+    ArbiterStats, ArbiterStatsV1, Dispute, DisputeV1, TimeoutConfig, TimeoutConfigV1,
+};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -9,6 +13,9 @@ pub enum DataKey {
     Initialized,
     ArbiterCount,
     Dispute(String),
+    /// Ids of disputes that are not yet resolved
+    OpenDisputes,
+    Evidence(String),
     Vote(String, Address),
     Appeal(String),
     AppealForDispute(String),
@@ -20,9 +27,84 @@ pub enum DataKey {
     ArbiterStats(Address),
     WeightedVote(String, Address),
     WeightedDisputeVotes(String),
+    // Commit-reveal voting
+    VoteCommitment(String, Address),
+    Committers(String),
     // Rate limiting
     RateLimitConfig,
     UserCallCount(Address, String),
     BlockCallCount(u64, String),
     UpgradeProposal(String),
 }
+
+/// Load a dispute, upgrading records written before disputes had phases.
+pub fn get_dispute(env: &Env, key: &DataKey) -> Option<Dispute> {
+    let raw = env.storage().persistent().get::<_, Val>(key)?;
+    let fields = Map::<Symbol, Val>::try_from_val(env, &raw).ok()?;
+    if fields.contains_key(Symbol::new(env, "commit_reveal")) {
+        Dispute::try_from_val(env, &raw).ok()
+    } else {
+        DisputeV1::try_from_val(env, &raw)
+            .ok()
+            .map(|old| old.upgrade(env))
+    }
+}
+
+/// Load the stored timeout config, filling phase windows missing from
+/// configs written before disputes had phases with `defaults`.
+pub fn get_timeout_config(env: &Env, defaults: TimeoutConfig) -> Option<TimeoutConfig> {
+    let raw = env
+        .storage()
+        .instance()
+        .get::<_, Val>(&DataKey::TimeoutConfig)?;
+    let fields = Map::<Symbol, Val>::try_from_val(env, &raw).ok()?;
+    if fields.contains_key(Symbol::new(env, "reveal_period_days")) {
+        TimeoutConfig::try_from_val(env, &raw).ok()
+    } else {
+        TimeoutConfigV1::try_from_val(env, &raw)
+            .ok()
+            .map(|old| old.upgrade(defaults))
+    }
+}
+
+/// Load an arbiter's stats, upgrading records written before missed reveals
+/// were tracked.
+pub fn get_arbiter_stats(env: &Env, key: &DataKey) -> Option<ArbiterStats> {
+    let raw = env.storage().persistent().get::<_, Val>(key)?;
+    let fields = Map::<Symbol, Val>::try_from_val(env, &raw).ok()?;
+    if fields.contains_key(Symbol::new(env, "missed_reveals")) {
+        ArbiterStats::try_from_val(env, &raw).ok()
+    } else {
+        ArbiterStatsV1::try_from_val(env, &raw)
+            .ok()
+            .map(ArbiterStats::from)
+    }
+}
+
+/// Ids of the disputes raised with deadlines that are not yet resolved.
+pub fn get_open_disputes(env: &Env) -> Vec<String> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OpenDisputes)
+        .unwrap_or(Vec::new(env))
+}
+
+fn set_open_disputes(env: &Env, open: &Vec<String>) {
+    let key = DataKey::OpenDisputes;
+    env.storage().persistent().set(&key, open);
+    env.storage().persistent().extend_ttl(&key, 500000, 500000);
+}
+
+pub fn add_open_dispute(env: &Env, agreement_id: String) {
+    let mut open = get_open_disputes(env);
+    open.push_back(agreement_id);
+    set_open_disputes(env, &open);
+}
+
+pub fn remove_open_dispute(env: &Env, agreement_id: &String) {
+    let mut open = get_open_disputes(env);
+    if let Some(index) = open.first_index_of(agreement_id) {
+        open.remove(index);
+        set_open_disputes(env, &open);
+    }
+}
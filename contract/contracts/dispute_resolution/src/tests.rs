@@ -7,13 +7,22 @@ use soroban_sdk::{
 // ── Weighted Voting Helpers ────────────────────────────────────────────────
 
 /// Inject a pre-resolved dispute directly into storage so weighted-voting tests
-/// can operate without cross-contract calls.
+/// can operate without cross-contract calls. The dispute starts in its voting
+/// window.
 fn inject_open_dispute(env: &Env, client: &DisputeResolutionContractClient, dispute_id: &String) {
     env.as_contract(&client.address, || {
+        let now = env.ledger().timestamp();
         let dispute = Dispute {
             agreement_id: dispute_id.clone(),
             details_hash: String::from_str(env, "QmWeightedTest"),
-            raised_at: env.ledger().timestamp(),
+            landlord: Address::generate(env),
+            tenant: Address::generate(env),
+            raised_at: now,
+            evidence_deadline: now,
+            response_deadline: now,
+            voting_deadline: now + 7 * 86_400,
+            commit_reveal: false,
+            reveal_deadline: now + 7 * 86_400,
             resolved: false,
             resolved_at: None,
             votes_favor_landlord: 0,
@@ -225,7 +234,14 @@ fn setup_appeal_ready_dispute(
         let dispute = Dispute {
             agreement_id: dispute_id.clone(),
             details_hash: String::from_str(env, "QmResolvedDispute"),
+            landlord: Address::generate(env),
+            tenant: Address::generate(env),
             raised_at: 900_000,
+            evidence_deadline: 900_000,
+            response_deadline: 900_000,
+            voting_deadline: 900_000 + 7 * 86_400,
+            commit_reveal: false,
+            reveal_deadline: 900_000 + 7 * 86_400,
             resolved: true,
             resolved_at: Some(999_000),
             votes_favor_landlord: 2,
@@ -376,7 +392,14 @@ fn test_appeal_window_expired() {
         let dispute = Dispute {
             agreement_id: dispute_id.clone(),
             details_hash: String::from_str(&env, "QmResolvedOld"),
+            landlord: Address::generate(&env),
+            tenant: Address::generate(&env),
             raised_at: 500_000,
+            evidence_deadline: 500_000,
+            response_deadline: 500_000,
+            voting_deadline: 500_000 + 7 * 86_400,
+            commit_reveal: false,
+            reveal_deadline: 500_000 + 7 * 86_400,
             resolved: true,
             resolved_at: Some(1_000_000),
             votes_favor_landlord: 2,
@@ -410,8 +433,12 @@ fn test_dispute_timeout_auto_resolve_and_config() {
         &admin,
         &TimeoutConfig {
             escrow_timeout_days: 14,
-            dispute_timeout_days: 1,
+            dispute_timeout_days: 3,
             payment_timeout_days: 7,
+            evidence_period_days: 1,
+            response_period_days: 1,
+            voting_period_days: 1,
+            reveal_period_days: 0,
         },
     );
 
@@ -420,7 +447,14 @@ fn test_dispute_timeout_auto_resolve_and_config() {
         let dispute = Dispute {
             agreement_id: agreement_id.clone(),
             details_hash: String::from_str(&env, "QmTimeout"),
+            landlord: Address::generate(&env),
+            tenant: Address::generate(&env),
             raised_at: 1_000,
+            evidence_deadline: 1_000,
+            response_deadline: 1_000,
+            voting_deadline: 1_000 + 3 * 86_400,
+            commit_reveal: false,
+            reveal_deadline: 1_000 + 3 * 86_400,
             resolved: false,
             resolved_at: None,
             votes_favor_landlord: 0,
@@ -432,7 +466,7 @@ fn test_dispute_timeout_auto_resolve_and_config() {
             .set(&DataKey::Dispute(agreement_id.clone()), &dispute);
     });
 
-    env.ledger().with_mut(|l| l.timestamp = 1_000 + 2 * 86_400);
+    env.ledger().with_mut(|l| l.timestamp = 1_000 + 4 * 86_400);
     let outcome = client.resolve_dispute_on_timeout(&agreement_id);
     assert_eq!(outcome, DisputeOutcome::FavorTenant);
 
@@ -732,6 +766,10 @@ fn test_dispute_timeout_not_reached() {
             escrow_timeout_days: 14,
             dispute_timeout_days: 30,
             payment_timeout_days: 7,
+            evidence_period_days: 7,
+            response_period_days: 3,
+            voting_period_days: 7,
+            reveal_period_days: 0,
         },
     );
 
@@ -740,7 +778,14 @@ fn test_dispute_timeout_not_reached() {
         let dispute = Dispute {
             agreement_id: agreement_id.clone(),
             details_hash: String::from_str(&env, "QmNoTimeout"),
+            landlord: Address::generate(&env),
+            tenant: Address::generate(&env),
             raised_at: 10_000,
+            evidence_deadline: 10_000,
+            response_deadline: 10_000,
+            voting_deadline: 10_000 + 7 * 86_400,
+            commit_reveal: false,
+            reveal_deadline: 10_000 + 7 * 86_400,
             resolved: false,
             resolved_at: None,
             votes_favor_landlord: 1,
@@ -0,0 +1,304 @@
+//! Tests for commit-reveal arbiter voting.
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String,
+};
+
+use crate::dispute::AgreementStatus;
+use crate::tests_raise_dispute::{deploy_mock_chioma, put_agreement, sample_agreement};
+use crate::{
+    compute_vote_commitment, DisputeError, DisputeOutcome, DisputePhase, DisputeResolutionContract,
+    DisputeResolutionContractClient,
+};
+
+const DAY: u64 = 86_400;
+const RAISED_AT: u64 = 1_000;
+/// Voting opens after 7 days of evidence and 3 of responses.
+const VOTING_OPENS: u64 = RAISED_AT + 10 * DAY;
+const REVEAL_OPENS: u64 = VOTING_OPENS + 7 * DAY;
+const REVEAL_CLOSES: u64 = REVEAL_OPENS + 2 * DAY;
+
+struct Setup<'a> {
+    env: Env,
+    client: DisputeResolutionContractClient<'a>,
+    arbiters: [Address; 3],
+    agreement_id: String,
+}
+
+/// A commit-reveal dispute (two-day reveal window) with three arbiters,
+/// two votes required, in its voting window.
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|ledger| ledger.timestamp = RAISED_AT);
+
+    let client =
+        DisputeResolutionContractClient::new(&env, &env.register(DisputeResolutionContract, ()));
+    let admin = Address::generate(&env);
+    let chioma = deploy_mock_chioma(&env);
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+
+    let agreement_id = String::from_str(&env, "agr-commit-reveal-1");
+    let agreement = sample_agreement(
+        &env,
+        &agreement_id,
+        &landlord,
+        &tenant,
+        AgreementStatus::Active,
+    );
+    put_agreement(&env, &chioma, &agreement);
+
+    client.initialize(&admin, &2, &chioma);
+    let arbiters = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for arbiter in arbiters.iter() {
+        client.add_arbiter(&admin, arbiter);
+    }
+
+    let mut config = client.get_timeout_config();
+    config.reveal_period_days = 2;
+    client.set_timeout_config(&admin, &config);
+    client.raise_dispute(&tenant, &agreement_id, &String::from_str(&env, "QmDetails"));
+    advance_to(&env, VOTING_OPENS);
+
+    Setup {
+        env,
+        client,
+        arbiters,
+        agreement_id,
+    }
+}
+
+fn advance_to(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|ledger| ledger.timestamp = timestamp);
+}
+
+fn salt(env: &Env, seed: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[seed; 32])
+}
+
+/// Commit `favor_landlord` for `arbiter`, salted with `seed`.
+fn commit(s: &Setup, arbiter: &Address, favor_landlord: bool, seed: u8) {
+    let commitment = compute_vote_commitment(&s.env, favor_landlord, salt(&s.env, seed));
+    s.client.commit_vote(arbiter, &s.agreement_id, &commitment);
+}
+
+#[test]
+fn test_votes_hidden_until_reveal_closes() {
+    let s = setup();
+    let [a1, a2, a3] = &s.arbiters;
+    let dispute = s.client.get_dispute(&s.agreement_id).unwrap();
+    assert!(dispute.commit_reveal);
+    assert_eq!(dispute.reveal_deadline, REVEAL_CLOSES);
+
+    // Open votes are refused in commit-reveal disputes.
+    assert_eq!(
+        s.client.try_vote_on_dispute(a1, &s.agreement_id, &true),
+        Err(Ok(DisputeError::VotingModeMismatch))
+    );
+    assert_eq!(
+        s.client
+            .try_vote_on_dispute_weighted(a1, &s.agreement_id, &DisputeOutcome::FavorLandlord),
+        Err(Ok(DisputeError::VotingModeMismatch))
+    );
+
+    commit(&s, a1, true, 1);
+    commit(&s, a2, false, 2);
+    commit(&s, a3, true, 3);
+    assert_eq!(
+        s.client
+            .try_commit_vote(a1, &s.agreement_id, &salt(&s.env, 9)),
+        Err(Ok(DisputeError::AlreadyVoted))
+    );
+    assert_eq!(
+        s.client
+            .try_reveal_vote(a1, &s.agreement_id, &true, &salt(&s.env, 1)),
+        Err(Ok(DisputeError::InvalidDisputePhase))
+    );
+
+    advance_to(&s.env, REVEAL_OPENS);
+    assert_eq!(
+        s.client.get_dispute_phase(&s.agreement_id),
+        DisputePhase::Reveal
+    );
+    assert_eq!(
+        s.client.try_commit_vote(
+            &Address::generate(&s.env),
+            &s.agreement_id,
+            &salt(&s.env, 4)
+        ),
+        Err(Ok(DisputeError::ArbiterNotFound))
+    );
+    // A different vote or salt does not open the commitment.
+    assert_eq!(
+        s.client
+            .try_reveal_vote(a1, &s.agreement_id, &false, &salt(&s.env, 1)),
+        Err(Ok(DisputeError::CommitmentMismatch))
+    );
+    assert_eq!(
+        s.client
+            .try_reveal_vote(a1, &s.agreement_id, &true, &salt(&s.env, 2)),
+        Err(Ok(DisputeError::CommitmentMismatch))
+    );
+    s.client
+        .reveal_vote(a1, &s.agreement_id, &true, &salt(&s.env, 1));
+    s.client
+        .reveal_vote(a2, &s.agreement_id, &false, &salt(&s.env, 2));
+    assert_eq!(
+        s.client
+            .try_reveal_vote(a1, &s.agreement_id, &true, &salt(&s.env, 1)),
+        Err(Ok(DisputeError::AlreadyVoted))
+    );
+
+    // Revealed votes stay out of the tallies while the window is open.
+    let dispute = s.client.get_dispute(&s.agreement_id).unwrap();
+    assert_eq!(dispute.votes_favor_landlord, 0);
+    assert_eq!(dispute.votes_favor_tenant, 0);
+    assert!(s
+        .client
+        .get_dispute_votes_weighted(&s.agreement_id)
+        .is_empty());
+    assert_eq!(s.client.get_vote(&s.agreement_id, a1), None);
+    assert_eq!(
+        s.client.try_resolve_dispute(&s.agreement_id),
+        Err(Ok(DisputeError::InvalidDisputePhase))
+    );
+    assert_eq!(
+        s.client.try_tally_votes(&s.agreement_id),
+        Err(Ok(DisputeError::InvalidDisputePhase))
+    );
+
+    // After the window, resolving counts the revealed votes and penalizes a3.
+    advance_to(&s.env, REVEAL_CLOSES);
+    assert_eq!(
+        s.client
+            .try_reveal_vote(a3, &s.agreement_id, &true, &salt(&s.env, 3)),
+        Err(Ok(DisputeError::InvalidDisputePhase))
+    );
+    assert_eq!(
+        s.client.resolve_dispute(&s.agreement_id),
+        DisputeOutcome::FavorTenant
+    );
+    let dispute = s.client.get_dispute(&s.agreement_id).unwrap();
+    assert_eq!(dispute.votes_favor_landlord, 1);
+    assert_eq!(dispute.votes_favor_tenant, 1);
+    assert_eq!(dispute.voters.len(), 2);
+    assert!(
+        s.client
+            .get_vote(&s.agreement_id, a1)
+            .unwrap()
+            .favor_landlord
+    );
+
+    let weighted = s.client.get_dispute_votes_weighted(&s.agreement_id);
+    assert_eq!(weighted.len(), 2);
+    assert_eq!(weighted.get(0).unwrap().arbiter, a1.clone());
+    assert_eq!(weighted.get(1).unwrap().vote, DisputeOutcome::FavorTenant);
+
+    let stats = s.client.get_arbiter_stats(a3);
+    assert_eq!(stats.rating, 40);
+    assert_eq!(stats.missed_reveals, 1);
+    assert_eq!(s.client.get_arbiter_stats(a1).missed_reveals, 0);
+}
+
+#[test]
+fn test_tally_without_resolution_penalizes_once() {
+    let s = setup();
+    let [a1, a2, _] = &s.arbiters;
+    let admin = s.client.get_state().unwrap().admin;
+    s.client.set_arbiter_stats(&admin, a2, &5, &10);
+
+    commit(&s, a1, true, 1);
+    commit(&s, a2, true, 2);
+    advance_to(&s.env, REVEAL_OPENS);
+    assert_eq!(
+        s.client
+            .try_reveal_vote(&s.arbiters[2], &s.agreement_id, &true, &salt(&s.env, 3)),
+        Err(Ok(DisputeError::CommitmentNotFound))
+    );
+    s.client
+        .reveal_vote(a1, &s.agreement_id, &true, &salt(&s.env, 1));
+
+    // Too few votes to resolve, but the tally and penalty can still land.
+    advance_to(&s.env, REVEAL_CLOSES);
+    assert_eq!(
+        s.client.try_resolve_dispute_weighted(&s.agreement_id),
+        Err(Ok(DisputeError::InsufficientVotes))
+    );
+    s.client.tally_votes(&s.agreement_id);
+    s.client.tally_votes(&s.agreement_id);
+    assert_eq!(
+        s.client
+            .get_dispute(&s.agreement_id)
+            .unwrap()
+            .votes_favor_landlord,
+        1
+    );
+    let stats = s.client.get_arbiter_stats(a2);
+    assert_eq!(stats.rating, 0);
+    assert_eq!(stats.missed_reveals, 1);
+    assert_eq!(stats.disputes_resolved, 10);
+
+    // Resetting the rating keeps the missed-reveal record.
+    s.client.set_arbiter_stats(&admin, a2, &50, &10);
+    assert_eq!(s.client.get_arbiter_stats(a2).missed_reveals, 1);
+}
+
+#[test]
+fn test_commit_reveal_configuration() {
+    let s = setup();
+    let admin = s.client.get_state().unwrap().admin;
+
+    // The timeout must also cover the reveal window.
+    let mut config = s.client.get_timeout_config();
+    config.dispute_timeout_days = 18;
+    assert_eq!(
+        s.client.try_set_timeout_config(&admin, &config),
+        Err(Ok(DisputeError::InvalidTimeoutConfig))
+    );
+
+    // Disputes raised with no reveal window vote openly.
+    config.dispute_timeout_days = 30;
+    config.reveal_period_days = 0;
+    s.client.set_timeout_config(&admin, &config);
+    let chioma = s.client.get_state().unwrap().chioma_contract;
+    let landlord = Address::generate(&s.env);
+    let tenant = Address::generate(&s.env);
+    let open_id = String::from_str(&s.env, "agr-open-vote");
+    put_agreement(
+        &s.env,
+        &chioma,
+        &sample_agreement(
+            &s.env,
+            &open_id,
+            &landlord,
+            &tenant,
+            AgreementStatus::Active,
+        ),
+    );
+    s.client
+        .raise_dispute(&tenant, &open_id, &String::from_str(&s.env, "QmOpen"));
+    let dispute = s.client.get_dispute(&open_id).unwrap();
+    assert!(!dispute.commit_reveal);
+    assert_eq!(dispute.reveal_deadline, dispute.voting_deadline);
+
+    advance_to(&s.env, dispute.response_deadline);
+    assert_eq!(
+        s.client
+            .try_commit_vote(&s.arbiters[0], &open_id, &salt(&s.env, 1)),
+        Err(Ok(DisputeError::VotingModeMismatch))
+    );
+    assert_eq!(
+        s.client.try_tally_votes(&open_id),
+        Err(Ok(DisputeError::VotingModeMismatch))
+    );
+    s.client.vote_on_dispute(&s.arbiters[0], &open_id, &true);
+
+    // The earlier dispute keeps the reveal window it was raised with.
+    assert!(s.client.get_dispute(&s.agreement_id).unwrap().commit_reveal);
+}
@@ -0,0 +1,431 @@
+//! Tests for evidence submission and the phased dispute timeline.
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String, Vec,
+};
+
+use crate::dispute::AgreementStatus;
+use crate::storage::DataKey;
+use crate::tests_raise_dispute::{deploy_mock_chioma, put_agreement, sample_agreement};
+use crate::types::{ArbiterStatsV1, DisputeV1, TimeoutConfigV1};
+use crate::{
+    DisputeError, DisputeOutcome, DisputePhase, DisputeResolutionContract,
+    DisputeResolutionContractClient, MAX_EVIDENCE_PER_DISPUTE,
+};
+
+const DAY: u64 = 86_400;
+const RAISED_AT: u64 = 1_000;
+
+struct Setup<'a> {
+    env: Env,
+    client: DisputeResolutionContractClient<'a>,
+    landlord: Address,
+    tenant: Address,
+    arbiter: Address,
+    agreement_id: String,
+}
+
+/// A dispute raised by the tenant at `RAISED_AT` under the default timeline
+/// (7 days of evidence, 3 of responses, 7 of voting).
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|ledger| ledger.timestamp = RAISED_AT);
+
+    let client =
+        DisputeResolutionContractClient::new(&env, &env.register(DisputeResolutionContract, ()));
+    let admin = Address::generate(&env);
+    let chioma = deploy_mock_chioma(&env);
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+
+    let agreement_id = String::from_str(&env, "agr-evidence-1");
+    let agreement = sample_agreement(
+        &env,
+        &agreement_id,
+        &landlord,
+        &tenant,
+        AgreementStatus::Active,
+    );
+    put_agreement(&env, &chioma, &agreement);
+
+    client.initialize(&admin, &1, &chioma);
+    client.add_arbiter(&admin, &arbiter);
+    client.raise_dispute(&tenant, &agreement_id, &String::from_str(&env, "QmDetails"));
+
+    Setup {
+        env,
+        client,
+        landlord,
+        tenant,
+        arbiter,
+        agreement_id,
+    }
+}
+
+fn advance_to(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|ledger| ledger.timestamp = timestamp);
+}
+
+fn text(s: &Setup, value: &str) -> String {
+    String::from_str(&s.env, value)
+}
+
+#[test]
+fn test_dispute_moves_through_phases() {
+    let s = setup();
+    let dispute = s.client.get_dispute(&s.agreement_id).unwrap();
+    assert_eq!(dispute.landlord, s.landlord);
+    assert_eq!(dispute.tenant, s.tenant);
+    assert_eq!(dispute.evidence_deadline, RAISED_AT + 7 * DAY);
+    assert_eq!(dispute.response_deadline, RAISED_AT + 10 * DAY);
+    assert_eq!(dispute.voting_deadline, RAISED_AT + 17 * DAY);
+
+    // Evidence window: both parties submit, responses and votes are rejected.
+    assert_eq!(
+        s.client.get_dispute_phase(&s.agreement_id),
+        DisputePhase::Evidence
+    );
+    s.client.submit_evidence(
+        &s.tenant,
+        &s.agreement_id,
+        &text(&s, "QmPhotos"),
+        &text(&s, "Move-in photos"),
+    );
+    s.client.submit_evidence(
+        &s.tenant,
+        &s.agreement_id,
+        &text(&s, "QmReceipts"),
+        &text(&s, "Repair receipts"),
+    );
+    s.client.submit_evidence(
+        &s.landlord,
+        &s.agreement_id,
+        &text(&s, "QmInspection"),
+        &text(&s, "Move-out inspection"),
+    );
+    assert_eq!(
+        s.client.try_submit_response(
+            &s.landlord,
+            &s.agreement_id,
+            &text(&s, "QmEarly"),
+            &text(&s, "Too early"),
+        ),
+        Err(Ok(DisputeError::InvalidDisputePhase))
+    );
+    assert_eq!(
+        s.client
+            .try_vote_on_dispute(&s.arbiter, &s.agreement_id, &true),
+        Err(Ok(DisputeError::InvalidDisputePhase))
+    );
+
+    // Response window.
+    advance_to(&s.env, RAISED_AT + 7 * DAY);
+    assert_eq!(
+        s.client.get_dispute_phase(&s.agreement_id),
+        DisputePhase::Response
+    );
+    assert_eq!(
+        s.client.try_submit_evidence(
+            &s.tenant,
+            &s.agreement_id,
+            &text(&s, "QmLate"),
+            &text(&s, "Late evidence"),
+        ),
+        Err(Ok(DisputeError::InvalidDisputePhase))
+    );
+    s.client.submit_response(
+        &s.tenant,
+        &s.agreement_id,
+        &text(&s, "QmRebuttal"),
+        &text(&s, "Damage predates tenancy"),
+    );
+    assert_eq!(
+        s.client.try_vote_on_dispute_weighted(
+            &s.arbiter,
+            &s.agreement_id,
+            &DisputeOutcome::FavorTenant
+        ),
+        Err(Ok(DisputeError::InvalidDisputePhase))
+    );
+
+    let evidence = s.client.get_evidence(&s.agreement_id);
+    assert_eq!(evidence.len(), 4);
+    let first = evidence.get(0).unwrap();
+    assert_eq!(first.submitter, s.tenant);
+    assert_eq!(first.evidence_hash, text(&s, "QmPhotos"));
+    assert_eq!(first.phase, DisputePhase::Evidence);
+    assert_eq!(evidence.get(2).unwrap().submitter, s.landlord);
+    let response = evidence.get(3).unwrap();
+    assert_eq!(response.phase, DisputePhase::Response);
+    assert_eq!(response.submitted_at, RAISED_AT + 7 * DAY);
+
+    // Voting window.
+    advance_to(&s.env, RAISED_AT + 10 * DAY);
+    assert_eq!(
+        s.client.get_dispute_phase(&s.agreement_id),
+        DisputePhase::Voting
+    );
+    assert_eq!(
+        s.client.try_submit_response(
+            &s.landlord,
+            &s.agreement_id,
+            &text(&s, "QmLate"),
+            &text(&s, "Late response"),
+        ),
+        Err(Ok(DisputeError::InvalidDisputePhase))
+    );
+    s.client
+        .vote_on_dispute(&s.arbiter, &s.agreement_id, &false);
+
+    // Votes are still accepted after the voting window, until resolution.
+    advance_to(&s.env, RAISED_AT + 17 * DAY);
+    assert_eq!(
+        s.client.get_dispute_phase(&s.agreement_id),
+        DisputePhase::Closed
+    );
+    let late_arbiter = Address::generate(&s.env);
+    let admin = s.client.get_state().unwrap().admin;
+    s.client.add_arbiter(&admin, &late_arbiter);
+    s.client
+        .vote_on_dispute(&late_arbiter, &s.agreement_id, &true);
+    assert_eq!(
+        s.client.resolve_dispute(&s.agreement_id),
+        DisputeOutcome::FavorTenant
+    );
+    assert_eq!(
+        s.client.get_dispute_phase(&s.agreement_id),
+        DisputePhase::Resolved
+    );
+}
+
+#[test]
+fn test_evidence_submission_checks() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_submit_evidence(
+            &Address::generate(&s.env),
+            &s.agreement_id,
+            &text(&s, "QmStranger"),
+            &text(&s, "Not a party"),
+        ),
+        Err(Ok(DisputeError::Unauthorized))
+    );
+    assert_eq!(
+        s.client.try_submit_evidence(
+            &s.tenant,
+            &s.agreement_id,
+            &text(&s, ""),
+            &text(&s, "No hash"),
+        ),
+        Err(Ok(DisputeError::InvalidDetailsHash))
+    );
+    assert_eq!(
+        s.client.try_submit_evidence(
+            &s.tenant,
+            &text(&s, "missing"),
+            &text(&s, "QmMissing"),
+            &text(&s, "No dispute"),
+        ),
+        Err(Ok(DisputeError::DisputeNotFound))
+    );
+    assert_eq!(
+        s.client.try_get_dispute_phase(&text(&s, "missing")),
+        Err(Ok(DisputeError::DisputeNotFound))
+    );
+
+    for i in 0..MAX_EVIDENCE_PER_DISPUTE {
+        // Stay under the per-block rate limit.
+        s.env.ledger().with_mut(|ledger| ledger.sequence_number = i);
+        let submitter = if i % 2 == 0 { &s.tenant } else { &s.landlord };
+        s.client.submit_evidence(
+            submitter,
+            &s.agreement_id,
+            &text(&s, "QmBulk"),
+            &text(&s, "Bulk evidence"),
+        );
+    }
+    s.env
+        .ledger()
+        .with_mut(|ledger| ledger.sequence_number = MAX_EVIDENCE_PER_DISPUTE);
+    assert_eq!(
+        s.client.try_submit_evidence(
+            &s.landlord,
+            &s.agreement_id,
+            &text(&s, "QmOneTooMany"),
+            &text(&s, "Over the limit"),
+        ),
+        Err(Ok(DisputeError::EvidenceLimitReached))
+    );
+}
+
+#[test]
+fn test_timeout_config_must_cover_phases() {
+    let s = setup();
+    let admin = s.client.get_state().unwrap().admin;
+    let mut config = s.client.get_timeout_config();
+    assert_eq!(config.evidence_period_days, 7);
+    assert_eq!(config.response_period_days, 3);
+    assert_eq!(config.voting_period_days, 7);
+
+    config.dispute_timeout_days = 16;
+    assert_eq!(
+        s.client.try_set_timeout_config(&admin, &config),
+        Err(Ok(DisputeError::InvalidTimeoutConfig))
+    );
+    config.dispute_timeout_days = 30;
+    config.response_period_days = 0;
+    assert_eq!(
+        s.client.try_set_timeout_config(&admin, &config),
+        Err(Ok(DisputeError::InvalidTimeoutConfig))
+    );
+
+    // New durations apply to disputes raised afterwards only.
+    config.evidence_period_days = 2;
+    config.response_period_days = 1;
+    config.voting_period_days = 2;
+    s.client.set_timeout_config(&admin, &config);
+    assert_eq!(
+        s.client
+            .get_dispute(&s.agreement_id)
+            .unwrap()
+            .evidence_deadline,
+        RAISED_AT + 7 * DAY
+    );
+}
+
+#[test]
+fn test_timeout_config_must_cover_open_disputes() {
+    let s = setup();
+    let admin = s.client.get_state().unwrap().admin;
+    let mut config = s.client.get_timeout_config();
+    config.dispute_timeout_days = 10;
+    config.evidence_period_days = 2;
+    config.response_period_days = 1;
+    config.voting_period_days = 2;
+
+    // The open dispute's voting window runs until day 17.
+    assert_eq!(
+        s.client.try_set_timeout_config(&admin, &config),
+        Err(Ok(DisputeError::InvalidTimeoutConfig))
+    );
+
+    advance_to(&s.env, RAISED_AT + 10 * DAY);
+    s.client.vote_on_dispute(&s.arbiter, &s.agreement_id, &true);
+    s.client.resolve_dispute(&s.agreement_id);
+    s.client.set_timeout_config(&admin, &config);
+}
+
+#[test]
+fn test_timeout_waits_for_reveal_deadline() {
+    let s = setup();
+    let admin = s.client.get_state().unwrap().admin;
+
+    // A dispute raised before open disputes were tracked escapes the config
+    // check, so the timeout itself must respect its deadlines.
+    let mut dispute = s.client.get_dispute(&s.agreement_id).unwrap();
+    let untracked = text(&s, "agr-untracked");
+    dispute.agreement_id = untracked.clone();
+    s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Dispute(untracked.clone()), &dispute);
+    });
+    advance_to(&s.env, RAISED_AT + 10 * DAY);
+    s.client.vote_on_dispute(&s.arbiter, &s.agreement_id, &true);
+    s.client.resolve_dispute(&s.agreement_id);
+
+    let mut config = s.client.get_timeout_config();
+    config.dispute_timeout_days = 10;
+    config.evidence_period_days = 2;
+    config.response_period_days = 1;
+    config.voting_period_days = 2;
+    s.client.set_timeout_config(&admin, &config);
+
+    advance_to(&s.env, RAISED_AT + 17 * DAY);
+    assert_eq!(
+        s.client.try_resolve_dispute_on_timeout(&untracked),
+        Err(Ok(DisputeError::TimeoutNotReached))
+    );
+    advance_to(&s.env, RAISED_AT + 17 * DAY + 1);
+    assert_eq!(
+        s.client.resolve_dispute_on_timeout(&untracked),
+        DisputeOutcome::FavorTenant
+    );
+}
+
+#[test]
+fn test_legacy_records_upgrade_on_read() {
+    let s = setup();
+    let legacy_id = text(&s, "agr-legacy");
+    let legacy = DisputeV1 {
+        agreement_id: legacy_id.clone(),
+        details_hash: text(&s, "QmLegacy"),
+        raised_at: RAISED_AT,
+        resolved: false,
+        resolved_at: None,
+        votes_favor_landlord: 0,
+        votes_favor_tenant: 0,
+        voters: Vec::new(&s.env),
+    };
+    s.env.as_contract(&s.client.address, || {
+        let storage = s.env.storage();
+        storage
+            .persistent()
+            .set(&DataKey::Dispute(legacy_id.clone()), &legacy);
+        storage.persistent().set(
+            &DataKey::ArbiterStats(s.arbiter.clone()),
+            &ArbiterStatsV1 {
+                rating: 80,
+                disputes_resolved: 4,
+            },
+        );
+        storage.instance().set(
+            &DataKey::TimeoutConfig,
+            &TimeoutConfigV1 {
+                escrow_timeout_days: 10,
+                dispute_timeout_days: 20,
+                payment_timeout_days: 5,
+            },
+        );
+    });
+
+    let config = s.client.get_timeout_config();
+    assert_eq!(config.dispute_timeout_days, 20);
+    assert_eq!(config.evidence_period_days, 7);
+    assert_eq!(config.reveal_period_days, 0);
+
+    let stats = s.client.get_arbiter_stats(&s.arbiter);
+    assert_eq!(stats.rating, 80);
+    assert_eq!(stats.disputes_resolved, 4);
+    assert_eq!(stats.missed_reveals, 0);
+
+    // Legacy disputes keep their old behaviour: votable straight away and
+    // timed out by the configured timeout alone.
+    let dispute = s.client.get_dispute(&legacy_id).unwrap();
+    assert!(!dispute.commit_reveal);
+    assert_eq!(dispute.voting_deadline, RAISED_AT);
+    assert_eq!(s.client.get_dispute_phase(&legacy_id), DisputePhase::Closed);
+    s.client.vote_on_dispute(&s.arbiter, &legacy_id, &true);
+    assert_eq!(
+        s.client
+            .get_dispute(&legacy_id)
+            .unwrap()
+            .votes_favor_landlord,
+        1
+    );
+
+    advance_to(&s.env, RAISED_AT + 20 * DAY);
+    assert_eq!(
+        s.client.try_resolve_dispute_on_timeout(&legacy_id),
+        Err(Ok(DisputeError::TimeoutNotReached))
+    );
+    advance_to(&s.env, RAISED_AT + 20 * DAY + 1);
+    assert_eq!(
+        s.client.resolve_dispute_on_timeout(&legacy_id),
+        DisputeOutcome::FavorLandlord
+    );
+}
@@ -2,7 +2,11 @@ use crate::dispute::{AgreementStatus, RentAgreement};
 use crate::{
     DisputeError, DisputeOutcome, DisputeResolutionContract, DisputeResolutionContractClient,
 };
-use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env, Map, String};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    Address, Env, Map, String,
+};
 
 fn create_contract(env: &Env) -> DisputeResolutionContractClient<'_> {
     let contract_id = env.register(DisputeResolutionContract, ());
@@ -24,11 +28,11 @@ impl MockChiomaContract {
     }
 }
 
-fn deploy_mock_chioma(env: &Env) -> Address {
+pub(crate) fn deploy_mock_chioma(env: &Env) -> Address {
     env.register(MockChiomaContract, ())
 }
 
-fn put_agreement(env: &Env, chioma: &Address, agreement: &RentAgreement) {
+pub(crate) fn put_agreement(env: &Env, chioma: &Address, agreement: &RentAgreement) {
     env.as_contract(chioma, || {
         env.storage()
             .instance()
@@ -36,7 +40,7 @@ fn put_agreement(env: &Env, chioma: &Address, agreement: &RentAgreement) {
     });
 }
 
-fn sample_agreement(
+pub(crate) fn sample_agreement(
     env: &Env,
     agreement_id: &String,
     landlord: &Address,
@@ -64,6 +68,13 @@ fn sample_agreement(
     }
 }
 
+/// Advance the ledger past the evidence and response windows.
+fn open_voting(env: &Env, client: &DisputeResolutionContractClient, agreement_id: &String) {
+    let dispute = client.get_dispute(agreement_id).unwrap();
+    env.ledger()
+        .with_mut(|ledger| ledger.timestamp = dispute.response_deadline);
+}
+
 #[test]
 fn raise_dispute_success_cross_contract_tenant() {
     let env = Env::default();
@@ -292,6 +303,7 @@ fn vote_on_dispute_happy_path_after_raise_dispute() {
     let details_hash = String::from_str(&env, "QmDetails");
     let raise = client.try_raise_dispute(&tenant, &agreement_id, &details_hash);
     assert_eq!(raise, Ok(Ok(())));
+    open_voting(&env, &client, &agreement_id);
 
     let vote = client.try_vote_on_dispute(&arbiter, &agreement_id, &true);
     assert_eq!(vote, Ok(Ok(())));
@@ -334,6 +346,7 @@ fn resolve_dispute_favor_landlord_after_raise_dispute() {
     let details_hash = String::from_str(&env, "QmDetails");
     let raise = client.try_raise_dispute(&tenant, &agreement_id, &details_hash);
     assert_eq!(raise, Ok(Ok(())));
+    open_voting(&env, &client, &agreement_id);
 
     assert_eq!(
         client.try_vote_on_dispute(&a1, &agreement_id, &true),
@@ -381,6 +394,7 @@ fn resolve_dispute_insufficient_votes_after_raise_dispute() {
     let details_hash = String::from_str(&env, "QmDetails");
     let raise = client.try_raise_dispute(&tenant, &agreement_id, &details_hash);
     assert_eq!(raise, Ok(Ok(())));
+    open_voting(&env, &client, &agreement_id);
 
     assert_eq!(
         client.try_vote_on_dispute(&a1, &agreement_id, &true),
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, String, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Env, String, Vec};
 
 // ── Weighted Voting Types ──────────────────────────────────────────────────
 
@@ -10,6 +10,27 @@ pub struct ArbiterStats {
     pub rating: u32,
     /// Total disputes the arbiter has resolved; drives experience_multiplier
     pub disputes_resolved: u32,
+    /// Vote commitments the arbiter never revealed; each one also costs rating
+    pub missed_reveals: u32,
+}
+
+/// `ArbiterStats` as stored before missed reveals were tracked. Only read
+/// from storage, and upgraded to `ArbiterStats` on load.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArbiterStatsV1 {
+    pub rating: u32,
+    pub disputes_resolved: u32,
+}
+
+impl From<ArbiterStatsV1> for ArbiterStats {
+    fn from(old: ArbiterStatsV1) -> Self {
+        ArbiterStats {
+            rating: old.rating,
+            disputes_resolved: old.disputes_resolved,
+            missed_reveals: 0,
+        }
+    }
 }
 
 /// Computed voting weight for an arbiter.
@@ -49,6 +70,22 @@ pub struct WeightedDisputeVotes {
     pub voters: Vec<Address>,
 }
 
+// ── Commit-Reveal Types ────────────────────────────────────────────────────
+
+/// A sealed arbiter vote in a commit-reveal dispute.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteCommitment {
+    /// sha256(vote || salt), where vote is 1 to favor the landlord, 0 the tenant
+    pub commitment: BytesN<32>,
+    pub committed_at: u64,
+    /// The revealed vote; counted only once the reveal window closes
+    pub favor_landlord: Option<bool>,
+    /// Voting weight at reveal time
+    pub weight: u32,
+    pub revealed_at: Option<u64>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DisputeOutcome {
@@ -69,8 +106,40 @@ pub struct ContractState {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TimeoutConfig {
     pub escrow_timeout_days: u64,
+    /// Measured from when the dispute is raised; must cover every phase
     pub dispute_timeout_days: u64,
     pub payment_timeout_days: u64,
+    /// Length of the window in which both parties submit evidence
+    pub evidence_period_days: u64,
+    /// Length of the window in which parties respond to each other's evidence
+    pub response_period_days: u64,
+    /// Length of the window in which arbiters vote
+    pub voting_period_days: u64,
+    /// Length of the window in which arbiters reveal committed votes. When
+    /// non-zero, new disputes use commit-reveal voting.
+    pub reveal_period_days: u64,
+}
+
+/// `TimeoutConfig` as stored before disputes had phases. Only read from
+/// storage, and upgraded to `TimeoutConfig` on load.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimeoutConfigV1 {
+    pub escrow_timeout_days: u64,
+    pub dispute_timeout_days: u64,
+    pub payment_timeout_days: u64,
+}
+
+impl TimeoutConfigV1 {
+    /// Keeps the stored timeouts and fills the phase windows with `defaults`.
+    pub fn upgrade(self, defaults: TimeoutConfig) -> TimeoutConfig {
+        TimeoutConfig {
+            escrow_timeout_days: self.escrow_timeout_days,
+            dispute_timeout_days: self.dispute_timeout_days,
+            payment_timeout_days: self.payment_timeout_days,
+            ..defaults
+        }
+    }
 }
 
 #[contracttype]
@@ -81,9 +150,66 @@ pub struct Arbiter {
     pub active: bool,
 }
 
+/// Stage of a dispute's timeline, derived from its deadlines.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputePhase {
+    /// Both parties may submit evidence
+    Evidence,
+    /// Both parties may respond to the evidence submitted
+    Response,
+    /// Arbiters may vote, or commit votes in commit-reveal disputes
+    Voting,
+    /// Arbiters reveal their committed votes (commit-reveal disputes only)
+    Reveal,
+    /// Voting has ended and the dispute awaits resolution
+    Closed,
+    Resolved,
+}
+
+/// An evidence or response submission by a party to a dispute.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Evidence {
+    pub submitter: Address,
+    /// Hash reference to the off-chain evidence (IPFS, etc.)
+    pub evidence_hash: String,
+    pub description: String,
+    /// `Evidence` for evidence, `Response` for responses
+    pub phase: DisputePhase,
+    pub submitted_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Dispute {
+    pub agreement_id: String,
+    pub details_hash: String,
+    pub landlord: Address,
+    pub tenant: Address,
+    pub raised_at: u64,
+    /// End of the evidence window
+    pub evidence_deadline: u64,
+    /// End of the response window
+    pub response_deadline: u64,
+    /// End of the voting window
+    pub voting_deadline: u64,
+    /// Whether arbiters commit and later reveal their votes
+    pub commit_reveal: bool,
+    /// End of the reveal window; equal to `voting_deadline` without commit-reveal
+    pub reveal_deadline: u64,
+    pub resolved: bool,
+    pub resolved_at: Option<u64>,
+    pub votes_favor_landlord: u32,
+    pub votes_favor_tenant: u32,
+    pub voters: Vec<Address>,
+}
+
+/// `Dispute` as stored before disputes had parties, phases and commit-reveal
+/// voting. Only read from storage, and upgraded to `Dispute` on load.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeV1 {
     pub agreement_id: String,
     pub details_hash: String,
     pub raised_at: u64,
@@ -94,7 +220,52 @@ pub struct Dispute {
     pub voters: Vec<Address>,
 }
 
+impl DisputeV1 {
+    /// Legacy disputes could be voted on from the moment they were raised, so
+    /// every deadline is `raised_at` and the dispute is already `Closed`.
+    /// Their parties were never recorded; the contract's own address stands
+    /// in for both, which no party can authorize as, so no evidence can be
+    /// added to them.
+    pub fn upgrade(self, env: &Env) -> Dispute {
+        let placeholder = env.current_contract_address();
+        Dispute {
+            agreement_id: self.agreement_id,
+            details_hash: self.details_hash,
+            landlord: placeholder.clone(),
+            tenant: placeholder,
+            raised_at: self.raised_at,
+            evidence_deadline: self.raised_at,
+            response_deadline: self.raised_at,
+            voting_deadline: self.raised_at,
+            commit_reveal: false,
+            reveal_deadline: self.raised_at,
+            resolved: self.resolved,
+            resolved_at: self.resolved_at,
+            votes_favor_landlord: self.votes_favor_landlord,
+            votes_favor_tenant: self.votes_favor_tenant,
+            voters: self.voters,
+        }
+    }
+}
+
 impl Dispute {
+    /// Phase of the dispute at timestamp `now`.
+    pub fn phase(&self, now: u64) -> DisputePhase {
+        if self.resolved {
+            DisputePhase::Resolved
+        } else if now < self.evidence_deadline {
+            DisputePhase::Evidence
+        } else if now < self.response_deadline {
+            DisputePhase::Response
+        } else if now < self.voting_deadline {
+            DisputePhase::Voting
+        } else if now < self.reveal_deadline {
+            DisputePhase::Reveal
+        } else {
+            DisputePhase::Closed
+        }
+    }
+
     pub fn get_outcome(&self) -> Option<DisputeOutcome> {
         if !self.resolved {
             return None;
@@ -4,13 +4,17 @@
 use soroban_sdk::{Address, Env};
 
 use crate::errors::EscrowError;
+use crate::freeze::FreezeHandler;
 use crate::storage::EscrowStorage;
-use crate::types::Escrow;
+use crate::types::{Escrow, SignerSet};
 
 /// Access control validation functions.
 pub struct AccessControl;
 
 impl AccessControl {
+    /// Upper bound on the number of signers in a signer set.
+    pub const MAX_SIGNERS: u32 = 20;
+
     /// Verify caller is the depositor (tenant).
     pub fn is_depositor(escrow: &Escrow, caller: &Address) -> Result<(), EscrowError> {
         if escrow.depositor == *caller {
@@ -48,6 +52,45 @@ impl AccessControl {
         }
     }
 
+    /// Verify caller is in the escrow's signer set and return its weight.
+    pub fn signer_weight(env: &Env, escrow: &Escrow, caller: &Address) -> Result<u32, EscrowError> {
+        EscrowStorage::get_signer_set(env, escrow)
+            .signers
+            .iter()
+            .find(|signer| signer.address == *caller)
+            .map(|signer| signer.weight)
+            .ok_or(EscrowError::InvalidSigner)
+    }
+
+    /// Validate a signer set: 1 to `MAX_SIGNERS` distinct signers with
+    /// non-zero weights and a threshold no greater than their total weight.
+    pub fn validate_signer_set(signer_set: &SignerSet) -> Result<(), EscrowError> {
+        let signers = &signer_set.signers;
+        if signers.is_empty() || signers.len() > Self::MAX_SIGNERS || signer_set.threshold == 0 {
+            return Err(EscrowError::InvalidSignerSet);
+        }
+
+        let mut total_weight: u32 = 0;
+        for (index, signer) in signers.iter().enumerate() {
+            if signer.weight == 0 {
+                return Err(EscrowError::InvalidSignerSet);
+            }
+            let repeated = signers
+                .iter()
+                .skip(index + 1)
+                .any(|other| other.address == signer.address);
+            if repeated {
+                return Err(EscrowError::InvalidSignerSet);
+            }
+            total_weight = total_weight.saturating_add(signer.weight);
+        }
+
+        if signer_set.threshold > total_weight {
+            return Err(EscrowError::InvalidSignerSet);
+        }
+        Ok(())
+    }
+
     /// Verify caller is either depositor or beneficiary (the two primary parties).
     /// Used for dispute initiation.
     pub fn is_primary_party(escrow: &Escrow, caller: &Address) -> Result<(), EscrowError> {
@@ -69,10 +112,27 @@ impl AccessControl {
         }
     }
 
+    /// When the escrow's freeze lapses. Freezes recorded before time-boxing
+    /// have no `frozen_until` and lapse `max_freeze_days` after `frozen_at`.
+    pub fn freeze_expiry(env: &Env, escrow: &Escrow) -> u64 {
+        escrow.frozen_until.unwrap_or_else(|| {
+            escrow
+                .frozen_at
+                .unwrap_or_default()
+                .saturating_add(FreezeHandler::freeze_duration(env))
+        })
+    }
+
+    /// Whether a freeze is in force. A freeze lapses on its own at its
+    /// expiry, without the escrow being updated.
+    pub fn is_frozen(env: &Env, escrow: &Escrow) -> bool {
+        escrow.is_frozen && env.ledger().timestamp() < Self::freeze_expiry(env, escrow)
+    }
+
     /// Verify escrow is not frozen.
     /// Most operations should check this before proceeding.
-    pub fn require_not_frozen(escrow: &Escrow) -> Result<(), EscrowError> {
-        if escrow.is_frozen {
+    pub fn require_not_frozen(env: &Env, escrow: &Escrow) -> Result<(), EscrowError> {
+        if Self::is_frozen(env, escrow) {
             Err(EscrowError::EscrowFrozen)
         } else {
             Ok(())
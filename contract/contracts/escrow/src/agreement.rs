@@ -0,0 +1,203 @@
+//! Escrows for chioma rental agreements.
+//! An agreement escrow is checked against the agreement when it is created:
+//! the tenant deposits the agreement's security deposit, in its payment
+//! token, for the landlord. Its ID is derived from the agreement ID and a
+//! per-agreement nonce, and every status change is reported back to chioma
+//! as the agreement's deposit status.
+use soroban_sdk::{contractclient, contracttype, xdr::ToXdr, Address, BytesN, Env, String};
+
+use crate::errors::EscrowError;
+use crate::events;
+use crate::storage::EscrowStorage;
+use crate::types::{AgreementLink, Escrow, EscrowStatus};
+
+/// Mirror of chioma's `AgreementStatus`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AgreementStatus {
+    Draft,
+    Pending,
+    PendingApproval,
+    Active,
+    Completed,
+    Cancelled,
+    Terminated,
+    Disputed,
+}
+
+/// Mirror of chioma's `DepositTerms`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositTerms {
+    pub agreement_id: String,
+    pub landlord: Address,
+    pub tenant: Address,
+    pub security_deposit: i128,
+    pub payment_token: Address,
+    pub status: AgreementStatus,
+}
+
+/// Mirror of chioma's `DepositStatus`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DepositStatus {
+    Pending,
+    Held,
+    Disputed,
+    Released,
+    Refunded,
+}
+
+/// Mirror of chioma's `CompoundingFrequency`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CompoundingFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+    Annually,
+}
+
+/// Mirror of chioma's `InterestRecipient`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InterestRecipient {
+    Tenant,
+    Landlord,
+    Split,
+}
+
+/// Mirror of chioma's `DepositInterestConfig`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositInterestConfig {
+    pub agreement_id: String,
+    pub annual_rate: u32,
+    pub compounding_frequency: CompoundingFrequency,
+    pub interest_recipient: InterestRecipient,
+}
+
+/// Subset of the chioma contract used by agreement escrows.
+#[allow(dead_code)]
+#[contractclient(name = "ChiomaClient")]
+pub trait Chioma {
+    fn get_deposit_terms(env: Env, agreement_id: String) -> Option<DepositTerms>;
+    fn update_deposit_status(
+        env: Env,
+        agreement_id: String,
+        escrow_id: BytesN<32>,
+        status: DepositStatus,
+    );
+    fn get_deposit_interest_config(env: Env, agreement_id: String) -> DepositInterestConfig;
+}
+
+/// Agreement escrow handling.
+pub struct AgreementHandler;
+
+impl AgreementHandler {
+    /// Check a new escrow against its agreement and derive its ID.
+    /// Returns the link to store once the escrow has been created.
+    ///
+    /// CHECKS:
+    /// - Chioma contract must be configured and know the agreement
+    /// - Agreement must not be completed, cancelled, terminated or disputed
+    /// - Depositor, beneficiary, amount and token must match the agreement
+    /// - The agreement's previous escrow, if any, must be settled
+    pub(crate) fn prepare(
+        env: &Env,
+        agreement_id: &String,
+        depositor: &Address,
+        beneficiary: &Address,
+        amount: i128,
+        token: &Address,
+    ) -> Result<(BytesN<32>, AgreementLink), EscrowError> {
+        let chioma_contract =
+            EscrowStorage::get_chioma_contract(env).ok_or(EscrowError::ChiomaContractNotSet)?;
+        let terms = ChiomaClient::new(env, &chioma_contract)
+            .get_deposit_terms(agreement_id)
+            .ok_or(EscrowError::AgreementNotFound)?;
+
+        match terms.status {
+            AgreementStatus::Draft
+            | AgreementStatus::Pending
+            | AgreementStatus::PendingApproval
+            | AgreementStatus::Active => {}
+            _ => return Err(EscrowError::InvalidState),
+        }
+        if *depositor != terms.tenant
+            || *beneficiary != terms.landlord
+            || amount != terms.security_deposit
+            || *token != terms.payment_token
+        {
+            return Err(EscrowError::AgreementMismatch);
+        }
+
+        if let Some(previous) = EscrowStorage::get_agreement_escrow(env, agreement_id)
+            .and_then(|escrow_id| EscrowStorage::get(env, &escrow_id))
+        {
+            match previous.status {
+                EscrowStatus::Released | EscrowStatus::Refunded => {}
+                _ => return Err(EscrowError::InvalidState),
+            }
+        }
+
+        let nonce = EscrowStorage::get_agreement_nonce(env, agreement_id);
+        let mut data = soroban_sdk::Bytes::new(env);
+        data.append(&agreement_id.clone().to_xdr(env));
+        data.append(&nonce.to_xdr(env));
+        let escrow_id: BytesN<32> = env.crypto().sha256(&data).into();
+
+        let link = AgreementLink {
+            agreement_id: agreement_id.clone(),
+            chioma_contract,
+            nonce,
+        };
+        Ok((escrow_id, link))
+    }
+
+    /// Store the link for a newly created escrow and report it to chioma.
+    pub(crate) fn link(env: &Env, escrow: &Escrow, link: &AgreementLink) {
+        EscrowStorage::set_agreement_link(env, &escrow.id, link);
+        Self::sync_deposit_status(env, escrow);
+    }
+
+    /// Who receives yield earned on an escrow's balance: the agreement's
+    /// `InterestRecipient`, or the tenant when the escrow has no agreement or
+    /// the agreement has no interest configuration.
+    pub(crate) fn interest_recipient(env: &Env, escrow: &Escrow) -> InterestRecipient {
+        let Some(link) = EscrowStorage::get_agreement_link(env, &escrow.id) else {
+            return InterestRecipient::Tenant;
+        };
+        match ChiomaClient::new(env, &link.chioma_contract)
+            .try_get_deposit_interest_config(&link.agreement_id)
+        {
+            Ok(Ok(config)) => config.interest_recipient,
+            _ => InterestRecipient::Tenant,
+        }
+    }
+
+    /// Report an agreement escrow's status to chioma. Escrows not created for
+    /// an agreement are ignored. A rejected report only emits
+    /// `deposit_sync_failed`, so chioma can never block the escrow's funds.
+    pub(crate) fn sync_deposit_status(env: &Env, escrow: &Escrow) {
+        let Some(link) = EscrowStorage::get_agreement_link(env, &escrow.id) else {
+            return;
+        };
+        let status = match escrow.status {
+            EscrowStatus::Pending => DepositStatus::Pending,
+            EscrowStatus::Funded => DepositStatus::Held,
+            EscrowStatus::Disputed => DepositStatus::Disputed,
+            EscrowStatus::Released => DepositStatus::Released,
+            EscrowStatus::Refunded => DepositStatus::Refunded,
+        };
+        let result = ChiomaClient::new(env, &link.chioma_contract).try_update_deposit_status(
+            &link.agreement_id,
+            &escrow.id,
+            &status,
+        );
+        if !matches!(result, Ok(Ok(()))) {
+            events::deposit_sync_failed(env, escrow.id.clone(), link.agreement_id, status);
+        }
+    }
+}
@@ -1,12 +1,18 @@
 //! Dispute resolution and admin override for the Escrow contract.
 //! Allows either party to freeze funds and requires admin to resolve.
-use soroban_sdk::{token, Address, BytesN, Env, String};
+use soroban_sdk::{token, Address, BytesN, Env, String, Vec};
 
 use crate::access::AccessControl;
+use crate::agreement::AgreementHandler;
 use crate::errors::EscrowError;
+use crate::escrow_index;
 use crate::events;
 use crate::storage::EscrowStorage;
-use crate::types::EscrowStatus;
+use crate::types::{Allocation, AllocationUnit, Escrow, EscrowStatus, ReleaseRecord};
+use crate::yield_vault::YieldHandler;
+
+/// Basis points making up the whole remaining balance.
+pub const BPS_DENOMINATOR: i128 = 10_000;
 
 /// Dispute handling and resolution.
 pub struct DisputeHandler;
@@ -51,7 +57,7 @@ impl DisputeHandler {
         }
 
         // EFFECTS: Update status and store reason
-        escrow.status = EscrowStatus::Disputed;
+        escrow_index::set_status(&env, &mut escrow, EscrowStatus::Disputed);
         escrow.disputed_at = Some(env.ledger().timestamp());
         escrow.dispute_reason = Some(reason);
         EscrowStorage::save(&env, &escrow);
@@ -59,30 +65,62 @@ impl DisputeHandler {
         // Freeze funds by clearing all approvals
         EscrowStorage::clear_approvals(&env, &escrow_id);
 
+        AgreementHandler::sync_deposit_status(&env, &escrow);
         Ok(())
     }
 
     /// Resolve a dispute (admin only).
-    /// Admin can release funds to either party or refund to depositor.
+    /// Admin releases the whole remaining balance to either party.
+    /// Shorthand for `resolve_dispute_split` with a single 100% allocation.
+    pub fn resolve_dispute(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        release_to: Address,
+    ) -> Result<(), EscrowError> {
+        let mut allocations = Vec::new(&env);
+        allocations.push_back(Allocation {
+            recipient: release_to,
+            share: BPS_DENOMINATOR,
+        });
+        let reason = String::from_str(&env, "Dispute resolution");
+        Self::resolve_dispute_split(
+            env,
+            escrow_id,
+            caller,
+            AllocationUnit::BasisPoints,
+            allocations,
+            reason,
+        )
+    }
+
+    /// Resolve a dispute by splitting the remaining balance across the parties
+    /// (admin only). Shares are basis points or token amounts depending on
+    /// `unit`; with basis points any rounding remainder goes to the last
+    /// recipient so the whole balance is paid out.
     ///
     /// CHECKS:
     /// - Escrow must exist
     /// - Escrow must be in Disputed state
     /// - Caller must be arbiter
-    /// - Release target must be beneficiary or depositor
+    /// - Reason must not be empty
+    /// - Each recipient must be beneficiary or depositor, listed once
+    /// - Shares must be non-negative and sum to 10_000 bps or the balance
     ///
     /// EFFECTS:
-    /// - Update escrow status to Released or Refunded
-    /// - Clear dispute reason
-    /// - Clear approvals
+    /// - Update escrow status to Released
+    /// - Clear dispute reason and approvals
+    /// - Record a ReleaseRecord for every non-zero payout
     ///
     /// INTERACTIONS:
-    /// - Token transfer would happen after state update
-    pub fn resolve_dispute(
+    /// - One token transfer per non-zero payout after all state updates
+    pub fn resolve_dispute_split(
         env: Env,
         escrow_id: BytesN<32>,
         caller: Address,
-        release_to: Address,
+        unit: AllocationUnit,
+        allocations: Vec<Allocation>,
+        reason: String,
     ) -> Result<(), EscrowError> {
         // CHECKS: Get and validate escrow
         let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
@@ -98,27 +136,120 @@ impl DisputeHandler {
         // Authorize the dispute resolution
         caller.require_auth();
 
-        // Verify release target is valid
-        if release_to != escrow.beneficiary && release_to != escrow.depositor {
-            return Err(EscrowError::InvalidApprovalTarget);
+        if reason.is_empty() {
+            return Err(EscrowError::EmptyReleaseReason);
         }
 
+        // Bring any vault position back into the escrow before splitting it
+        YieldHandler::realize(&env, &mut escrow)?;
+
+        let payouts = Self::allocation_amounts(&env, &escrow, escrow.amount, unit, &allocations)?;
+
         // EFFECTS: Update status and clear dispute
-        escrow.status = EscrowStatus::Released;
+        escrow_index::set_status(&env, &mut escrow, EscrowStatus::Released);
         escrow.disputed_at = None;
         escrow.dispute_reason = None;
         EscrowStorage::save(&env, &escrow);
 
         // Clear approvals
-        EscrowStorage::clear_approvals(&env, &escrow_id);
-
-        // INTERACTIONS: Token transfer from escrow contract to release target
-        let token_client = token::Client::new(&env, &escrow.token);
-        token_client.transfer(&env.current_contract_address(), &release_to, &escrow.amount);
+        EscrowStorage::clear_release_approvals(&env, &escrow);
 
+        Self::pay_out(&env, &escrow, &payouts, &reason);
+        AgreementHandler::sync_deposit_status(&env, &escrow);
         Ok(())
     }
 
+    /// Record every non-zero payout in the release history, then transfer it.
+    pub(crate) fn pay_out(env: &Env, escrow: &Escrow, payouts: &Vec<Allocation>, reason: &String) {
+        let now = env.ledger().timestamp();
+        for payout in payouts.iter().filter(|payout| payout.share > 0) {
+            EscrowStorage::add_release_record(
+                env,
+                &escrow.id,
+                ReleaseRecord {
+                    escrow_id: escrow.id.clone(),
+                    amount: payout.share,
+                    recipient: payout.recipient.clone(),
+                    released_at: now,
+                    reason: reason.clone(),
+                },
+            );
+        }
+
+        // INTERACTIONS: Token transfers from escrow contract to each recipient
+        let token_client = token::Client::new(env, &escrow.token);
+        for payout in payouts.iter().filter(|payout| payout.share > 0) {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &payout.recipient,
+                &payout.share,
+            );
+            events::dispute_payout(env, escrow.id.clone(), payout.recipient, payout.share);
+        }
+    }
+
+    /// Validate an allocation of `balance` between the depositor and the
+    /// beneficiary and convert it to token amounts that sum to `balance`.
+    pub(crate) fn allocation_amounts(
+        env: &Env,
+        escrow: &Escrow,
+        balance: i128,
+        unit: AllocationUnit,
+        allocations: &Vec<Allocation>,
+    ) -> Result<Vec<Allocation>, EscrowError> {
+        if allocations.is_empty() {
+            return Err(EscrowError::InvalidAllocation);
+        }
+
+        let mut recipients: Vec<Address> = Vec::new(env);
+        let mut total: i128 = 0;
+        for allocation in allocations.iter() {
+            if allocation.recipient != escrow.beneficiary
+                && allocation.recipient != escrow.depositor
+            {
+                return Err(EscrowError::InvalidApprovalTarget);
+            }
+            if recipients.contains(&allocation.recipient) {
+                return Err(EscrowError::InvalidAllocation);
+            }
+            if allocation.share < 0 {
+                return Err(EscrowError::InvalidAmount);
+            }
+            recipients.push_back(allocation.recipient.clone());
+            total = total
+                .checked_add(allocation.share)
+                .ok_or(EscrowError::InvalidAllocation)?;
+        }
+
+        let expected = match unit {
+            AllocationUnit::BasisPoints => BPS_DENOMINATOR,
+            AllocationUnit::Amount => balance,
+        };
+        if total != expected {
+            return Err(EscrowError::InvalidAllocation);
+        }
+
+        let last = allocations.len() - 1;
+        let mut paid: i128 = 0;
+        let mut payouts = Vec::new(env);
+        for (index, allocation) in allocations.iter().enumerate() {
+            let amount = if index as u32 == last {
+                balance - paid
+            } else {
+                match unit {
+                    AllocationUnit::BasisPoints => balance * allocation.share / BPS_DENOMINATOR,
+                    AllocationUnit::Amount => allocation.share,
+                }
+            };
+            paid += amount;
+            payouts.push_back(Allocation {
+                recipient: allocation.recipient,
+                share: amount,
+            });
+        }
+        Ok(payouts)
+    }
+
     /// Get dispute information for an escrow.
     /// Returns the dispute reason if escrow is disputed, None otherwise.
     pub fn get_dispute_info(
@@ -154,19 +285,14 @@ impl DisputeHandler {
             return Err(EscrowError::TimeoutNotReached);
         }
 
-        escrow.status = EscrowStatus::Refunded;
+        YieldHandler::realize(&env, &mut escrow)?;
+
+        escrow_index::set_status(&env, &mut escrow, EscrowStatus::Refunded);
         escrow.disputed_at = None;
         escrow.dispute_reason = None;
         EscrowStorage::save(&env, &escrow);
 
-        EscrowStorage::clear_approvals(&env, &escrow_id);
-        let targets = [escrow.beneficiary.clone(), escrow.depositor.clone()];
-        let signers = [
-            escrow.depositor.clone(),
-            escrow.beneficiary.clone(),
-            escrow.arbiter.clone(),
-        ];
-        EscrowStorage::clear_approval_counts(&env, &escrow_id, &targets, &signers);
+        EscrowStorage::clear_release_approvals(&env, &escrow);
 
         let token_client = token::Client::new(&env, &escrow.token);
         token_client.transfer(
@@ -176,6 +302,7 @@ impl DisputeHandler {
         );
 
         events::dispute_timeout(&env, escrow_id);
+        AgreementHandler::sync_deposit_status(&env, &escrow);
         Ok(())
     }
 }
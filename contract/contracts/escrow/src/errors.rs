@@ -50,4 +50,34 @@ pub enum EscrowError {
     EmptyFreezeReason = 21,
     /// System admin not set
     AdminNotSet = 22,
+    /// Dispute allocation is empty, repeats a recipient or does not sum to the balance
+    InvalidAllocation = 23,
+    /// Signer set is empty, repeats a signer, has a zero weight or an unreachable threshold
+    InvalidSignerSet = 24,
+    /// Escrow has no milestone at this index
+    MilestoneNotFound = 25,
+    /// An earlier milestone has not been submitted yet
+    MilestoneOutOfOrder = 26,
+    /// Milestone list is empty or longer than the maximum
+    InvalidMilestones = 27,
+    /// No target increase has been proposed for this escrow
+    TargetIncreaseNotFound = 28,
+    /// Chioma contract has not been configured
+    ChiomaContractNotSet = 29,
+    /// Agreement does not exist in the chioma contract
+    AgreementNotFound = 30,
+    /// Escrow parties, amount or token differ from the agreement
+    AgreementMismatch = 31,
+    /// No yield vault is configured for the escrow's token
+    YieldVaultNotSet = 32,
+    /// Yield vault call failed or returned an invalid amount
+    YieldVaultFailed = 33,
+    /// Page size is zero or larger than the maximum
+    InvalidPageSize = 34,
+    /// Freeze duration is zero or above the cap, or the extension council repeats a member or has an invalid threshold
+    InvalidFreezeConfig = 35,
+    /// Changing the freeze council needs approval from the current council
+    CouncilApprovalRequired = 36,
+    /// Escrow was frozen too recently to be frozen again
+    FreezeCooldownActive = 37,
 }
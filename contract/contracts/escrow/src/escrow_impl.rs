@@ -1,15 +1,26 @@
 //! Core escrow lifecycle logic: creation, funding, approvals, and release.
 //! Implements checks-effects-interactions pattern for reentrancy safety.
-use soroban_sdk::{contract, contractimpl, token, xdr::ToXdr, Address, BytesN, Env, String};
+use soroban_sdk::{contract, contractimpl, token, xdr::ToXdr, Address, BytesN, Env, String, Vec};
 
 use crate::access::AccessControl;
+use crate::agreement::AgreementHandler;
 use crate::dispute::DisputeHandler;
 use crate::errors::EscrowError;
+use crate::escrow_index;
 use crate::events;
+use crate::freeze::FreezeHandler;
+use crate::funding::FundingHandler;
+use crate::milestone::MilestoneHandler;
 use crate::rate_limit;
 use crate::storage::EscrowStorage;
-use crate::types::{Escrow, EscrowStatus, ReleaseApproval, ReleaseRecord, TimeoutConfig};
+use crate::types::{
+    AgreementLink, Allocation, AllocationUnit, Contribution, Escrow, EscrowIndex, EscrowPage,
+    EscrowStatus, FreezeConfig, FreezeRecord, Milestone, MilestoneTerms, ReleaseApproval,
+    ReleaseRecord, SignerSet, TargetIncrease, TimeoutConfig, UnfreezePetition, YieldPosition,
+    YieldRecord,
+};
 use crate::upgrade;
+use crate::yield_vault::YieldHandler;
 
 /// Core escrow contract implementation.
 #[contract]
@@ -59,10 +70,41 @@ impl EscrowContract {
         data.append(&amount.to_xdr(&env));
         data.append(&token.clone().to_xdr(&env));
         data.append(&env.ledger().timestamp().to_xdr(&env));
+        data.append(&EscrowStorage::get_count(&env).to_xdr(&env));
 
         let escrow_id: BytesN<32> = env.crypto().sha256(&data).into();
 
         // EFFECTS: Create and save escrow
+        Self::insert(
+            &env,
+            escrow_id,
+            depositor,
+            beneficiary,
+            arbiter,
+            platform_governance,
+            agent_referral,
+            amount,
+            token,
+        )
+        .map(|escrow| escrow.id)
+    }
+
+    /// Store a new Pending escrow under `escrow_id`.
+    fn insert(
+        env: &Env,
+        escrow_id: BytesN<32>,
+        depositor: Address,
+        beneficiary: Address,
+        arbiter: Address,
+        platform_governance: Address,
+        agent_referral: Address,
+        amount: i128,
+        token: Address,
+    ) -> Result<Escrow, EscrowError> {
+        if EscrowStorage::get(env, &escrow_id).is_some() {
+            return Err(EscrowError::InvalidEscrowId);
+        }
+
         let escrow = Escrow {
             id: escrow_id.clone(),
             depositor: depositor.clone(),
@@ -71,25 +113,271 @@ impl EscrowContract {
             platform_governance,
             agent_referral,
             amount,
+            required_amount: amount,
+            funded_amount: 0,
             token,
             status: EscrowStatus::Pending,
             created_at: env.ledger().timestamp(),
-            timeout_days: EscrowStorage::get_timeout_config(&env).escrow_timeout_days,
+            timeout_days: EscrowStorage::get_timeout_config(env).escrow_timeout_days,
             disputed_at: None,
             dispute_reason: None,
             is_frozen: false,
             frozen_at: None,
+            frozen_until: None,
             freeze_reason: None,
         };
 
-        EscrowStorage::save(&env, &escrow);
-        EscrowStorage::increment_count(&env);
+        EscrowStorage::save(env, &escrow);
+        EscrowStorage::increment_count(env);
+        escrow_index::index_escrow(env, &escrow);
+
+        Ok(escrow)
+    }
+
+    /// Create an escrow for the security deposit of a chioma rental agreement.
+    /// The ID is derived from the agreement ID and a per-agreement nonce, and
+    /// the escrow's status is reported to chioma as the deposit status.
+    ///
+    /// CHECKS:
+    /// - Same as `create`
+    /// - Depositor (tenant) and beneficiary (landlord) must both authorize, so
+    ///   the landlord approves the arbiter and nobody else can claim the
+    ///   agreement's escrow slot
+    /// - Chioma contract must be configured and know the agreement
+    /// - Depositor (tenant), beneficiary (landlord), amount and token must
+    ///   match the agreement
+    /// - The agreement's previous escrow, if any, must be Released or Refunded
+    ///
+    /// EFFECTS:
+    /// - Creates the escrow as `create` does and links it to the agreement
+    ///
+    /// INTERACTIONS:
+    /// - Reports the Pending deposit to chioma
+    pub fn create_for_agreement(
+        env: Env,
+        agreement_id: String,
+        depositor: Address,
+        beneficiary: Address,
+        arbiter: Address,
+        platform_governance: Address,
+        agent_referral: Address,
+        amount: i128,
+        token: Address,
+    ) -> Result<BytesN<32>, EscrowError> {
+        depositor.require_auth();
+        beneficiary.require_auth();
+
+        if amount <= 0 {
+            return Err(EscrowError::InsufficientFunds);
+        }
+        if depositor == beneficiary || depositor == arbiter || beneficiary == arbiter {
+            return Err(EscrowError::InvalidSigner);
+        }
+
+        let (escrow_id, link) = AgreementHandler::prepare(
+            &env,
+            &agreement_id,
+            &depositor,
+            &beneficiary,
+            amount,
+            &token,
+        )?;
+        let escrow = Self::insert(
+            &env,
+            escrow_id,
+            depositor,
+            beneficiary,
+            arbiter,
+            platform_governance,
+            agent_referral,
+            amount,
+            token,
+        )?;
+        AgreementHandler::link(&env, &escrow, &link);
+
+        Ok(escrow.id)
+    }
+
+    /// Set the chioma contract agreement escrows are created against (admin only).
+    pub fn set_chioma_contract(
+        env: Env,
+        caller: Address,
+        chioma_contract: Address,
+    ) -> Result<(), EscrowError> {
+        AccessControl::is_system_admin(&env, &caller)?;
+        caller.require_auth();
+        EscrowStorage::set_chioma_contract(&env, &chioma_contract);
+        Ok(())
+    }
+
+    /// Get the configured chioma contract, if any.
+    pub fn get_chioma_contract(env: Env) -> Option<Address> {
+        EscrowStorage::get_chioma_contract(&env)
+    }
+
+    /// Get the latest escrow created for an agreement, if any.
+    pub fn get_escrow_for_agreement(env: Env, agreement_id: String) -> Option<BytesN<32>> {
+        EscrowStorage::get_agreement_escrow(&env, &agreement_id)
+    }
+
+    /// Get the agreement an escrow was created for, if any.
+    pub fn get_agreement_link(env: Env, escrow_id: BytesN<32>) -> Option<AgreementLink> {
+        EscrowStorage::get_agreement_link(&env, &escrow_id)
+    }
+
+    /// Create a new escrow governed by a custom N-of-M signer set.
+    /// Releases need approvals whose weights add up to `signer_set.threshold`;
+    /// funds are still paid only to the beneficiary or the depositor.
+    ///
+    /// CHECKS:
+    /// - Same as `create`
+    /// - Signer set must be valid (see `AccessControl::validate_signer_set`)
+    ///
+    /// EFFECTS:
+    /// - Creates the escrow as `create` does
+    /// - Stores the signer set for the escrow
+    pub fn create_with_signers(
+        env: Env,
+        depositor: Address,
+        beneficiary: Address,
+        arbiter: Address,
+        platform_governance: Address,
+        agent_referral: Address,
+        amount: i128,
+        token: Address,
+        signer_set: SignerSet,
+    ) -> Result<BytesN<32>, EscrowError> {
+        AccessControl::validate_signer_set(&signer_set)?;
+
+        let escrow_id = Self::create(
+            env.clone(),
+            depositor,
+            beneficiary,
+            arbiter,
+            platform_governance,
+            agent_referral,
+            amount,
+            token,
+        )?;
+        EscrowStorage::set_signer_set(&env, &escrow_id, &signer_set);
 
         Ok(escrow_id)
     }
 
-    /// Fund an existing escrow by depositing funds.
-    /// Transitions status from Pending to Funded.
+    /// Create a milestone escrow for staged work.
+    /// The escrow amount is the sum of the milestone amounts; each milestone is
+    /// released to the beneficiary once the depositor or the optional
+    /// inspector approves the submitted evidence.
+    ///
+    /// CHECKS:
+    /// - Same as `create`
+    /// - 1 to `MAX_MILESTONES` milestones, each with a positive amount
+    /// - Inspector, if any, must not be the beneficiary
+    ///
+    /// EFFECTS:
+    /// - Creates the escrow as `create` does
+    /// - Stores the milestones and inspector
+    pub fn create_milestone_escrow(
+        env: Env,
+        depositor: Address,
+        beneficiary: Address,
+        arbiter: Address,
+        platform_governance: Address,
+        agent_referral: Address,
+        token: Address,
+        milestones: Vec<MilestoneTerms>,
+        inspector: Option<Address>,
+    ) -> Result<BytesN<32>, EscrowError> {
+        let (milestones, total) = MilestoneHandler::build_milestones(&env, &milestones)?;
+        if inspector.as_ref() == Some(&beneficiary) {
+            return Err(EscrowError::InvalidSigner);
+        }
+
+        let escrow_id = Self::create(
+            env.clone(),
+            depositor,
+            beneficiary,
+            arbiter,
+            platform_governance,
+            agent_referral,
+            total,
+            token,
+        )?;
+        EscrowStorage::set_milestones(&env, &escrow_id, &milestones);
+        if let Some(inspector) = inspector {
+            EscrowStorage::set_milestone_inspector(&env, &escrow_id, &inspector);
+        }
+
+        Ok(escrow_id)
+    }
+
+    /// Submit evidence for a milestone.
+    pub fn submit_milestone(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        index: u32,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), EscrowError> {
+        MilestoneHandler::submit_milestone(env, escrow_id, caller, index, evidence_hash)
+    }
+
+    /// Approve a submitted milestone and release its funds.
+    pub fn approve_milestone(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        index: u32,
+    ) -> Result<(), EscrowError> {
+        MilestoneHandler::approve_milestone(env, escrow_id, caller, index)
+    }
+
+    /// Dispute a submitted milestone.
+    pub fn dispute_milestone(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        index: u32,
+        reason: String,
+    ) -> Result<(), EscrowError> {
+        MilestoneHandler::dispute_milestone(env, escrow_id, caller, index, reason)
+    }
+
+    /// Resolve a disputed milestone by allocating its amount across the parties.
+    pub fn resolve_milestone_dispute(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        index: u32,
+        unit: AllocationUnit,
+        allocations: Vec<Allocation>,
+        reason: String,
+    ) -> Result<(), EscrowError> {
+        MilestoneHandler::resolve_milestone_dispute(
+            env,
+            escrow_id,
+            caller,
+            index,
+            unit,
+            allocations,
+            reason,
+        )
+    }
+
+    /// Get the milestones of an escrow.
+    /// Returns an empty list for escrows that are not milestone escrows.
+    pub fn get_milestones(env: Env, escrow_id: BytesN<32>) -> Result<Vec<Milestone>, EscrowError> {
+        EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_milestones(&env, &escrow_id).unwrap_or(Vec::new(&env)))
+    }
+
+    /// Get the inspector allowed to approve milestones, if any.
+    pub fn get_milestone_inspector(env: Env, escrow_id: BytesN<32>) -> Option<Address> {
+        EscrowStorage::get_milestone_inspector(&env, &escrow_id)
+    }
+
+    /// Fund an existing escrow by depositing whatever is still missing from its
+    /// funding target. Transitions status from Pending to Funded.
     ///
     /// CHECKS:
     /// - Escrow must exist
@@ -97,10 +385,11 @@ impl EscrowContract {
     /// - Caller must be depositor
     ///
     /// EFFECTS:
+    /// - Record the remaining amount as a contribution
     /// - Update escrow status to Funded
     ///
     /// INTERACTIONS:
-    /// - Token transfer would happen after state update (not yet in this version)
+    /// - Token transfer from depositor after state update
     pub fn fund_escrow(
         env: Env,
         escrow_id: BytesN<32>,
@@ -120,30 +409,115 @@ impl EscrowContract {
         // Authorize the deposit
         caller.require_auth();
 
-        // EFFECTS: Update status
-        escrow.status = EscrowStatus::Funded;
-        EscrowStorage::save(&env, &escrow);
-
-        // INTERACTIONS: Token transfer from depositor to escrow contract
-        let token_client = token::Client::new(&env, &escrow.token);
-        token_client.transfer(&caller, env.current_contract_address(), &escrow.amount);
+        // EFFECTS + INTERACTIONS: Contribute the shortfall, which funds the escrow
+        let shortfall = escrow.required_amount - escrow.funded_amount;
+        FundingHandler::contribute_unchecked(&env, &mut escrow, &caller, shortfall);
 
         Ok(())
     }
 
+    /// Contribute towards an escrow's funding target, or top up a funded escrow.
+    pub fn contribute(
+        env: Env,
+        escrow_id: BytesN<32>,
+        contributor: Address,
+        amount: i128,
+    ) -> Result<(), EscrowError> {
+        FundingHandler::contribute(env, escrow_id, contributor, amount)
+    }
+
+    /// Propose raising the escrow's funding target (depositor or beneficiary).
+    pub fn propose_target_increase(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        new_required_amount: i128,
+    ) -> Result<(), EscrowError> {
+        FundingHandler::propose_target_increase(env, escrow_id, caller, new_required_amount)
+    }
+
+    /// Accept the other party's proposed target increase.
+    pub fn accept_target_increase(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        FundingHandler::accept_target_increase(env, escrow_id, caller)
+    }
+
+    /// Get the pending target increase proposal, if any.
+    pub fn get_target_increase(env: Env, escrow_id: BytesN<32>) -> Option<TargetIncrease> {
+        EscrowStorage::get_target_increase(&env, &escrow_id)
+    }
+
+    /// Get every funding contribution made to an escrow.
+    pub fn get_contributions(env: Env, escrow_id: BytesN<32>) -> Vec<Contribution> {
+        EscrowStorage::get_contributions(&env, &escrow_id)
+    }
+
+    // --- Yield Vault Functions ---
+
+    /// Set the yield vault for a token (admin only).
+    pub fn set_yield_vault(
+        env: Env,
+        caller: Address,
+        token: Address,
+        vault: Address,
+    ) -> Result<(), EscrowError> {
+        YieldHandler::set_yield_vault(env, caller, token, vault)
+    }
+
+    /// Get the yield vault configured for a token.
+    pub fn get_yield_vault(env: Env, token: Address) -> Option<Address> {
+        EscrowStorage::get_yield_vault(&env, &token)
+    }
+
+    /// Deposit a funded escrow's balance in its token's yield vault.
+    /// Requires both the depositor and the beneficiary to authorize.
+    pub fn enable_yield(env: Env, escrow_id: BytesN<32>) -> Result<(), EscrowError> {
+        YieldHandler::enable_yield(env, escrow_id)
+    }
+
+    /// Withdraw an escrow's vault position before release.
+    pub fn withdraw_yield(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        YieldHandler::withdraw_yield(env, escrow_id, caller)
+    }
+
+    /// Get an escrow's open vault position.
+    pub fn get_yield_position(env: Env, escrow_id: BytesN<32>) -> Option<YieldPosition> {
+        EscrowStorage::get_yield_position(&env, &escrow_id)
+    }
+
+    /// Get the current value of an escrow's vault position.
+    pub fn get_yield_value(env: Env, escrow_id: BytesN<32>) -> Option<i128> {
+        YieldHandler::get_yield_value(env, escrow_id)
+    }
+
+    /// Get every realized vault position of an escrow.
+    pub fn get_yield_history(env: Env, escrow_id: BytesN<32>) -> Vec<YieldRecord> {
+        EscrowStorage::get_yield_history(&env, &escrow_id)
+    }
+
     /// Approve release of funds to a target address.
-    /// Implements 2-of-3 multi-sig: executes transfer when ≥2 unique signers approve same target.
+    /// Implements N-of-M multi-sig: executes transfer once the approval weight for the
+    /// same target reaches the signer set's threshold (2-of-3 by default).
     ///
     /// CHECKS:
     /// - Escrow must exist and be Funded (or Disputed if arbiter)
+    /// - Escrow must not have milestones
+    /// - Escrow must not have milestones
     /// - Escrow must not be frozen
-    /// - Caller must be a valid party
+    /// - Caller must be in the signer set
     /// - Release target must be beneficiary or depositor
     /// - Caller must not have already approved this same target
     ///
     /// EFFECTS:
     /// - Add approval to storage
-    /// - Count approvals; if ≥2 unique parties approve same target, update escrow status
+    /// - Add the caller's weight; if the threshold is reached, update escrow status
     /// - Clear approvals after execution
     ///
     /// INTERACTIONS:
@@ -156,12 +530,13 @@ impl EscrowContract {
     ) -> Result<(), EscrowError> {
         // CHECKS: Get and validate escrow
         let escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        MilestoneHandler::require_no_milestones(&env, &escrow_id)?;
 
         // Check if escrow is frozen
-        AccessControl::require_not_frozen(&escrow)?;
+        AccessControl::require_not_frozen(&env, &escrow)?;
 
-        // Verify caller is a valid party
-        AccessControl::is_party(&escrow, &caller)?;
+        // Verify caller is in the signer set
+        let weight = AccessControl::signer_weight(&env, &escrow, &caller)?;
 
         // Verify escrow is in Funded state
         if escrow.status != EscrowStatus::Funded {
@@ -184,9 +559,9 @@ impl EscrowContract {
             return Err(EscrowError::AlreadySigned);
         }
 
-        // EFFECTS: Record the approval flag and increment the counter
+        // EFFECTS: Record the approval flag and add the signer's weight
         EscrowStorage::set_signer_approved(&env, &escrow_id, &caller, &release_to);
-        EscrowStorage::increment_approval_count(&env, &escrow_id, &release_to);
+        EscrowStorage::add_approval_weight(&env, &escrow_id, &release_to, weight);
 
         // Also persist the approval record for audit trail
         let new_approval = ReleaseApproval {
@@ -196,32 +571,34 @@ impl EscrowContract {
         };
         EscrowStorage::add_approval(&env, &escrow_id, new_approval);
 
-        // Read the updated count via O(1) lookup
+        // Read the updated weight via O(1) lookup
         let approval_count =
             EscrowStorage::get_approval_count_for_target(&env, &escrow_id, &release_to);
 
-        // If 2 or more unique signers approve, execute release
-        if approval_count >= 2 {
+        // If the signer set's threshold is reached, execute release
+        if approval_count >= EscrowStorage::get_signer_set(&env, &escrow).threshold {
             let mut escrow_to_update =
                 EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
 
+            // Bring any vault position back into the escrow first
+            YieldHandler::realize(&env, &mut escrow_to_update)?;
+
             // Determine final status based on release target
-            escrow_to_update.status = EscrowStatus::Released;
+            escrow_index::set_status(&env, &mut escrow_to_update, EscrowStatus::Released);
             EscrowStorage::save(&env, &escrow_to_update);
 
             // Clear approvals and counters after execution
-            EscrowStorage::clear_approvals(&env, &escrow_id);
-            let targets = [escrow.beneficiary.clone(), escrow.depositor.clone()];
-            let signers = [
-                escrow.depositor.clone(),
-                escrow.beneficiary.clone(),
-                escrow.arbiter.clone(),
-            ];
-            EscrowStorage::clear_approval_counts(&env, &escrow_id, &targets, &signers);
+            EscrowStorage::clear_release_approvals(&env, &escrow);
 
             // INTERACTIONS: Token transfer from escrow contract to release target
             let token_client = token::Client::new(&env, &escrow.token);
-            token_client.transfer(&env.current_contract_address(), &release_to, &escrow.amount);
+            token_client.transfer(
+                &env.current_contract_address(),
+                &release_to,
+                &escrow_to_update.amount,
+            );
+
+            AgreementHandler::sync_deposit_status(&env, &escrow_to_update);
         }
 
         Ok(())
@@ -247,10 +624,25 @@ impl EscrowContract {
         DisputeHandler::resolve_dispute(env, escrow_id, caller, release_to)
     }
 
+    /// Resolve a dispute by splitting the remaining balance across the parties.
+    pub fn resolve_dispute_split(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        unit: AllocationUnit,
+        allocations: Vec<Allocation>,
+        reason: String,
+    ) -> Result<(), EscrowError> {
+        DisputeHandler::resolve_dispute_split(env, escrow_id, caller, unit, allocations, reason)
+    }
+
     /// Refund escrow to depositor if escrow timeout has elapsed.
-    /// Intended for stale escrows that are not released yet.
+    /// Intended for stale escrows that are not released yet. A Pending escrow
+    /// refunds whatever has been contributed so far. Milestone escrows are
+    /// settled milestone by milestone instead.
     pub fn release_escrow_on_timeout(env: Env, escrow_id: BytesN<32>) -> Result<(), EscrowError> {
         let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        MilestoneHandler::require_no_milestones(&env, &escrow_id)?;
 
         if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
             return Err(EscrowError::InvalidState);
@@ -263,26 +655,26 @@ impl EscrowContract {
             return Err(EscrowError::TimeoutNotReached);
         }
 
-        escrow.status = EscrowStatus::Refunded;
+        YieldHandler::realize(&env, &mut escrow)?;
+
+        let refund = if escrow.status == EscrowStatus::Pending {
+            escrow.funded_amount
+        } else {
+            escrow.amount
+        };
+        escrow_index::set_status(&env, &mut escrow, EscrowStatus::Refunded);
         EscrowStorage::save(&env, &escrow);
 
-        EscrowStorage::clear_approvals(&env, &escrow_id);
-        let targets = [escrow.beneficiary.clone(), escrow.depositor.clone()];
-        let signers = [
-            escrow.depositor.clone(),
-            escrow.beneficiary.clone(),
-            escrow.arbiter.clone(),
-        ];
-        EscrowStorage::clear_approval_counts(&env, &escrow_id, &targets, &signers);
+        EscrowStorage::clear_release_approvals(&env, &escrow);
+        EscrowStorage::remove_target_increase(&env, &escrow_id);
 
-        let token_client = token::Client::new(&env, &escrow.token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &escrow.depositor,
-            &escrow.amount,
-        );
+        if refund > 0 {
+            let token_client = token::Client::new(&env, &escrow.token);
+            token_client.transfer(&env.current_contract_address(), &escrow.depositor, &refund);
+        }
 
         events::escrow_timeout(&env, escrow_id);
+        AgreementHandler::sync_deposit_status(&env, &escrow);
         Ok(())
     }
 
@@ -321,8 +713,53 @@ impl EscrowContract {
         EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)
     }
 
+    /// List escrows where `depositor` is the depositor.
+    /// Paginated; pass the returned `next_cursor` to fetch the next page.
+    pub fn get_escrows_by_depositor(
+        env: Env,
+        depositor: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<EscrowPage, EscrowError> {
+        escrow_index::get_page(&env, EscrowIndex::Depositor(depositor), cursor, limit)
+    }
+
+    /// List escrows where `beneficiary` is the beneficiary.
+    /// Paginated; pass the returned `next_cursor` to fetch the next page.
+    pub fn get_escrows_by_beneficiary(
+        env: Env,
+        beneficiary: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<EscrowPage, EscrowError> {
+        escrow_index::get_page(&env, EscrowIndex::Beneficiary(beneficiary), cursor, limit)
+    }
+
+    /// List escrows arbitrated by `arbiter`.
+    /// Paginated; pass the returned `next_cursor` to fetch the next page.
+    pub fn get_escrows_by_arbiter(
+        env: Env,
+        arbiter: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<EscrowPage, EscrowError> {
+        escrow_index::get_page(&env, EscrowIndex::Arbiter(arbiter), cursor, limit)
+    }
+
+    /// List escrows currently in `status`.
+    /// Paginated; the index is updated on every status transition.
+    pub fn get_escrows_by_status(
+        env: Env,
+        status: EscrowStatus,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<EscrowPage, EscrowError> {
+        escrow_index::get_page(&env, EscrowIndex::Status(status), cursor, limit)
+    }
+
     /// Get approval count for a specific release target.
-    /// Returns number of unique signers approving release to a specific address.
+    /// Returns the total weight of signers approving release to a specific address,
+    /// which is the number of signers under the default 2-of-3 preset.
     /// Uses O(1) dedicated counter storage instead of iterating the approvals list.
     pub fn get_approval_count(
         env: Env,
@@ -338,6 +775,13 @@ impl EscrowContract {
         ))
     }
 
+    /// Get the signer set governing releases from an escrow.
+    /// Returns the 2-of-3 preset for escrows created without a custom set.
+    pub fn get_signer_set(env: Env, escrow_id: BytesN<32>) -> Result<SignerSet, EscrowError> {
+        let escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        Ok(EscrowStorage::get_signer_set(&env, &escrow))
+    }
+
     /// Approve a partial release without auto-executing.
     /// This is used for partial releases where we don't want automatic full release.
     pub fn approve_partial_release(
@@ -348,9 +792,10 @@ impl EscrowContract {
     ) -> Result<(), EscrowError> {
         // CHECKS: Get and validate escrow
         let escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        MilestoneHandler::require_no_milestones(&env, &escrow_id)?;
 
-        // Verify caller is a valid party
-        AccessControl::is_party(&escrow, &caller)?;
+        // Verify caller is in the signer set
+        let weight = AccessControl::signer_weight(&env, &escrow, &caller)?;
 
         // Verify escrow is in Funded state
         if escrow.status != EscrowStatus::Funded {
@@ -370,9 +815,9 @@ impl EscrowContract {
             return Err(EscrowError::AlreadySigned);
         }
 
-        // EFFECTS: Record the approval flag and increment the counter
+        // EFFECTS: Record the approval flag and add the signer's weight
         EscrowStorage::set_signer_approved(&env, &escrow_id, &caller, &release_to);
-        EscrowStorage::increment_approval_count(&env, &escrow_id, &release_to);
+        EscrowStorage::add_approval_weight(&env, &escrow_id, &release_to, weight);
 
         // Also persist the approval record for audit trail
         let new_approval = ReleaseApproval {
@@ -386,15 +831,16 @@ impl EscrowContract {
     }
 
     /// Release a partial amount from escrow to a recipient.
-    /// Requires multi-sig approval (2-of-3) via approve_partial_release.
+    /// Requires multi-sig approval (signer set threshold) via approve_partial_release.
     ///
     /// CHECKS:
     /// - Escrow must exist and be Funded
+    /// - Escrow must not have milestones
     /// - Escrow must not be frozen
     /// - Amount must be positive and not exceed escrow balance
     /// - Recipient must be beneficiary or depositor
     /// - Reason must not be empty
-    /// - Must have 2-of-3 approval
+    /// - Approval weight must reach the signer set's threshold
     ///
     /// EFFECTS:
     /// - Update escrow amount
@@ -413,9 +859,10 @@ impl EscrowContract {
     ) -> Result<(), EscrowError> {
         // CHECKS: Get and validate escrow
         let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        MilestoneHandler::require_no_milestones(&env, &escrow_id)?;
 
         // Check if escrow is frozen
-        AccessControl::require_not_frozen(&escrow)?;
+        AccessControl::require_not_frozen(&env, &escrow)?;
 
         // Verify escrow is in Funded state
         if escrow.status != EscrowStatus::Funded {
@@ -427,6 +874,9 @@ impl EscrowContract {
             return Err(EscrowError::InvalidAmount);
         }
 
+        // Bring any vault position back into the escrow before checking the balance
+        YieldHandler::realize(&env, &mut escrow)?;
+
         if amount > escrow.amount {
             return Err(EscrowError::InsufficientFunds);
         }
@@ -441,10 +891,10 @@ impl EscrowContract {
             return Err(EscrowError::EmptyReleaseReason);
         }
 
-        // Check for 2-of-3 approval
+        // Check the approval weight against the signer set's threshold
         let approval_count =
             EscrowStorage::get_approval_count_for_target(&env, &escrow_id, &recipient);
-        if approval_count < 2 {
+        if approval_count < EscrowStorage::get_signer_set(&env, &escrow).threshold {
             return Err(EscrowError::NotAuthorized);
         }
 
@@ -463,14 +913,7 @@ impl EscrowContract {
         EscrowStorage::add_release_record(&env, &escrow_id, release_record);
 
         // Clear approvals after execution
-        EscrowStorage::clear_approvals(&env, &escrow_id);
-        let targets = [escrow.beneficiary.clone(), escrow.depositor.clone()];
-        let signers = [
-            escrow.depositor.clone(),
-            escrow.beneficiary.clone(),
-            escrow.arbiter.clone(),
-        ];
-        EscrowStorage::clear_approval_counts(&env, &escrow_id, &targets, &signers);
+        EscrowStorage::clear_release_approvals(&env, &escrow);
 
         // INTERACTIONS: Token transfer
         let token_client = token::Client::new(&env, &escrow.token);
@@ -485,14 +928,15 @@ impl EscrowContract {
     /// Release escrow with damage deduction.
     /// Deducts damage amount and releases the remainder to the depositor (guest).
     /// The damage amount goes to the beneficiary (landlord).
-    /// Requires multi-sig approval (2-of-3).
+    /// Requires multi-sig approval (signer set threshold).
     ///
     /// CHECKS:
     /// - Escrow must exist and be Funded
+    /// - Escrow must not have milestones
     /// - Escrow must not be frozen
     /// - Damage amount must be non-negative and not exceed escrow balance
     /// - Reason must not be empty
-    /// - Must have 2-of-3 approval for release to depositor
+    /// - Approval weight for release to depositor must reach the threshold
     ///
     /// EFFECTS:
     /// - Update escrow status to Released
@@ -510,9 +954,10 @@ impl EscrowContract {
     ) -> Result<(), EscrowError> {
         // CHECKS: Get and validate escrow
         let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        MilestoneHandler::require_no_milestones(&env, &escrow_id)?;
 
         // Check if escrow is frozen
-        AccessControl::require_not_frozen(&escrow)?;
+        AccessControl::require_not_frozen(&env, &escrow)?;
 
         // Verify escrow is in Funded state
         if escrow.status != EscrowStatus::Funded {
@@ -524,6 +969,9 @@ impl EscrowContract {
             return Err(EscrowError::InvalidAmount);
         }
 
+        // Bring any vault position back into the escrow before checking the balance
+        YieldHandler::realize(&env, &mut escrow)?;
+
         if damage_amount > escrow.amount {
             return Err(EscrowError::InsufficientFunds);
         }
@@ -533,10 +981,10 @@ impl EscrowContract {
             return Err(EscrowError::EmptyReleaseReason);
         }
 
-        // Check for 2-of-3 approval for release to depositor
+        // Check the approval weight for release to depositor against the threshold
         let approval_count =
             EscrowStorage::get_approval_count_for_target(&env, &escrow_id, &escrow.depositor);
-        if approval_count < 2 {
+        if approval_count < EscrowStorage::get_signer_set(&env, &escrow).threshold {
             return Err(EscrowError::NotAuthorized);
         }
 
@@ -544,7 +992,7 @@ impl EscrowContract {
         let refund_amount = escrow.amount - damage_amount;
 
         // EFFECTS: Update escrow status (all funds will be released)
-        escrow.status = EscrowStatus::Released;
+        escrow_index::set_status(&env, &mut escrow, EscrowStatus::Released);
         EscrowStorage::save(&env, &escrow);
 
         // Record damage deduction release in history
@@ -572,14 +1020,7 @@ impl EscrowContract {
         }
 
         // Clear approvals after execution
-        EscrowStorage::clear_approvals(&env, &escrow_id);
-        let targets = [escrow.beneficiary.clone(), escrow.depositor.clone()];
-        let signers = [
-            escrow.depositor.clone(),
-            escrow.beneficiary.clone(),
-            escrow.arbiter.clone(),
-        ];
-        EscrowStorage::clear_approval_counts(&env, &escrow_id, &targets, &signers);
+        EscrowStorage::clear_release_approvals(&env, &escrow);
 
         // INTERACTIONS: Token transfers
         let token_client = token::Client::new(&env, &escrow.token);
@@ -604,6 +1045,7 @@ impl EscrowContract {
 
         // Emit event
         events::damage_deduction(&env, escrow_id, damage_amount, refund_amount);
+        AgreementHandler::sync_deposit_status(&env, &escrow);
 
         Ok(())
     }
@@ -669,114 +1111,77 @@ impl EscrowContract {
         EscrowStorage::get_admin(&env)
     }
 
-    /// Freeze an escrow to prevent all fund movements.
-    /// Used in case of verified exploit, major dispute, or security incident.
-    /// Only the system admin or arbiter can freeze an escrow.
-    ///
-    /// CHECKS:
-    /// - Escrow must exist
-    /// - Escrow must not already be frozen
-    /// - Caller must be system admin or arbiter
-    /// - Reason must not be empty
-    ///
-    /// EFFECTS:
-    /// - Set is_frozen flag to true
-    /// - Record freeze timestamp and reason
-    /// - Emit EscrowFrozen event
+    /// Freeze an escrow to prevent all fund movements (admin or arbiter).
+    /// The freeze lapses after `max_freeze_days` unless the council extends it.
     pub fn freeze_escrow(
         env: Env,
         escrow_id: BytesN<32>,
         caller: Address,
         reason: soroban_sdk::String,
     ) -> Result<(), EscrowError> {
-        // CHECKS: Get and validate escrow
-        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
-
-        // Check if already frozen
-        if escrow.is_frozen {
-            return Err(EscrowError::AlreadyFrozen);
-        }
-
-        // Verify reason is not empty
-        if reason.is_empty() {
-            return Err(EscrowError::EmptyFreezeReason);
-        }
-
-        // Verify caller is system admin or arbiter
-        let is_admin = AccessControl::is_system_admin(&env, &caller).is_ok();
-        let is_arbiter = AccessControl::is_arbiter(&escrow, &caller).is_ok();
-
-        if !is_admin && !is_arbiter {
-            return Err(EscrowError::NotAuthorized);
-        }
-
-        // Require authorization
-        caller.require_auth();
-
-        // EFFECTS: Freeze the escrow
-        let timestamp = env.ledger().timestamp();
-        escrow.is_frozen = true;
-        escrow.frozen_at = Some(timestamp);
-        escrow.freeze_reason = Some(reason.clone());
-
-        EscrowStorage::save(&env, &escrow);
-
-        // Emit event
-        events::escrow_frozen(&env, escrow_id, caller, reason, timestamp);
-
-        Ok(())
+        FreezeHandler::freeze_escrow(env, escrow_id, caller, reason)
     }
 
-    /// Unfreeze an escrow to allow fund movements again.
-    /// Only the system admin can unfreeze an escrow.
-    ///
-    /// CHECKS:
-    /// - Escrow must exist
-    /// - Escrow must be frozen
-    /// - Caller must be system admin
-    ///
-    /// EFFECTS:
-    /// - Set is_frozen flag to false
-    /// - Clear freeze timestamp and reason
-    /// - Emit EscrowUnfrozen event
+    /// Unfreeze an escrow to allow fund movements again (admin only).
     pub fn unfreeze_escrow(
         env: Env,
         escrow_id: BytesN<32>,
         caller: Address,
     ) -> Result<(), EscrowError> {
-        // CHECKS: Get and validate escrow
-        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
-
-        // Check if escrow is frozen
-        if !escrow.is_frozen {
-            return Err(EscrowError::NotFrozen);
-        }
+        FreezeHandler::unfreeze_escrow(env, escrow_id, caller)
+    }
 
-        // Verify caller is system admin
-        AccessControl::is_system_admin(&env, &caller)?;
+    /// Approve extending an escrow's freeze (freeze council only).
+    pub fn approve_freeze_extension(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        FreezeHandler::approve_freeze_extension(env, escrow_id, caller)
+    }
 
-        // Require authorization
-        caller.require_auth();
+    /// Petition to lift an escrow's freeze (depositor or beneficiary).
+    /// The freeze is lifted once both have petitioned.
+    pub fn petition_unfreeze(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        reason: String,
+    ) -> Result<(), EscrowError> {
+        FreezeHandler::petition_unfreeze(env, escrow_id, caller, reason)
+    }
 
-        // EFFECTS: Unfreeze the escrow
-        let timestamp = env.ledger().timestamp();
-        escrow.is_frozen = false;
-        escrow.frozen_at = None;
-        escrow.freeze_reason = None;
+    /// Set the freeze duration and extension council (admin only). Replacing
+    /// an existing council needs its approval.
+    pub fn set_freeze_config(
+        env: Env,
+        caller: Address,
+        config: FreezeConfig,
+        council_approvals: Vec<Address>,
+    ) -> Result<(), EscrowError> {
+        FreezeHandler::set_freeze_config(env, caller, config, council_approvals)
+    }
 
-        EscrowStorage::save(&env, &escrow);
+    /// Get the freeze config, or the defaults if none was set.
+    pub fn get_freeze_config(env: Env) -> FreezeConfig {
+        EscrowStorage::get_freeze_config(&env)
+    }
 
-        // Emit event
-        events::escrow_unfrozen(&env, escrow_id, caller, timestamp);
+    /// Get every freeze of an escrow, oldest first.
+    pub fn get_freeze_history(env: Env, escrow_id: BytesN<32>) -> Vec<FreezeRecord> {
+        EscrowStorage::get_freeze_history(&env, &escrow_id)
+    }
 
-        Ok(())
+    /// Get the unfreeze petitions filed against an escrow's current freeze.
+    pub fn get_unfreeze_petitions(env: Env, escrow_id: BytesN<32>) -> Vec<UnfreezePetition> {
+        EscrowStorage::get_unfreeze_petitions(&env, &escrow_id)
     }
 
     /// Check if an escrow is frozen.
     /// Read-only view function.
     pub fn is_escrow_frozen(env: Env, escrow_id: BytesN<32>) -> Result<bool, EscrowError> {
         let escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
-        Ok(escrow.is_frozen)
+        Ok(AccessControl::is_frozen(&env, &escrow))
     }
 
     /// Release rent with automatic 90/5/5 fee split.
@@ -791,6 +1196,7 @@ impl EscrowContract {
     ///
     /// CHECKS:
     /// - Escrow must exist and be in Funded state (not Disputed)
+    /// - Escrow must not have milestones
     /// - Caller must be the arbiter
     ///
     /// EFFECTS:
@@ -804,6 +1210,7 @@ impl EscrowContract {
         caller: Address,
     ) -> Result<(), EscrowError> {
         let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        MilestoneHandler::require_no_milestones(&env, &escrow_id)?;
 
         AccessControl::is_arbiter(&escrow, &caller)?;
 
@@ -813,13 +1220,15 @@ impl EscrowContract {
 
         caller.require_auth();
 
+        YieldHandler::realize(&env, &mut escrow)?;
+
         let total = escrow.amount;
         let beneficiary_share = total * 90 / 100;
         let governance_share = total * 5 / 100;
         let agent_share = total - beneficiary_share - governance_share;
 
         // EFFECTS: mark as released before any transfers (checks-effects-interactions)
-        escrow.status = EscrowStatus::Released;
+        escrow_index::set_status(&env, &mut escrow, EscrowStatus::Released);
         EscrowStorage::save(&env, &escrow);
 
         // INTERACTIONS: distribute funds
@@ -847,6 +1256,7 @@ impl EscrowContract {
             governance_share,
             agent_share,
         );
+        AgreementHandler::sync_deposit_status(&env, &escrow);
         Ok(())
     }
 
@@ -892,8 +1302,10 @@ impl EscrowContract {
             return Err(EscrowError::TimeoutNotReached);
         }
 
+        YieldHandler::realize(&env, &mut escrow)?;
+
         // EFFECTS
-        escrow.status = EscrowStatus::Refunded;
+        escrow_index::set_status(&env, &mut escrow, EscrowStatus::Refunded);
         EscrowStorage::save(&env, &escrow);
 
         // INTERACTIONS
@@ -905,6 +1317,7 @@ impl EscrowContract {
         );
 
         events::safety_deposit_withdrawn(&env, escrow_id, escrow.amount);
+        AgreementHandler::sync_deposit_status(&env, &escrow);
         Ok(())
     }
 
@@ -0,0 +1,125 @@
+//! Secondary indexes over escrows by party and status.
+//!
+//! Each index is stored as a length plus one entry per position, with a
+//! reverse position key so entries can be swap-removed in O(1). Reads only
+//! touch the requested page, never the whole index.
+use soroban_sdk::{BytesN, Env, Vec};
+
+use crate::errors::EscrowError;
+use crate::storage::EscrowStorage;
+use crate::types::{DataKey, Escrow, EscrowIndex, EscrowPage, EscrowStatus};
+
+/// Largest page returned by a single query.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+fn len(env: &Env, index: &EscrowIndex) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::IndexLen(index.clone()))
+        .unwrap_or(0)
+}
+
+fn set_entry(env: &Env, index: &EscrowIndex, position: u32, escrow_id: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::IndexEntry(index.clone(), position), escrow_id);
+    env.storage().persistent().set(
+        &DataKey::IndexPosition(index.clone(), escrow_id.clone()),
+        &position,
+    );
+}
+
+/// Append `escrow_id` to `index` unless it is already present.
+pub(crate) fn insert(env: &Env, index: EscrowIndex, escrow_id: &BytesN<32>) {
+    if env
+        .storage()
+        .persistent()
+        .has(&DataKey::IndexPosition(index.clone(), escrow_id.clone()))
+    {
+        return;
+    }
+    let position = len(env, &index);
+    set_entry(env, &index, position, escrow_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::IndexLen(index), &(position + 1));
+}
+
+/// Remove `escrow_id` from `index` by moving the last entry into its slot.
+pub(crate) fn remove(env: &Env, index: EscrowIndex, escrow_id: &BytesN<32>) {
+    let position_key = DataKey::IndexPosition(index.clone(), escrow_id.clone());
+    let Some(position) = env.storage().persistent().get::<_, u32>(&position_key) else {
+        return;
+    };
+    let last = len(env, &index) - 1;
+    let last_key = DataKey::IndexEntry(index.clone(), last);
+
+    if position != last {
+        let moved: BytesN<32> = env.storage().persistent().get(&last_key).unwrap();
+        set_entry(env, &index, position, &moved);
+    }
+    env.storage().persistent().remove(&last_key);
+    env.storage().persistent().remove(&position_key);
+    env.storage()
+        .persistent()
+        .set(&DataKey::IndexLen(index), &last);
+}
+
+/// Index a newly created escrow under each of its parties and its status.
+pub(crate) fn index_escrow(env: &Env, escrow: &Escrow) {
+    let id = &escrow.id;
+    insert(env, EscrowIndex::Depositor(escrow.depositor.clone()), id);
+    insert(
+        env,
+        EscrowIndex::Beneficiary(escrow.beneficiary.clone()),
+        id,
+    );
+    insert(env, EscrowIndex::Arbiter(escrow.arbiter.clone()), id);
+    insert(env, EscrowIndex::Status(escrow.status.clone()), id);
+}
+
+/// Move an escrow between status indexes and update its status field.
+/// The caller still saves the escrow.
+pub(crate) fn set_status(env: &Env, escrow: &mut Escrow, status: EscrowStatus) {
+    if escrow.status == status {
+        return;
+    }
+    remove(env, EscrowIndex::Status(escrow.status.clone()), &escrow.id);
+    insert(env, EscrowIndex::Status(status.clone()), &escrow.id);
+    escrow.status = status;
+}
+
+/// Read one page of `index`, starting at position `cursor`.
+///
+/// Removals swap the last entry into the freed slot, so a page walk that
+/// overlaps a status change may skip or repeat one escrow.
+pub fn get_page(
+    env: &Env,
+    index: EscrowIndex,
+    cursor: u32,
+    limit: u32,
+) -> Result<EscrowPage, EscrowError> {
+    if limit == 0 || limit > MAX_PAGE_SIZE {
+        return Err(EscrowError::InvalidPageSize);
+    }
+
+    let total = len(env, &index);
+    let end = cursor.saturating_add(limit).min(total);
+    let mut escrows = Vec::new(env);
+    for position in cursor..end {
+        let escrow = env
+            .storage()
+            .persistent()
+            .get::<_, BytesN<32>>(&DataKey::IndexEntry(index.clone(), position))
+            .and_then(|escrow_id| EscrowStorage::get(env, &escrow_id));
+        if let Some(escrow) = escrow {
+            escrows.push_back(escrow);
+        }
+    }
+
+    Ok(EscrowPage {
+        escrows,
+        next_cursor: if end < total { Some(end) } else { None },
+        total,
+    })
+}
@@ -1,6 +1,9 @@
 //! Contract events for escrow lifecycle and timeout handling.
 use soroban_sdk::{contractevent, Address, BytesN, Env, String};
 
+use crate::agreement::DepositStatus;
+use crate::types::YieldRecord;
+
 #[contractevent(topics = ["escrow_timeout"])]
 pub struct EscrowTimeout {
     #[topic]
@@ -29,6 +32,77 @@ pub struct DamageDeduction {
     pub refund_amount: i128,
 }
 
+#[contractevent(topics = ["dispute_payout"])]
+pub struct DisputePayout {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["milestone_submitted"])]
+pub struct MilestoneSubmitted {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub index: u32,
+    pub evidence_hash: BytesN<32>,
+}
+
+#[contractevent(topics = ["milestone_released"])]
+pub struct MilestoneReleased {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub index: u32,
+    pub approved_by: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["milestone_disputed"])]
+pub struct MilestoneDisputed {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub index: u32,
+    pub raised_by: Address,
+}
+
+#[contractevent(topics = ["milestone_resolved"])]
+pub struct MilestoneResolved {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub index: u32,
+}
+
+#[contractevent(topics = ["escrow_contribution"])]
+pub struct EscrowContribution {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub contributor: Address,
+    pub amount: i128,
+    pub funded_amount: i128,
+}
+
+#[contractevent(topics = ["escrow_funded"])]
+pub struct EscrowFunded {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub funded_amount: i128,
+}
+
+#[contractevent(topics = ["target_increase_proposed"])]
+pub struct TargetIncreaseProposed {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub proposed_by: Address,
+    pub new_required_amount: i128,
+}
+
+#[contractevent(topics = ["target_increased"])]
+pub struct TargetIncreased {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub required_amount: i128,
+}
+
 #[contractevent(topics = ["escrow_frozen"])]
 pub struct EscrowFrozen {
     #[topic]
@@ -36,6 +110,7 @@ pub struct EscrowFrozen {
     pub frozen_by: Address,
     pub reason: String,
     pub timestamp: u64,
+    pub expires_at: u64,
 }
 
 #[contractevent(topics = ["escrow_unfrozen"])]
@@ -46,6 +121,22 @@ pub struct EscrowUnfrozen {
     pub timestamp: u64,
 }
 
+#[contractevent(topics = ["freeze_extended"])]
+pub struct FreezeExtended {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub expires_at: u64,
+    pub extensions: u32,
+}
+
+#[contractevent(topics = ["unfreeze_petitioned"])]
+pub struct UnfreezePetitioned {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub petitioner: Address,
+    pub reason: String,
+}
+
 pub(crate) fn escrow_timeout(env: &Env, escrow_id: BytesN<32>) {
     EscrowTimeout { escrow_id }.publish(env);
 }
@@ -77,18 +168,141 @@ pub(crate) fn damage_deduction(
     .publish(env);
 }
 
+pub(crate) fn dispute_payout(env: &Env, escrow_id: BytesN<32>, recipient: Address, amount: i128) {
+    DisputePayout {
+        escrow_id,
+        recipient,
+        amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn milestone_submitted(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    index: u32,
+    evidence_hash: BytesN<32>,
+) {
+    MilestoneSubmitted {
+        escrow_id,
+        index,
+        evidence_hash,
+    }
+    .publish(env);
+}
+
+pub(crate) fn milestone_released(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    index: u32,
+    approved_by: Address,
+    amount: i128,
+) {
+    MilestoneReleased {
+        escrow_id,
+        index,
+        approved_by,
+        amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn milestone_disputed(env: &Env, escrow_id: BytesN<32>, index: u32, raised_by: Address) {
+    MilestoneDisputed {
+        escrow_id,
+        index,
+        raised_by,
+    }
+    .publish(env);
+}
+
+pub(crate) fn milestone_resolved(env: &Env, escrow_id: BytesN<32>, index: u32) {
+    MilestoneResolved { escrow_id, index }.publish(env);
+}
+
+pub(crate) fn escrow_contribution(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    contributor: Address,
+    amount: i128,
+    funded_amount: i128,
+) {
+    EscrowContribution {
+        escrow_id,
+        contributor,
+        amount,
+        funded_amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn escrow_funded(env: &Env, escrow_id: BytesN<32>, funded_amount: i128) {
+    EscrowFunded {
+        escrow_id,
+        funded_amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn target_increase_proposed(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    proposed_by: Address,
+    new_required_amount: i128,
+) {
+    TargetIncreaseProposed {
+        escrow_id,
+        proposed_by,
+        new_required_amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn target_increased(env: &Env, escrow_id: BytesN<32>, required_amount: i128) {
+    TargetIncreased {
+        escrow_id,
+        required_amount,
+    }
+    .publish(env);
+}
+
 pub(crate) fn escrow_frozen(
     env: &Env,
     escrow_id: BytesN<32>,
     frozen_by: Address,
     reason: String,
     timestamp: u64,
+    expires_at: u64,
 ) {
     EscrowFrozen {
         escrow_id,
         frozen_by,
         reason,
         timestamp,
+        expires_at,
+    }
+    .publish(env);
+}
+
+pub(crate) fn freeze_extended(env: &Env, escrow_id: BytesN<32>, expires_at: u64, extensions: u32) {
+    FreezeExtended {
+        escrow_id,
+        expires_at,
+        extensions,
+    }
+    .publish(env);
+}
+
+pub(crate) fn unfreeze_petitioned(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    petitioner: Address,
+    reason: String,
+) {
+    UnfreezePetitioned {
+        escrow_id,
+        petitioner,
+        reason,
     }
     .publish(env);
 }
@@ -142,3 +356,88 @@ pub(crate) fn rent_released(
 pub(crate) fn safety_deposit_withdrawn(env: &Env, escrow_id: BytesN<32>, amount: i128) {
     SafetyDepositWithdrawn { escrow_id, amount }.publish(env);
 }
+
+#[contractevent(topics = ["yield_deposited"])]
+pub struct YieldDeposited {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub vault: Address,
+    pub amount: i128,
+    pub shares: i128,
+}
+
+#[contractevent(topics = ["yield_realized"])]
+pub struct YieldRealized {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub principal: i128,
+    pub withdrawn: i128,
+    pub to_depositor: i128,
+    pub to_beneficiary: i128,
+}
+
+#[contractevent(topics = ["vault_loss"])]
+pub struct VaultLoss {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub loss: i128,
+    pub remaining_amount: i128,
+}
+
+pub(crate) fn yield_deposited(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    vault: Address,
+    amount: i128,
+    shares: i128,
+) {
+    YieldDeposited {
+        escrow_id,
+        vault,
+        amount,
+        shares,
+    }
+    .publish(env);
+}
+
+pub(crate) fn yield_realized(env: &Env, escrow_id: BytesN<32>, record: &YieldRecord) {
+    YieldRealized {
+        escrow_id,
+        principal: record.principal,
+        withdrawn: record.withdrawn,
+        to_depositor: record.to_depositor,
+        to_beneficiary: record.to_beneficiary,
+    }
+    .publish(env);
+}
+
+pub(crate) fn vault_loss(env: &Env, escrow_id: BytesN<32>, loss: i128, remaining_amount: i128) {
+    VaultLoss {
+        escrow_id,
+        loss,
+        remaining_amount,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["deposit_sync_failed"])]
+pub struct DepositSyncFailed {
+    #[topic]
+    pub escrow_id: BytesN<32>,
+    pub agreement_id: String,
+    pub status: DepositStatus,
+}
+
+pub(crate) fn deposit_sync_failed(
+    env: &Env,
+    escrow_id: BytesN<32>,
+    agreement_id: String,
+    status: DepositStatus,
+) {
+    DepositSyncFailed {
+        escrow_id,
+        agreement_id,
+        status,
+    }
+    .publish(env);
+}
@@ -0,0 +1,365 @@
+//! Emergency freezes for the Escrow contract.
+//! A freeze blocks fund movements for at most `max_freeze_days`, then lapses
+//! on its own. Only a council of system admins other than the freezer can
+//! extend it, and the escrow cannot be frozen again until `max_freeze_days`
+//! after the freeze ends. The depositor and beneficiary can lift it
+//! together by petition. Every freeze is kept in the escrow's freeze
+//! history.
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
+
+use crate::access::AccessControl;
+use crate::errors::EscrowError;
+use crate::events;
+use crate::storage::EscrowStorage;
+use crate::types::{Escrow, FreezeConfig, FreezeExtension, FreezeRecord, UnfreezePetition};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Freeze handling.
+pub struct FreezeHandler;
+
+impl FreezeHandler {
+    /// Upper bound on `max_freeze_days`.
+    pub const MAX_FREEZE_DAYS: u64 = 90;
+
+    pub(crate) fn freeze_duration(env: &Env) -> u64 {
+        EscrowStorage::get_freeze_config(env)
+            .max_freeze_days
+            .saturating_mul(SECONDS_PER_DAY)
+    }
+
+    /// Set the freeze duration and extension council (admin only).
+    ///
+    /// CHECKS:
+    /// - Caller must be system admin
+    /// - `max_freeze_days` must be between 1 and `MAX_FREEZE_DAYS`
+    /// - Council members must be distinct; with a council the threshold must
+    ///   be between 2 and its size, without one it must be 0
+    /// - Changing the council or its threshold needs `council_approvals` from
+    ///   at least `extension_threshold` distinct members of the current
+    ///   council, each authorizing the call
+    pub fn set_freeze_config(
+        env: Env,
+        caller: Address,
+        config: FreezeConfig,
+        council_approvals: Vec<Address>,
+    ) -> Result<(), EscrowError> {
+        AccessControl::is_system_admin(&env, &caller)?;
+        caller.require_auth();
+
+        if config.max_freeze_days == 0 || config.max_freeze_days > Self::MAX_FREEZE_DAYS {
+            return Err(EscrowError::InvalidFreezeConfig);
+        }
+        let size = config.council.len();
+        for (i, member) in config.council.iter().enumerate() {
+            if config.council.first_index_of(&member) != Some(i as u32) {
+                return Err(EscrowError::InvalidFreezeConfig);
+            }
+        }
+        let threshold_valid = if size == 0 {
+            config.extension_threshold == 0
+        } else {
+            config.extension_threshold >= 2 && config.extension_threshold <= size
+        };
+        if !threshold_valid {
+            return Err(EscrowError::InvalidFreezeConfig);
+        }
+
+        let current = EscrowStorage::get_freeze_config(&env);
+        let council_changed = config.council != current.council
+            || config.extension_threshold != current.extension_threshold;
+        if council_changed && !current.council.is_empty() {
+            Self::require_council_approval(&current, &council_approvals)?;
+        }
+
+        EscrowStorage::set_freeze_config(&env, &config);
+        Ok(())
+    }
+
+    /// Check that `approvals` holds enough distinct members of the council
+    /// in `config`, and require each of them to authorize.
+    fn require_council_approval(
+        config: &FreezeConfig,
+        approvals: &Vec<Address>,
+    ) -> Result<(), EscrowError> {
+        for (i, member) in approvals.iter().enumerate() {
+            if !config.council.contains(&member)
+                || approvals.first_index_of(&member) != Some(i as u32)
+            {
+                return Err(EscrowError::CouncilApprovalRequired);
+            }
+        }
+        if approvals.len() < config.extension_threshold {
+            return Err(EscrowError::CouncilApprovalRequired);
+        }
+        for member in approvals.iter() {
+            member.require_auth();
+        }
+        Ok(())
+    }
+
+    /// Freeze an escrow to prevent all fund movements.
+    /// Used in case of verified exploit, major dispute, or security incident.
+    /// Only the system admin or arbiter can freeze an escrow.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist
+    /// - Escrow must not already be frozen (a lapsed freeze does not count)
+    /// - `max_freeze_days` must have passed since its previous freeze ended,
+    ///   so a lapsed freeze cannot simply be renewed without the council
+    /// - Caller must be system admin or arbiter
+    /// - Reason must not be empty
+    ///
+    /// EFFECTS:
+    /// - Set is_frozen flag and expiry
+    /// - Record freeze timestamp and reason
+    /// - Append the freeze to the freeze history
+    /// - Emit EscrowFrozen event
+    pub fn freeze_escrow(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        reason: String,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Get and validate escrow
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+
+        // Check if already frozen
+        if AccessControl::is_frozen(&env, &escrow) {
+            return Err(EscrowError::AlreadyFrozen);
+        }
+
+        // Verify the previous freeze is past its cooldown
+        if let Some(previous) = EscrowStorage::get_freeze_history(&env, &escrow_id).last() {
+            let ended_at = previous.lifted_at.unwrap_or(previous.expires_at);
+            let cooldown_ends = ended_at.saturating_add(Self::freeze_duration(&env));
+            if env.ledger().timestamp() < cooldown_ends {
+                return Err(EscrowError::FreezeCooldownActive);
+            }
+        }
+
+        // Verify reason is not empty
+        if reason.is_empty() {
+            return Err(EscrowError::EmptyFreezeReason);
+        }
+
+        // Verify caller is system admin or arbiter
+        let is_admin = AccessControl::is_system_admin(&env, &caller).is_ok();
+        let is_arbiter = AccessControl::is_arbiter(&escrow, &caller).is_ok();
+
+        if !is_admin && !is_arbiter {
+            return Err(EscrowError::NotAuthorized);
+        }
+
+        // Require authorization
+        caller.require_auth();
+
+        // EFFECTS: Freeze the escrow until the maximum duration has passed
+        let timestamp = env.ledger().timestamp();
+        let expires_at = timestamp.saturating_add(Self::freeze_duration(&env));
+        escrow.is_frozen = true;
+        escrow.frozen_at = Some(timestamp);
+        escrow.frozen_until = Some(expires_at);
+        escrow.freeze_reason = Some(reason.clone());
+
+        EscrowStorage::save(&env, &escrow);
+
+        // Requests left over from a lapsed freeze do not carry over
+        EscrowStorage::clear_freeze_requests(&env, &escrow_id);
+        let mut history = EscrowStorage::get_freeze_history(&env, &escrow_id);
+        history.push_back(FreezeRecord {
+            frozen_by: caller.clone(),
+            reason: reason.clone(),
+            frozen_at: timestamp,
+            expires_at,
+            extensions: 0,
+            lifted_by: None,
+            lifted_at: None,
+        });
+        EscrowStorage::set_freeze_history(&env, &escrow_id, &history);
+
+        // Emit event
+        events::escrow_frozen(&env, escrow_id, caller, reason, timestamp, expires_at);
+
+        Ok(())
+    }
+
+    /// Unfreeze an escrow to allow fund movements again.
+    /// Only the system admin can unfreeze an escrow.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist
+    /// - Escrow must be frozen
+    /// - Caller must be system admin
+    ///
+    /// EFFECTS:
+    /// - Lift the freeze (see `lift`)
+    pub fn unfreeze_escrow(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Get and validate escrow
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+
+        // Check if escrow is frozen
+        if !AccessControl::is_frozen(&env, &escrow) {
+            return Err(EscrowError::NotFrozen);
+        }
+
+        // Verify caller is system admin
+        AccessControl::is_system_admin(&env, &caller)?;
+
+        // Require authorization
+        caller.require_auth();
+
+        Self::lift(&env, &mut escrow, &caller);
+        Ok(())
+    }
+
+    /// Approve extending an escrow's freeze (council member only). Once
+    /// `extension_threshold` members approve, the freeze is extended by
+    /// `max_freeze_days` from its current expiry.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist and be frozen
+    /// - Caller must be on the freeze council and must not be who froze the
+    ///   escrow
+    /// - Caller must not have approved the current expiry already
+    ///
+    /// EFFECTS:
+    /// - Record the approval
+    /// - On reaching the threshold, move the expiry and record the extension
+    pub fn approve_freeze_extension(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        if !AccessControl::is_frozen(&env, &escrow) {
+            return Err(EscrowError::NotFrozen);
+        }
+        let config = EscrowStorage::get_freeze_config(&env);
+        if !config.council.contains(&caller) {
+            return Err(EscrowError::NotAuthorized);
+        }
+        let mut history = EscrowStorage::get_freeze_history(&env, &escrow_id);
+        if history
+            .last()
+            .is_some_and(|record| record.frozen_by == caller)
+        {
+            return Err(EscrowError::NotAuthorized);
+        }
+        caller.require_auth();
+
+        let expires_at = AccessControl::freeze_expiry(&env, &escrow);
+        let mut extension = EscrowStorage::get_freeze_extension(&env, &escrow_id)
+            .filter(|extension| extension.expires_at == expires_at)
+            .unwrap_or(FreezeExtension {
+                expires_at,
+                approvals: Vec::new(&env),
+            });
+        if extension.approvals.contains(&caller) {
+            return Err(EscrowError::AlreadySigned);
+        }
+        extension.approvals.push_back(caller);
+
+        if extension.approvals.len() < config.extension_threshold {
+            EscrowStorage::set_freeze_extension(&env, &escrow_id, &extension);
+            return Ok(());
+        }
+
+        // EFFECTS: Threshold reached, extend the freeze
+        let new_expiry = expires_at.saturating_add(Self::freeze_duration(&env));
+        escrow.frozen_until = Some(new_expiry);
+        EscrowStorage::save(&env, &escrow);
+        EscrowStorage::clear_freeze_requests(&env, &escrow_id);
+
+        let mut extensions = 1;
+        if let Some(mut record) = history.last() {
+            record.expires_at = new_expiry;
+            record.extensions += 1;
+            extensions = record.extensions;
+            history.set(history.len() - 1, record);
+            EscrowStorage::set_freeze_history(&env, &escrow_id, &history);
+        }
+
+        events::freeze_extended(&env, escrow_id, new_expiry, extensions);
+        Ok(())
+    }
+
+    /// Petition to lift an escrow's freeze (depositor or beneficiary). The
+    /// freeze is lifted once both have petitioned.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist and be frozen
+    /// - Caller must be depositor or beneficiary, petitioning once per freeze
+    /// - Reason must not be empty
+    ///
+    /// EFFECTS:
+    /// - Record the petition
+    /// - With both petitions in, lift the freeze (see `lift`)
+    pub fn petition_unfreeze(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        reason: String,
+    ) -> Result<(), EscrowError> {
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        if !AccessControl::is_frozen(&env, &escrow) {
+            return Err(EscrowError::NotFrozen);
+        }
+        AccessControl::is_primary_party(&escrow, &caller)?;
+        if reason.is_empty() {
+            return Err(EscrowError::EmptyFreezeReason);
+        }
+        let mut petitions = EscrowStorage::get_unfreeze_petitions(&env, &escrow_id);
+        if petitions
+            .iter()
+            .any(|petition| petition.petitioner == caller)
+        {
+            return Err(EscrowError::AlreadySigned);
+        }
+        caller.require_auth();
+
+        petitions.push_back(UnfreezePetition {
+            petitioner: caller.clone(),
+            reason: reason.clone(),
+            petitioned_at: env.ledger().timestamp(),
+        });
+        EscrowStorage::set_unfreeze_petitions(&env, &escrow_id, &petitions);
+        events::unfreeze_petitioned(&env, escrow_id, caller.clone(), reason);
+
+        // Each primary party can petition once, so two petitions are both
+        if petitions.len() == 2 {
+            Self::lift(&env, &mut escrow, &caller);
+        }
+        Ok(())
+    }
+
+    /// Lift an escrow's freeze.
+    ///
+    /// EFFECTS:
+    /// - Clear the freeze fields
+    /// - Mark the freeze lifted in the history and drop its pending requests
+    /// - Emit EscrowUnfrozen event
+    fn lift(env: &Env, escrow: &mut Escrow, lifted_by: &Address) {
+        let timestamp = env.ledger().timestamp();
+        escrow.is_frozen = false;
+        escrow.frozen_at = None;
+        escrow.frozen_until = None;
+        escrow.freeze_reason = None;
+        EscrowStorage::save(env, escrow);
+        EscrowStorage::clear_freeze_requests(env, &escrow.id);
+
+        let mut history = EscrowStorage::get_freeze_history(env, &escrow.id);
+        if let Some(mut record) = history.last() {
+            record.lifted_by = Some(lifted_by.clone());
+            record.lifted_at = Some(timestamp);
+            history.set(history.len() - 1, record);
+            EscrowStorage::set_freeze_history(env, &escrow.id, &history);
+        }
+
+        events::escrow_unfrozen(env, escrow.id.clone(), lifted_by.clone(), timestamp);
+    }
+}
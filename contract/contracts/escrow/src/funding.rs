@@ -0,0 +1,209 @@
+//! Incremental funding for the Escrow contract.
+//! An escrow accepts any number of contributions, from the depositor or on
+//! its behalf, until `funded_amount` reaches `required_amount`; it then
+//! becomes Funded and everything contributed is available for release.
+//! Funded escrows can still be topped up, and the target can be raised when
+//! the depositor and beneficiary both agree; a target above what has been
+//! contributed moves the escrow back to Pending until the shortfall is paid.
+use soroban_sdk::{token, Address, BytesN, Env};
+
+use crate::access::AccessControl;
+use crate::agreement::AgreementHandler;
+use crate::errors::EscrowError;
+use crate::escrow_index;
+use crate::events;
+use crate::storage::EscrowStorage;
+use crate::types::{Contribution, Escrow, EscrowStatus, TargetIncrease};
+
+/// Escrow funding and target management.
+pub struct FundingHandler;
+
+impl FundingHandler {
+    fn require_open(escrow: &Escrow) -> Result<(), EscrowError> {
+        match escrow.status {
+            EscrowStatus::Pending | EscrowStatus::Funded => Ok(()),
+            _ => Err(EscrowError::InvalidState),
+        }
+    }
+
+    /// Record a contribution and move a Pending escrow to Funded once the
+    /// target is met. The caller transfers the tokens afterwards.
+    fn apply_contribution(
+        env: &Env,
+        escrow: &mut Escrow,
+        contributor: &Address,
+        amount: i128,
+    ) -> bool {
+        escrow.funded_amount += amount;
+        let mut became_funded = false;
+        if escrow.status == EscrowStatus::Pending {
+            if escrow.funded_amount >= escrow.required_amount {
+                escrow_index::set_status(env, escrow, EscrowStatus::Funded);
+                escrow.amount = escrow.funded_amount;
+                became_funded = true;
+            }
+        } else {
+            escrow.amount += amount;
+        }
+        EscrowStorage::save(env, escrow);
+
+        EscrowStorage::add_contribution(
+            env,
+            &escrow.id,
+            Contribution {
+                contributor: contributor.clone(),
+                amount,
+                contributed_at: env.ledger().timestamp(),
+            },
+        );
+        became_funded
+    }
+
+    /// Transfer a contribution into the escrow and publish its events.
+    pub(crate) fn contribute_unchecked(
+        env: &Env,
+        escrow: &mut Escrow,
+        contributor: &Address,
+        amount: i128,
+    ) {
+        // EFFECTS: Update funded amounts and status
+        let became_funded = Self::apply_contribution(env, escrow, contributor, amount);
+
+        // INTERACTIONS: Token transfer from contributor to escrow contract
+        let token_client = token::Client::new(env, &escrow.token);
+        token_client.transfer(contributor, env.current_contract_address(), &amount);
+
+        events::escrow_contribution(
+            env,
+            escrow.id.clone(),
+            contributor.clone(),
+            amount,
+            escrow.funded_amount,
+        );
+        if became_funded {
+            events::escrow_funded(env, escrow.id.clone(), escrow.funded_amount);
+            AgreementHandler::sync_deposit_status(env, escrow);
+        }
+    }
+
+    /// Contribute towards an escrow. Anyone may contribute on the depositor's
+    /// behalf; refunds always go to the depositor.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist, be Pending or Funded and not frozen
+    /// - Amount must be positive
+    ///
+    /// EFFECTS:
+    /// - Add to funded amount and record the contribution
+    /// - Pending → Funded once the target is met
+    ///
+    /// INTERACTIONS:
+    /// - Token transfer from contributor after state updates
+    pub fn contribute(
+        env: Env,
+        escrow_id: BytesN<32>,
+        contributor: Address,
+        amount: i128,
+    ) -> Result<(), EscrowError> {
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::require_not_frozen(&env, &escrow)?;
+        Self::require_open(&escrow)?;
+        if amount <= 0 {
+            return Err(EscrowError::InvalidAmount);
+        }
+        contributor.require_auth();
+
+        Self::contribute_unchecked(&env, &mut escrow, &contributor, amount);
+        Ok(())
+    }
+
+    /// Propose raising the funding target (depositor or beneficiary).
+    /// Replaces any earlier proposal.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist and be Pending or Funded
+    /// - Caller must be depositor or beneficiary
+    /// - New target must exceed the current one
+    /// - Milestone escrows cannot change their target
+    pub fn propose_target_increase(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        new_required_amount: i128,
+    ) -> Result<(), EscrowError> {
+        let escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_primary_party(&escrow, &caller)?;
+        Self::require_open(&escrow)?;
+        if EscrowStorage::get_milestones(&env, &escrow_id).is_some() {
+            return Err(EscrowError::InvalidState);
+        }
+        if new_required_amount <= escrow.required_amount {
+            return Err(EscrowError::InvalidAmount);
+        }
+        caller.require_auth();
+
+        let proposal = TargetIncrease {
+            proposed_by: caller.clone(),
+            new_required_amount,
+            proposed_at: env.ledger().timestamp(),
+        };
+        EscrowStorage::set_target_increase(&env, &escrow_id, &proposal);
+
+        events::target_increase_proposed(&env, escrow_id, caller, new_required_amount);
+        Ok(())
+    }
+
+    /// Accept the other party's target increase. The escrow needs the new
+    /// target before it is Funded: a Funded escrow that falls short moves
+    /// back to Pending, with its release approvals cleared, until the
+    /// shortfall is contributed.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist and be Pending or Funded
+    /// - Caller must be the primary party that did not propose
+    /// - A Funded escrow that would fall short must hold everything
+    ///   contributed: no partial release and no yield position
+    ///
+    /// EFFECTS:
+    /// - Target raised; Funded → Pending if contributions are now short
+    pub fn accept_target_increase(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_primary_party(&escrow, &caller)?;
+        Self::require_open(&escrow)?;
+        let proposal = EscrowStorage::get_target_increase(&env, &escrow_id)
+            .ok_or(EscrowError::TargetIncreaseNotFound)?;
+        if proposal.proposed_by == caller {
+            return Err(EscrowError::NotAuthorized);
+        }
+        let reopens = escrow.status == EscrowStatus::Funded
+            && escrow.funded_amount < proposal.new_required_amount;
+        if reopens
+            && (escrow.amount != escrow.funded_amount
+                || EscrowStorage::get_yield_position(&env, &escrow_id).is_some())
+        {
+            return Err(EscrowError::InvalidState);
+        }
+        caller.require_auth();
+
+        escrow.required_amount = proposal.new_required_amount;
+        if reopens {
+            escrow_index::set_status(&env, &mut escrow, EscrowStatus::Pending);
+            EscrowStorage::clear_release_approvals(&env, &escrow);
+        }
+        if escrow.status == EscrowStatus::Pending {
+            escrow.amount = escrow.required_amount;
+        }
+        EscrowStorage::save(&env, &escrow);
+        EscrowStorage::remove_target_increase(&env, &escrow_id);
+
+        events::target_increased(&env, escrow_id, escrow.required_amount);
+        if reopens {
+            AgreementHandler::sync_deposit_status(&env, &escrow);
+        }
+        Ok(())
+    }
+}
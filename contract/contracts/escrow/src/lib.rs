@@ -3,32 +3,73 @@
 
 //! Escrow Contract
 //!
-//! Manages security deposit escrows with 2-of-3 multi-sig release mechanism.
+//! Manages security deposit escrows with N-of-M multi-sig release (2-of-3 by default).
 //! Supports dispute resolution with arbiter involvement.
 
 pub mod access;
+pub mod agreement;
 pub mod dispute;
 pub mod errors;
 pub mod escrow_impl;
+pub mod escrow_index;
 pub mod events;
+pub mod freeze;
+pub mod funding;
+pub mod milestone;
 pub mod rate_limit;
 pub mod storage;
 pub mod types;
 pub mod upgrade;
+pub mod yield_vault;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod tests_agreements;
+
+#[cfg(test)]
+mod tests_escrow_index;
+
+#[cfg(test)]
+mod tests_freeze;
+
+#[cfg(test)]
+mod tests_milestones;
+
 #[cfg(test)]
 mod tests_rate_limit;
 
 #[cfg(test)]
 mod tests_rbac;
 
+#[cfg(test)]
+mod tests_signer_sets;
+
+#[cfg(test)]
+mod tests_split_resolution;
+
+#[cfg(test)]
+mod tests_top_ups;
+
+#[cfg(test)]
+mod tests_yield;
+
 // Re-export public APIs
 pub use access::AccessControl;
+pub use agreement::AgreementHandler;
 pub use dispute::DisputeHandler;
 pub use errors::EscrowError;
 pub use escrow_impl::EscrowContract;
+pub use escrow_index::MAX_PAGE_SIZE;
+pub use freeze::FreezeHandler;
+pub use funding::FundingHandler;
+pub use milestone::MilestoneHandler;
 pub use storage::EscrowStorage;
-pub use types::{DataKey, Escrow, EscrowStatus, ReleaseApproval, TimeoutConfig};
+pub use types::{
+    AgreementLink, Allocation, AllocationUnit, Contribution, DataKey, Escrow, EscrowIndex,
+    EscrowPage, EscrowStatus, FreezeConfig, FreezeRecord, Milestone, MilestoneStatus,
+    MilestoneTerms, ReleaseApproval, Signer, SignerSet, TargetIncrease, TimeoutConfig,
+    UnfreezePetition, YieldPosition, YieldRecord,
+};
+pub use yield_vault::YieldHandler;
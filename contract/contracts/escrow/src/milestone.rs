@@ -0,0 +1,353 @@
+//! Milestone escrows for staged work such as renovations and fit-outs.
+//! The escrow holds the sum of an ordered list of milestones. The beneficiary
+//! submits evidence for each milestone and the depositor or an inspector
+//! approves it, releasing that milestone's amount. A disputed milestone is
+//! settled by the arbiter through the dispute allocation path while the other
+//! milestones carry on.
+use soroban_sdk::{token, Address, BytesN, Env, String, Vec};
+
+use crate::access::AccessControl;
+use crate::dispute::DisputeHandler;
+use crate::errors::EscrowError;
+use crate::escrow_index;
+use crate::events;
+use crate::storage::EscrowStorage;
+use crate::types::{
+    Allocation, AllocationUnit, Escrow, EscrowStatus, Milestone, MilestoneStatus, MilestoneTerms,
+    ReleaseRecord,
+};
+
+/// Milestone escrow handling.
+pub struct MilestoneHandler;
+
+impl MilestoneHandler {
+    /// Upper bound on the number of milestones in an escrow.
+    pub const MAX_MILESTONES: u32 = 20;
+
+    /// Validate milestone terms and build the initial milestones.
+    /// Returns the milestones and the total amount to escrow.
+    pub(crate) fn build_milestones(
+        env: &Env,
+        terms: &Vec<MilestoneTerms>,
+    ) -> Result<(Vec<Milestone>, i128), EscrowError> {
+        if terms.is_empty() || terms.len() > Self::MAX_MILESTONES {
+            return Err(EscrowError::InvalidMilestones);
+        }
+
+        let mut milestones = Vec::new(env);
+        let mut total: i128 = 0;
+        for (index, term) in terms.iter().enumerate() {
+            if term.amount <= 0 {
+                return Err(EscrowError::InvalidAmount);
+            }
+            total = total
+                .checked_add(term.amount)
+                .ok_or(EscrowError::InvalidAmount)?;
+            milestones.push_back(Milestone {
+                index: index as u32,
+                description: term.description,
+                amount: term.amount,
+                status: MilestoneStatus::Pending,
+                evidence_hash: None,
+                submitted_at: None,
+                settled_at: None,
+                dispute_reason: None,
+            });
+        }
+        Ok((milestones, total))
+    }
+
+    /// Refuse whole-escrow releases for milestone escrows, which only pay
+    /// out through their milestones.
+    pub fn require_no_milestones(env: &Env, escrow_id: &BytesN<32>) -> Result<(), EscrowError> {
+        if EscrowStorage::get_milestones(env, escrow_id).is_some() {
+            return Err(EscrowError::InvalidState);
+        }
+        Ok(())
+    }
+
+    fn load_milestones(env: &Env, escrow_id: &BytesN<32>) -> Result<Vec<Milestone>, EscrowError> {
+        EscrowStorage::get_milestones(env, escrow_id).ok_or(EscrowError::MilestoneNotFound)
+    }
+
+    fn milestone_at(milestones: &Vec<Milestone>, index: u32) -> Result<Milestone, EscrowError> {
+        milestones.get(index).ok_or(EscrowError::MilestoneNotFound)
+    }
+
+    /// Pay a settled milestone out of the escrow balance. Once every milestone
+    /// is settled the escrow becomes Released.
+    fn settle(
+        env: &Env,
+        escrow: &mut Escrow,
+        milestones: &mut Vec<Milestone>,
+        mut milestone: Milestone,
+        status: MilestoneStatus,
+    ) {
+        milestone.status = status;
+        milestone.settled_at = Some(env.ledger().timestamp());
+        milestone.dispute_reason = None;
+        escrow.amount -= milestone.amount;
+        milestones.set(milestone.index, milestone);
+
+        let all_settled = milestones.iter().all(|milestone| {
+            milestone.status == MilestoneStatus::Released
+                || milestone.status == MilestoneStatus::Resolved
+        });
+        if all_settled {
+            escrow_index::set_status(env, escrow, EscrowStatus::Released);
+            EscrowStorage::clear_release_approvals(env, escrow);
+        }
+
+        EscrowStorage::save(env, escrow);
+        EscrowStorage::set_milestones(env, &escrow.id, milestones);
+    }
+
+    /// Submit evidence that a milestone is complete (beneficiary only).
+    /// Evidence may be resubmitted until the milestone is approved.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist, be Funded and not frozen
+    /// - Caller must be beneficiary
+    /// - Milestone must be Pending or Submitted
+    /// - Every earlier milestone must have been submitted
+    ///
+    /// EFFECTS:
+    /// - Milestone status → Submitted with the evidence hash
+    pub fn submit_milestone(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        index: u32,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Get and validate escrow
+        let escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::require_not_frozen(&env, &escrow)?;
+        AccessControl::is_beneficiary(&escrow, &caller)?;
+        if escrow.status != EscrowStatus::Funded {
+            return Err(EscrowError::InvalidState);
+        }
+        caller.require_auth();
+
+        let mut milestones = Self::load_milestones(&env, &escrow_id)?;
+        let mut milestone = Self::milestone_at(&milestones, index)?;
+        if milestone.status != MilestoneStatus::Pending
+            && milestone.status != MilestoneStatus::Submitted
+        {
+            return Err(EscrowError::InvalidState);
+        }
+        let earlier_pending = milestones
+            .iter()
+            .take(index as usize)
+            .any(|earlier| earlier.status == MilestoneStatus::Pending);
+        if earlier_pending {
+            return Err(EscrowError::MilestoneOutOfOrder);
+        }
+
+        // EFFECTS: Record the evidence
+        milestone.status = MilestoneStatus::Submitted;
+        milestone.evidence_hash = Some(evidence_hash.clone());
+        milestone.submitted_at = Some(env.ledger().timestamp());
+        milestones.set(index, milestone);
+        EscrowStorage::set_milestones(&env, &escrow_id, &milestones);
+
+        events::milestone_submitted(&env, escrow_id, index, evidence_hash);
+        Ok(())
+    }
+
+    /// Approve a submitted milestone and release its amount to the beneficiary.
+    /// The depositor or the escrow's inspector may approve.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist, be Funded and not frozen
+    /// - Caller must be depositor or inspector
+    /// - Milestone must be Submitted
+    /// - Milestone amount must not exceed the remaining balance
+    ///
+    /// EFFECTS:
+    /// - Milestone status → Released
+    /// - Escrow balance reduced; escrow Released once every milestone is settled
+    /// - Release recorded in history
+    ///
+    /// INTERACTIONS:
+    /// - Token transfer to beneficiary after state updates
+    pub fn approve_milestone(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        index: u32,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Get and validate escrow
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::require_not_frozen(&env, &escrow)?;
+        let is_inspector =
+            EscrowStorage::get_milestone_inspector(&env, &escrow_id).as_ref() == Some(&caller);
+        if caller != escrow.depositor && !is_inspector {
+            return Err(EscrowError::NotAuthorized);
+        }
+        if escrow.status != EscrowStatus::Funded {
+            return Err(EscrowError::InvalidState);
+        }
+        caller.require_auth();
+
+        let mut milestones = Self::load_milestones(&env, &escrow_id)?;
+        let milestone = Self::milestone_at(&milestones, index)?;
+        if milestone.status != MilestoneStatus::Submitted {
+            return Err(EscrowError::InvalidState);
+        }
+        if milestone.amount > escrow.amount {
+            return Err(EscrowError::InsufficientFunds);
+        }
+
+        // EFFECTS: Settle the milestone and record the release
+        let amount = milestone.amount;
+        let reason = milestone.description.clone();
+        Self::settle(
+            &env,
+            &mut escrow,
+            &mut milestones,
+            milestone,
+            MilestoneStatus::Released,
+        );
+
+        EscrowStorage::add_release_record(
+            &env,
+            &escrow_id,
+            ReleaseRecord {
+                escrow_id: escrow_id.clone(),
+                amount,
+                recipient: escrow.beneficiary.clone(),
+                released_at: env.ledger().timestamp(),
+                reason,
+            },
+        );
+
+        // INTERACTIONS: Token transfer to beneficiary
+        let token_client = token::Client::new(&env, &escrow.token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &escrow.beneficiary,
+            &amount,
+        );
+
+        events::milestone_released(&env, escrow_id, index, caller, amount);
+        Ok(())
+    }
+
+    /// Dispute a submitted milestone (depositor or beneficiary).
+    /// Only this milestone's funds are held; the escrow stays Funded so the
+    /// other milestones can still be submitted and approved.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist and be Funded
+    /// - Escrow must not be frozen
+    /// - Caller must be depositor or beneficiary
+    /// - Reason must not be empty
+    /// - Milestone must be Submitted
+    ///
+    /// EFFECTS:
+    /// - Milestone status → Disputed with the reason
+    pub fn dispute_milestone(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        index: u32,
+        reason: String,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Get and validate escrow
+        let escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::require_not_frozen(&env, &escrow)?;
+        AccessControl::is_primary_party(&escrow, &caller)?;
+        if escrow.status != EscrowStatus::Funded {
+            return Err(EscrowError::InvalidState);
+        }
+        caller.require_auth();
+        if reason.is_empty() {
+            return Err(EscrowError::EmptyDisputeReason);
+        }
+
+        let mut milestones = Self::load_milestones(&env, &escrow_id)?;
+        let mut milestone = Self::milestone_at(&milestones, index)?;
+        if milestone.status != MilestoneStatus::Submitted {
+            return Err(EscrowError::InvalidState);
+        }
+
+        // EFFECTS: Hold the milestone for the arbiter
+        milestone.status = MilestoneStatus::Disputed;
+        milestone.dispute_reason = Some(reason);
+        milestones.set(index, milestone);
+        EscrowStorage::set_milestones(&env, &escrow_id, &milestones);
+
+        events::milestone_disputed(&env, escrow_id, index, caller);
+        Ok(())
+    }
+
+    /// Resolve a disputed milestone by allocating its amount between the
+    /// parties, as `DisputeHandler::resolve_dispute_split` does for a whole
+    /// escrow (arbiter only).
+    ///
+    /// CHECKS:
+    /// - Escrow must exist, be Funded and not frozen
+    /// - Caller must be arbiter
+    /// - Reason must not be empty
+    /// - Milestone must be Disputed
+    /// - Allocation must be valid for the milestone amount
+    ///
+    /// EFFECTS:
+    /// - Milestone status → Resolved
+    /// - Escrow balance reduced; escrow Released once every milestone is settled
+    /// - A ReleaseRecord for every non-zero payout
+    ///
+    /// INTERACTIONS:
+    /// - One token transfer per non-zero payout after state updates
+    pub fn resolve_milestone_dispute(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+        index: u32,
+        unit: AllocationUnit,
+        allocations: Vec<Allocation>,
+        reason: String,
+    ) -> Result<(), EscrowError> {
+        // CHECKS: Get and validate escrow
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::require_not_frozen(&env, &escrow)?;
+        AccessControl::is_arbiter(&escrow, &caller)?;
+        if escrow.status != EscrowStatus::Funded {
+            return Err(EscrowError::InvalidState);
+        }
+        caller.require_auth();
+        if reason.is_empty() {
+            return Err(EscrowError::EmptyReleaseReason);
+        }
+
+        let mut milestones = Self::load_milestones(&env, &escrow_id)?;
+        let milestone = Self::milestone_at(&milestones, index)?;
+        if milestone.status != MilestoneStatus::Disputed {
+            return Err(EscrowError::InvalidState);
+        }
+        if milestone.amount > escrow.amount {
+            return Err(EscrowError::InsufficientFunds);
+        }
+        let payouts = DisputeHandler::allocation_amounts(
+            &env,
+            &escrow,
+            milestone.amount,
+            unit,
+            &allocations,
+        )?;
+
+        // EFFECTS: Settle the milestone, then pay out the allocation
+        Self::settle(
+            &env,
+            &mut escrow,
+            &mut milestones,
+            milestone,
+            MilestoneStatus::Resolved,
+        );
+        DisputeHandler::pay_out(&env, &escrow, &payouts, &reason);
+
+        events::milestone_resolved(&env, escrow_id, index);
+        Ok(())
+    }
+}
@@ -1,8 +1,12 @@
 //! Storage operations for the Escrow contract.
 //! Implements single-responsibility getter/setter helpers.
-use soroban_sdk::{Address, BytesN, Env, Vec};
+use soroban_sdk::{vec, Address, BytesN, Env, Map, String, Symbol, TryFromVal, Val, Vec};
 
-use crate::types::{DataKey, Escrow, ReleaseApproval, ReleaseRecord, TimeoutConfig};
+use crate::types::{
+    AgreementLink, Contribution, DataKey, Escrow, EscrowV1, FreezeConfig, FreezeExtension,
+    FreezeRecord, Milestone, ReleaseApproval, ReleaseRecord, Signer, SignerSet, TargetIncrease,
+    TimeoutConfig, UnfreezePetition, YieldPosition, YieldRecord,
+};
 
 /// Escrow storage management.
 pub struct EscrowStorage;
@@ -11,12 +15,20 @@ impl EscrowStorage {
     pub const DEFAULT_ESCROW_TIMEOUT_DAYS: u64 = 14;
     pub const DEFAULT_DISPUTE_TIMEOUT_DAYS: u64 = 30;
     pub const DEFAULT_PAYMENT_TIMEOUT_DAYS: u64 = 7;
+    pub const DEFAULT_MAX_FREEZE_DAYS: u64 = 7;
 
     /// Retrieve an escrow by ID.
-    /// Returns None if escrow doesn't exist.
+    /// Returns None if escrow doesn't exist. Escrows stored before funding
+    /// targets existed are upgraded here and rewritten on their next save.
     pub fn get(env: &Env, id: &BytesN<32>) -> Option<Escrow> {
         let key = DataKey::Escrow(id.clone());
-        env.storage().persistent().get::<_, Escrow>(&key)
+        let raw = env.storage().persistent().get::<_, Val>(&key)?;
+        let fields = Map::<Symbol, Val>::try_from_val(env, &raw).ok()?;
+        if fields.contains_key(Symbol::new(env, "required_amount")) {
+            Escrow::try_from_val(env, &raw).ok()
+        } else {
+            EscrowV1::try_from_val(env, &raw).ok().map(Escrow::from)
+        }
     }
 
     /// Save or update an escrow.
@@ -56,7 +68,9 @@ impl EscrowStorage {
         env.storage().persistent().remove(&key);
     }
 
-    /// Get the approval count for a specific release target (O(1) lookup).
+    /// Get the approval weight for a specific release target (O(1) lookup).
+    /// Under the default preset every signer weighs 1, so this is the number
+    /// of signers that approved.
     pub fn get_approval_count_for_target(
         env: &Env,
         escrow_id: &BytesN<32>,
@@ -66,11 +80,18 @@ impl EscrowStorage {
         env.storage().persistent().get::<_, u32>(&key).unwrap_or(0)
     }
 
-    /// Increment the approval count for a specific release target.
-    pub fn increment_approval_count(env: &Env, escrow_id: &BytesN<32>, release_to: &Address) {
+    /// Add a signer's weight to the approval weight for a specific release target.
+    pub fn add_approval_weight(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        release_to: &Address,
+        weight: u32,
+    ) {
         let count = Self::get_approval_count_for_target(env, escrow_id, release_to);
         let key = DataKey::ApprovalCount(escrow_id.clone(), release_to.clone());
-        env.storage().persistent().set(&key, &(count + 1));
+        env.storage()
+            .persistent()
+            .set(&key, &count.saturating_add(weight));
     }
 
     /// Check if a specific signer has already approved a specific target (O(1) lookup).
@@ -116,6 +137,116 @@ impl EscrowStorage {
         }
     }
 
+    /// Clear approvals, per-target weights and the flags of every signer in
+    /// the escrow's signer set.
+    pub fn clear_release_approvals(env: &Env, escrow: &Escrow) {
+        Self::clear_approvals(env, &escrow.id);
+        for target in [escrow.beneficiary.clone(), escrow.depositor.clone()] {
+            let count_key = DataKey::ApprovalCount(escrow.id.clone(), target.clone());
+            env.storage().persistent().remove(&count_key);
+            for signer in Self::get_signer_set(env, escrow).signers.iter() {
+                let flag_key =
+                    DataKey::SignerApproved(escrow.id.clone(), signer.address, target.clone());
+                env.storage().persistent().remove(&flag_key);
+            }
+        }
+    }
+
+    /// The 2-of-3 preset: depositor, beneficiary and arbiter with unit weights.
+    pub fn default_signer_set(env: &Env, escrow: &Escrow) -> SignerSet {
+        let signer = |address: &Address| Signer {
+            address: address.clone(),
+            weight: 1,
+        };
+        SignerSet {
+            signers: vec![
+                env,
+                signer(&escrow.depositor),
+                signer(&escrow.beneficiary),
+                signer(&escrow.arbiter),
+            ],
+            threshold: 2,
+        }
+    }
+
+    /// Retrieve the signer set of an escrow.
+    /// Falls back to the 2-of-3 preset if none was configured.
+    pub fn get_signer_set(env: &Env, escrow: &Escrow) -> SignerSet {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SignerSet(escrow.id.clone()))
+            .unwrap_or_else(|| Self::default_signer_set(env, escrow))
+    }
+
+    /// Save a custom signer set for an escrow.
+    pub fn set_signer_set(env: &Env, escrow_id: &BytesN<32>, signer_set: &SignerSet) {
+        let key = DataKey::SignerSet(escrow_id.clone());
+        env.storage().persistent().set(&key, signer_set);
+    }
+
+    /// Retrieve the milestones of an escrow.
+    /// Returns None for escrows that are not milestone escrows.
+    pub fn get_milestones(env: &Env, escrow_id: &BytesN<32>) -> Option<Vec<Milestone>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Milestones(escrow_id.clone()))
+    }
+
+    /// Save the milestones of an escrow.
+    pub fn set_milestones(env: &Env, escrow_id: &BytesN<32>, milestones: &Vec<Milestone>) {
+        let key = DataKey::Milestones(escrow_id.clone());
+        env.storage().persistent().set(&key, milestones);
+    }
+
+    /// Get the inspector allowed to approve milestones, if any.
+    pub fn get_milestone_inspector(env: &Env, escrow_id: &BytesN<32>) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MilestoneInspector(escrow_id.clone()))
+    }
+
+    /// Set the inspector allowed to approve milestones.
+    pub fn set_milestone_inspector(env: &Env, escrow_id: &BytesN<32>, inspector: &Address) {
+        let key = DataKey::MilestoneInspector(escrow_id.clone());
+        env.storage().persistent().set(&key, inspector);
+    }
+
+    /// Retrieve the funding contributions made to an escrow.
+    /// Returns empty Vec if nothing has been contributed yet.
+    pub fn get_contributions(env: &Env, escrow_id: &BytesN<32>) -> Vec<Contribution> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contributions(escrow_id.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Append a funding contribution to an escrow's history.
+    pub fn add_contribution(env: &Env, escrow_id: &BytesN<32>, contribution: Contribution) {
+        let mut contributions = Self::get_contributions(env, escrow_id);
+        contributions.push_back(contribution);
+        let key = DataKey::Contributions(escrow_id.clone());
+        env.storage().persistent().set(&key, &contributions);
+    }
+
+    /// Get the pending target increase for an escrow, if any.
+    pub fn get_target_increase(env: &Env, escrow_id: &BytesN<32>) -> Option<TargetIncrease> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TargetIncrease(escrow_id.clone()))
+    }
+
+    /// Save a target increase proposal, replacing any earlier one.
+    pub fn set_target_increase(env: &Env, escrow_id: &BytesN<32>, proposal: &TargetIncrease) {
+        let key = DataKey::TargetIncrease(escrow_id.clone());
+        env.storage().persistent().set(&key, proposal);
+    }
+
+    /// Remove the target increase proposal for an escrow.
+    pub fn remove_target_increase(env: &Env, escrow_id: &BytesN<32>) {
+        let key = DataKey::TargetIncrease(escrow_id.clone());
+        env.storage().persistent().remove(&key);
+    }
+
     /// Get the current count of escrows created.
     pub fn get_count(env: &Env) -> u32 {
         env.storage()
@@ -174,6 +305,177 @@ impl EscrowStorage {
         env.storage().persistent().set(&key, &history);
     }
 
+    /// Get the chioma contract agreement escrows are created against.
+    pub fn get_chioma_contract(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::ChiomaContract)
+    }
+
+    /// Set the chioma contract agreement escrows are created against.
+    pub fn set_chioma_contract(env: &Env, chioma_contract: &Address) {
+        env.storage()
+            .instance()
+            .set(&DataKey::ChiomaContract, chioma_contract);
+    }
+
+    /// Get the agreement an escrow was created for, if any.
+    pub fn get_agreement_link(env: &Env, escrow_id: &BytesN<32>) -> Option<AgreementLink> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EscrowAgreement(escrow_id.clone()))
+    }
+
+    /// Get the latest escrow created for an agreement, if any.
+    pub fn get_agreement_escrow(env: &Env, agreement_id: &String) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AgreementEscrow(agreement_id.clone()))
+    }
+
+    /// Get the number of escrows created so far for an agreement.
+    pub fn get_agreement_nonce(env: &Env, agreement_id: &String) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AgreementNonce(agreement_id.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Link an escrow to its agreement in both directions and advance the
+    /// agreement's nonce.
+    pub fn set_agreement_link(env: &Env, escrow_id: &BytesN<32>, link: &AgreementLink) {
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::EscrowAgreement(escrow_id.clone()), link);
+        storage.set(
+            &DataKey::AgreementEscrow(link.agreement_id.clone()),
+            escrow_id,
+        );
+        storage.set(
+            &DataKey::AgreementNonce(link.agreement_id.clone()),
+            &(link.nonce + 1),
+        );
+    }
+
+    /// Get the yield vault configured for a token, if any.
+    pub fn get_yield_vault(env: &Env, token: &Address) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::YieldVault(token.clone()))
+    }
+
+    /// Set the yield vault for a token.
+    pub fn set_yield_vault(env: &Env, token: &Address, vault: &Address) {
+        env.storage()
+            .instance()
+            .set(&DataKey::YieldVault(token.clone()), vault);
+    }
+
+    /// Get an escrow's open vault position, if any.
+    pub fn get_yield_position(env: &Env, escrow_id: &BytesN<32>) -> Option<YieldPosition> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::YieldPosition(escrow_id.clone()))
+    }
+
+    /// Save an escrow's open vault position.
+    pub fn set_yield_position(env: &Env, escrow_id: &BytesN<32>, position: &YieldPosition) {
+        let key = DataKey::YieldPosition(escrow_id.clone());
+        env.storage().persistent().set(&key, position);
+    }
+
+    /// Remove an escrow's vault position once withdrawn.
+    pub fn remove_yield_position(env: &Env, escrow_id: &BytesN<32>) {
+        let key = DataKey::YieldPosition(escrow_id.clone());
+        env.storage().persistent().remove(&key);
+    }
+
+    /// Retrieve the withdrawn vault positions of an escrow.
+    /// Returns empty Vec if none.
+    pub fn get_yield_history(env: &Env, escrow_id: &BytesN<32>) -> Vec<YieldRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::YieldHistory(escrow_id.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Append a withdrawn vault position to an escrow's history.
+    pub fn add_yield_record(env: &Env, escrow_id: &BytesN<32>, record: YieldRecord) {
+        let mut history = Self::get_yield_history(env, escrow_id);
+        history.push_back(record);
+        let key = DataKey::YieldHistory(escrow_id.clone());
+        env.storage().persistent().set(&key, &history);
+    }
+
+    /// Fetch freeze config or return defaults: freezes last up to
+    /// `DEFAULT_MAX_FREEZE_DAYS` and cannot be extended.
+    pub fn get_freeze_config(env: &Env) -> FreezeConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::FreezeConfig)
+            .unwrap_or(FreezeConfig {
+                max_freeze_days: Self::DEFAULT_MAX_FREEZE_DAYS,
+                council: Vec::new(env),
+                extension_threshold: 0,
+            })
+    }
+
+    /// Persist freeze config.
+    pub fn set_freeze_config(env: &Env, config: &FreezeConfig) {
+        env.storage().instance().set(&DataKey::FreezeConfig, config);
+    }
+
+    /// Retrieve every freeze of an escrow, oldest first.
+    /// Returns empty Vec if the escrow was never frozen.
+    pub fn get_freeze_history(env: &Env, escrow_id: &BytesN<32>) -> Vec<FreezeRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FreezeHistory(escrow_id.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Overwrite the freeze history of an escrow.
+    pub fn set_freeze_history(env: &Env, escrow_id: &BytesN<32>, history: &Vec<FreezeRecord>) {
+        let key = DataKey::FreezeHistory(escrow_id.clone());
+        env.storage().persistent().set(&key, history);
+    }
+
+    /// Get the extension approvals collected for an escrow's freeze, if any.
+    pub fn get_freeze_extension(env: &Env, escrow_id: &BytesN<32>) -> Option<FreezeExtension> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FreezeExtension(escrow_id.clone()))
+    }
+
+    /// Save the extension approvals for an escrow's freeze.
+    pub fn set_freeze_extension(env: &Env, escrow_id: &BytesN<32>, extension: &FreezeExtension) {
+        let key = DataKey::FreezeExtension(escrow_id.clone());
+        env.storage().persistent().set(&key, extension);
+    }
+
+    /// Retrieve the unfreeze petitions for an escrow's current freeze.
+    /// Returns empty Vec if none.
+    pub fn get_unfreeze_petitions(env: &Env, escrow_id: &BytesN<32>) -> Vec<UnfreezePetition> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UnfreezePetitions(escrow_id.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Save the unfreeze petitions for an escrow's current freeze.
+    pub fn set_unfreeze_petitions(
+        env: &Env,
+        escrow_id: &BytesN<32>,
+        petitions: &Vec<UnfreezePetition>,
+    ) {
+        let key = DataKey::UnfreezePetitions(escrow_id.clone());
+        env.storage().persistent().set(&key, petitions);
+    }
+
+    /// Drop the extension approvals and petitions of an escrow's last freeze.
+    pub fn clear_freeze_requests(env: &Env, escrow_id: &BytesN<32>) {
+        let storage = env.storage().persistent();
+        storage.remove(&DataKey::FreezeExtension(escrow_id.clone()));
+        storage.remove(&DataKey::UnfreezePetitions(escrow_id.clone()));
+    }
+
     /// Get the system admin address.
     /// Returns None if admin has not been set.
     pub fn get_admin(env: &Env) -> Option<Address> {
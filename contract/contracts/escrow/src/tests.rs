@@ -6,7 +6,7 @@ use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
 use soroban_sdk::{Address, Env};
 
 use crate::escrow_impl::{EscrowContract, EscrowContractClient};
-use crate::types::{EscrowStatus, TimeoutConfig};
+use crate::types::{DataKey, EscrowStatus, EscrowV1, TimeoutConfig};
 
 fn setup_test(
     env: &Env,
@@ -2019,3 +2019,65 @@ fn test_withdraw_safety_deposit_only_depositor_can_call() {
     let result = client.try_withdraw_safety_deposit(&escrow_id, &beneficiary);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_escrow_stored_before_funding_targets_is_upgraded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (
+        client,
+        depositor,
+        beneficiary,
+        arbiter,
+        platform_governance,
+        agent_referral,
+        token_address,
+    ) = setup_test(&env);
+    let escrow_id = client.create(
+        &depositor,
+        &beneficiary,
+        &arbiter,
+        &platform_governance,
+        &agent_referral,
+        &1000,
+        &token_address,
+    );
+
+    // Rewrite the record in the layout used before funding targets.
+    let escrow = client.get_escrow(&escrow_id);
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(
+            &DataKey::Escrow(escrow_id.clone()),
+            &EscrowV1 {
+                id: escrow.id.clone(),
+                depositor: escrow.depositor.clone(),
+                beneficiary: escrow.beneficiary.clone(),
+                arbiter: escrow.arbiter.clone(),
+                platform_governance: escrow.platform_governance.clone(),
+                agent_referral: escrow.agent_referral.clone(),
+                amount: 1000,
+                token: escrow.token.clone(),
+                status: EscrowStatus::Pending,
+                created_at: escrow.created_at,
+                timeout_days: escrow.timeout_days,
+                disputed_at: None,
+                dispute_reason: None,
+                is_frozen: false,
+                frozen_at: None,
+                freeze_reason: None,
+            },
+        );
+    });
+
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.required_amount, 1000);
+    assert_eq!(escrow.funded_amount, 0);
+    assert_eq!(escrow.frozen_until, None);
+
+    TokenAdminClient::new(&env, &token_address).mint(&depositor, &1000);
+    client.fund_escrow(&escrow_id, &depositor);
+    let escrow = client.get_escrow(&escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(escrow.funded_amount, 1000);
+}
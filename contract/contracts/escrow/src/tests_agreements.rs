@@ -0,0 +1,338 @@
+//! Tests for escrows created against chioma rental agreements.
+
+use soroban_sdk::testutils::{Address as _, Events};
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, Address, BytesN, Env, FromVal, String, Symbol, Vec,
+};
+
+use crate::agreement::{AgreementStatus, DepositInterestConfig, DepositStatus, DepositTerms};
+use crate::errors::EscrowError;
+use crate::escrow_impl::{EscrowContract, EscrowContractClient};
+use crate::types::EscrowStatus;
+
+/// Minimal chioma stand-in: deposit terms are kept in instance storage under
+/// their agreement ID, every deposit status report is appended to a list
+/// (or rejected once `reject_reports` is set) and a single interest
+/// configuration may be set for all agreements.
+#[contract]
+pub struct MockChiomaContract;
+
+#[contractimpl]
+impl MockChiomaContract {
+    pub fn get_deposit_terms(env: Env, agreement_id: String) -> Option<DepositTerms> {
+        env.storage().instance().get(&agreement_id)
+    }
+
+    pub fn update_deposit_status(
+        env: Env,
+        _agreement_id: String,
+        escrow_id: BytesN<32>,
+        status: DepositStatus,
+    ) {
+        if env.storage().instance().has(&symbol_short!("reject")) {
+            panic!("deposit report rejected");
+        }
+        let mut reports = Self::reports(env.clone());
+        reports.push_back((escrow_id, status));
+        env.storage()
+            .instance()
+            .set(&symbol_short!("reports"), &reports);
+    }
+
+    pub fn get_deposit_interest_config(env: Env, _agreement_id: String) -> DepositInterestConfig {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("interest"))
+            .unwrap()
+    }
+
+    pub fn set_deposit_interest_config(env: Env, config: DepositInterestConfig) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("interest"), &config);
+    }
+
+    pub fn reject_reports(env: Env) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("reject"), &true);
+    }
+
+    pub fn reports(env: Env) -> Vec<(BytesN<32>, DepositStatus)> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("reports"))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+pub(crate) struct Setup<'a> {
+    pub(crate) env: Env,
+    pub(crate) client: EscrowContractClient<'a>,
+    pub(crate) chioma: MockChiomaContractClient<'a>,
+    pub(crate) admin: Address,
+    pub(crate) landlord: Address,
+    pub(crate) tenant: Address,
+    pub(crate) arbiter: Address,
+    pub(crate) token: Address,
+    pub(crate) agreement_id: String,
+}
+
+/// An escrow contract wired to a chioma holding one active agreement with a
+/// 2_000 deposit.
+pub(crate) fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = EscrowContractClient::new(&env, &env.register(EscrowContract, ()));
+    let chioma = MockChiomaContractClient::new(&env, &env.register(MockChiomaContract, ()));
+    let admin = Address::generate(&env);
+    client.initialize_admin(&admin);
+    client.set_chioma_contract(&admin, &chioma.address);
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    TokenAdminClient::new(&env, &token).mint(&tenant, &10_000);
+
+    let agreement_id = String::from_str(&env, "LEASE-1");
+    let s = Setup {
+        arbiter: Address::generate(&env),
+        admin,
+        env,
+        client,
+        chioma,
+        landlord,
+        tenant,
+        token,
+        agreement_id,
+    };
+    put_agreement(&s, AgreementStatus::Active);
+    s
+}
+
+fn put_agreement(s: &Setup, status: AgreementStatus) {
+    let env = &s.env;
+    let terms = DepositTerms {
+        agreement_id: s.agreement_id.clone(),
+        landlord: s.landlord.clone(),
+        tenant: s.tenant.clone(),
+        security_deposit: 2_000,
+        payment_token: s.token.clone(),
+        status,
+    };
+    env.as_contract(&s.chioma.address, || {
+        env.storage().instance().set(&terms.agreement_id, &terms);
+    });
+}
+
+pub(crate) fn create(s: &Setup, depositor: &Address, amount: i128, token: &Address) -> BytesN<32> {
+    s.client.create_for_agreement(
+        &s.agreement_id,
+        depositor,
+        &s.landlord,
+        &s.arbiter,
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &amount,
+        token,
+    )
+}
+
+fn last_report(s: &Setup) -> (BytesN<32>, DepositStatus) {
+    s.chioma.reports().last().unwrap()
+}
+
+#[test]
+fn test_agreement_escrow_reports_deposit_status() {
+    let s = setup();
+    let escrow_id = create(&s, &s.tenant, 2_000, &s.token);
+
+    assert_eq!(
+        s.client.get_escrow_for_agreement(&s.agreement_id),
+        Some(escrow_id.clone())
+    );
+    let link = s.client.get_agreement_link(&escrow_id).unwrap();
+    assert_eq!(link.agreement_id, s.agreement_id);
+    assert_eq!(link.nonce, 0);
+    assert_eq!(last_report(&s), (escrow_id.clone(), DepositStatus::Pending));
+
+    s.client.fund_escrow(&escrow_id, &s.tenant);
+    assert_eq!(last_report(&s), (escrow_id.clone(), DepositStatus::Held));
+
+    s.client.initiate_dispute(
+        &escrow_id,
+        &s.landlord,
+        &String::from_str(&s.env, "Broken window"),
+    );
+    assert_eq!(last_report(&s).1, DepositStatus::Disputed);
+
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &s.landlord);
+    assert_eq!(last_report(&s).1, DepositStatus::Released);
+    assert_eq!(s.chioma.reports().len(), 4);
+
+    // A settled escrow makes way for a new one under the next nonce.
+    let next_id = create(&s, &s.tenant, 2_000, &s.token);
+    assert_ne!(next_id, escrow_id);
+    assert_eq!(s.client.get_agreement_link(&next_id).unwrap().nonce, 1);
+    assert_eq!(
+        s.client.get_escrow_for_agreement(&s.agreement_id),
+        Some(next_id)
+    );
+}
+
+#[test]
+fn test_rejected_deposit_report_does_not_block_funds() {
+    let s = setup();
+    let escrow_id = create(&s, &s.tenant, 2_000, &s.token);
+    s.chioma.reject_reports();
+
+    s.client.fund_escrow(&escrow_id, &s.tenant);
+    let failed = Symbol::new(&s.env, "deposit_sync_failed");
+    assert!(s.env.events().all().iter().any(|(contract, topics, _)| {
+        contract == s.client.address
+            && topics.get(0).map(|topic| Symbol::from_val(&s.env, &topic)) == Some(failed.clone())
+    }));
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+
+    s.client.initiate_dispute(
+        &escrow_id,
+        &s.landlord,
+        &String::from_str(&s.env, "Broken window"),
+    );
+    s.client
+        .resolve_dispute(&escrow_id, &s.arbiter, &s.landlord);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(
+        soroban_sdk::token::Client::new(&s.env, &s.token).balance(&s.landlord),
+        2_000
+    );
+    // Only the report made before chioma started rejecting them is kept.
+    assert_eq!(s.chioma.reports().len(), 1);
+}
+
+#[test]
+fn test_agreement_escrow_requires_tenant_and_landlord_auth() {
+    let s = setup();
+    create(&s, &s.tenant, 2_000, &s.token);
+
+    let signers: Vec<Address> = Vec::from_iter(
+        &s.env,
+        s.env.auths().iter().map(|(address, _)| address.clone()),
+    );
+    assert!(signers.contains(&s.tenant));
+    assert!(signers.contains(&s.landlord));
+}
+
+#[test]
+fn test_agreement_escrow_checked_against_agreement() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    let result = |depositor: &Address, amount: i128, token: &Address| {
+        s.client.try_create_for_agreement(
+            &s.agreement_id,
+            depositor,
+            &s.landlord,
+            &s.arbiter,
+            &Address::generate(&s.env),
+            &Address::generate(&s.env),
+            &amount,
+            token,
+        )
+    };
+
+    for (depositor, amount, token) in [
+        (&other, 2_000, &s.token),
+        (&s.tenant, 1_500, &s.token),
+        (&s.tenant, 2_000, &other),
+    ] {
+        assert_eq!(
+            result(depositor, amount, token),
+            Err(Ok(EscrowError::AgreementMismatch))
+        );
+    }
+
+    put_agreement(&s, AgreementStatus::Cancelled);
+    assert_eq!(
+        result(&s.tenant, 2_000, &s.token),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    put_agreement(&s, AgreementStatus::Active);
+    let escrow_id = create(&s, &s.tenant, 2_000, &s.token);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Pending
+    );
+    // The first escrow has not settled yet.
+    assert_eq!(
+        result(&s.tenant, 2_000, &s.token),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    assert_eq!(
+        s.client.try_create_for_agreement(
+            &String::from_str(&s.env, "MISSING"),
+            &s.tenant,
+            &s.landlord,
+            &s.arbiter,
+            &Address::generate(&s.env),
+            &Address::generate(&s.env),
+            &2_000,
+            &s.token,
+        ),
+        Err(Ok(EscrowError::AgreementNotFound))
+    );
+}
+
+#[test]
+fn test_agreement_escrow_requires_chioma_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = EscrowContractClient::new(&env, &env.register(EscrowContract, ()));
+
+    assert_eq!(
+        client.try_create_for_agreement(
+            &String::from_str(&env, "LEASE-1"),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &2_000,
+            &Address::generate(&env),
+        ),
+        Err(Ok(EscrowError::ChiomaContractNotSet))
+    );
+    assert_eq!(
+        client.try_set_chioma_contract(&Address::generate(&env), &Address::generate(&env)),
+        Err(Ok(EscrowError::AdminNotSet))
+    );
+}
+
+#[test]
+fn test_identical_escrows_get_distinct_ids() {
+    let s = setup();
+    let governance = Address::generate(&s.env);
+    let referral = Address::generate(&s.env);
+    let create_plain = || {
+        s.client.create(
+            &s.tenant,
+            &s.landlord,
+            &s.arbiter,
+            &governance,
+            &referral,
+            &2_000,
+            &s.token,
+        )
+    };
+
+    assert_ne!(create_plain(), create_plain());
+}
@@ -0,0 +1,164 @@
+//! Tests for the escrow indexes by party and status.
+
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{Address, BytesN, Env, String, Vec};
+
+use crate::errors::EscrowError;
+use crate::escrow_impl::{EscrowContract, EscrowContractClient};
+use crate::escrow_index::MAX_PAGE_SIZE;
+use crate::types::{EscrowPage, EscrowStatus};
+
+struct Setup<'a> {
+    env: Env,
+    client: EscrowContractClient<'a>,
+    depositor: Address,
+    arbiter: Address,
+    token: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = EscrowContractClient::new(&env, &env.register(EscrowContract, ()));
+    let depositor = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    TokenAdminClient::new(&env, &token).mint(&depositor, &10_000);
+
+    Setup {
+        arbiter: Address::generate(&env),
+        env,
+        client,
+        depositor,
+        token,
+    }
+}
+
+fn create(s: &Setup, beneficiary: &Address) -> BytesN<32> {
+    s.client.create(
+        &s.depositor,
+        beneficiary,
+        &s.arbiter,
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &1_000,
+        &s.token,
+    )
+}
+
+fn ids(env: &Env, page: &EscrowPage) -> Vec<BytesN<32>> {
+    let mut out = Vec::new(env);
+    for escrow in page.escrows.iter() {
+        out.push_back(escrow.id);
+    }
+    out
+}
+
+#[test]
+fn test_escrows_by_party_paginated() {
+    let s = setup();
+    let landlord = Address::generate(&s.env);
+    let other_landlord = Address::generate(&s.env);
+    let first = create(&s, &landlord);
+    let second = create(&s, &other_landlord);
+    let third = create(&s, &landlord);
+
+    let page = s.client.get_escrows_by_depositor(&s.depositor, &0, &2);
+    assert_eq!(page.total, 3);
+    assert_eq!(page.next_cursor, Some(2));
+    assert_eq!(
+        ids(&s.env, &page),
+        Vec::from_array(&s.env, [first.clone(), second.clone()])
+    );
+
+    let page = s.client.get_escrows_by_depositor(&s.depositor, &2, &2);
+    assert_eq!(page.next_cursor, None);
+    assert_eq!(ids(&s.env, &page), Vec::from_array(&s.env, [third.clone()]));
+
+    let page = s
+        .client
+        .get_escrows_by_beneficiary(&landlord, &0, &MAX_PAGE_SIZE);
+    assert_eq!(ids(&s.env, &page), Vec::from_array(&s.env, [first, third]));
+    let page = s
+        .client
+        .get_escrows_by_beneficiary(&other_landlord, &0, &10);
+    assert_eq!(ids(&s.env, &page), Vec::from_array(&s.env, [second]));
+
+    assert_eq!(
+        s.client.get_escrows_by_arbiter(&s.arbiter, &0, &10).total,
+        3
+    );
+    let stranger = Address::generate(&s.env);
+    let page = s.client.get_escrows_by_arbiter(&stranger, &0, &10);
+    assert_eq!(page.total, 0);
+    assert!(page.escrows.is_empty());
+
+    // Cursors past the end return an empty last page.
+    let page = s.client.get_escrows_by_depositor(&s.depositor, &5, &10);
+    assert!(page.escrows.is_empty());
+    assert_eq!(page.next_cursor, None);
+}
+
+#[test]
+fn test_status_index_follows_transitions() {
+    let s = setup();
+    let landlord = Address::generate(&s.env);
+    let first = create(&s, &landlord);
+    let second = create(&s, &landlord);
+    let by_status =
+        |status: EscrowStatus| ids(&s.env, &s.client.get_escrows_by_status(&status, &0, &10));
+
+    assert_eq!(by_status(EscrowStatus::Pending).len(), 2);
+
+    s.client.fund_escrow(&first, &s.depositor);
+    assert_eq!(
+        by_status(EscrowStatus::Pending),
+        Vec::from_array(&s.env, [second.clone()])
+    );
+    assert_eq!(
+        by_status(EscrowStatus::Funded),
+        Vec::from_array(&s.env, [first.clone()])
+    );
+
+    s.client.initiate_dispute(
+        &first,
+        &landlord,
+        &String::from_str(&s.env, "Unpaid cleaning"),
+    );
+    assert!(by_status(EscrowStatus::Funded).is_empty());
+    assert_eq!(
+        by_status(EscrowStatus::Disputed),
+        Vec::from_array(&s.env, [first.clone()])
+    );
+
+    s.client.resolve_dispute(&first, &s.arbiter, &landlord);
+    assert!(by_status(EscrowStatus::Disputed).is_empty());
+    assert_eq!(
+        by_status(EscrowStatus::Released),
+        Vec::from_array(&s.env, [first.clone()])
+    );
+
+    // Party indexes keep settled escrows.
+    assert_eq!(
+        s.client
+            .get_escrows_by_beneficiary(&landlord, &0, &10)
+            .total,
+        2
+    );
+    assert_eq!(s.client.get_escrow(&first).status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_escrow_page_size_bounds() {
+    let s = setup();
+    for limit in [0, MAX_PAGE_SIZE + 1] {
+        assert_eq!(
+            s.client
+                .try_get_escrows_by_depositor(&s.depositor, &0, &limit),
+            Err(Ok(EscrowError::InvalidPageSize))
+        );
+    }
+}
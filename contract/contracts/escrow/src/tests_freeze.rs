@@ -0,0 +1,365 @@
+//! Tests for time-boxed freezes, council extensions and unfreeze petitions.
+
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{vec, Address, BytesN, Env, String, Vec};
+
+use crate::errors::EscrowError;
+use crate::escrow_impl::{EscrowContract, EscrowContractClient};
+use crate::freeze::FreezeHandler;
+use crate::storage::EscrowStorage;
+use crate::types::FreezeConfig;
+
+const DAY: u64 = 86_400;
+
+struct Setup<'a> {
+    env: Env,
+    client: EscrowContractClient<'a>,
+    admin: Address,
+    depositor: Address,
+    beneficiary: Address,
+    arbiter: Address,
+    escrow_id: BytesN<32>,
+}
+
+/// A funded escrow frozen by the admin at t = 1_000 under the default
+/// seven-day limit.
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let client = EscrowContractClient::new(&env, &env.register(EscrowContract, ()));
+    let admin = Address::generate(&env);
+    client.initialize_admin(&admin);
+
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    TokenAdminClient::new(&env, &token).mint(&depositor, &1_000);
+    let escrow_id = client.create(
+        &depositor,
+        &beneficiary,
+        &arbiter,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &1_000,
+        &token,
+    );
+    client.fund_escrow(&escrow_id, &depositor);
+    client.freeze_escrow(&escrow_id, &admin, &String::from_str(&env, "Exploit"));
+
+    Setup {
+        env,
+        client,
+        admin,
+        depositor,
+        beneficiary,
+        arbiter,
+        escrow_id,
+    }
+}
+
+fn advance_to(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|li| li.timestamp = timestamp);
+}
+
+fn reason(s: &Setup, text: &str) -> String {
+    String::from_str(&s.env, text)
+}
+
+#[test]
+fn test_freeze_lapses_after_max_duration() {
+    let s = setup();
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.frozen_until, Some(1_000 + 7 * DAY));
+    assert_eq!(
+        s.client
+            .try_approve_release(&s.escrow_id, &s.depositor, &s.beneficiary),
+        Err(Ok(EscrowError::EscrowFrozen))
+    );
+
+    advance_to(&s.env, 1_000 + 7 * DAY);
+    assert!(!s.client.is_escrow_frozen(&s.escrow_id));
+    assert_eq!(
+        s.client.try_unfreeze_escrow(&s.escrow_id, &s.admin),
+        Err(Ok(EscrowError::NotFrozen))
+    );
+    s.client
+        .approve_release(&s.escrow_id, &s.depositor, &s.beneficiary);
+
+    // A lapsed freeze cannot be renewed straight away.
+    assert_eq!(
+        s.client
+            .try_freeze_escrow(&s.escrow_id, &s.arbiter, &reason(&s, "Second look")),
+        Err(Ok(EscrowError::FreezeCooldownActive))
+    );
+
+    // It leaves an open record; a new freeze after the cooldown starts another.
+    advance_to(&s.env, 1_000 + 14 * DAY);
+    s.client
+        .freeze_escrow(&s.escrow_id, &s.arbiter, &reason(&s, "Second look"));
+    let history = s.client.get_freeze_history(&s.escrow_id);
+    assert_eq!(history.len(), 2);
+    let first = history.get(0).unwrap();
+    assert_eq!(first.frozen_by, s.admin);
+    assert_eq!(first.expires_at, 1_000 + 7 * DAY);
+    assert_eq!(first.lifted_at, None);
+    assert_eq!(history.get(1).unwrap().frozen_by, s.arbiter);
+}
+
+#[test]
+fn test_council_extends_freeze() {
+    let s = setup();
+    let council: Vec<Address> = vec![
+        &s.env,
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+    ];
+    s.client.set_freeze_config(
+        &s.admin,
+        &FreezeConfig {
+            max_freeze_days: 7,
+            council: council.clone(),
+            extension_threshold: 2,
+        },
+        &Vec::new(&s.env),
+    );
+    let first = council.get(0).unwrap();
+
+    s.client.approve_freeze_extension(&s.escrow_id, &first);
+    assert_eq!(
+        s.client.try_approve_freeze_extension(&s.escrow_id, &first),
+        Err(Ok(EscrowError::AlreadySigned))
+    );
+    // The system admin alone is not on the council.
+    assert_eq!(
+        s.client
+            .try_approve_freeze_extension(&s.escrow_id, &s.admin),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+    assert_eq!(
+        s.client.get_escrow(&s.escrow_id).frozen_until,
+        Some(1_000 + 7 * DAY)
+    );
+
+    s.client
+        .approve_freeze_extension(&s.escrow_id, &council.get(2).unwrap());
+    assert_eq!(
+        s.client.get_escrow(&s.escrow_id).frozen_until,
+        Some(1_000 + 14 * DAY)
+    );
+    advance_to(&s.env, 1_000 + 10 * DAY);
+    assert!(s.client.is_escrow_frozen(&s.escrow_id));
+
+    // Approvals start over for the next extension.
+    s.client.approve_freeze_extension(&s.escrow_id, &first);
+    let record = s.client.get_freeze_history(&s.escrow_id).get(0).unwrap();
+    assert_eq!(record.extensions, 1);
+    assert_eq!(record.expires_at, 1_000 + 14 * DAY);
+}
+
+#[test]
+fn test_freezer_cannot_extend_own_freeze() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    s.client.set_freeze_config(
+        &s.admin,
+        &FreezeConfig {
+            max_freeze_days: 7,
+            council: vec![&s.env, s.admin.clone(), other.clone()],
+            extension_threshold: 2,
+        },
+        &Vec::new(&s.env),
+    );
+
+    s.client.approve_freeze_extension(&s.escrow_id, &other);
+    assert_eq!(
+        s.client
+            .try_approve_freeze_extension(&s.escrow_id, &s.admin),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+    assert_eq!(
+        s.client.get_escrow(&s.escrow_id).frozen_until,
+        Some(1_000 + 7 * DAY)
+    );
+}
+
+#[test]
+fn test_parties_lift_freeze_by_petition() {
+    let s = setup();
+    s.client
+        .petition_unfreeze(&s.escrow_id, &s.depositor, &reason(&s, "Funds needed"));
+    assert!(s.client.is_escrow_frozen(&s.escrow_id));
+    assert_eq!(
+        s.client
+            .try_petition_unfreeze(&s.escrow_id, &s.depositor, &reason(&s, "Again")),
+        Err(Ok(EscrowError::AlreadySigned))
+    );
+    assert_eq!(
+        s.client
+            .try_petition_unfreeze(&s.escrow_id, &s.arbiter, &reason(&s, "Lift")),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+    assert_eq!(s.client.get_unfreeze_petitions(&s.escrow_id).len(), 1);
+
+    advance_to(&s.env, 2_000);
+    s.client
+        .petition_unfreeze(&s.escrow_id, &s.beneficiary, &reason(&s, "Agreed"));
+    assert!(!s.client.is_escrow_frozen(&s.escrow_id));
+    assert!(s.client.get_unfreeze_petitions(&s.escrow_id).is_empty());
+
+    let record = s.client.get_freeze_history(&s.escrow_id).get(0).unwrap();
+    assert_eq!(record.lifted_by, Some(s.beneficiary.clone()));
+    assert_eq!(record.lifted_at, Some(2_000));
+    assert_eq!(
+        s.client
+            .try_petition_unfreeze(&s.escrow_id, &s.depositor, &reason(&s, "Late")),
+        Err(Ok(EscrowError::NotFrozen))
+    );
+
+    // The cooldown runs from when the freeze was lifted.
+    advance_to(&s.env, 2_000 + 7 * DAY - 1);
+    assert_eq!(
+        s.client
+            .try_freeze_escrow(&s.escrow_id, &s.admin, &reason(&s, "Again")),
+        Err(Ok(EscrowError::FreezeCooldownActive))
+    );
+    advance_to(&s.env, 2_000 + 7 * DAY);
+    s.client
+        .freeze_escrow(&s.escrow_id, &s.admin, &reason(&s, "Again"));
+}
+
+#[test]
+fn test_freeze_config_validation() {
+    let s = setup();
+    let member = Address::generate(&s.env);
+    let config =
+        |max_freeze_days: u64, council: Vec<Address>, extension_threshold: u32| FreezeConfig {
+            max_freeze_days,
+            council,
+            extension_threshold,
+        };
+    let pair = vec![&s.env, member.clone(), Address::generate(&s.env)];
+
+    for invalid in [
+        config(0, Vec::new(&s.env), 0),
+        config(7, Vec::new(&s.env), 1),
+        config(7, pair.clone(), 1),
+        config(7, pair.clone(), 3),
+        config(7, vec![&s.env, member.clone(), member.clone()], 2),
+    ] {
+        assert_eq!(
+            s.client
+                .try_set_freeze_config(&s.admin, &invalid, &Vec::new(&s.env)),
+            Err(Ok(EscrowError::InvalidFreezeConfig))
+        );
+    }
+    assert_eq!(
+        s.client.try_set_freeze_config(
+            &s.depositor,
+            &config(7, pair.clone(), 2),
+            &Vec::new(&s.env)
+        ),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+
+    s.client
+        .set_freeze_config(&s.admin, &config(3, pair, 2), &Vec::new(&s.env));
+    assert_eq!(s.client.get_freeze_config().max_freeze_days, 3);
+}
+
+#[test]
+fn test_freeze_duration_is_capped() {
+    let s = setup();
+    let config = |max_freeze_days: u64| FreezeConfig {
+        max_freeze_days,
+        council: Vec::new(&s.env),
+        extension_threshold: 0,
+    };
+
+    assert_eq!(
+        s.client.try_set_freeze_config(
+            &s.admin,
+            &config(FreezeHandler::MAX_FREEZE_DAYS + 1),
+            &Vec::new(&s.env)
+        ),
+        Err(Ok(EscrowError::InvalidFreezeConfig))
+    );
+    s.client.set_freeze_config(
+        &s.admin,
+        &config(FreezeHandler::MAX_FREEZE_DAYS),
+        &Vec::new(&s.env),
+    );
+    assert_eq!(
+        s.client.get_freeze_config().max_freeze_days,
+        FreezeHandler::MAX_FREEZE_DAYS
+    );
+}
+
+#[test]
+fn test_council_change_needs_current_council() {
+    let s = setup();
+    let council: Vec<Address> = vec![
+        &s.env,
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+        Address::generate(&s.env),
+    ];
+    let config = |council: Vec<Address>, extension_threshold: u32| FreezeConfig {
+        max_freeze_days: 7,
+        council,
+        extension_threshold,
+    };
+    s.client
+        .set_freeze_config(&s.admin, &config(council.clone(), 2), &Vec::new(&s.env));
+
+    let replacement = vec![&s.env, Address::generate(&s.env), Address::generate(&s.env)];
+    let first = council.get(0).unwrap();
+    for approvals in [
+        Vec::new(&s.env),
+        vec![&s.env, first.clone()],
+        vec![&s.env, first.clone(), first.clone()],
+        vec![&s.env, first.clone(), replacement.get(0).unwrap()],
+    ] {
+        assert_eq!(
+            s.client
+                .try_set_freeze_config(&s.admin, &config(replacement.clone(), 2), &approvals),
+            Err(Ok(EscrowError::CouncilApprovalRequired))
+        );
+    }
+
+    // The duration can still change without the council.
+    let mut longer = config(council.clone(), 2);
+    longer.max_freeze_days = 14;
+    s.client
+        .set_freeze_config(&s.admin, &longer, &Vec::new(&s.env));
+
+    s.client.set_freeze_config(
+        &s.admin,
+        &config(replacement.clone(), 2),
+        &vec![&s.env, first, council.get(2).unwrap()],
+    );
+    assert_eq!(s.client.get_freeze_config().council, replacement);
+}
+
+#[test]
+fn test_freeze_without_expiry_lapses_after_max_duration() {
+    let s = setup();
+    // A freeze recorded before time-boxing has no `frozen_until`.
+    let mut escrow = s.client.get_escrow(&s.escrow_id);
+    escrow.frozen_until = None;
+    s.env.as_contract(&s.client.address, || {
+        EscrowStorage::save(&s.env, &escrow);
+    });
+    assert!(s.client.is_escrow_frozen(&s.escrow_id));
+
+    advance_to(&s.env, 1_000 + 7 * DAY);
+    assert!(!s.client.is_escrow_frozen(&s.escrow_id));
+    s.client
+        .approve_release(&s.escrow_id, &s.depositor, &s.beneficiary);
+}
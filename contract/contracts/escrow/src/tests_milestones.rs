@@ -0,0 +1,307 @@
+//! Tests for milestone escrows.
+
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{vec, Address, BytesN, Env, String, Vec};
+
+use crate::errors::EscrowError;
+use crate::escrow_impl::{EscrowContract, EscrowContractClient};
+use crate::types::{Allocation, AllocationUnit, EscrowStatus, MilestoneStatus, MilestoneTerms};
+
+struct Setup<'a> {
+    env: Env,
+    client: EscrowContractClient<'a>,
+    landlord: Address,
+    contractor: Address,
+    arbiter: Address,
+    inspector: Address,
+    token: TokenClient<'a>,
+    escrow_id: BytesN<32>,
+}
+
+/// A funded three-stage renovation: the landlord deposits, the contractor
+/// is paid per milestone and an inspector may sign off on the work.
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EscrowContract, ());
+    let client = EscrowContractClient::new(&env, &contract_id);
+    let landlord = Address::generate(&env);
+    let contractor = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let inspector = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    let escrow_id = client.create_milestone_escrow(
+        &landlord,
+        &contractor,
+        &arbiter,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &token_address,
+        &vec![
+            &env,
+            terms(&env, "Demolition", 1_000),
+            terms(&env, "Plumbing", 2_000),
+            terms(&env, "Finishing", 3_000),
+        ],
+        &Some(inspector.clone()),
+    );
+    TokenAdminClient::new(&env, &token_address).mint(&landlord, &6_000);
+    client.fund_escrow(&escrow_id, &landlord);
+
+    Setup {
+        token: TokenClient::new(&env, &token_address),
+        env,
+        client,
+        landlord,
+        contractor,
+        arbiter,
+        inspector,
+        escrow_id,
+    }
+}
+
+fn terms(env: &Env, description: &str, amount: i128) -> MilestoneTerms {
+    MilestoneTerms {
+        description: String::from_str(env, description),
+        amount,
+    }
+}
+
+fn evidence(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+fn status(s: &Setup, index: u32) -> MilestoneStatus {
+    s.client
+        .get_milestones(&s.escrow_id)
+        .get(index)
+        .unwrap()
+        .status
+}
+
+#[test]
+fn test_milestones_released_in_stages() {
+    let s = setup();
+    assert_eq!(s.client.get_escrow(&s.escrow_id).amount, 6_000);
+
+    s.client
+        .submit_milestone(&s.escrow_id, &s.contractor, &0, &evidence(&s.env, 1));
+    s.client.approve_milestone(&s.escrow_id, &s.landlord, &0);
+    assert_eq!(s.token.balance(&s.contractor), 1_000);
+    assert_eq!(status(&s, 0), MilestoneStatus::Released);
+    assert_eq!(s.client.get_escrow(&s.escrow_id).amount, 5_000);
+
+    s.client
+        .submit_milestone(&s.escrow_id, &s.contractor, &1, &evidence(&s.env, 2));
+    s.client.approve_milestone(&s.escrow_id, &s.inspector, &1);
+    s.client
+        .submit_milestone(&s.escrow_id, &s.contractor, &2, &evidence(&s.env, 3));
+    s.client.approve_milestone(&s.escrow_id, &s.landlord, &2);
+
+    assert_eq!(s.token.balance(&s.contractor), 6_000);
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.amount, 0);
+
+    let history = s.client.get_release_history(&s.escrow_id);
+    assert_eq!(history.len(), 3);
+    assert_eq!(
+        history.get(1).unwrap().reason,
+        String::from_str(&s.env, "Plumbing")
+    );
+}
+
+#[test]
+fn test_disputed_milestone_does_not_block_others() {
+    let s = setup();
+    s.client
+        .submit_milestone(&s.escrow_id, &s.contractor, &0, &evidence(&s.env, 1));
+    s.client.approve_milestone(&s.escrow_id, &s.landlord, &0);
+    s.client
+        .submit_milestone(&s.escrow_id, &s.contractor, &1, &evidence(&s.env, 2));
+    s.client.dispute_milestone(
+        &s.escrow_id,
+        &s.landlord,
+        &1,
+        &String::from_str(&s.env, "Leaking joints"),
+    );
+    assert_eq!(status(&s, 1), MilestoneStatus::Disputed);
+    assert_eq!(
+        s.client
+            .try_approve_milestone(&s.escrow_id, &s.landlord, &1),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    // Work continues on the next milestone while the dispute is open.
+    s.client
+        .submit_milestone(&s.escrow_id, &s.contractor, &2, &evidence(&s.env, 3));
+    s.client.approve_milestone(&s.escrow_id, &s.inspector, &2);
+    assert_eq!(s.token.balance(&s.contractor), 4_000);
+    assert_eq!(
+        s.client.get_escrow(&s.escrow_id).status,
+        EscrowStatus::Funded
+    );
+
+    // The arbiter splits the disputed milestone 50/50, settling the escrow.
+    s.client.resolve_milestone_dispute(
+        &s.escrow_id,
+        &s.arbiter,
+        &1,
+        &AllocationUnit::BasisPoints,
+        &vec![
+            &s.env,
+            Allocation {
+                recipient: s.contractor.clone(),
+                share: 5_000,
+            },
+            Allocation {
+                recipient: s.landlord.clone(),
+                share: 5_000,
+            },
+        ],
+        &String::from_str(&s.env, "Partial rework needed"),
+    );
+    assert_eq!(status(&s, 1), MilestoneStatus::Resolved);
+    assert_eq!(s.token.balance(&s.contractor), 5_000);
+    assert_eq!(s.token.balance(&s.landlord), 1_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+    assert_eq!(
+        s.client.get_escrow(&s.escrow_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_milestone_checks() {
+    let s = setup();
+    let env = &s.env;
+
+    assert_eq!(
+        s.client
+            .try_submit_milestone(&s.escrow_id, &s.contractor, &1, &evidence(env, 1)),
+        Err(Ok(EscrowError::MilestoneOutOfOrder))
+    );
+    assert_eq!(
+        s.client
+            .try_submit_milestone(&s.escrow_id, &s.landlord, &0, &evidence(env, 1)),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+    assert_eq!(
+        s.client
+            .try_submit_milestone(&s.escrow_id, &s.contractor, &3, &evidence(env, 1)),
+        Err(Ok(EscrowError::MilestoneNotFound))
+    );
+    assert_eq!(
+        s.client
+            .try_approve_milestone(&s.escrow_id, &s.landlord, &0),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    s.client
+        .submit_milestone(&s.escrow_id, &s.contractor, &0, &evidence(env, 1));
+    assert_eq!(
+        s.client
+            .try_approve_milestone(&s.escrow_id, &s.contractor, &0),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+    assert_eq!(
+        s.client.try_approve_milestone(&s.escrow_id, &s.arbiter, &0),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+
+    let create = |milestones: Vec<MilestoneTerms>, inspector: Option<Address>| {
+        s.client.try_create_milestone_escrow(
+            &s.landlord,
+            &s.contractor,
+            &s.arbiter,
+            &Address::generate(env),
+            &Address::generate(env),
+            &s.token.address,
+            &milestones,
+            &inspector,
+        )
+    };
+    assert_eq!(
+        create(Vec::new(env), None),
+        Err(Ok(EscrowError::InvalidMilestones))
+    );
+    assert_eq!(
+        create(vec![env, terms(env, "Free", 0)], None),
+        Err(Ok(EscrowError::InvalidAmount))
+    );
+    assert_eq!(
+        create(
+            vec![env, terms(env, "Works", 100)],
+            Some(s.contractor.clone())
+        ),
+        Err(Ok(EscrowError::InvalidSigner))
+    );
+}
+
+#[test]
+fn test_whole_escrow_releases_refused_for_milestones() {
+    let s = setup();
+    let reason = String::from_str(&s.env, "Early payout");
+
+    assert_eq!(
+        s.client
+            .try_approve_release(&s.escrow_id, &s.landlord, &s.contractor),
+        Err(Ok(EscrowError::InvalidState))
+    );
+    assert_eq!(
+        s.client
+            .try_approve_partial_release(&s.escrow_id, &s.landlord, &s.contractor),
+        Err(Ok(EscrowError::InvalidState))
+    );
+    assert_eq!(
+        s.client
+            .try_release_escrow_partial(&s.escrow_id, &500, &s.contractor, &reason),
+        Err(Ok(EscrowError::InvalidState))
+    );
+    assert_eq!(
+        s.client
+            .try_release_with_deduction(&s.escrow_id, &0, &reason),
+        Err(Ok(EscrowError::InvalidState))
+    );
+    assert_eq!(
+        s.client.try_release_rent(&s.escrow_id, &s.arbiter),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    s.env.ledger().with_mut(|li| li.timestamp += 15 * 86_400);
+    assert_eq!(
+        s.client.try_release_escrow_on_timeout(&s.escrow_id),
+        Err(Ok(EscrowError::InvalidState))
+    );
+    assert_eq!(s.token.balance(&s.contractor), 0);
+    assert_eq!(s.client.get_escrow(&s.escrow_id).amount, 6_000);
+}
+
+#[test]
+fn test_milestone_dispute_refused_while_frozen() {
+    let s = setup();
+    s.client
+        .submit_milestone(&s.escrow_id, &s.contractor, &0, &evidence(&s.env, 1));
+    s.client.freeze_escrow(
+        &s.escrow_id,
+        &s.arbiter,
+        &String::from_str(&s.env, "Investigation"),
+    );
+
+    assert_eq!(
+        s.client.try_dispute_milestone(
+            &s.escrow_id,
+            &s.landlord,
+            &0,
+            &String::from_str(&s.env, "Poor work")
+        ),
+        Err(Ok(EscrowError::EscrowFrozen))
+    );
+    assert_eq!(status(&s, 0), MilestoneStatus::Submitted);
+}
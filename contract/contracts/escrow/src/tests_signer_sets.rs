@@ -0,0 +1,220 @@
+//! Tests for N-of-M signer sets and weighted approvals.
+
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{vec, Address, BytesN, Env, String, Vec};
+
+use crate::errors::EscrowError;
+use crate::escrow_impl::{EscrowContract, EscrowContractClient};
+use crate::types::{EscrowStatus, Signer, SignerSet};
+
+struct Setup<'a> {
+    env: Env,
+    client: EscrowContractClient<'a>,
+    depositor: Address,
+    beneficiary: Address,
+    arbiter: Address,
+    co_landlord: Address,
+    manager: Address,
+    token: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EscrowContract, ());
+    let client = EscrowContractClient::new(&env, &contract_id);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    Setup {
+        depositor: Address::generate(&env),
+        beneficiary: Address::generate(&env),
+        arbiter: Address::generate(&env),
+        co_landlord: Address::generate(&env),
+        manager: Address::generate(&env),
+        client,
+        token,
+        env,
+    }
+}
+
+fn signer(address: &Address, weight: u32) -> Signer {
+    Signer {
+        address: address.clone(),
+        weight,
+    }
+}
+
+fn funded_escrow(s: &Setup, amount: i128, signer_set: SignerSet) -> BytesN<32> {
+    let escrow_id = s.client.create_with_signers(
+        &s.depositor,
+        &s.beneficiary,
+        &s.arbiter,
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &amount,
+        &s.token,
+        &signer_set,
+    );
+    TokenAdminClient::new(&s.env, &s.token).mint(&s.depositor, &amount);
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    escrow_id
+}
+
+#[test]
+fn test_default_preset_is_two_of_three() {
+    let s = setup();
+    let escrow_id = s.client.create(
+        &s.depositor,
+        &s.beneficiary,
+        &s.arbiter,
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &1_000,
+        &s.token,
+    );
+
+    let signer_set = s.client.get_signer_set(&escrow_id);
+    assert_eq!(signer_set.threshold, 2);
+    assert_eq!(
+        signer_set.signers,
+        vec![
+            &s.env,
+            signer(&s.depositor, 1),
+            signer(&s.beneficiary, 1),
+            signer(&s.arbiter, 1),
+        ]
+    );
+}
+
+#[test]
+fn test_weighted_signers_release() {
+    let s = setup();
+    // Threshold 3 of a total weight of 6: the co-landlord and manager alone
+    // are not enough, but either primary party tips it over.
+    let escrow_id = funded_escrow(
+        &s,
+        1_000,
+        SignerSet {
+            signers: vec![
+                &s.env,
+                signer(&s.depositor, 2),
+                signer(&s.beneficiary, 2),
+                signer(&s.co_landlord, 1),
+                signer(&s.manager, 1),
+            ],
+            threshold: 3,
+        },
+    );
+
+    // The arbiter is not part of this set.
+    assert_eq!(
+        s.client
+            .try_approve_release(&escrow_id, &s.arbiter, &s.beneficiary),
+        Err(Ok(EscrowError::InvalidSigner))
+    );
+
+    s.client
+        .approve_release(&escrow_id, &s.co_landlord, &s.beneficiary);
+    s.client
+        .approve_release(&escrow_id, &s.manager, &s.beneficiary);
+    assert_eq!(s.client.get_approval_count(&escrow_id, &s.beneficiary), 2);
+    assert_eq!(s.client.get_escrow(&escrow_id).status, EscrowStatus::Funded);
+
+    s.client
+        .approve_release(&escrow_id, &s.beneficiary, &s.beneficiary);
+    assert_eq!(
+        s.client.get_escrow(&escrow_id).status,
+        EscrowStatus::Released
+    );
+    assert_eq!(
+        TokenClient::new(&s.env, &s.token).balance(&s.beneficiary),
+        1_000
+    );
+    assert_eq!(s.client.get_approval_count(&escrow_id, &s.beneficiary), 0);
+}
+
+#[test]
+fn test_partial_release_uses_threshold() {
+    let s = setup();
+    let escrow_id = funded_escrow(
+        &s,
+        1_000,
+        SignerSet {
+            signers: vec![
+                &s.env,
+                signer(&s.depositor, 1),
+                signer(&s.beneficiary, 1),
+                signer(&s.co_landlord, 1),
+            ],
+            threshold: 3,
+        },
+    );
+    let reason = String::from_str(&s.env, "Cleaning");
+
+    s.client
+        .approve_partial_release(&escrow_id, &s.depositor, &s.beneficiary);
+    s.client
+        .approve_partial_release(&escrow_id, &s.beneficiary, &s.beneficiary);
+    assert_eq!(
+        s.client
+            .try_release_escrow_partial(&escrow_id, &200, &s.beneficiary, &reason),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+
+    s.client
+        .approve_partial_release(&escrow_id, &s.co_landlord, &s.beneficiary);
+    s.client
+        .release_escrow_partial(&escrow_id, &200, &s.beneficiary, &reason);
+    assert_eq!(s.client.get_escrow(&escrow_id).amount, 800);
+}
+
+#[test]
+fn test_invalid_signer_sets_rejected() {
+    let s = setup();
+    let env = &s.env;
+    let too_many = {
+        let mut signers = Vec::new(env);
+        for _ in 0..21 {
+            signers.push_back(signer(&Address::generate(env), 1));
+        }
+        signers
+    };
+
+    let cases = [
+        (Vec::new(env), 1),
+        (vec![env, signer(&s.depositor, 1)], 0),
+        (
+            vec![env, signer(&s.depositor, 1), signer(&s.beneficiary, 1)],
+            3,
+        ),
+        (
+            vec![env, signer(&s.depositor, 0), signer(&s.beneficiary, 2)],
+            2,
+        ),
+        (
+            vec![env, signer(&s.depositor, 1), signer(&s.depositor, 1)],
+            2,
+        ),
+        (too_many, 2),
+    ];
+    for (signers, threshold) in cases {
+        assert_eq!(
+            s.client.try_create_with_signers(
+                &s.depositor,
+                &s.beneficiary,
+                &s.arbiter,
+                &Address::generate(env),
+                &Address::generate(env),
+                &1_000,
+                &s.token,
+                &SignerSet { signers, threshold },
+            ),
+            Err(Ok(EscrowError::InvalidSignerSet))
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Tests for split-outcome dispute resolution.
+
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{vec, Address, BytesN, Env, String};
+
+use crate::errors::EscrowError;
+use crate::escrow_impl::{EscrowContract, EscrowContractClient};
+use crate::types::{Allocation, AllocationUnit, EscrowStatus};
+
+struct Setup<'a> {
+    env: Env,
+    client: EscrowContractClient<'a>,
+    depositor: Address,
+    beneficiary: Address,
+    arbiter: Address,
+    token: TokenClient<'a>,
+    escrow_id: BytesN<32>,
+}
+
+/// A funded escrow of `amount` under dispute.
+fn setup<'a>(amount: i128) -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EscrowContract, ());
+    let client = EscrowContractClient::new(&env, &contract_id);
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    let escrow_id = client.create(
+        &depositor,
+        &beneficiary,
+        &arbiter,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &amount,
+        &token_address,
+    );
+    TokenAdminClient::new(&env, &token_address).mint(&depositor, &amount);
+    client.fund_escrow(&escrow_id, &depositor);
+    client.initiate_dispute(
+        &escrow_id,
+        &depositor,
+        &String::from_str(&env, "Damage claim disputed"),
+    );
+
+    Setup {
+        token: TokenClient::new(&env, &token_address),
+        env,
+        client,
+        depositor,
+        beneficiary,
+        arbiter,
+        escrow_id,
+    }
+}
+
+fn allocation(recipient: &Address, share: i128) -> Allocation {
+    Allocation {
+        recipient: recipient.clone(),
+        share,
+    }
+}
+
+#[test]
+fn test_split_by_basis_points() {
+    let s = setup(1_001);
+    let reason = String::from_str(&s.env, "Damage to flooring");
+    s.client.resolve_dispute_split(
+        &s.escrow_id,
+        &s.arbiter,
+        &AllocationUnit::BasisPoints,
+        &vec![
+            &s.env,
+            allocation(&s.beneficiary, 6_000),
+            allocation(&s.depositor, 4_000),
+        ],
+        &reason,
+    );
+
+    // The rounding remainder goes to the last recipient.
+    assert_eq!(s.token.balance(&s.beneficiary), 600);
+    assert_eq!(s.token.balance(&s.depositor), 401);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.dispute_reason, None);
+
+    let history = s.client.get_release_history(&s.escrow_id);
+    assert_eq!(history.len(), 2);
+    let first = history.get(0).unwrap();
+    assert_eq!(first.recipient, s.beneficiary);
+    assert_eq!(first.amount, 600);
+    assert_eq!(first.reason, reason);
+    assert_eq!(history.get(1).unwrap().amount, 401);
+}
+
+#[test]
+fn test_split_by_amount() {
+    let s = setup(1_000);
+    s.client.resolve_dispute_split(
+        &s.escrow_id,
+        &s.arbiter,
+        &AllocationUnit::Amount,
+        &vec![
+            &s.env,
+            allocation(&s.depositor, 1_000),
+            allocation(&s.beneficiary, 0),
+        ],
+        &String::from_str(&s.env, "Claim rejected"),
+    );
+
+    assert_eq!(s.token.balance(&s.depositor), 1_000);
+    assert_eq!(s.token.balance(&s.beneficiary), 0);
+    // Zero shares are not paid out or recorded.
+    assert_eq!(s.client.get_release_history(&s.escrow_id).len(), 1);
+}
+
+#[test]
+fn test_full_resolution_is_recorded() {
+    let s = setup(1_000);
+    s.client
+        .resolve_dispute(&s.escrow_id, &s.arbiter, &s.beneficiary);
+
+    assert_eq!(s.token.balance(&s.beneficiary), 1_000);
+    let history = s.client.get_release_history(&s.escrow_id);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().recipient, s.beneficiary);
+    assert_eq!(history.get(0).unwrap().amount, 1_000);
+}
+
+#[test]
+fn test_invalid_allocations_rejected() {
+    let s = setup(1_000);
+    let env = &s.env;
+    let reason = String::from_str(env, "Split");
+    let bps = AllocationUnit::BasisPoints;
+
+    let cases = [
+        (bps, vec![env], EscrowError::InvalidAllocation),
+        (
+            bps,
+            vec![env, allocation(&s.beneficiary, 6_000)],
+            EscrowError::InvalidAllocation,
+        ),
+        (
+            bps,
+            vec![
+                env,
+                allocation(&s.beneficiary, 5_000),
+                allocation(&s.beneficiary, 5_000),
+            ],
+            EscrowError::InvalidAllocation,
+        ),
+        (
+            bps,
+            vec![
+                env,
+                allocation(&s.beneficiary, 11_000),
+                allocation(&s.depositor, -1_000),
+            ],
+            EscrowError::InvalidAmount,
+        ),
+        (
+            bps,
+            vec![env, allocation(&s.arbiter, 10_000)],
+            EscrowError::InvalidApprovalTarget,
+        ),
+        (
+            AllocationUnit::Amount,
+            vec![
+                env,
+                allocation(&s.beneficiary, 600),
+                allocation(&s.depositor, 300),
+            ],
+            EscrowError::InvalidAllocation,
+        ),
+    ];
+    for (unit, allocations, error) in cases {
+        assert_eq!(
+            s.client.try_resolve_dispute_split(
+                &s.escrow_id,
+                &s.arbiter,
+                &unit,
+                &allocations,
+                &reason
+            ),
+            Err(Ok(error))
+        );
+    }
+
+    let full = vec![env, allocation(&s.depositor, 10_000)];
+    assert_eq!(
+        s.client
+            .try_resolve_dispute_split(&s.escrow_id, &s.depositor, &bps, &full, &reason),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+    assert_eq!(
+        s.client.try_resolve_dispute_split(
+            &s.escrow_id,
+            &s.arbiter,
+            &bps,
+            &full,
+            &String::from_str(env, "")
+        ),
+        Err(Ok(EscrowError::EmptyReleaseReason))
+    );
+    assert_eq!(
+        s.client.get_escrow(&s.escrow_id).status,
+        EscrowStatus::Disputed
+    );
+}
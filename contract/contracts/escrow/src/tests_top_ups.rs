@@ -0,0 +1,230 @@
+//! Tests for incremental funding, top-ups and target increases.
+
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{Address, BytesN, Env};
+
+use crate::errors::EscrowError;
+use crate::escrow_impl::{EscrowContract, EscrowContractClient};
+use crate::types::EscrowStatus;
+
+struct Setup<'a> {
+    env: Env,
+    client: EscrowContractClient<'a>,
+    depositor: Address,
+    beneficiary: Address,
+    guarantor: Address,
+    token: TokenClient<'a>,
+    escrow_id: BytesN<32>,
+}
+
+/// A Pending escrow with a target of 1_000; the depositor and a guarantor
+/// each hold 2_000.
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(EscrowContract, ());
+    let client = EscrowContractClient::new(&env, &contract_id);
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let guarantor = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    let escrow_id = client.create(
+        &depositor,
+        &beneficiary,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &1_000,
+        &token_address,
+    );
+    let token_admin = TokenAdminClient::new(&env, &token_address);
+    token_admin.mint(&depositor, &2_000);
+    token_admin.mint(&guarantor, &2_000);
+
+    Setup {
+        token: TokenClient::new(&env, &token_address),
+        env,
+        client,
+        depositor,
+        beneficiary,
+        guarantor,
+        escrow_id,
+    }
+}
+
+#[test]
+fn test_contributions_fund_escrow_at_target() {
+    let s = setup();
+    s.client.contribute(&s.escrow_id, &s.guarantor, &400);
+
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Pending);
+    assert_eq!(escrow.funded_amount, 400);
+    assert_eq!(escrow.required_amount, 1_000);
+
+    // fund_escrow pays only the shortfall.
+    s.client.fund_escrow(&s.escrow_id, &s.depositor);
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(escrow.funded_amount, 1_000);
+    assert_eq!(escrow.amount, 1_000);
+    assert_eq!(s.token.balance(&s.depositor), 1_400);
+
+    let contributions = s.client.get_contributions(&s.escrow_id);
+    assert_eq!(contributions.len(), 2);
+    assert_eq!(contributions.get(0).unwrap().contributor, s.guarantor);
+    assert_eq!(contributions.get(1).unwrap().amount, 600);
+}
+
+#[test]
+fn test_overfunding_and_top_ups_are_releasable() {
+    let s = setup();
+    s.client.contribute(&s.escrow_id, &s.depositor, &1_200);
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(escrow.amount, 1_200);
+
+    s.client.contribute(&s.escrow_id, &s.guarantor, &300);
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.funded_amount, 1_500);
+    assert_eq!(escrow.amount, 1_500);
+    assert_eq!(
+        s.client.try_fund_escrow(&s.escrow_id, &s.depositor),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    s.client
+        .approve_release(&s.escrow_id, &s.depositor, &s.beneficiary);
+    s.client
+        .approve_release(&s.escrow_id, &s.beneficiary, &s.beneficiary);
+    assert_eq!(s.token.balance(&s.beneficiary), 1_500);
+    assert_eq!(
+        s.client.try_contribute(&s.escrow_id, &s.depositor, &100),
+        Err(Ok(EscrowError::InvalidState))
+    );
+}
+
+#[test]
+fn test_target_increase_needs_both_parties() {
+    let s = setup();
+    s.client.contribute(&s.escrow_id, &s.depositor, &800);
+
+    assert_eq!(
+        s.client
+            .try_accept_target_increase(&s.escrow_id, &s.depositor),
+        Err(Ok(EscrowError::TargetIncreaseNotFound))
+    );
+    assert_eq!(
+        s.client
+            .try_propose_target_increase(&s.escrow_id, &s.beneficiary, &1_000),
+        Err(Ok(EscrowError::InvalidAmount))
+    );
+    assert_eq!(
+        s.client
+            .try_propose_target_increase(&s.escrow_id, &s.guarantor, &1_500),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+
+    s.client
+        .propose_target_increase(&s.escrow_id, &s.beneficiary, &1_500);
+    assert_eq!(
+        s.client
+            .try_accept_target_increase(&s.escrow_id, &s.beneficiary),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+    s.client.accept_target_increase(&s.escrow_id, &s.depositor);
+    assert_eq!(s.client.get_target_increase(&s.escrow_id), None);
+
+    // 1_000 would have met the old target but not the new one.
+    s.client.contribute(&s.escrow_id, &s.depositor, &200);
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Pending);
+    assert_eq!(escrow.required_amount, 1_500);
+
+    s.client.fund_escrow(&s.escrow_id, &s.depositor);
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(escrow.amount, 1_500);
+}
+
+#[test]
+fn test_pending_timeout_refunds_contributions() {
+    let s = setup();
+    s.client.contribute(&s.escrow_id, &s.guarantor, &300);
+    assert_eq!(
+        s.client.try_contribute(&s.escrow_id, &s.guarantor, &0),
+        Err(Ok(EscrowError::InvalidAmount))
+    );
+
+    let timeout_days = s.client.get_escrow(&s.escrow_id).timeout_days;
+    s.env
+        .ledger()
+        .with_mut(|ledger| ledger.timestamp += timeout_days * 86_400 + 1);
+    s.client.release_escrow_on_timeout(&s.escrow_id);
+
+    assert_eq!(
+        s.client.get_escrow(&s.escrow_id).status,
+        EscrowStatus::Refunded
+    );
+    assert_eq!(s.token.balance(&s.depositor), 2_300);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_target_increase_reopens_funded_escrow() {
+    let s = setup();
+    s.client.fund_escrow(&s.escrow_id, &s.depositor);
+    s.client
+        .approve_release(&s.escrow_id, &s.depositor, &s.beneficiary);
+
+    s.client
+        .propose_target_increase(&s.escrow_id, &s.beneficiary, &1_500);
+    s.client.accept_target_increase(&s.escrow_id, &s.depositor);
+
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Pending);
+    assert_eq!(escrow.amount, 1_500);
+    assert_eq!(escrow.funded_amount, 1_000);
+    // The approval given at the old target no longer counts.
+    assert_eq!(s.client.get_approval_count(&s.escrow_id, &s.beneficiary), 0);
+    assert_eq!(
+        s.client
+            .try_approve_release(&s.escrow_id, &s.beneficiary, &s.beneficiary),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    s.client.contribute(&s.escrow_id, &s.guarantor, &500);
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Funded);
+    assert_eq!(escrow.amount, 1_500);
+}
+
+#[test]
+fn test_target_increase_refused_after_partial_release() {
+    let s = setup();
+    s.client.fund_escrow(&s.escrow_id, &s.depositor);
+    s.client
+        .approve_partial_release(&s.escrow_id, &s.depositor, &s.beneficiary);
+    s.client
+        .approve_partial_release(&s.escrow_id, &s.beneficiary, &s.beneficiary);
+    s.client.release_escrow_partial(
+        &s.escrow_id,
+        &400,
+        &s.beneficiary,
+        &soroban_sdk::String::from_str(&s.env, "first month"),
+    );
+
+    s.client
+        .propose_target_increase(&s.escrow_id, &s.beneficiary, &1_500);
+    assert_eq!(
+        s.client
+            .try_accept_target_increase(&s.escrow_id, &s.depositor),
+        Err(Ok(EscrowError::InvalidState))
+    );
+}
@@ -0,0 +1,307 @@
+//! Tests for depositing escrow balances in yield vaults.
+
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::Client as TokenClient;
+use soroban_sdk::token::StellarAssetClient as TokenAdminClient;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, token, Address, BytesN, Env, String, Symbol,
+};
+
+use crate::agreement::{CompoundingFrequency, DepositInterestConfig, InterestRecipient};
+use crate::errors::EscrowError;
+use crate::escrow_impl::{EscrowContract, EscrowContractClient};
+use crate::tests_agreements;
+use crate::types::EscrowStatus;
+
+const TOKEN: Symbol = symbol_short!("token");
+const SHARES: Symbol = symbol_short!("shares");
+
+/// Single-token vault issuing shares pro rata to its balance. Yield is
+/// simulated by minting to the vault, losses by `lose`.
+#[contract]
+pub struct MockVaultContract;
+
+#[contractimpl]
+impl MockVaultContract {
+    pub fn __constructor(env: Env, token: Address) {
+        env.storage().instance().set(&TOKEN, &token);
+    }
+
+    pub fn token(env: Env) -> Address {
+        env.storage().instance().get(&TOKEN).unwrap()
+    }
+
+    pub fn deposit(env: Env, from: Address, amount: i128) -> i128 {
+        let balance = Self::balance(&env);
+        let total: i128 = env.storage().instance().get(&SHARES).unwrap_or(0);
+        let shares = if total == 0 {
+            amount
+        } else {
+            amount * total / balance
+        };
+        token::Client::new(&env, &Self::token(env.clone())).transfer(
+            &from,
+            env.current_contract_address(),
+            &amount,
+        );
+        env.storage().instance().set(&SHARES, &(total + shares));
+        shares
+    }
+
+    pub fn withdraw(env: Env, owner: Address, shares: i128) -> i128 {
+        owner.require_auth();
+        let amount = Self::value_of(env.clone(), shares);
+        let total: i128 = env.storage().instance().get(&SHARES).unwrap();
+        env.storage().instance().set(&SHARES, &(total - shares));
+        token::Client::new(&env, &Self::token(env.clone())).transfer(
+            &env.current_contract_address(),
+            &owner,
+            &amount,
+        );
+        amount
+    }
+
+    pub fn value_of(env: Env, shares: i128) -> i128 {
+        let total: i128 = env.storage().instance().get(&SHARES).unwrap_or(0);
+        if total == 0 {
+            return 0;
+        }
+        shares * Self::balance(&env) / total
+    }
+
+    pub fn lose(env: Env, amount: i128, to: Address) {
+        token::Client::new(&env, &Self::token(env.clone())).transfer(
+            &env.current_contract_address(),
+            &to,
+            &amount,
+        );
+    }
+
+    fn balance(env: &Env) -> i128 {
+        token::Client::new(env, &Self::token(env.clone())).balance(&env.current_contract_address())
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    client: EscrowContractClient<'a>,
+    vault: MockVaultContractClient<'a>,
+    admin: Address,
+    depositor: Address,
+    beneficiary: Address,
+    arbiter: Address,
+    token: TokenClient<'a>,
+    escrow_id: BytesN<32>,
+}
+
+/// A funded 1_000 escrow whose token has a vault configured.
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = EscrowContractClient::new(&env, &env.register(EscrowContract, ()));
+    let admin = Address::generate(&env);
+    client.initialize_admin(&admin);
+
+    let token_address = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let vault = MockVaultContractClient::new(
+        &env,
+        &env.register(MockVaultContract, (token_address.clone(),)),
+    );
+    client.set_yield_vault(&admin, &token_address, &vault.address);
+
+    let depositor = Address::generate(&env);
+    let beneficiary = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    TokenAdminClient::new(&env, &token_address).mint(&depositor, &1_000);
+    let escrow_id = client.create(
+        &depositor,
+        &beneficiary,
+        &arbiter,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &1_000,
+        &token_address,
+    );
+    client.fund_escrow(&escrow_id, &depositor);
+
+    Setup {
+        token: TokenClient::new(&env, &token_address),
+        env,
+        client,
+        vault,
+        admin,
+        depositor,
+        beneficiary,
+        arbiter,
+        escrow_id,
+    }
+}
+
+fn earn(env: &Env, vault: &MockVaultContractClient, token: &TokenClient, amount: i128) {
+    TokenAdminClient::new(env, &token.address).mint(&vault.address, &amount);
+}
+
+#[test]
+fn test_yield_goes_to_tenant_by_default() {
+    let s = setup();
+    s.client.enable_yield(&s.escrow_id);
+
+    let position = s.client.get_yield_position(&s.escrow_id).unwrap();
+    assert_eq!(position.vault, s.vault.address);
+    assert_eq!(position.principal, 1_000);
+    assert_eq!(s.token.balance(&s.vault.address), 1_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    earn(&s.env, &s.vault, &s.token, 100);
+    assert_eq!(s.client.get_yield_value(&s.escrow_id), Some(1_100));
+
+    s.client
+        .approve_release(&s.escrow_id, &s.depositor, &s.beneficiary);
+    s.client
+        .approve_release(&s.escrow_id, &s.beneficiary, &s.beneficiary);
+
+    assert_eq!(s.token.balance(&s.beneficiary), 1_000);
+    assert_eq!(s.token.balance(&s.depositor), 100);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+    assert_eq!(s.client.get_yield_position(&s.escrow_id), None);
+
+    let history = s.client.get_yield_history(&s.escrow_id);
+    assert_eq!(history.len(), 1);
+    let record = history.get(0).unwrap();
+    assert_eq!(record.withdrawn, 1_100);
+    assert_eq!(record.to_depositor, 100);
+    assert_eq!(record.to_beneficiary, 0);
+}
+
+#[test]
+fn test_vault_loss_reduces_releasable_balance() {
+    let s = setup();
+    s.client.enable_yield(&s.escrow_id);
+    s.vault.lose(&200, &Address::generate(&s.env));
+
+    s.client.initiate_dispute(
+        &s.escrow_id,
+        &s.depositor,
+        &String::from_str(&s.env, "Damage"),
+    );
+    s.client
+        .resolve_dispute(&s.escrow_id, &s.arbiter, &s.beneficiary);
+
+    let escrow = s.client.get_escrow(&s.escrow_id);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.amount, 800);
+    assert_eq!(s.token.balance(&s.beneficiary), 800);
+    assert_eq!(s.token.balance(&s.depositor), 0);
+    assert_eq!(
+        s.client
+            .get_yield_history(&s.escrow_id)
+            .get(0)
+            .unwrap()
+            .withdrawn,
+        800
+    );
+}
+
+#[test]
+fn test_early_withdrawal_and_checks() {
+    let s = setup();
+    assert_eq!(
+        s.client.try_withdraw_yield(&s.escrow_id, &s.depositor),
+        Err(Ok(EscrowError::InvalidState))
+    );
+
+    s.client.enable_yield(&s.escrow_id);
+    assert_eq!(
+        s.client.try_enable_yield(&s.escrow_id),
+        Err(Ok(EscrowError::InvalidState))
+    );
+    assert_eq!(
+        s.client.try_withdraw_yield(&s.escrow_id, &s.arbiter),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+
+    earn(&s.env, &s.vault, &s.token, 50);
+    s.client.withdraw_yield(&s.escrow_id, &s.beneficiary);
+    assert_eq!(s.client.get_yield_position(&s.escrow_id), None);
+    assert_eq!(s.token.balance(&s.client.address), 1_000);
+    assert_eq!(s.token.balance(&s.depositor), 50);
+    assert_eq!(s.client.get_escrow(&s.escrow_id).amount, 1_000);
+
+    // The escrow can go back into the vault afterwards.
+    s.client.enable_yield(&s.escrow_id);
+    assert!(s.client.get_yield_position(&s.escrow_id).is_some());
+}
+
+#[test]
+fn test_yield_requires_configured_vault() {
+    let s = setup();
+    let other_token = s
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&s.env))
+        .address();
+
+    // The vault does not hold the other token.
+    assert_eq!(
+        s.client
+            .try_set_yield_vault(&s.admin, &other_token, &s.vault.address),
+        Err(Ok(EscrowError::YieldVaultFailed))
+    );
+    assert_eq!(
+        s.client
+            .try_set_yield_vault(&s.depositor, &s.token.address, &s.vault.address),
+        Err(Ok(EscrowError::NotAuthorized))
+    );
+
+    TokenAdminClient::new(&s.env, &other_token).mint(&s.depositor, &500);
+    let escrow_id = s.client.create(
+        &s.depositor,
+        &s.beneficiary,
+        &s.arbiter,
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &500,
+        &other_token,
+    );
+    assert_eq!(
+        s.client.try_enable_yield(&escrow_id),
+        Err(Ok(EscrowError::InvalidState))
+    );
+    s.client.fund_escrow(&escrow_id, &s.depositor);
+    assert_eq!(
+        s.client.try_enable_yield(&escrow_id),
+        Err(Ok(EscrowError::YieldVaultNotSet))
+    );
+}
+
+#[test]
+fn test_yield_follows_agreement_interest_recipient() {
+    let s = tests_agreements::setup();
+    let vault = MockVaultContractClient::new(
+        &s.env,
+        &s.env.register(MockVaultContract, (s.token.clone(),)),
+    );
+    s.client.set_yield_vault(&s.admin, &s.token, &vault.address);
+    s.chioma
+        .set_deposit_interest_config(&DepositInterestConfig {
+            agreement_id: s.agreement_id.clone(),
+            annual_rate: 500,
+            compounding_frequency: CompoundingFrequency::Monthly,
+            interest_recipient: InterestRecipient::Split,
+        });
+
+    let escrow_id = tests_agreements::create(&s, &s.tenant, 2_000, &s.token);
+    s.client.fund_escrow(&escrow_id, &s.tenant);
+    s.client.enable_yield(&escrow_id);
+
+    let token = TokenClient::new(&s.env, &s.token);
+    earn(&s.env, &vault, &token, 101);
+    let tenant_before = token.balance(&s.tenant);
+    s.client.withdraw_yield(&escrow_id, &s.tenant);
+
+    assert_eq!(token.balance(&s.landlord), 50);
+    assert_eq!(token.balance(&s.tenant), tenant_before + 51);
+    assert_eq!(token.balance(&s.client.address), 2_000);
+}
@@ -1,5 +1,5 @@
 //! Data structures and enums for the Escrow contract.
-use soroban_sdk::{contracttype, Address, BytesN, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 /// Status of an escrow throughout its lifecycle.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -33,8 +33,13 @@ pub struct Escrow {
     pub platform_governance: Address,
     /// Agent/referral address receiving 5% on rent release
     pub agent_referral: Address,
-    /// Amount of funds in the escrow
+    /// Amount of funds in the escrow: the funding target while Pending,
+    /// then the balance available for release
     pub amount: i128,
+    /// Amount that must be contributed before the escrow becomes Funded
+    pub required_amount: i128,
+    /// Total contributed so far, including any amount above the target
+    pub funded_amount: i128,
     /// Token contract address (USDC, XLM, etc.)
     pub token: Address,
     /// Current status of the escrow
@@ -51,10 +56,68 @@ pub struct Escrow {
     pub is_frozen: bool,
     /// Timestamp when escrow was frozen
     pub frozen_at: Option<u64>,
+    /// Timestamp at which the freeze lapses on its own
+    pub frozen_until: Option<u64>,
     /// Reason for freezing the escrow
     pub freeze_reason: Option<String>,
 }
 
+/// Escrow layout stored before funding targets and freeze expiry were added.
+/// Only read back from storage, and upgraded to `Escrow` on load.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype(export = false)]
+pub struct EscrowV1 {
+    pub id: BytesN<32>,
+    pub depositor: Address,
+    pub beneficiary: Address,
+    pub arbiter: Address,
+    pub platform_governance: Address,
+    pub agent_referral: Address,
+    pub amount: i128,
+    pub token: Address,
+    pub status: EscrowStatus,
+    pub created_at: u64,
+    pub timeout_days: u64,
+    pub disputed_at: Option<u64>,
+    pub dispute_reason: Option<String>,
+    pub is_frozen: bool,
+    pub frozen_at: Option<u64>,
+    pub freeze_reason: Option<String>,
+}
+
+impl From<EscrowV1> for Escrow {
+    /// `amount` was both the target and the balance, so a pending escrow has
+    /// received nothing yet and any other has been funded in full.
+    fn from(old: EscrowV1) -> Self {
+        let funded_amount = if old.status == EscrowStatus::Pending {
+            0
+        } else {
+            old.amount
+        };
+        Escrow {
+            id: old.id,
+            depositor: old.depositor,
+            beneficiary: old.beneficiary,
+            arbiter: old.arbiter,
+            platform_governance: old.platform_governance,
+            agent_referral: old.agent_referral,
+            amount: old.amount,
+            required_amount: old.amount,
+            funded_amount,
+            token: old.token,
+            status: old.status,
+            created_at: old.created_at,
+            timeout_days: old.timeout_days,
+            disputed_at: old.disputed_at,
+            dispute_reason: old.dispute_reason,
+            is_frozen: old.is_frozen,
+            frozen_at: old.frozen_at,
+            frozen_until: None,
+            freeze_reason: old.freeze_reason,
+        }
+    }
+}
+
 /// Contract-level timeout configuration.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
@@ -64,6 +127,28 @@ pub struct TimeoutConfig {
     pub payment_timeout_days: u64,
 }
 
+/// A party allowed to approve releases, with the weight of its approval.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Signer {
+    /// Address of the signer
+    pub address: Address,
+    /// Weight counted towards the threshold (1 for an unweighted set)
+    pub weight: u32,
+}
+
+/// N-of-M signer set governing releases from an escrow.
+/// Escrows created without one use the 2-of-3 preset of depositor,
+/// beneficiary and arbiter with unit weights.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct SignerSet {
+    /// Parties allowed to approve releases
+    pub signers: Vec<Signer>,
+    /// Total approval weight required to release funds
+    pub threshold: u32,
+}
+
 /// Records approval of fund release by a single party.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -92,6 +177,142 @@ pub struct ReleaseRecord {
     pub reason: String,
 }
 
+/// How the shares of a dispute allocation are expressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum AllocationUnit {
+    /// Shares are basis points of the remaining balance and sum to 10_000
+    BasisPoints = 0,
+    /// Shares are token amounts and sum to the remaining balance
+    Amount = 1,
+}
+
+/// One party's share of a disputed escrow.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Allocation {
+    /// Depositor or beneficiary receiving the share
+    pub recipient: Address,
+    /// Share in the unit chosen for the resolution
+    pub share: i128,
+}
+
+/// Status of a single milestone in a milestone escrow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum MilestoneStatus {
+    /// Work not yet submitted
+    Pending = 0,
+    /// Beneficiary has submitted evidence, awaiting approval
+    Submitted = 1,
+    /// Approved and paid to the beneficiary
+    Released = 2,
+    /// Under dispute, awaiting arbiter resolution
+    Disputed = 3,
+    /// Dispute resolved by the arbiter and paid out
+    Resolved = 4,
+}
+
+/// Description and amount of a milestone, supplied at creation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct MilestoneTerms {
+    /// What has to be delivered
+    pub description: String,
+    /// Amount released when the milestone is approved
+    pub amount: i128,
+}
+
+/// A stage of work paid out of a milestone escrow.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Milestone {
+    /// Position in the escrow's ordered list of milestones
+    pub index: u32,
+    /// What has to be delivered
+    pub description: String,
+    /// Amount released when the milestone is approved
+    pub amount: i128,
+    /// Current status of the milestone
+    pub status: MilestoneStatus,
+    /// Hash of the latest evidence submitted by the beneficiary
+    pub evidence_hash: Option<BytesN<32>>,
+    /// Timestamp of the latest evidence submission
+    pub submitted_at: Option<u64>,
+    /// Timestamp when the milestone was paid out
+    pub settled_at: Option<u64>,
+    /// Reason for dispute, if any
+    pub dispute_reason: Option<String>,
+}
+
+/// A single funding contribution to an escrow.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Contribution {
+    /// Address that paid the contribution
+    pub contributor: Address,
+    /// Amount contributed
+    pub amount: i128,
+    /// Timestamp of the contribution
+    pub contributed_at: u64,
+}
+
+/// Proposal to raise an escrow's funding target, awaiting the other party.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct TargetIncrease {
+    /// Depositor or beneficiary proposing the increase
+    pub proposed_by: Address,
+    /// Proposed new funding target
+    pub new_required_amount: i128,
+    /// Timestamp of the proposal
+    pub proposed_at: u64,
+}
+
+/// An escrow balance deposited in a yield vault.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct YieldPosition {
+    /// Vault holding the funds
+    pub vault: Address,
+    /// Vault shares owned on behalf of the escrow
+    pub shares: i128,
+    /// Amount deposited
+    pub principal: i128,
+    /// Timestamp of the deposit
+    pub deposited_at: u64,
+}
+
+/// Outcome of withdrawing a yield position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct YieldRecord {
+    /// Vault the funds were withdrawn from
+    pub vault: Address,
+    /// Amount originally deposited
+    pub principal: i128,
+    /// Amount the vault paid back; below `principal` on a loss
+    pub withdrawn: i128,
+    /// Share of the yield paid to the depositor
+    pub to_depositor: i128,
+    /// Share of the yield paid to the beneficiary
+    pub to_beneficiary: i128,
+    /// Timestamp of the withdrawal
+    pub realized_at: u64,
+}
+
+/// Link from an escrow to the chioma rental agreement whose deposit it holds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct AgreementLink {
+    /// Agreement ID in the chioma contract
+    pub agreement_id: String,
+    /// Chioma contract the agreement lives in
+    pub chioma_contract: Address,
+    /// Nonce the escrow ID was derived from
+    pub nonce: u32,
+}
+
 /// Rate limiting configuration.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
@@ -112,6 +333,73 @@ pub struct UserCallCount {
     pub daily_reset_block: u64,
 }
 
+/// Freeze limits and the council of system admins that can extend freezes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct FreezeConfig {
+    /// Longest a freeze lasts, and how long each extension adds, in days
+    pub max_freeze_days: u64,
+    /// System admins allowed to approve freeze extensions
+    pub council: Vec<Address>,
+    /// Council approvals needed to extend a freeze; 0 when there is no council
+    pub extension_threshold: u32,
+}
+
+/// One freeze of an escrow. A freeze that was neither lifted nor extended
+/// lapsed at `expires_at`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct FreezeRecord {
+    pub frozen_by: Address,
+    pub reason: String,
+    pub frozen_at: u64,
+    pub expires_at: u64,
+    /// Number of times the council extended the freeze
+    pub extensions: u32,
+    /// System admin, or the party whose petition completed the unfreeze
+    pub lifted_by: Option<Address>,
+    pub lifted_at: Option<u64>,
+}
+
+/// Council approvals collected towards extending the current freeze.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct FreezeExtension {
+    /// Expiry the approvals apply to; approvals for an earlier expiry are stale
+    pub expires_at: u64,
+    pub approvals: Vec<Address>,
+}
+
+/// Request by the depositor or beneficiary to lift a freeze.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct UnfreezePetition {
+    pub petitioner: Address,
+    pub reason: String,
+    pub petitioned_at: u64,
+}
+
+/// Secondary index an escrow can be listed under.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum EscrowIndex {
+    Depositor(Address),
+    Beneficiary(Address),
+    Arbiter(Address),
+    Status(EscrowStatus),
+}
+
+/// One page of an escrow index query.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EscrowPage {
+    pub escrows: Vec<Escrow>,
+    /// Cursor for the next page, or `None` when this page is the last.
+    pub next_cursor: Option<u32>,
+    /// Number of escrows in the index.
+    pub total: u32,
+}
+
 /// Storage key variants for persistent storage.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
@@ -124,7 +412,7 @@ pub enum DataKey {
     DisputeInfo(BytesN<32>),
     /// Counter for total escrows created
     EscrowCount,
-    /// Per-target approval count: DataKey::ApprovalCount(escrow_id, release_to) => u32
+    /// Per-target approval weight: DataKey::ApprovalCount(escrow_id, release_to) => u32
     ApprovalCount(BytesN<32>, Address),
     /// Per-signer-per-target flag: DataKey::SignerApproved(escrow_id, signer, release_to) => bool
     SignerApproved(BytesN<32>, Address, Address),
@@ -142,4 +430,42 @@ pub enum DataKey {
     SystemAdmin,
     /// Upgrade proposal
     UpgradeProposal(String),
+    /// Custom signer set for an escrow: DataKey::SignerSet(escrow_id) => SignerSet
+    SignerSet(BytesN<32>),
+    /// Milestones of a milestone escrow: DataKey::Milestones(escrow_id) => Vec<Milestone>
+    Milestones(BytesN<32>),
+    /// Optional inspector of a milestone escrow: DataKey::MilestoneInspector(escrow_id) => Address
+    MilestoneInspector(BytesN<32>),
+    /// Funding contributions: DataKey::Contributions(escrow_id) => Vec<Contribution>
+    Contributions(BytesN<32>),
+    /// Pending target increase: DataKey::TargetIncrease(escrow_id) => TargetIncrease
+    TargetIncrease(BytesN<32>),
+    /// Chioma contract that agreement escrows are created against (instance storage)
+    ChiomaContract,
+    /// Agreement behind an escrow: DataKey::EscrowAgreement(escrow_id) => AgreementLink
+    EscrowAgreement(BytesN<32>),
+    /// Latest escrow for an agreement: DataKey::AgreementEscrow(agreement_id) => BytesN<32>
+    AgreementEscrow(String),
+    /// Escrows created so far for an agreement: DataKey::AgreementNonce(agreement_id) => u32
+    AgreementNonce(String),
+    /// Yield vault for a token (instance storage): DataKey::YieldVault(token) => Address
+    YieldVault(Address),
+    /// Open vault position: DataKey::YieldPosition(escrow_id) => YieldPosition
+    YieldPosition(BytesN<32>),
+    /// Withdrawn vault positions: DataKey::YieldHistory(escrow_id) => Vec<YieldRecord>
+    YieldHistory(BytesN<32>),
+    /// Number of escrows in an index: DataKey::IndexLen(index) => u32
+    IndexLen(EscrowIndex),
+    /// Escrow at a position of an index: DataKey::IndexEntry(index, position) => BytesN<32>
+    IndexEntry(EscrowIndex, u32),
+    /// Position of an escrow in an index: DataKey::IndexPosition(index, escrow_id) => u32
+    IndexPosition(EscrowIndex, BytesN<32>),
+    /// Freeze limits and extension council (instance storage)
+    FreezeConfig,
+    /// Freezes of an escrow: DataKey::FreezeHistory(escrow_id) => Vec<FreezeRecord>
+    FreezeHistory(BytesN<32>),
+    /// Extension approvals for the current freeze: DataKey::FreezeExtension(escrow_id) => FreezeExtension
+    FreezeExtension(BytesN<32>),
+    /// Unfreeze petitions for the current freeze: DataKey::UnfreezePetitions(escrow_id) => Vec<UnfreezePetition>
+    UnfreezePetitions(BytesN<32>),
 }
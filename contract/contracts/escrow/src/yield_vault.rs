@@ -0,0 +1,209 @@
+//! Yield-bearing escrow balances.
+//! With the consent of both primary parties, a funded escrow's balance is
+//! deposited in the yield vault the admin configured for its token. The
+//! position is withdrawn before any funds leave the escrow: yield is paid
+//! out according to the agreement's `InterestRecipient`, and a shortfall is
+//! taken off the releasable balance.
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contractclient, token, vec, Address, BytesN, Env, IntoVal, Symbol,
+};
+
+use crate::access::AccessControl;
+use crate::agreement::{AgreementHandler, InterestRecipient};
+use crate::errors::EscrowError;
+use crate::events;
+use crate::storage::EscrowStorage;
+use crate::types::{Escrow, EscrowStatus, YieldPosition, YieldRecord};
+
+/// Interface a yield vault must implement. A vault holds a single token.
+#[allow(dead_code)]
+#[contractclient(name = "YieldVaultClient")]
+pub trait YieldVault {
+    /// Token the vault accepts.
+    fn token(env: Env) -> Address;
+    /// Transfer `amount` of the token from `from` into the vault and return
+    /// the shares credited to `from`.
+    fn deposit(env: Env, from: Address, amount: i128) -> i128;
+    /// Burn `shares` owned by `owner`, transfer the underlying tokens to
+    /// `owner` and return the amount transferred.
+    fn withdraw(env: Env, owner: Address, shares: i128) -> i128;
+    /// Current value of `shares` in the vault's token.
+    fn value_of(env: Env, shares: i128) -> i128;
+}
+
+/// Yield vault handling.
+pub struct YieldHandler;
+
+impl YieldHandler {
+    /// Set the yield vault used for escrows in `token` (admin only).
+    ///
+    /// CHECKS:
+    /// - Caller must be system admin
+    /// - The vault must hold `token`
+    pub fn set_yield_vault(
+        env: Env,
+        caller: Address,
+        token: Address,
+        vault: Address,
+    ) -> Result<(), EscrowError> {
+        AccessControl::is_system_admin(&env, &caller)?;
+        caller.require_auth();
+
+        match YieldVaultClient::new(&env, &vault).try_token() {
+            Ok(Ok(vault_token)) if vault_token == token => {}
+            _ => return Err(EscrowError::YieldVaultFailed),
+        }
+        EscrowStorage::set_yield_vault(&env, &token, &vault);
+        Ok(())
+    }
+
+    /// Deposit a funded escrow's balance in the yield vault for its token.
+    /// Needs the consent of both the depositor and the beneficiary, since
+    /// both bear the risk of a vault loss.
+    ///
+    /// CHECKS:
+    /// - Escrow must exist, be Funded and not frozen
+    /// - Milestone escrows and escrows already in a vault are rejected
+    /// - A vault must be configured for the escrow's token
+    ///
+    /// EFFECTS:
+    /// - Records the position and its principal
+    ///
+    /// INTERACTIONS:
+    /// - The vault pulls the balance from the escrow contract
+    pub fn enable_yield(env: Env, escrow_id: BytesN<32>) -> Result<(), EscrowError> {
+        let escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::require_not_frozen(&env, &escrow)?;
+        if escrow.status != EscrowStatus::Funded || escrow.amount <= 0 {
+            return Err(EscrowError::InvalidState);
+        }
+        if EscrowStorage::get_milestones(&env, &escrow_id).is_some()
+            || EscrowStorage::get_yield_position(&env, &escrow_id).is_some()
+        {
+            return Err(EscrowError::InvalidState);
+        }
+        let vault = EscrowStorage::get_yield_vault(&env, &escrow.token)
+            .ok_or(EscrowError::YieldVaultNotSet)?;
+        escrow.depositor.require_auth();
+        escrow.beneficiary.require_auth();
+
+        // INTERACTIONS: Authorize the vault's pull, then deposit
+        let this = env.current_contract_address();
+        env.authorize_as_current_contract(vec![
+            &env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: escrow.token.clone(),
+                    fn_name: Symbol::new(&env, "transfer"),
+                    args: (this.clone(), vault.clone(), escrow.amount).into_val(&env),
+                },
+                sub_invocations: vec![&env],
+            }),
+        ]);
+        let shares = match YieldVaultClient::new(&env, &vault).try_deposit(&this, &escrow.amount) {
+            Ok(Ok(shares)) if shares > 0 => shares,
+            _ => return Err(EscrowError::YieldVaultFailed),
+        };
+
+        // EFFECTS: Record the position
+        let position = YieldPosition {
+            vault: vault.clone(),
+            shares,
+            principal: escrow.amount,
+            deposited_at: env.ledger().timestamp(),
+        };
+        EscrowStorage::set_yield_position(&env, &escrow_id, &position);
+
+        events::yield_deposited(&env, escrow_id, vault, escrow.amount, shares);
+        Ok(())
+    }
+
+    /// Withdraw an escrow's vault position early (depositor or beneficiary).
+    pub fn withdraw_yield(
+        env: Env,
+        escrow_id: BytesN<32>,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        let mut escrow = EscrowStorage::get(&env, &escrow_id).ok_or(EscrowError::EscrowNotFound)?;
+        AccessControl::is_primary_party(&escrow, &caller)?;
+        if EscrowStorage::get_yield_position(&env, &escrow_id).is_none() {
+            return Err(EscrowError::InvalidState);
+        }
+        caller.require_auth();
+
+        Self::realize(&env, &mut escrow)
+    }
+
+    /// Current value of an escrow's vault position, if it has one.
+    pub fn get_yield_value(env: Env, escrow_id: BytesN<32>) -> Option<i128> {
+        let position = EscrowStorage::get_yield_position(&env, &escrow_id)?;
+        YieldVaultClient::new(&env, &position.vault)
+            .try_value_of(&position.shares)
+            .ok()
+            .and_then(|value| value.ok())
+    }
+
+    /// Withdraw an escrow's vault position, if any, so that its whole balance
+    /// is held by the contract again. Called before any funds leave the escrow.
+    ///
+    /// EFFECTS:
+    /// - A loss reduces `escrow.amount` by the shortfall
+    /// - Position removed and the outcome recorded in the yield history
+    ///
+    /// INTERACTIONS:
+    /// - Vault withdrawal, then yield transfers to the interest recipients
+    pub(crate) fn realize(env: &Env, escrow: &mut Escrow) -> Result<(), EscrowError> {
+        let Some(position) = EscrowStorage::get_yield_position(env, &escrow.id) else {
+            return Ok(());
+        };
+
+        let this = env.current_contract_address();
+        let withdrawn = match YieldVaultClient::new(env, &position.vault)
+            .try_withdraw(&this, &position.shares)
+        {
+            Ok(Ok(withdrawn)) if withdrawn >= 0 => withdrawn,
+            _ => return Err(EscrowError::YieldVaultFailed),
+        };
+
+        let mut record = YieldRecord {
+            vault: position.vault,
+            principal: position.principal,
+            withdrawn,
+            to_depositor: 0,
+            to_beneficiary: 0,
+            realized_at: env.ledger().timestamp(),
+        };
+        let gain = withdrawn - position.principal;
+        if gain > 0 {
+            match AgreementHandler::interest_recipient(env, escrow) {
+                InterestRecipient::Tenant => record.to_depositor = gain,
+                InterestRecipient::Landlord => record.to_beneficiary = gain,
+                InterestRecipient::Split => {
+                    record.to_beneficiary = gain / 2;
+                    record.to_depositor = gain - record.to_beneficiary;
+                }
+            }
+        } else if gain < 0 {
+            escrow.amount += gain;
+            EscrowStorage::save(env, escrow);
+        }
+
+        EscrowStorage::remove_yield_position(env, &escrow.id);
+        EscrowStorage::add_yield_record(env, &escrow.id, record.clone());
+
+        let token_client = token::Client::new(env, &escrow.token);
+        if record.to_depositor > 0 {
+            token_client.transfer(&this, &escrow.depositor, &record.to_depositor);
+        }
+        if record.to_beneficiary > 0 {
+            token_client.transfer(&this, &escrow.beneficiary, &record.to_beneficiary);
+        }
+
+        if gain < 0 {
+            events::vault_loss(env, escrow.id.clone(), -gain, escrow.amount);
+        }
+        events::yield_realized(env, escrow.id.clone(), &record);
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+//! Third-party payer support.
+//!
+//! Guarantors, employers and housing-subsidy programs can be registered on an
+//! agreement as co-payers. Each co-payer covers a fixed share of every rent
+//! period (optionally capped), and a rent period is closed once the combined
+//! contributions of the tenant and co-payers reach the rent due.
+
+use soroban_sdk::{Address, Env, Map, String, Vec};
+
+use crate::payment_impl::RENT_PERIOD_SECONDS;
+use crate::storage::DataKey;
+use crate::types::{CoPayer, PeriodContributions, RentAgreement};
+
+/// Maximum number of co-payers per agreement
+pub const MAX_CO_PAYERS: u32 = 10;
+
+/// Basis points representing 100%
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+pub fn get_co_payers(env: &Env, agreement_id: &String) -> Vec<CoPayer> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CoPayers(agreement_id.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn find_co_payer(env: &Env, agreement_id: &String, payer: &Address) -> Option<CoPayer> {
+    get_co_payers(env, agreement_id)
+        .iter()
+        .find(|co_payer| co_payer.payer == *payer)
+}
+
+/// Contributions towards the agreement's current period. Stale entries left
+/// over from an earlier period are ignored.
+pub fn current_contributions(
+    env: &Env,
+    agreement_id: &String,
+    agreement: &RentAgreement,
+) -> PeriodContributions {
+    let period = agreement.payment_history.len();
+    env.storage()
+        .persistent()
+        .get::<DataKey, PeriodContributions>(&DataKey::PeriodContributions(agreement_id.clone()))
+        .filter(|progress| progress.period == period)
+        .unwrap_or(PeriodContributions {
+            period,
+            amount_paid: 0,
+            landlord_amount: 0,
+            platform_amount: 0,
+            contributions: Map::new(env),
+        })
+}
+
+pub fn contributed_by(progress: &PeriodContributions, payer: &Address) -> i128 {
+    progress.contributions.get(payer.clone()).unwrap_or(0)
+}
+
+/// Largest amount a payer may contribute in a period: their share of the
+/// rent, further limited by their per-period cap.
+pub fn period_limit(co_payer: &CoPayer, expected_rent: i128) -> i128 {
+    let share = expected_rent * (co_payer.share_bps as i128) / (BPS_DENOMINATOR as i128);
+    match co_payer.cap_per_period {
+        Some(cap) if cap < share => cap,
+        _ => share,
+    }
+}
+
+/// Total rent overdue on the agreement: every elapsed period counts at the
+/// current period's rent, less what has already been paid towards it.
+pub fn arrears(
+    env: &Env,
+    agreement: &RentAgreement,
+    expected_rent: i128,
+    progress: &PeriodContributions,
+) -> i128 {
+    let now = env.ledger().timestamp();
+    if now < agreement.next_payment_due {
+        return 0;
+    }
+    let overdue_periods = ((now - agreement.next_payment_due) / RENT_PERIOD_SECONDS) + 1;
+    (overdue_periods as i128)
+        .saturating_mul(expected_rent)
+        .saturating_sub(progress.amount_paid)
+}
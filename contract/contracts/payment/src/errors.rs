@@ -55,4 +55,42 @@ pub enum PaymentError {
     PaymentNotLate = 34,
     /// Caller is not the landlord
     NotLandlord = 35,
+    /// Batch contains no items
+    EmptyBatch = 36,
+    /// Batch exceeds the maximum number of items
+    BatchTooLarge = 37,
+    /// The same agreement appears more than once in a batch
+    DuplicateBatchItem = 38,
+    /// Caller is neither the tenant nor an authorized co-payer
+    NotAuthorizedPayer = 39,
+    /// Payer is already registered on the agreement
+    CoPayerAlreadyExists = 40,
+    /// Payer is not registered on the agreement
+    CoPayerNotFound = 41,
+    /// Co-payer share is invalid or shares exceed 100%
+    InvalidPayerShare = 42,
+    /// Agreement already has the maximum number of co-payers
+    TooManyCoPayers = 43,
+    /// Payment exceeds the payer's share or cap for this period
+    PayerCapExceeded = 44,
+    /// Registered co-payer is not a guarantor
+    NotGuarantor = 45,
+    /// Arrears have not exceeded the guarantor's call threshold
+    ArrearsBelowThreshold = 46,
+    /// No rent stream exists for the agreement
+    StreamNotFound = 47,
+    /// Agreement already has a running rent stream
+    StreamAlreadyActive = 48,
+    /// Rent stream has been stopped
+    StreamNotActive = 49,
+    /// Nothing has accrued since the last withdrawal
+    NothingToWithdraw = 50,
+    /// Caller is neither the landlord nor the platform fee collector
+    NotAuthorizedRefunder = 51,
+    /// Refund exceeds the caller's remaining share of the payment
+    RefundExceedsPayment = 52,
+    /// Refund record not found
+    RefundNotFound = 53,
+    /// Payment belongs to a settled period older than the latest one
+    RefundPeriodClosed = 54,
 }
@@ -1,4 +1,6 @@
-use soroban_sdk::{contractevent, Env, String};
+use soroban_sdk::{contractevent, Address, Env, String};
+
+use crate::types::{PayerRole, RefundParty};
 
 #[contractevent(topics = ["rent_escalation_config_set"])]
 pub struct RentEscalationConfigSet {
@@ -141,3 +143,247 @@ pub(crate) fn recurring_payment_cancelled(env: &Env, recurring_id: String) {
 pub(crate) fn recurring_payment_failed(env: &Env, recurring_id: String) {
     RecurringPaymentFailed { recurring_id }.publish(env);
 }
+
+#[contractevent(topics = ["rent_paid"])]
+pub struct RentPaid {
+    #[topic]
+    pub agreement_id: String,
+    pub payment_id: String,
+    pub payer: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["batch_rent_paid"])]
+pub struct BatchRentPaid {
+    #[topic]
+    pub payer: Address,
+    pub item_count: u32,
+    pub total_amount: i128,
+}
+
+pub(crate) fn rent_paid(
+    env: &Env,
+    agreement_id: String,
+    payment_id: String,
+    payer: Address,
+    amount: i128,
+) {
+    RentPaid {
+        agreement_id,
+        payment_id,
+        payer,
+        amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn batch_rent_paid(env: &Env, payer: Address, item_count: u32, total_amount: i128) {
+    BatchRentPaid {
+        payer,
+        item_count,
+        total_amount,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["co_payer_added"])]
+pub struct CoPayerAdded {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub payer: Address,
+    pub role: PayerRole,
+    pub share_bps: u32,
+}
+
+#[contractevent(topics = ["co_payer_removed"])]
+pub struct CoPayerRemoved {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub payer: Address,
+}
+
+#[contractevent(topics = ["guarantor_called"])]
+pub struct GuarantorCalled {
+    #[topic]
+    pub agreement_id: String,
+    #[topic]
+    pub guarantor: Address,
+    pub amount: i128,
+    pub arrears: i128,
+}
+
+pub(crate) fn co_payer_added(
+    env: &Env,
+    agreement_id: String,
+    payer: Address,
+    role: PayerRole,
+    share_bps: u32,
+) {
+    CoPayerAdded {
+        agreement_id,
+        payer,
+        role,
+        share_bps,
+    }
+    .publish(env);
+}
+
+pub(crate) fn co_payer_removed(env: &Env, agreement_id: String, payer: Address) {
+    CoPayerRemoved {
+        agreement_id,
+        payer,
+    }
+    .publish(env);
+}
+
+pub(crate) fn guarantor_called(
+    env: &Env,
+    agreement_id: String,
+    guarantor: Address,
+    amount: i128,
+    arrears: i128,
+) {
+    GuarantorCalled {
+        agreement_id,
+        guarantor,
+        amount,
+        arrears,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["rent_stream_started"])]
+pub struct RentStreamStarted {
+    #[topic]
+    pub agreement_id: String,
+    pub tenant: Address,
+    pub deposit: i128,
+    pub rent_per_period: i128,
+}
+
+#[contractevent(topics = ["rent_stream_topped_up"])]
+pub struct RentStreamToppedUp {
+    #[topic]
+    pub agreement_id: String,
+    pub amount: i128,
+    pub deposited: i128,
+}
+
+#[contractevent(topics = ["rent_stream_withdrawn"])]
+pub struct RentStreamWithdrawn {
+    #[topic]
+    pub agreement_id: String,
+    pub landlord: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["rent_stream_stopped"])]
+pub struct RentStreamStopped {
+    #[topic]
+    pub agreement_id: String,
+    pub settled_to_landlord: i128,
+    pub refunded_to_tenant: i128,
+}
+
+#[contractevent(topics = ["rent_stream_insolvent"])]
+pub struct RentStreamInsolvent {
+    #[topic]
+    pub agreement_id: String,
+    pub depletion_time: u64,
+}
+
+pub(crate) fn rent_stream_started(
+    env: &Env,
+    agreement_id: String,
+    tenant: Address,
+    deposit: i128,
+    rent_per_period: i128,
+) {
+    RentStreamStarted {
+        agreement_id,
+        tenant,
+        deposit,
+        rent_per_period,
+    }
+    .publish(env);
+}
+
+pub(crate) fn rent_stream_topped_up(
+    env: &Env,
+    agreement_id: String,
+    amount: i128,
+    deposited: i128,
+) {
+    RentStreamToppedUp {
+        agreement_id,
+        amount,
+        deposited,
+    }
+    .publish(env);
+}
+
+pub(crate) fn rent_stream_withdrawn(
+    env: &Env,
+    agreement_id: String,
+    landlord: Address,
+    amount: i128,
+) {
+    RentStreamWithdrawn {
+        agreement_id,
+        landlord,
+        amount,
+    }
+    .publish(env);
+}
+
+pub(crate) fn rent_stream_stopped(
+    env: &Env,
+    agreement_id: String,
+    settled_to_landlord: i128,
+    refunded_to_tenant: i128,
+) {
+    RentStreamStopped {
+        agreement_id,
+        settled_to_landlord,
+        refunded_to_tenant,
+    }
+    .publish(env);
+}
+
+pub(crate) fn rent_stream_insolvent(env: &Env, agreement_id: String, depletion_time: u64) {
+    RentStreamInsolvent {
+        agreement_id,
+        depletion_time,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["payment_refunded"])]
+pub struct PaymentRefunded {
+    #[topic]
+    pub payment_id: String,
+    pub refund_id: String,
+    pub agreement_id: String,
+    pub party: RefundParty,
+    pub amount: i128,
+}
+
+pub(crate) fn payment_refunded(
+    env: &Env,
+    payment_id: String,
+    refund_id: String,
+    agreement_id: String,
+    party: RefundParty,
+    amount: i128,
+) {
+    PaymentRefunded {
+        payment_id,
+        refund_id,
+        agreement_id,
+        party,
+        amount,
+    }
+    .publish(env);
+}
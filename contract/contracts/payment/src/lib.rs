@@ -6,14 +6,17 @@
 //! Handles rent payment processing with automatic commission splitting
 //! and payment record management.
 
-use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, Address, Env, Map, String, Vec};
 
+pub mod co_payer;
 pub mod errors;
 pub mod events;
 pub mod late_fee;
 pub mod payment_impl;
 pub mod rate_limit;
+pub mod refund;
 pub mod storage;
+pub mod streaming;
 pub mod types;
 pub mod upgrade;
 
@@ -26,20 +29,37 @@ mod tests_recurring;
 #[cfg(test)]
 mod tests_rate_limit;
 
+#[cfg(test)]
+mod tests_batch;
+
+#[cfg(test)]
+mod tests_co_payer;
+
+#[cfg(test)]
+mod tests_streaming;
+
+#[cfg(test)]
+mod tests_refund;
+
 // Re-export public APIs
 pub use errors::PaymentError;
 pub use payment_impl::{calculate_payment_split, calculate_rent_for_period, create_payment_record};
 pub use storage::DataKey;
 pub use types::{
-    EscalationType, ExecutionStatus, LateFeeConfig, LateFeeRecord, PaymentExecution,
-    PaymentFrequency, PaymentRecord, PaymentSplit, RecurringPayment, RecurringPaymentEvent,
-    RecurringStatus, RentEscalationConfig,
+    BatchItemOutcome, BatchItemStatus, BatchPaymentReport, CoPayer, EscalationType,
+    ExecutionStatus, LateFeeConfig, LateFeeRecord, PayerRole, PaymentExecution, PaymentFrequency,
+    PaymentRecord, PaymentSplit, PeriodContributions, RecurringPayment, RecurringPaymentEvent,
+    RecurringStatus, RefundParty, RefundReason, RefundRecord, RefundedAmounts,
+    RentEscalationConfig, RentStream, StreamStatus,
 };
 
 use crate::errors::PaymentError as Error;
 use crate::storage::DataKey as StorageKey;
 use crate::types::{AgreementStatus, RentAgreement};
 
+/// Maximum number of agreements that can be paid in a single batch
+pub const MAX_BATCH_SIZE: u32 = 50;
+
 #[contract]
 pub struct PaymentContract;
 
@@ -188,9 +208,7 @@ impl PaymentContract {
 
     /// Get a payment record by ID
     pub fn get_payment(env: Env, payment_id: String) -> Result<PaymentRecord, Error> {
-        env.storage()
-            .persistent()
-            .get(&StorageKey::Payment(payment_id))
+        storage::get_payment_record(&env, &StorageKey::Payment(payment_id))
             .ok_or(Error::PaymentNotFound)
     }
 
@@ -213,11 +231,9 @@ impl PaymentContract {
         let mut total: i128 = 0;
 
         for i in 0..payment_count {
-            let payment_id = Self::u32_to_string(&env, i);
-            if let Some(payment) = env
-                .storage()
-                .persistent()
-                .get::<StorageKey, PaymentRecord>(&StorageKey::Payment(payment_id))
+            let payment_id = payment_impl::u32_to_string(&env, i);
+            if let Some(payment) =
+                storage::get_payment_record(&env, &StorageKey::Payment(payment_id))
             {
                 if payment.agreement_id == agreement_id {
                     total += payment.amount;
@@ -228,23 +244,6 @@ impl PaymentContract {
         Ok(total)
     }
 
-    fn u32_to_string(env: &Env, num: u32) -> String {
-        match num {
-            0 => String::from_str(env, "0"),
-            1 => String::from_str(env, "1"),
-            2 => String::from_str(env, "2"),
-            3 => String::from_str(env, "3"),
-            4 => String::from_str(env, "4"),
-            5 => String::from_str(env, "5"),
-            6 => String::from_str(env, "6"),
-            7 => String::from_str(env, "7"),
-            8 => String::from_str(env, "8"),
-            9 => String::from_str(env, "9"),
-            10 => String::from_str(env, "10"),
-            _ => String::from_str(env, "unknown"),
-        }
-    }
-
     /// Process rent payment with 90/10 landlord/platform split
     /// Follows checks-effects-interactions pattern for reentrancy safety
     pub fn pay_rent(
@@ -261,50 +260,332 @@ impl PaymentContract {
         // Rate limiting check
         crate::rate_limit::check_rate_limit(&env, &from, "pay_rent")?;
 
-        // Load agreement
-        let mut agreement: RentAgreement = env
+        // Validation
+        let agreement =
+            payment_impl::validate_rent_payment(&env, &from, &agreement_id, payment_amount)?;
+
+        let platform_collector: Address = env
+            .storage()
+            .instance()
+            .get(&StorageKey::PlatformFeeCollector)
+            .ok_or(Error::PaymentFailed)?;
+
+        let (landlord_amount, platform_amount) =
+            payment_impl::calculate_platform_split(payment_amount);
+        let landlord = agreement.landlord.clone();
+        let payment_token = agreement.payment_token.clone();
+
+        // Effects: Update state BEFORE external calls
+        payment_impl::record_rent_payment(&env, &from, &agreement_id, agreement, payment_amount)?;
+
+        // Interactions: External calls AFTER state updates
+        let token_client = token::Client::new(&env, &payment_token);
+        token_client.transfer(&from, &landlord, &landlord_amount);
+        token_client.transfer(&from, &platform_collector, &platform_amount);
+
+        Ok(())
+    }
+
+    /// Pay rent for several agreements in one all-or-nothing call.
+    ///
+    /// Every item is validated as if it were a standalone `pay_rent` call.
+    /// If any item fails, nothing is paid and the returned report carries the
+    /// error code of each failing item. Otherwise a `PaymentRecord` is written
+    /// per item and transfers are aggregated per recipient and token.
+    pub fn pay_rent_batch(
+        env: Env,
+        from: Address,
+        payments: Vec<(String, i128)>,
+    ) -> Result<BatchPaymentReport, Error> {
+        use soroban_sdk::token;
+
+        from.require_auth();
+
+        if payments.is_empty() {
+            return Err(Error::EmptyBatch);
+        }
+        if payments.len() > MAX_BATCH_SIZE {
+            return Err(Error::BatchTooLarge);
+        }
+
+        // Each item counts as one `pay_rent` call
+        crate::rate_limit::charge_rate_limit(&env, &from, "pay_rent", payments.len())?;
+
+        let platform_collector: Address = env
+            .storage()
+            .instance()
+            .get(&StorageKey::PlatformFeeCollector)
+            .ok_or(Error::PaymentFailed)?;
+
+        // Checks: validate every item before touching state
+        let mut seen: Map<String, bool> = Map::new(&env);
+        let mut agreements: Vec<RentAgreement> = Vec::new(&env);
+        let mut items: Vec<BatchItemOutcome> = Vec::new(&env);
+        let mut all_valid = true;
+
+        for (agreement_id, amount) in payments.iter() {
+            let result = if seen.contains_key(agreement_id.clone()) {
+                Err(Error::DuplicateBatchItem)
+            } else {
+                payment_impl::validate_rent_payment(&env, &from, &agreement_id, amount)
+            };
+            seen.set(agreement_id.clone(), true);
+
+            let status = match result {
+                Ok(agreement) => {
+                    agreements.push_back(agreement);
+                    BatchItemStatus::Skipped
+                }
+                Err(err) => {
+                    all_valid = false;
+                    BatchItemStatus::Failed(err as u32)
+                }
+            };
+            items.push_back(BatchItemOutcome {
+                agreement_id,
+                amount,
+                status,
+                payment_id: None,
+            });
+        }
+
+        if !all_valid {
+            return Ok(BatchPaymentReport {
+                payer: from,
+                executed: false,
+                total_amount: 0,
+                items,
+            });
+        }
+
+        // Effects: record every payment and aggregate transfers
+        let mut transfers: Map<(Address, Address), i128> = Map::new(&env);
+        let mut total_amount: i128 = 0;
+
+        for i in 0..items.len() {
+            let mut item = items.get(i).unwrap();
+            let agreement = agreements.get(i).unwrap();
+            let (landlord_amount, platform_amount) =
+                payment_impl::calculate_platform_split(item.amount);
+
+            for (recipient, share) in [
+                (agreement.landlord.clone(), landlord_amount),
+                (platform_collector.clone(), platform_amount),
+            ] {
+                let key = (recipient, agreement.payment_token.clone());
+                let current = transfers.get(key.clone()).unwrap_or(0);
+                transfers.set(key, current + share);
+            }
+            total_amount = total_amount
+                .checked_add(item.amount)
+                .ok_or(Error::InvalidPaymentAmount)?;
+
+            let payment_id = payment_impl::record_rent_payment(
+                &env,
+                &from,
+                &item.agreement_id,
+                agreement,
+                item.amount,
+            )?;
+            item.status = BatchItemStatus::Paid;
+            item.payment_id = Some(payment_id);
+            items.set(i, item);
+        }
+
+        events::batch_rent_paid(&env, from.clone(), items.len(), total_amount);
+
+        // Interactions: one transfer per recipient and token
+        for ((recipient, payment_token), amount) in transfers.iter() {
+            if amount > 0 {
+                token::Client::new(&env, &payment_token).transfer(&from, &recipient, &amount);
+            }
+        }
+
+        Ok(BatchPaymentReport {
+            payer: from,
+            executed: true,
+            total_amount,
+            items,
+        })
+    }
+
+    // ─── Third-Party Payers ───────────────────────────────────────────────────
+
+    /// Register a guarantor, employer or subsidy program as a co-payer.
+    /// Requires consent from both the tenant and the co-payer.
+    pub fn add_co_payer(
+        env: Env,
+        agreement_id: String,
+        payer: Address,
+        role: PayerRole,
+        share_bps: u32,
+        cap_per_period: Option<i128>,
+        call_threshold: i128,
+    ) -> Result<(), Error> {
+        let agreement: RentAgreement = env
             .storage()
             .persistent()
             .get(&StorageKey::Agreement(agreement_id.clone()))
             .ok_or(Error::AgreementNotFound)?;
 
-        // Validation
+        agreement.tenant.require_auth();
+        payer.require_auth();
+
         if agreement.status != AgreementStatus::Active {
             return Err(Error::AgreementNotActive);
         }
 
-        if from != agreement.tenant {
-            return Err(Error::NotTenant);
+        if payer == agreement.tenant {
+            return Err(Error::NotAuthorizedPayer);
         }
 
-        if payment_amount <= 0 {
-            return Err(Error::InvalidPaymentAmount);
+        if share_bps > co_payer::BPS_DENOMINATOR
+            || call_threshold < 0
+            || matches!(cap_per_period, Some(cap) if cap <= 0)
+        {
+            return Err(Error::InvalidPayerShare);
         }
 
-        let payment_number = agreement.payment_history.len() + 1;
-        let expected_amount = if let Some(config) = env
+        let mut co_payers = co_payer::get_co_payers(&env, &agreement_id);
+        if co_payers.len() >= co_payer::MAX_CO_PAYERS {
+            return Err(Error::TooManyCoPayers);
+        }
+
+        let mut total_share = share_bps;
+        for existing in co_payers.iter() {
+            if existing.payer == payer {
+                return Err(Error::CoPayerAlreadyExists);
+            }
+            total_share += existing.share_bps;
+        }
+        if total_share > co_payer::BPS_DENOMINATOR {
+            return Err(Error::InvalidPayerShare);
+        }
+
+        co_payers.push_back(CoPayer {
+            payer: payer.clone(),
+            role: role.clone(),
+            share_bps,
+            cap_per_period,
+            call_threshold,
+            added_at: env.ledger().timestamp(),
+        });
+        env.storage()
+            .persistent()
+            .set(&StorageKey::CoPayers(agreement_id.clone()), &co_payers);
+
+        events::co_payer_added(&env, agreement_id, payer, role, share_bps);
+
+        Ok(())
+    }
+
+    /// Remove a co-payer. Either the tenant or the co-payer itself may call this.
+    pub fn remove_co_payer(
+        env: Env,
+        caller: Address,
+        agreement_id: String,
+        payer: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let agreement: RentAgreement = env
             .storage()
             .persistent()
-            .get::<StorageKey, RentEscalationConfig>(&StorageKey::RentEscalationConfig(
-                agreement_id.clone(),
-            )) {
-            calculate_rent_for_period(agreement.monthly_rent, payment_number, &config)
-        } else {
-            agreement.monthly_rent
-        };
+            .get(&StorageKey::Agreement(agreement_id.clone()))
+            .ok_or(Error::AgreementNotFound)?;
 
-        if payment_amount != expected_amount {
-            return Err(Error::InvalidPaymentAmount);
+        if caller != agreement.tenant && caller != payer {
+            return Err(Error::NotAuthorizedPayer);
         }
 
-        let current_time = env.ledger().timestamp();
-        if current_time < agreement.next_payment_due {
-            return Err(Error::PaymentNotDue);
+        let co_payers = co_payer::get_co_payers(&env, &agreement_id);
+        let mut remaining = Vec::new(&env);
+        for existing in co_payers.iter() {
+            if existing.payer != payer {
+                remaining.push_back(existing);
+            }
+        }
+        if remaining.len() == co_payers.len() {
+            return Err(Error::CoPayerNotFound);
         }
 
-        // Calculate 90/10 split
-        let landlord_amount = (payment_amount * 90) / 100;
-        let platform_amount = payment_amount - landlord_amount;
+        env.storage()
+            .persistent()
+            .set(&StorageKey::CoPayers(agreement_id.clone()), &remaining);
+
+        events::co_payer_removed(&env, agreement_id, payer);
+
+        Ok(())
+    }
+
+    /// Get the co-payers registered on an agreement
+    pub fn get_co_payers(env: Env, agreement_id: String) -> Vec<CoPayer> {
+        co_payer::get_co_payers(&env, &agreement_id)
+    }
+
+    /// Get the contributions made so far towards the agreement's current period
+    pub fn get_period_contributions(
+        env: Env,
+        agreement_id: String,
+    ) -> Result<PeriodContributions, Error> {
+        let agreement: RentAgreement = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::Agreement(agreement_id.clone()))
+            .ok_or(Error::AgreementNotFound)?;
+
+        Ok(co_payer::current_contributions(
+            &env,
+            &agreement_id,
+            &agreement,
+        ))
+    }
+
+    /// Charge a guarantor for the current period's outstanding rent once the
+    /// agreement's arrears exceed the guarantor's call threshold. The
+    /// guarantor must have approved this contract as a spender on the
+    /// payment token. Only the landlord may call this.
+    pub fn call_guarantor(
+        env: Env,
+        agreement_id: String,
+        guarantor: Address,
+    ) -> Result<String, Error> {
+        use soroban_sdk::token;
+
+        let agreement: RentAgreement = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::Agreement(agreement_id.clone()))
+            .ok_or(Error::AgreementNotFound)?;
+
+        agreement.landlord.require_auth();
+
+        if agreement.status != AgreementStatus::Active {
+            return Err(Error::AgreementNotActive);
+        }
+
+        let co_payer = co_payer::find_co_payer(&env, &agreement_id, &guarantor)
+            .ok_or(Error::CoPayerNotFound)?;
+        if co_payer.role != PayerRole::Guarantor {
+            return Err(Error::NotGuarantor);
+        }
+
+        let expected_amount = payment_impl::expected_rent(&env, &agreement_id, &agreement);
+        let progress = co_payer::current_contributions(&env, &agreement_id, &agreement);
+        let arrears = co_payer::arrears(&env, &agreement, expected_amount, &progress);
+        if arrears <= co_payer.call_threshold {
+            return Err(Error::ArrearsBelowThreshold);
+        }
+
+        let mut charge = expected_amount - progress.amount_paid;
+        let remaining_limit = co_payer::period_limit(&co_payer, expected_amount)
+            - co_payer::contributed_by(&progress, &guarantor);
+        if remaining_limit < charge {
+            charge = remaining_limit;
+        }
+        if charge <= 0 {
+            return Err(Error::PayerCapExceeded);
+        }
 
         let platform_collector: Address = env
             .storage()
@@ -312,29 +593,21 @@ impl PaymentContract {
             .get(&StorageKey::PlatformFeeCollector)
             .ok_or(Error::PaymentFailed)?;
 
-        // Effects: Update state BEFORE external calls
-        let payment_month = agreement.payment_history.len();
-        agreement.payment_history.set(
-            payment_month,
-            PaymentSplit {
-                landlord_amount,
-                platform_amount,
-                token: agreement.payment_token.clone(),
-                payment_date: current_time,
-            },
-        );
-        agreement.next_payment_due = current_time + 2_592_000; // 30 days
+        let (landlord_amount, platform_amount) = payment_impl::calculate_platform_split(charge);
+        let landlord = agreement.landlord.clone();
+        let payment_token = agreement.payment_token.clone();
 
-        env.storage()
-            .persistent()
-            .set(&StorageKey::Agreement(agreement_id.clone()), &agreement);
+        let payment_id =
+            payment_impl::record_rent_payment(&env, &guarantor, &agreement_id, agreement, charge)?;
 
-        // Interactions: External calls AFTER state updates
-        let token_client = token::Client::new(&env, &agreement.payment_token);
-        token_client.transfer(&from, &agreement.landlord, &landlord_amount);
-        token_client.transfer(&from, &platform_collector, &platform_amount);
+        events::guarantor_called(&env, agreement_id, guarantor.clone(), charge, arrears);
 
-        Ok(())
+        let spender = env.current_contract_address();
+        let token_client = token::Client::new(&env, &payment_token);
+        token_client.transfer_from(&spender, &guarantor, &landlord, &landlord_amount);
+        token_client.transfer_from(&spender, &guarantor, &platform_collector, &platform_amount);
+
+        Ok(payment_id)
     }
 
     /// Get payment details for a specific month
@@ -387,7 +660,7 @@ impl PaymentContract {
             .unwrap_or(0);
         count = count.saturating_add(1);
 
-        let recurring_id = Self::u32_to_string(&env, count);
+        let recurring_id = payment_impl::u32_to_string(&env, count);
         let recurring = RecurringPayment {
             id: recurring_id.clone(),
             agreement_id: agreement_id.clone(),
@@ -588,7 +861,7 @@ impl PaymentContract {
         let mut due = Vec::new(&env);
 
         for i in 1..=count {
-            let recurring_id = Self::u32_to_string(&env, i);
+            let recurring_id = payment_impl::u32_to_string(&env, i);
             if let Some(recurring) = env
                 .storage()
                 .persistent()
@@ -639,6 +912,358 @@ impl PaymentContract {
             .unwrap_or_else(|| Vec::new(&env)))
     }
 
+    // ─── Rent Streaming ───────────────────────────────────────────────────────
+
+    /// Transfer streamed rent held by the contract to the landlord and
+    /// platform using the standard 90/10 split.
+    fn pay_out_stream(env: &Env, stream: &RentStream, amount: i128) -> Result<(), Error> {
+        use soroban_sdk::token;
+
+        if amount <= 0 {
+            return Ok(());
+        }
+
+        let platform_collector: Address = env
+            .storage()
+            .instance()
+            .get(&StorageKey::PlatformFeeCollector)
+            .ok_or(Error::PaymentFailed)?;
+
+        let (landlord_amount, platform_amount) = payment_impl::calculate_platform_split(amount);
+        let contract = env.current_contract_address();
+        let token_client = token::Client::new(env, &stream.token);
+        token_client.transfer(&contract, &stream.landlord, &landlord_amount);
+        token_client.transfer(&contract, &platform_collector, &platform_amount);
+
+        Ok(())
+    }
+
+    /// Start streaming rent for an agreement. The tenant deposits `deposit`
+    /// into the contract and rent accrues to the landlord per second. While a
+    /// stream is running, one-off `pay_rent` payments are rejected. The
+    /// stream opens at the current period, which must be due and unpaid.
+    pub fn start_rent_stream(env: Env, agreement_id: String, deposit: i128) -> Result<(), Error> {
+        use soroban_sdk::token;
+
+        let agreement: RentAgreement = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::Agreement(agreement_id.clone()))
+            .ok_or(Error::AgreementNotFound)?;
+
+        agreement.tenant.require_auth();
+
+        if agreement.status != AgreementStatus::Active {
+            return Err(Error::AgreementNotActive);
+        }
+
+        if deposit <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        if streaming::get_stream(&env, &agreement_id).is_some_and(|s| streaming::is_running(&s)) {
+            return Err(Error::StreamAlreadyActive);
+        }
+
+        let now = env.ledger().timestamp();
+        let progress = co_payer::current_contributions(&env, &agreement_id, &agreement);
+        if now < agreement.next_payment_due || progress.amount_paid > 0 {
+            return Err(Error::PaymentNotDue);
+        }
+
+        let rent_per_period = payment_impl::expected_rent(&env, &agreement_id, &agreement);
+        let mut stream = RentStream {
+            agreement_id: agreement_id.clone(),
+            tenant: agreement.tenant.clone(),
+            landlord: agreement.landlord.clone(),
+            token: agreement.payment_token.clone(),
+            rent_per_period,
+            period_seconds: payment_impl::RENT_PERIOD_SECONDS,
+            start_time: now,
+            end_time: if agreement.end_date > now {
+                agreement.end_date
+            } else {
+                u64::MAX
+            },
+            deposited: deposit,
+            withdrawn: 0,
+            status: StreamStatus::Active,
+            stopped_at: None,
+            periods_recorded: 0,
+        };
+        streaming::refresh_status(&env, &mut stream);
+        streaming::save_stream(&env, &stream);
+
+        events::rent_stream_started(
+            &env,
+            agreement_id,
+            agreement.tenant.clone(),
+            deposit,
+            rent_per_period,
+        );
+
+        token::Client::new(&env, &agreement.payment_token).transfer(
+            &agreement.tenant,
+            env.current_contract_address(),
+            &deposit,
+        );
+
+        Ok(())
+    }
+
+    /// Add funds to a running stream. Topping up an insolvent stream makes
+    /// it active again once the balance covers the current period.
+    pub fn top_up_rent_stream(env: Env, agreement_id: String, amount: i128) -> Result<(), Error> {
+        use soroban_sdk::token;
+
+        let mut stream = streaming::get_stream(&env, &agreement_id).ok_or(Error::StreamNotFound)?;
+
+        stream.tenant.require_auth();
+
+        if !streaming::is_running(&stream) {
+            return Err(Error::StreamNotActive);
+        }
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        stream.deposited += amount;
+        streaming::refresh_status(&env, &mut stream);
+        streaming::save_stream(&env, &stream);
+
+        events::rent_stream_topped_up(&env, agreement_id, amount, stream.deposited);
+
+        token::Client::new(&env, &stream.token).transfer(
+            &stream.tenant,
+            env.current_contract_address(),
+            &amount,
+        );
+
+        Ok(())
+    }
+
+    /// Withdraw everything accrued to the landlord so far. Returns the gross
+    /// amount withdrawn before the platform split.
+    pub fn withdraw_rent_stream(env: Env, agreement_id: String) -> Result<i128, Error> {
+        let mut stream = streaming::get_stream(&env, &agreement_id).ok_or(Error::StreamNotFound)?;
+
+        stream.landlord.require_auth();
+
+        let amount = streaming::withdrawable(&env, &stream);
+        if amount <= 0 {
+            return Err(Error::NothingToWithdraw);
+        }
+
+        stream.withdrawn += amount;
+        streaming::refresh_status(&env, &mut stream);
+        streaming::credit_agreement(&env, &mut stream, amount);
+        streaming::save_stream(&env, &stream);
+
+        events::rent_stream_withdrawn(&env, agreement_id, stream.landlord.clone(), amount);
+
+        Self::pay_out_stream(&env, &stream, amount)?;
+
+        Ok(amount)
+    }
+
+    /// Stop a stream with a final settlement: the landlord receives whatever
+    /// has accrued and the tenant is refunded the unaccrued balance.
+    pub fn stop_rent_stream(env: Env, agreement_id: String) -> Result<(), Error> {
+        use soroban_sdk::token;
+
+        let mut stream = streaming::get_stream(&env, &agreement_id).ok_or(Error::StreamNotFound)?;
+
+        stream.tenant.require_auth();
+
+        if !streaming::is_running(&stream) {
+            return Err(Error::StreamNotActive);
+        }
+
+        let now = env.ledger().timestamp();
+        let accrued = streaming::accrued_at(&stream, now);
+        let settled = accrued - stream.withdrawn;
+        let refund = stream.deposited - accrued;
+
+        stream.withdrawn = accrued;
+        stream.status = StreamStatus::Stopped;
+        stream.stopped_at = Some(now);
+        streaming::credit_agreement(&env, &mut stream, settled);
+        streaming::save_stream(&env, &stream);
+
+        events::rent_stream_stopped(&env, agreement_id, settled, refund);
+
+        Self::pay_out_stream(&env, &stream, settled)?;
+        if refund > 0 {
+            token::Client::new(&env, &stream.token).transfer(
+                &env.current_contract_address(),
+                &stream.tenant,
+                &refund,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Check whether a running stream's balance covers the current rent
+    /// period, flagging it `Insolvent` if not. Callable by anyone (e.g. a
+    /// keeper). Returns true if the stream is solvent.
+    pub fn check_stream_solvency(env: Env, agreement_id: String) -> Result<bool, Error> {
+        let mut stream = streaming::get_stream(&env, &agreement_id).ok_or(Error::StreamNotFound)?;
+
+        if !streaming::is_running(&stream) {
+            return Err(Error::StreamNotActive);
+        }
+
+        let solvent = streaming::refresh_status(&env, &mut stream);
+        streaming::save_stream(&env, &stream);
+
+        Ok(solvent)
+    }
+
+    /// Get the rent stream for an agreement
+    pub fn get_rent_stream(env: Env, agreement_id: String) -> Result<RentStream, Error> {
+        streaming::get_stream(&env, &agreement_id).ok_or(Error::StreamNotFound)
+    }
+
+    /// Get the amount the landlord could withdraw from the stream right now
+    pub fn get_stream_withdrawable(env: Env, agreement_id: String) -> Result<i128, Error> {
+        let stream = streaming::get_stream(&env, &agreement_id).ok_or(Error::StreamNotFound)?;
+        Ok(streaming::withdrawable(&env, &stream))
+    }
+
+    // ─── Refunds ──────────────────────────────────────────────────────────────
+
+    /// Refund part or all of the caller's share of a payment back to the
+    /// original payer. The landlord refunds from the landlord share and the
+    /// platform fee collector from the platform share. Returns the refund ID.
+    pub fn refund_payment(
+        env: Env,
+        issuer: Address,
+        payment_id: String,
+        amount: i128,
+        reason: RefundReason,
+        note: String,
+    ) -> Result<String, Error> {
+        use soroban_sdk::token;
+
+        issuer.require_auth();
+
+        let record = storage::get_payment_record(&env, &StorageKey::Payment(payment_id.clone()))
+            .ok_or(Error::PaymentNotFound)?;
+
+        let mut agreement: RentAgreement = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::Agreement(record.agreement_id.clone()))
+            .ok_or(Error::AgreementNotFound)?;
+
+        let platform_collector: Option<Address> = env
+            .storage()
+            .instance()
+            .get(&StorageKey::PlatformFeeCollector);
+
+        let party = if issuer == agreement.landlord {
+            RefundParty::Landlord
+        } else if Some(issuer.clone()) == platform_collector {
+            RefundParty::Platform
+        } else {
+            return Err(Error::NotAuthorizedRefunder);
+        };
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        if !refund::is_refundable_period(&agreement, &record) {
+            return Err(Error::RefundPeriodClosed);
+        }
+
+        let mut refunded = refund::get_refunded_amounts(&env, &payment_id);
+        if amount > refund::refundable(&record, &refunded, &party) {
+            return Err(Error::RefundExceedsPayment);
+        }
+
+        // Effects
+        match party {
+            RefundParty::Landlord => refunded.landlord_amount += amount,
+            RefundParty::Platform => refunded.platform_amount += amount,
+        }
+        env.storage()
+            .persistent()
+            .set(&StorageKey::RefundedAmounts(payment_id.clone()), &refunded);
+
+        refund::adjust_agreement(
+            &env,
+            &record.agreement_id,
+            &mut agreement,
+            &record,
+            &party,
+            amount,
+        );
+        env.storage().persistent().set(
+            &StorageKey::Agreement(record.agreement_id.clone()),
+            &agreement,
+        );
+
+        let refund_id = refund::store_refund(
+            &env,
+            RefundRecord {
+                refund_id: String::from_str(&env, ""),
+                payment_id: payment_id.clone(),
+                agreement_id: record.agreement_id.clone(),
+                party: party.clone(),
+                issuer: issuer.clone(),
+                recipient: record.payer.clone(),
+                amount,
+                reason,
+                note,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        events::payment_refunded(
+            &env,
+            payment_id,
+            refund_id.clone(),
+            record.agreement_id,
+            party,
+            amount,
+        );
+
+        // Interactions
+        token::Client::new(&env, &agreement.payment_token).transfer(
+            &issuer,
+            &record.payer,
+            &amount,
+        );
+
+        Ok(refund_id)
+    }
+
+    /// Get a refund audit record by ID
+    pub fn get_refund(env: Env, refund_id: String) -> Result<RefundRecord, Error> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Refund(refund_id))
+            .ok_or(Error::RefundNotFound)
+    }
+
+    /// Get every refund issued against a payment, oldest first
+    pub fn get_payment_refunds(env: Env, payment_id: String) -> Vec<RefundRecord> {
+        refund::get_refunds(&env, StorageKey::PaymentRefunds(payment_id))
+    }
+
+    /// Get every refund issued against an agreement, oldest first
+    pub fn get_agreement_refunds(env: Env, agreement_id: String) -> Vec<RefundRecord> {
+        refund::get_refunds(&env, StorageKey::AgreementRefunds(agreement_id))
+    }
+
+    /// Get the amounts refunded so far from each share of a payment
+    pub fn get_refunded_amounts(env: Env, payment_id: String) -> RefundedAmounts {
+        refund::get_refunded_amounts(&env, &payment_id)
+    }
+
     // ─── Late Fee Functions ───────────────────────────────────────────────────
 
     /// Set or update the late fee configuration for an agreement.
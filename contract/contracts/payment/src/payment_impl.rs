@@ -1,13 +1,20 @@
 //! Payment processing implementation.
 use soroban_sdk::{Address, Env, String};
 
+use crate::co_payer;
 use crate::errors::PaymentError;
+use crate::events;
 use crate::storage::DataKey;
+use crate::streaming;
 use crate::types::{
-    AgreementStatus, EscalationType, PaymentRecord, RentAgreement, RentEscalationConfig,
+    AgreementStatus, EscalationType, PaymentRecord, PaymentSplit, RentAgreement,
+    RentEscalationConfig,
 };
 use crate::upgrade;
 
+/// Interval between rent payments once a payment has been made (30 days)
+pub const RENT_PERIOD_SECONDS: u64 = 2_592_000;
+
 /// Calculate the rent amount for a specific period (payment number) with escalation
 pub fn calculate_rent_for_period(
     base_rent: i128,
@@ -60,6 +67,8 @@ pub fn create_payment_record(
         agent_amount,
         timestamp,
         tenant: tenant.clone(),
+        payer: tenant.clone(),
+        period: payment_number.saturating_sub(1),
     })
 }
 
@@ -71,6 +80,209 @@ pub fn calculate_payment_split(amount: &i128, commission_rate: &u32) -> (i128, i
     (landlord_amount, agent_amount)
 }
 
+/// Calculate the 90/10 landlord/platform split applied to rent payments
+pub fn calculate_platform_split(amount: i128) -> (i128, i128) {
+    let landlord_amount = (amount * 90) / 100;
+    let platform_amount = amount - landlord_amount;
+    (landlord_amount, platform_amount)
+}
+
+/// Render a counter as a decimal string ID
+pub(crate) fn u32_to_string(env: &Env, num: u32) -> String {
+    let mut buf = [0u8; 10];
+    let mut start = buf.len();
+    let mut n = num;
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    String::from_bytes(env, &buf[start..])
+}
+
+/// Rent due for the agreement's current period, with escalation applied
+pub(crate) fn expected_rent(env: &Env, agreement_id: &String, agreement: &RentAgreement) -> i128 {
+    let payment_number = agreement.payment_history.len() + 1;
+    if let Some(config) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, RentEscalationConfig>(&DataKey::RentEscalationConfig(agreement_id.clone()))
+    {
+        calculate_rent_for_period(agreement.monthly_rent, payment_number, &config)
+    } else {
+        agreement.monthly_rent
+    }
+}
+
+/// Check that `from` may pay `amount` towards `agreement_id` right now.
+/// Returns the loaded agreement on success; no state is modified.
+///
+/// Without co-payers the tenant must pay the full period's rent. Once
+/// co-payers are registered, the tenant may pay any part of what is still
+/// outstanding and each co-payer may pay up to their share for the period.
+pub(crate) fn validate_rent_payment(
+    env: &Env,
+    from: &Address,
+    agreement_id: &String,
+    amount: i128,
+) -> Result<RentAgreement, PaymentError> {
+    let agreement: RentAgreement = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Agreement(agreement_id.clone()))
+        .ok_or(PaymentError::AgreementNotFound)?;
+
+    if agreement.status != AgreementStatus::Active {
+        return Err(PaymentError::AgreementNotActive);
+    }
+
+    if streaming::get_stream(env, agreement_id).is_some_and(|s| streaming::is_running(&s)) {
+        return Err(PaymentError::StreamAlreadyActive);
+    }
+
+    let co_payer = if *from == agreement.tenant {
+        None
+    } else {
+        Some(
+            co_payer::find_co_payer(env, agreement_id, from)
+                .ok_or(PaymentError::NotAuthorizedPayer)?,
+        )
+    };
+
+    if amount <= 0 {
+        return Err(PaymentError::InvalidPaymentAmount);
+    }
+
+    let expected_amount = expected_rent(env, agreement_id, &agreement);
+    let progress = co_payer::current_contributions(env, agreement_id, &agreement);
+    let outstanding = expected_amount - progress.amount_paid;
+
+    match co_payer {
+        None => {
+            let split_payments =
+                progress.amount_paid > 0 || !co_payer::get_co_payers(env, agreement_id).is_empty();
+            if amount > outstanding || (!split_payments && amount != expected_amount) {
+                return Err(PaymentError::InvalidPaymentAmount);
+            }
+        }
+        Some(co_payer) => {
+            if amount > outstanding {
+                return Err(PaymentError::InvalidPaymentAmount);
+            }
+            let already_paid = co_payer::contributed_by(&progress, from);
+            if already_paid + amount > co_payer::period_limit(&co_payer, expected_amount) {
+                return Err(PaymentError::PayerCapExceeded);
+            }
+        }
+    }
+
+    if env.ledger().timestamp() < agreement.next_payment_due {
+        return Err(PaymentError::PaymentNotDue);
+    }
+
+    Ok(agreement)
+}
+
+/// Apply a validated rent payment to the agreement and persist its
+/// `PaymentRecord`, attributed to `from`. The period is closed into
+/// `payment_history` once contributions reach the rent due. Token transfers
+/// are left to the caller so that they happen after all state updates.
+/// Returns the new payment ID.
+pub(crate) fn record_rent_payment(
+    env: &Env,
+    from: &Address,
+    agreement_id: &String,
+    mut agreement: RentAgreement,
+    amount: i128,
+) -> Result<String, PaymentError> {
+    let (landlord_amount, platform_amount) = calculate_platform_split(amount);
+    let current_time = env.ledger().timestamp();
+    let expected_amount = expected_rent(env, agreement_id, &agreement);
+
+    let mut progress = co_payer::current_contributions(env, agreement_id, &agreement);
+    progress.amount_paid += amount;
+    progress.landlord_amount += landlord_amount;
+    progress.platform_amount += platform_amount;
+    let contributed = co_payer::contributed_by(&progress, from);
+    progress
+        .contributions
+        .set(from.clone(), contributed + amount);
+
+    let progress_key = DataKey::PeriodContributions(agreement_id.clone());
+    if progress.amount_paid >= expected_amount {
+        agreement.payment_history.set(
+            progress.period,
+            PaymentSplit {
+                landlord_amount: progress.landlord_amount,
+                platform_amount: progress.platform_amount,
+                token: agreement.payment_token.clone(),
+                payment_date: current_time,
+            },
+        );
+        agreement.next_payment_due = current_time + RENT_PERIOD_SECONDS;
+        env.storage().persistent().remove(&progress_key);
+    } else {
+        env.storage().persistent().set(&progress_key, &progress);
+    }
+    agreement.total_rent_paid += amount;
+    agreement.payment_count += 1;
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+
+    let mut record = create_payment_record(
+        env,
+        agreement_id,
+        amount,
+        landlord_amount,
+        0,
+        &agreement.tenant,
+        agreement.payment_count,
+        current_time,
+    )?;
+    record.payer = from.clone();
+    record.period = progress.period;
+    let payment_id = store_payment_record(env, &record);
+
+    events::rent_paid(
+        env,
+        agreement_id.clone(),
+        payment_id.clone(),
+        from.clone(),
+        amount,
+    );
+
+    Ok(payment_id)
+}
+
+/// Store a payment record under the next payment ID and under its
+/// agreement and payment number. Returns the payment ID.
+pub(crate) fn store_payment_record(env: &Env, record: &PaymentRecord) -> String {
+    let payment_count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::PaymentCount)
+        .unwrap_or(0);
+    let payment_id = u32_to_string(env, payment_count);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Payment(payment_id.clone()), record);
+    env.storage().persistent().set(
+        &DataKey::PaymentRecord(record.agreement_id.clone(), record.payment_number),
+        record,
+    );
+    env.storage()
+        .instance()
+        .set(&DataKey::PaymentCount, &(payment_count + 1));
+
+    payment_id
+}
+
 /// Process rent payment with automatic commission splitting
 /// This is the alternate implementation used by RentalContract
 #[allow(deprecated)]
@@ -25,6 +25,17 @@ pub fn check_rate_limit(
     env: &Env,
     user: &Address,
     function_name: &str,
+) -> Result<(), PaymentError> {
+    charge_rate_limit(env, user, function_name, 1)
+}
+
+/// Charge `calls` invocations of `function_name` at once, as if they had been
+/// made separately in the current block. The cooldown applies once.
+pub fn charge_rate_limit(
+    env: &Env,
+    user: &Address,
+    function_name: &str,
+    calls: u32,
 ) -> Result<(), PaymentError> {
     let config = get_rate_limit_config(env);
     let current_block = env.ledger().sequence() as u64;
@@ -59,7 +70,7 @@ pub fn check_rate_limit(
     }
 
     // Check daily limit
-    if user_calls.daily_count >= config.max_calls_per_user_per_day {
+    if user_calls.daily_count.saturating_add(calls) > config.max_calls_per_user_per_day {
         return Err(PaymentError::RateLimitExceeded);
     }
 
@@ -67,14 +78,14 @@ pub fn check_rate_limit(
     let block_key = DataKey::BlockCallCount(current_block, String::from_str(env, function_name));
     let block_calls: u32 = env.storage().temporary().get(&block_key).unwrap_or(0);
 
-    if block_calls >= config.max_calls_per_block {
+    if block_calls.saturating_add(calls) > config.max_calls_per_block {
         return Err(PaymentError::RateLimitExceeded);
     }
 
     // Update counters
-    user_calls.call_count += 1;
+    user_calls.call_count += calls;
     user_calls.last_call_block = current_block;
-    user_calls.daily_count += 1;
+    user_calls.daily_count += calls;
 
     // Save user call count
     env.storage().persistent().set(&key, &user_calls);
@@ -83,7 +94,7 @@ pub fn check_rate_limit(
     // Update block call count
     env.storage()
         .temporary()
-        .set(&block_key, &(block_calls + 1));
+        .set(&block_key, &(block_calls + calls));
     env.storage()
         .temporary()
         .extend_ttl(&block_key, BLOCKS_PER_DAY as u32, BLOCKS_PER_DAY as u32);
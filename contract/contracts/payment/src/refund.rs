@@ -0,0 +1,216 @@
+//! Refunds and payment corrections.
+//!
+//! The landlord and the platform can each refund up to their own share of a
+//! `PaymentRecord`. Refunds go back to whoever made the original payment and
+//! are kept as an append-only trail of `RefundRecord`s. The agreement's
+//! totals and the affected rent period are adjusted to reflect the refund.
+
+use soroban_sdk::{Address, Env, Map, String, Vec};
+
+use crate::co_payer;
+use crate::payment_impl::expected_rent;
+use crate::storage::DataKey;
+use crate::types::{
+    PaymentRecord, PeriodContributions, RefundParty, RefundRecord, RefundedAmounts, RentAgreement,
+};
+
+pub fn get_refunded_amounts(env: &Env, payment_id: &String) -> RefundedAmounts {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RefundedAmounts(payment_id.clone()))
+        .unwrap_or(RefundedAmounts {
+            landlord_amount: 0,
+            platform_amount: 0,
+        })
+}
+
+/// Amount of `party`'s share of the payment that has not yet been refunded
+pub fn refundable(record: &PaymentRecord, refunded: &RefundedAmounts, party: &RefundParty) -> i128 {
+    match party {
+        RefundParty::Landlord => record.landlord_amount - refunded.landlord_amount,
+        RefundParty::Platform => {
+            record.amount - record.landlord_amount - record.agent_amount - refunded.platform_amount
+        }
+    }
+}
+
+/// Append a refund to the audit trail, indexed by payment and agreement.
+/// Returns the new refund ID.
+pub fn store_refund(env: &Env, mut refund: RefundRecord) -> String {
+    let count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::RefundCount)
+        .unwrap_or(0);
+    let refund_id = crate::payment_impl::u32_to_string(env, count);
+    refund.refund_id = refund_id.clone();
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::Refund(refund_id.clone()), &refund);
+    env.storage()
+        .instance()
+        .set(&DataKey::RefundCount, &(count + 1));
+
+    for key in [
+        DataKey::PaymentRefunds(refund.payment_id.clone()),
+        DataKey::AgreementRefunds(refund.agreement_id.clone()),
+    ] {
+        let mut ids: Vec<String> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        ids.push_back(refund_id.clone());
+        env.storage().persistent().set(&key, &ids);
+    }
+
+    refund_id
+}
+
+pub fn get_refunds(env: &Env, key: DataKey) -> Vec<RefundRecord> {
+    let ids: Vec<String> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env));
+    let mut refunds = Vec::new(env);
+    for refund_id in ids.iter() {
+        if let Some(refund) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, RefundRecord>(&DataKey::Refund(refund_id))
+        {
+            refunds.push_back(refund);
+        }
+    }
+    refunds
+}
+
+/// Whether `record` can still be refunded: it must belong to the open
+/// period or the most recently closed one. Older periods are settled, and
+/// reopening them would leave the periods after them out of order.
+pub fn is_refundable_period(agreement: &RentAgreement, record: &PaymentRecord) -> bool {
+    record.period + 1 >= agreement.payment_history.len()
+}
+
+/// Reflect a refund in the agreement's totals and payment schedule.
+///
+/// Refunds against the open period reduce the contributions made towards
+/// it. Refunds against the latest closed period reduce its recorded split;
+/// if that leaves it short of the rent due it is reopened with whatever
+/// remains as contributions, and the rest is due immediately.
+pub fn adjust_agreement(
+    env: &Env,
+    agreement_id: &String,
+    agreement: &mut RentAgreement,
+    record: &PaymentRecord,
+    party: &RefundParty,
+    amount: i128,
+) {
+    agreement.total_rent_paid -= amount;
+
+    let open_period = agreement.payment_history.len();
+    if record.period == open_period {
+        let mut progress = co_payer::current_contributions(env, agreement_id, agreement);
+        progress.amount_paid -= amount;
+        match party {
+            RefundParty::Landlord => progress.landlord_amount -= amount,
+            RefundParty::Platform => progress.platform_amount -= amount,
+        }
+        let contributed = co_payer::contributed_by(&progress, &record.payer);
+        progress
+            .contributions
+            .set(record.payer.clone(), contributed - amount);
+        env.storage().persistent().set(
+            &DataKey::PeriodContributions(agreement_id.clone()),
+            &progress,
+        );
+        return;
+    }
+
+    if let Some(mut split) = agreement.payment_history.get(record.period) {
+        match party {
+            RefundParty::Landlord => split.landlord_amount -= amount,
+            RefundParty::Platform => split.platform_amount -= amount,
+        }
+
+        let remaining = split.landlord_amount + split.platform_amount;
+        if remaining >= expected_rent(env, agreement_id, agreement) {
+            agreement.payment_history.set(record.period, split);
+            return;
+        }
+
+        agreement.payment_history.remove(record.period);
+        agreement.next_payment_due = env.ledger().timestamp();
+        if remaining > 0 {
+            env.storage().persistent().set(
+                &DataKey::PeriodContributions(agreement_id.clone()),
+                &PeriodContributions {
+                    period: record.period,
+                    amount_paid: remaining,
+                    landlord_amount: split.landlord_amount,
+                    platform_amount: split.platform_amount,
+                    contributions: period_contributions(
+                        env,
+                        agreement_id,
+                        agreement,
+                        record,
+                        amount,
+                    ),
+                },
+            );
+        }
+    }
+}
+
+/// What each payer still has paid towards `record`'s period: their payments
+/// in that period, less earlier refunds and the `amount` being refunded now.
+fn period_contributions(
+    env: &Env,
+    agreement_id: &String,
+    agreement: &RentAgreement,
+    record: &PaymentRecord,
+    amount: i128,
+) -> Map<Address, i128> {
+    let mut contributions: Map<Address, i128> = Map::new(env);
+    let mut add = |payer: Address, delta: i128| {
+        let total = contributions.get(payer.clone()).unwrap_or(0) + delta;
+        contributions.set(payer, total);
+    };
+
+    // Payment records are numbered in order, so the period's payments are
+    // the most recent ones.
+    for payment_number in (1..=agreement.payment_count).rev() {
+        let Some(paid) =
+            env.storage()
+                .persistent()
+                .get::<DataKey, PaymentRecord>(&DataKey::PaymentRecord(
+                    agreement_id.clone(),
+                    payment_number,
+                ))
+        else {
+            continue;
+        };
+        if paid.period < record.period {
+            break;
+        }
+        if paid.period == record.period {
+            add(paid.payer, paid.amount);
+        }
+    }
+
+    for refund in get_refunds(env, DataKey::AgreementRefunds(agreement_id.clone())).iter() {
+        let refunded_period = env
+            .storage()
+            .persistent()
+            .get::<DataKey, PaymentRecord>(&DataKey::Payment(refund.payment_id))
+            .map(|paid| paid.period);
+        if refunded_period == Some(record.period) {
+            add(refund.recipient, -refund.amount);
+        }
+    }
+    add(record.payer.clone(), -amount);
+
+    contributions
+}
@@ -1,5 +1,7 @@
 //! Storage key definitions for the Payment contract.
-use soroban_sdk::{contracttype, String};
+use soroban_sdk::{contracttype, Env, Map, String, Symbol, TryFromVal, Val};
+
+use crate::types::{PaymentRecord, PaymentRecordV1};
 
 /// Storage key variants for persistent storage.
 #[contracttype]
@@ -37,4 +39,35 @@ pub enum DataKey {
     RentEscalationConfig(String),
     /// Upgrade proposal
     UpgradeProposal(String),
+    /// Authorized co-payers per agreement
+    CoPayers(String),
+    /// Partial contributions towards the current rent period per agreement
+    PeriodContributions(String),
+    /// Continuous rent stream per agreement
+    RentStream(String),
+    /// Refund audit record by ID
+    Refund(String),
+    /// Counter for refunds
+    RefundCount,
+    /// Refund IDs issued against a payment
+    PaymentRefunds(String),
+    /// Amounts refunded from each share of a payment
+    RefundedAmounts(String),
+    /// Refund IDs issued against an agreement
+    AgreementRefunds(String),
+}
+
+/// Load a payment record stored under `key` (`Payment` or `PaymentRecord`).
+/// Records written before co-payers existed are upgraded to the current
+/// layout.
+pub fn get_payment_record(env: &Env, key: &DataKey) -> Option<PaymentRecord> {
+    let raw = env.storage().persistent().get::<_, Val>(key)?;
+    let fields = Map::<Symbol, Val>::try_from_val(env, &raw).ok()?;
+    if fields.contains_key(Symbol::new(env, "payer")) {
+        PaymentRecord::try_from_val(env, &raw).ok()
+    } else {
+        PaymentRecordV1::try_from_val(env, &raw)
+            .ok()
+            .map(PaymentRecord::from)
+    }
 }
@@ -0,0 +1,191 @@
+//! Continuous rent streaming.
+//!
+//! The tenant deposits a balance into the contract and rent accrues to the
+//! landlord linearly per second at `rent_per_period / period_seconds`. The
+//! landlord can withdraw whatever has accrued at any time; accrual is capped
+//! at the deposited balance, so a stream that runs dry simply stops paying
+//! until it is topped up. Each rent period the landlord has been paid in
+//! full is closed into the agreement's `payment_history`, just like a
+//! discrete payment.
+
+use soroban_sdk::{Address, Env, Map, String};
+
+use crate::events;
+use crate::payment_impl::{calculate_platform_split, store_payment_record};
+use crate::storage::DataKey;
+use crate::types::{
+    PaymentRecord, PaymentSplit, PeriodContributions, RentAgreement, RentStream, StreamStatus,
+};
+
+pub fn get_stream(env: &Env, agreement_id: &String) -> Option<RentStream> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RentStream(agreement_id.clone()))
+}
+
+pub fn save_stream(env: &Env, stream: &RentStream) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::RentStream(stream.agreement_id.clone()), stream);
+}
+
+/// Whether the stream still accrues rent (an insolvent stream keeps running
+/// and resumes paying once topped up).
+pub fn is_running(stream: &RentStream) -> bool {
+    stream.status != StreamStatus::Stopped
+}
+
+/// Rent owed on time alone, ignoring the deposited balance
+fn owed_at(stream: &RentStream, timestamp: u64) -> i128 {
+    let mut until = timestamp.min(stream.end_time);
+    if let Some(stopped_at) = stream.stopped_at {
+        until = until.min(stopped_at);
+    }
+    let elapsed = until.saturating_sub(stream.start_time) as i128;
+    stream.rent_per_period.saturating_mul(elapsed) / (stream.period_seconds as i128)
+}
+
+/// Total accrued to the landlord at `timestamp`, capped at the deposit
+pub fn accrued_at(stream: &RentStream, timestamp: u64) -> i128 {
+    let owed = owed_at(stream, timestamp);
+    if owed > stream.deposited {
+        stream.deposited
+    } else {
+        owed
+    }
+}
+
+/// Amount the landlord can withdraw right now
+pub fn withdrawable(env: &Env, stream: &RentStream) -> i128 {
+    accrued_at(stream, env.ledger().timestamp()) - stream.withdrawn
+}
+
+/// Time at which the deposited balance is fully accrued
+pub fn depletion_time(stream: &RentStream) -> u64 {
+    if stream.rent_per_period <= 0 {
+        return u64::MAX;
+    }
+    let funded_seconds = stream
+        .deposited
+        .saturating_mul(stream.period_seconds as i128)
+        / stream.rent_per_period;
+    stream
+        .start_time
+        .saturating_add(u64::try_from(funded_seconds).unwrap_or(u64::MAX))
+}
+
+/// End of the rent period containing `timestamp`, clamped to the stream end
+pub fn current_period_end(stream: &RentStream, timestamp: u64) -> u64 {
+    let elapsed = timestamp.saturating_sub(stream.start_time);
+    let periods = elapsed / stream.period_seconds + 1;
+    stream
+        .start_time
+        .saturating_add(periods.saturating_mul(stream.period_seconds))
+        .min(stream.end_time)
+}
+
+/// A stream is insolvent when its balance runs out before the current rent
+/// period ends.
+pub fn is_insolvent(env: &Env, stream: &RentStream) -> bool {
+    let now = env.ledger().timestamp();
+    depletion_time(stream) < current_period_end(stream, now)
+}
+
+/// Re-evaluate solvency, moving the stream between `Active` and `Insolvent`
+/// and emitting an event when it becomes insolvent. Returns true if solvent.
+pub fn refresh_status(env: &Env, stream: &mut RentStream) -> bool {
+    if !is_running(stream) {
+        return true;
+    }
+    let insolvent = is_insolvent(env, stream);
+    if insolvent && stream.status == StreamStatus::Active {
+        stream.status = StreamStatus::Insolvent;
+        events::rent_stream_insolvent(env, stream.agreement_id.clone(), depletion_time(stream));
+    } else if !insolvent && stream.status == StreamStatus::Insolvent {
+        stream.status = StreamStatus::Active;
+    }
+    !insolvent
+}
+
+/// Add streamed rent paid out to the landlord to the agreement's total and
+/// close every period it now covers in full, moving `next_payment_due` on
+/// and writing a payment record for each closed period.
+/// Once the stream has stopped, rent paid towards a final part-period is
+/// kept as the tenant's contribution, so `pay_rent` only asks for the rest.
+pub fn credit_agreement(env: &Env, stream: &mut RentStream, amount: i128) {
+    let key = DataKey::Agreement(stream.agreement_id.clone());
+    let Some(mut agreement) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, RentAgreement>(&key)
+    else {
+        return;
+    };
+    agreement.total_rent_paid += amount;
+
+    let now = env.ledger().timestamp();
+    let paid_periods = if stream.rent_per_period > 0 {
+        u32::try_from(stream.withdrawn / stream.rent_per_period).unwrap_or(u32::MAX)
+    } else {
+        0
+    };
+    let (landlord_amount, platform_amount) = calculate_platform_split(stream.rent_per_period);
+    while stream.periods_recorded < paid_periods {
+        let period = agreement.payment_history.len();
+        agreement.payment_history.set(
+            period,
+            PaymentSplit {
+                landlord_amount,
+                platform_amount,
+                token: stream.token.clone(),
+                payment_date: now,
+            },
+        );
+        agreement.payment_count += 1;
+        store_payment_record(
+            env,
+            &PaymentRecord {
+                agreement_id: agreement.agreement_id.clone(),
+                payment_number: agreement.payment_count,
+                amount: stream.rent_per_period,
+                landlord_amount,
+                agent_amount: 0,
+                timestamp: now,
+                tenant: agreement.tenant.clone(),
+                payer: stream.tenant.clone(),
+                period,
+            },
+        );
+        stream.periods_recorded += 1;
+        agreement.next_payment_due = stream.start_time.saturating_add(
+            stream
+                .period_seconds
+                .saturating_mul(stream.periods_recorded as u64),
+        );
+    }
+
+    let remainder = stream.withdrawn - (paid_periods as i128) * stream.rent_per_period;
+    if !is_running(stream) && remainder > 0 {
+        record_part_period(env, &agreement, &stream.tenant, remainder);
+    }
+
+    env.storage().persistent().set(&key, &agreement);
+}
+
+/// Record streamed rent towards the agreement's open period as the tenant's
+/// contribution.
+fn record_part_period(env: &Env, agreement: &RentAgreement, tenant: &Address, amount: i128) {
+    let (landlord_amount, platform_amount) = calculate_platform_split(amount);
+    let mut contributions = Map::new(env);
+    contributions.set(tenant.clone(), amount);
+    env.storage().persistent().set(
+        &DataKey::PeriodContributions(agreement.agreement_id.clone()),
+        &PeriodContributions {
+            period: agreement.payment_history.len(),
+            amount_paid: amount,
+            landlord_amount,
+            platform_amount,
+            contributions,
+        },
+    );
+}
@@ -0,0 +1,312 @@
+//! Tests for batch rent payments
+#![allow(unused_results)]
+
+use crate::errors::PaymentError;
+use crate::storage::DataKey;
+use crate::types::*;
+use crate::PaymentContract;
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient as TokenAdminClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, Map, String, Vec};
+
+fn create_test_agreement(
+    env: &Env,
+    id: &str,
+    tenant: &Address,
+    landlord: &Address,
+    monthly_rent: i128,
+    payment_token: Address,
+) -> RentAgreement {
+    RentAgreement {
+        agreement_id: String::from_str(env, id),
+        tenant: tenant.clone(),
+        landlord: landlord.clone(),
+        agent: None,
+        monthly_rent,
+        agent_commission_rate: 0,
+        status: AgreementStatus::Active,
+        total_rent_paid: 0,
+        payment_count: 0,
+        security_deposit: 0,
+        start_date: 0,
+        end_date: 0,
+        signed_at: None,
+        payment_token,
+        next_payment_due: 0,
+        payment_history: Map::new(env),
+    }
+}
+
+fn create_token(env: &Env, admin: &Address) -> Address {
+    env.register_stellar_asset_contract_v2(admin.clone())
+        .address()
+}
+
+fn seed_agreement(env: &Env, client: &crate::PaymentContractClient<'_>, agreement: &RentAgreement) {
+    let key = DataKey::Agreement(agreement.agreement_id.clone());
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&key, agreement);
+    });
+}
+
+struct Setup<'a> {
+    env: Env,
+    client: crate::PaymentContractClient<'a>,
+    tenant: Address,
+    collector: Address,
+    token: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PaymentContract, ());
+    let client = crate::PaymentContractClient::new(&env, &contract_id);
+    let tenant = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token(&env, &token_admin);
+
+    TokenAdminClient::new(&env, &token).mint(&tenant, &1_000_000);
+    client.set_platform_fee_collector(&collector);
+
+    Setup {
+        env,
+        client,
+        tenant,
+        collector,
+        token,
+    }
+}
+
+fn item(env: &Env, id: &str, amount: i128) -> (String, i128) {
+    (String::from_str(env, id), amount)
+}
+
+#[test]
+fn test_batch_pays_every_item_and_aggregates_transfers() {
+    let s = setup();
+    let env = &s.env;
+    let landlord_a = Address::generate(env);
+    let landlord_b = Address::generate(env);
+
+    seed_agreement(
+        env,
+        &s.client,
+        &create_test_agreement(env, "b_1", &s.tenant, &landlord_a, 1000, s.token.clone()),
+    );
+    seed_agreement(
+        env,
+        &s.client,
+        &create_test_agreement(env, "b_2", &s.tenant, &landlord_a, 2000, s.token.clone()),
+    );
+    seed_agreement(
+        env,
+        &s.client,
+        &create_test_agreement(env, "b_3", &s.tenant, &landlord_b, 500, s.token.clone()),
+    );
+
+    let payments = vec![
+        env,
+        item(env, "b_1", 1000),
+        item(env, "b_2", 2000),
+        item(env, "b_3", 500),
+    ];
+    let report = s.client.pay_rent_batch(&s.tenant, &payments);
+
+    assert!(report.executed);
+    assert_eq!(report.total_amount, 3500);
+    assert_eq!(report.items.len(), 3);
+    for outcome in report.items.iter() {
+        assert_eq!(outcome.status, BatchItemStatus::Paid);
+        assert!(outcome.payment_id.is_some());
+    }
+
+    let token = TokenClient::new(env, &s.token);
+    assert_eq!(token.balance(&landlord_a), 2700);
+    assert_eq!(token.balance(&landlord_b), 450);
+    assert_eq!(token.balance(&s.collector), 350);
+    assert_eq!(token.balance(&s.tenant), 1_000_000 - 3500);
+
+    assert_eq!(s.client.get_payment_count(), 3);
+    let second = report.items.get(1).unwrap();
+    let record = s.client.get_payment(&second.payment_id.unwrap());
+    assert_eq!(record.agreement_id, String::from_str(env, "b_2"));
+    assert_eq!(record.amount, 2000);
+    assert_eq!(record.tenant, s.tenant);
+
+    let split = s
+        .client
+        .get_payment_split(&String::from_str(env, "b_3"), &0);
+    assert_eq!(split.landlord_amount, 450);
+    assert_eq!(split.platform_amount, 50);
+}
+
+#[test]
+fn test_batch_is_all_or_nothing() {
+    let s = setup();
+    let env = &s.env;
+    let landlord = Address::generate(env);
+
+    seed_agreement(
+        env,
+        &s.client,
+        &create_test_agreement(env, "b_ok", &s.tenant, &landlord, 1000, s.token.clone()),
+    );
+    seed_agreement(
+        env,
+        &s.client,
+        &create_test_agreement(env, "b_bad", &s.tenant, &landlord, 1000, s.token.clone()),
+    );
+
+    let payments = vec![
+        env,
+        item(env, "b_ok", 1000),
+        item(env, "b_bad", 999),
+        item(env, "b_missing", 1000),
+    ];
+    let report = s.client.pay_rent_batch(&s.tenant, &payments);
+
+    assert!(!report.executed);
+    assert_eq!(report.total_amount, 0);
+    assert_eq!(
+        report.items.get(0).unwrap().status,
+        BatchItemStatus::Skipped
+    );
+    assert_eq!(
+        report.items.get(1).unwrap().status,
+        BatchItemStatus::Failed(PaymentError::InvalidPaymentAmount as u32)
+    );
+    assert_eq!(
+        report.items.get(2).unwrap().status,
+        BatchItemStatus::Failed(PaymentError::AgreementNotFound as u32)
+    );
+
+    let token = TokenClient::new(env, &s.token);
+    assert_eq!(token.balance(&landlord), 0);
+    assert_eq!(token.balance(&s.tenant), 1_000_000);
+    assert_eq!(s.client.get_payment_count(), 0);
+}
+
+#[test]
+fn test_batch_rejects_duplicate_agreement() {
+    let s = setup();
+    let env = &s.env;
+    let landlord = Address::generate(env);
+
+    seed_agreement(
+        env,
+        &s.client,
+        &create_test_agreement(env, "b_dup", &s.tenant, &landlord, 1000, s.token.clone()),
+    );
+
+    let payments = vec![env, item(env, "b_dup", 1000), item(env, "b_dup", 1000)];
+    let report = s.client.pay_rent_batch(&s.tenant, &payments);
+
+    assert!(!report.executed);
+    assert_eq!(
+        report.items.get(0).unwrap().status,
+        BatchItemStatus::Skipped
+    );
+    assert_eq!(
+        report.items.get(1).unwrap().status,
+        BatchItemStatus::Failed(PaymentError::DuplicateBatchItem as u32)
+    );
+}
+
+#[test]
+fn test_batch_rejects_non_tenant_item() {
+    let s = setup();
+    let env = &s.env;
+    let landlord = Address::generate(env);
+    let other_tenant = Address::generate(env);
+
+    seed_agreement(
+        env,
+        &s.client,
+        &create_test_agreement(
+            env,
+            "b_other",
+            &other_tenant,
+            &landlord,
+            1000,
+            s.token.clone(),
+        ),
+    );
+
+    let payments = vec![env, item(env, "b_other", 1000)];
+    let report = s.client.pay_rent_batch(&s.tenant, &payments);
+
+    assert!(!report.executed);
+    assert_eq!(
+        report.items.get(0).unwrap().status,
+        BatchItemStatus::Failed(PaymentError::NotAuthorizedPayer as u32)
+    );
+}
+
+#[test]
+fn test_batch_empty_and_oversized() {
+    let s = setup();
+    let env = &s.env;
+
+    let empty: Vec<(String, i128)> = Vec::new(env);
+    assert_eq!(
+        s.client.try_pay_rent_batch(&s.tenant, &empty),
+        Err(Ok(PaymentError::EmptyBatch))
+    );
+
+    let mut oversized: Vec<(String, i128)> = Vec::new(env);
+    for _ in 0..=crate::MAX_BATCH_SIZE {
+        oversized.push_back(item(env, "b_x", 1000));
+    }
+    assert_eq!(
+        s.client.try_pay_rent_batch(&s.tenant, &oversized),
+        Err(Ok(PaymentError::BatchTooLarge))
+    );
+}
+
+#[test]
+fn test_batch_charges_pay_rent_quota_per_item() {
+    let s = setup();
+    let env = &s.env;
+    let landlord = Address::generate(env);
+
+    env.as_contract(&s.client.address, || {
+        env.storage().persistent().set(
+            &DataKey::RateLimitConfig,
+            &RateLimitConfig {
+                max_calls_per_block: 10,
+                max_calls_per_user_per_day: 2,
+                cooldown_blocks: 0,
+            },
+        );
+    });
+    for id in ["b_q1", "b_q2", "b_q3"] {
+        seed_agreement(
+            env,
+            &s.client,
+            &create_test_agreement(env, id, &s.tenant, &landlord, 1000, s.token.clone()),
+        );
+    }
+
+    let payments = vec![
+        env,
+        item(env, "b_q1", 1000),
+        item(env, "b_q2", 1000),
+        item(env, "b_q3", 1000),
+    ];
+    assert_eq!(
+        s.client.try_pay_rent_batch(&s.tenant, &payments),
+        Err(Ok(PaymentError::RateLimitExceeded))
+    );
+
+    let payments = vec![env, item(env, "b_q1", 1000), item(env, "b_q2", 1000)];
+    assert!(s.client.pay_rent_batch(&s.tenant, &payments).executed);
+
+    // The batch used up the daily `pay_rent` quota
+    assert_eq!(
+        s.client
+            .try_pay_rent(&s.tenant, &String::from_str(env, "b_q3"), &1000),
+        Err(Ok(PaymentError::RateLimitExceeded))
+    );
+}
@@ -0,0 +1,359 @@
+//! Tests for third-party payers (guarantors, employers, subsidy programs)
+#![allow(unused_results)]
+
+use crate::errors::PaymentError;
+use crate::storage::DataKey;
+use crate::types::*;
+use crate::PaymentContract;
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient as TokenAdminClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Map, String,
+};
+
+const RENT: i128 = 1000;
+
+struct Setup<'a> {
+    env: Env,
+    client: crate::PaymentContractClient<'a>,
+    agreement_id: String,
+    tenant: Address,
+    landlord: Address,
+    collector: Address,
+    token: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PaymentContract, ());
+    let client = crate::PaymentContractClient::new(&env, &contract_id);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+
+    TokenAdminClient::new(&env, &token).mint(&tenant, &100_000);
+    client.set_platform_fee_collector(&collector);
+
+    let agreement_id = String::from_str(&env, "cp_001");
+    let agreement = RentAgreement {
+        agreement_id: agreement_id.clone(),
+        tenant: tenant.clone(),
+        landlord: landlord.clone(),
+        agent: None,
+        monthly_rent: RENT,
+        agent_commission_rate: 0,
+        status: AgreementStatus::Active,
+        total_rent_paid: 0,
+        payment_count: 0,
+        security_deposit: 0,
+        start_date: 0,
+        end_date: 0,
+        signed_at: None,
+        payment_token: token.clone(),
+        next_payment_due: 0,
+        payment_history: Map::new(&env),
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    });
+
+    Setup {
+        env,
+        client,
+        agreement_id,
+        tenant,
+        landlord,
+        collector,
+        token,
+    }
+}
+
+fn funded_payer(s: &Setup, amount: i128) -> Address {
+    let payer = Address::generate(&s.env);
+    TokenAdminClient::new(&s.env, &s.token).mint(&payer, &amount);
+    payer
+}
+
+#[test]
+fn test_add_and_remove_co_payer() {
+    let s = setup();
+    let employer = Address::generate(&s.env);
+
+    s.client.add_co_payer(
+        &s.agreement_id,
+        &employer,
+        &PayerRole::Employer,
+        &4000,
+        &None,
+        &0,
+    );
+
+    let co_payers = s.client.get_co_payers(&s.agreement_id);
+    assert_eq!(co_payers.len(), 1);
+    assert_eq!(co_payers.get(0).unwrap().share_bps, 4000);
+
+    let duplicate = s.client.try_add_co_payer(
+        &s.agreement_id,
+        &employer,
+        &PayerRole::Employer,
+        &1000,
+        &None,
+        &0,
+    );
+    assert_eq!(duplicate, Err(Ok(PaymentError::CoPayerAlreadyExists)));
+
+    s.client
+        .remove_co_payer(&employer, &s.agreement_id, &employer);
+    assert_eq!(s.client.get_co_payers(&s.agreement_id).len(), 0);
+}
+
+#[test]
+fn test_co_payer_shares_cannot_exceed_full_rent() {
+    let s = setup();
+    let first = Address::generate(&s.env);
+    let second = Address::generate(&s.env);
+
+    s.client.add_co_payer(
+        &s.agreement_id,
+        &first,
+        &PayerRole::SubsidyProgram,
+        &7000,
+        &None,
+        &0,
+    );
+    let result = s.client.try_add_co_payer(
+        &s.agreement_id,
+        &second,
+        &PayerRole::Employer,
+        &4000,
+        &None,
+        &0,
+    );
+    assert_eq!(result, Err(Ok(PaymentError::InvalidPayerShare)));
+}
+
+#[test]
+fn test_split_payment_between_subsidy_and_tenant() {
+    let s = setup();
+    let program = funded_payer(&s, 10_000);
+
+    s.client.add_co_payer(
+        &s.agreement_id,
+        &program,
+        &PayerRole::SubsidyProgram,
+        &7000,
+        &None,
+        &0,
+    );
+
+    s.client.pay_rent(&program, &s.agreement_id, &700);
+
+    let progress = s.client.get_period_contributions(&s.agreement_id);
+    assert_eq!(progress.amount_paid, 700);
+    assert_eq!(progress.contributions.get(program.clone()), Some(700));
+    assert!(s.client.try_get_payment_split(&s.agreement_id, &0).is_err());
+
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &300);
+
+    let split = s.client.get_payment_split(&s.agreement_id, &0);
+    assert_eq!(split.landlord_amount, 900);
+    assert_eq!(split.platform_amount, 100);
+
+    let first = s.client.get_payment(&String::from_str(&s.env, "0"));
+    assert_eq!(first.payer, program);
+    assert_eq!(first.tenant, s.tenant);
+    let second = s.client.get_payment(&String::from_str(&s.env, "1"));
+    assert_eq!(second.payer, s.tenant);
+
+    let token = TokenClient::new(&s.env, &s.token);
+    assert_eq!(token.balance(&s.landlord), 900);
+    assert_eq!(token.balance(&s.collector), 100);
+    assert_eq!(token.balance(&program), 10_000 - 700);
+
+    let progress = s.client.get_period_contributions(&s.agreement_id);
+    assert_eq!(progress.period, 1);
+    assert_eq!(progress.amount_paid, 0);
+}
+
+#[test]
+fn test_co_payer_limited_by_share_and_cap() {
+    let s = setup();
+    let employer = funded_payer(&s, 10_000);
+
+    s.client.add_co_payer(
+        &s.agreement_id,
+        &employer,
+        &PayerRole::Employer,
+        &5000,
+        &Some(300),
+        &0,
+    );
+
+    let over_cap = s.client.try_pay_rent(&employer, &s.agreement_id, &400);
+    assert_eq!(over_cap, Err(Ok(PaymentError::PayerCapExceeded)));
+
+    s.client.pay_rent(&employer, &s.agreement_id, &300);
+    let again = s.client.try_pay_rent(&employer, &s.agreement_id, &1);
+    assert_eq!(again, Err(Ok(PaymentError::PayerCapExceeded)));
+}
+
+#[test]
+fn test_unregistered_payer_rejected() {
+    let s = setup();
+    let stranger = funded_payer(&s, 10_000);
+
+    let result = s.client.try_pay_rent(&stranger, &s.agreement_id, &RENT);
+    assert_eq!(result, Err(Ok(PaymentError::NotAuthorizedPayer)));
+}
+
+#[test]
+fn test_call_guarantor_after_arrears_threshold() {
+    let s = setup();
+    let guarantor = funded_payer(&s, 10_000);
+
+    s.client.add_co_payer(
+        &s.agreement_id,
+        &guarantor,
+        &PayerRole::Guarantor,
+        &10_000,
+        &None,
+        &1500,
+    );
+    TokenClient::new(&s.env, &s.token).approve(
+        &guarantor,
+        &s.client.address,
+        &10_000,
+        &(s.env.ledger().sequence() + 1000),
+    );
+
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &RENT);
+
+    // One missed period: arrears equal one month's rent, below threshold
+    s.env.ledger().with_mut(|li| {
+        li.timestamp = 2_592_000 + 10;
+    });
+    let early = s.client.try_call_guarantor(&s.agreement_id, &guarantor);
+    assert_eq!(early, Err(Ok(PaymentError::ArrearsBelowThreshold)));
+
+    // Two missed periods: arrears exceed the threshold
+    s.env.ledger().with_mut(|li| {
+        li.timestamp = 2 * 2_592_000 + 10;
+    });
+    let payment_id = s.client.call_guarantor(&s.agreement_id, &guarantor);
+
+    let record = s.client.get_payment(&payment_id);
+    assert_eq!(record.payer, guarantor);
+    assert_eq!(record.amount, RENT);
+
+    let token = TokenClient::new(&s.env, &s.token);
+    assert_eq!(token.balance(&guarantor), 10_000 - RENT);
+    assert_eq!(token.balance(&s.landlord), 1800);
+    assert!(s.client.try_get_payment_split(&s.agreement_id, &1).is_ok());
+}
+
+#[test]
+fn test_call_guarantor_rejects_non_guarantor() {
+    let s = setup();
+    let employer = Address::generate(&s.env);
+
+    s.client.add_co_payer(
+        &s.agreement_id,
+        &employer,
+        &PayerRole::Employer,
+        &2000,
+        &None,
+        &0,
+    );
+
+    let result = s.client.try_call_guarantor(&s.agreement_id, &employer);
+    assert_eq!(result, Err(Ok(PaymentError::NotGuarantor)));
+}
+
+#[test]
+fn test_call_guarantor_limited_by_share_and_cap() {
+    let s = setup();
+    let guarantor = funded_payer(&s, 10_000);
+
+    s.client.add_co_payer(
+        &s.agreement_id,
+        &guarantor,
+        &PayerRole::Guarantor,
+        &5000,
+        &Some(300),
+        &0,
+    );
+    TokenClient::new(&s.env, &s.token).approve(
+        &guarantor,
+        &s.client.address,
+        &10_000,
+        &(s.env.ledger().sequence() + 1000),
+    );
+
+    s.env.ledger().with_mut(|li| {
+        li.timestamp = 10;
+    });
+    let payment_id = s.client.call_guarantor(&s.agreement_id, &guarantor);
+    assert_eq!(s.client.get_payment(&payment_id).amount, 300);
+
+    // The cap is spent for this period
+    let again = s.client.try_call_guarantor(&s.agreement_id, &guarantor);
+    assert_eq!(again, Err(Ok(PaymentError::PayerCapExceeded)));
+}
+
+#[test]
+fn test_add_co_payer_requires_active_agreement() {
+    let s = setup();
+    s.env.as_contract(&s.client.address, || {
+        let key = DataKey::Agreement(s.agreement_id.clone());
+        let mut agreement: RentAgreement = s.env.storage().persistent().get(&key).unwrap();
+        agreement.status = AgreementStatus::Terminated;
+        s.env.storage().persistent().set(&key, &agreement);
+    });
+
+    let payer = Address::generate(&s.env);
+    let result = s.client.try_add_co_payer(
+        &s.agreement_id,
+        &payer,
+        &PayerRole::Employer,
+        &2000,
+        &None,
+        &0,
+    );
+    assert_eq!(result, Err(Ok(PaymentError::AgreementNotActive)));
+}
+
+#[test]
+fn test_payment_recorded_before_co_payers_is_upgraded() {
+    let s = setup();
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    let payment_id = String::from_str(&s.env, "0");
+
+    let legacy = PaymentRecordV1 {
+        agreement_id: s.agreement_id.clone(),
+        payment_number: 1,
+        amount: RENT,
+        landlord_amount: 900,
+        agent_amount: 0,
+        timestamp: 100,
+        tenant: s.tenant.clone(),
+    };
+    s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Payment(payment_id.clone()), &legacy);
+    });
+
+    let record = s.client.get_payment(&payment_id);
+    assert_eq!(record.payer, s.tenant);
+    assert_eq!(record.amount, RENT);
+    assert_eq!(s.client.get_total_paid(&s.agreement_id), RENT);
+}
@@ -0,0 +1,370 @@
+//! Tests for refunds and payment corrections
+#![allow(unused_results)]
+
+use crate::errors::PaymentError;
+use crate::storage::DataKey;
+use crate::types::*;
+use crate::PaymentContract;
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient as TokenAdminClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Map, String,
+};
+
+const RENT: i128 = 1000;
+
+struct Setup<'a> {
+    env: Env,
+    client: crate::PaymentContractClient<'a>,
+    agreement_id: String,
+    tenant: Address,
+    landlord: Address,
+    collector: Address,
+    token: TokenClient<'a>,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PaymentContract, ());
+    let client = crate::PaymentContractClient::new(&env, &contract_id);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+
+    TokenAdminClient::new(&env, &token).mint(&tenant, &100_000);
+    client.set_platform_fee_collector(&collector);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+
+    let agreement_id = String::from_str(&env, "refund_001");
+    let agreement = RentAgreement {
+        agreement_id: agreement_id.clone(),
+        tenant: tenant.clone(),
+        landlord: landlord.clone(),
+        agent: None,
+        monthly_rent: RENT,
+        agent_commission_rate: 0,
+        status: AgreementStatus::Active,
+        total_rent_paid: 0,
+        payment_count: 0,
+        security_deposit: 0,
+        start_date: 0,
+        end_date: 0,
+        signed_at: None,
+        payment_token: token.clone(),
+        next_payment_due: 0,
+        payment_history: Map::new(&env),
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    });
+
+    let token = TokenClient::new(&env, &token);
+    Setup {
+        env,
+        client,
+        agreement_id,
+        tenant,
+        landlord,
+        collector,
+        token,
+    }
+}
+
+fn get_agreement(s: &Setup) -> RentAgreement {
+    s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .get(&DataKey::Agreement(s.agreement_id.clone()))
+            .unwrap()
+    })
+}
+
+fn note(env: &Env) -> String {
+    String::from_str(env, "correction")
+}
+
+#[test]
+fn test_landlord_partial_refund_adjusts_totals() {
+    let s = setup();
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    let payment_id = String::from_str(&s.env, "0");
+
+    let refund_id = s.client.refund_payment(
+        &s.landlord,
+        &payment_id,
+        &200,
+        &RefundReason::Overcharge,
+        &note(&s.env),
+    );
+
+    let refund = s.client.get_refund(&refund_id);
+    assert_eq!(refund.party, RefundParty::Landlord);
+    assert_eq!(refund.recipient, s.tenant);
+    assert_eq!(refund.amount, 200);
+
+    assert_eq!(s.token.balance(&s.landlord), 700);
+    assert_eq!(s.token.balance(&s.tenant), 100_000 - RENT + 200);
+
+    let agreement = get_agreement(&s);
+    assert_eq!(agreement.total_rent_paid, 800);
+    // The period is now short of the rent due, so it is reopened
+    assert_eq!(agreement.payment_history.len(), 0);
+    assert_eq!(agreement.next_payment_due, s.env.ledger().timestamp());
+    let progress = s.client.get_period_contributions(&s.agreement_id);
+    assert_eq!(progress.period, 0);
+    assert_eq!(progress.amount_paid, 800);
+    assert_eq!(progress.landlord_amount, 700);
+    assert_eq!(progress.platform_amount, 100);
+}
+
+#[test]
+fn test_refund_limited_to_own_share() {
+    let s = setup();
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    let payment_id = String::from_str(&s.env, "0");
+
+    let too_much = s.client.try_refund_payment(
+        &s.collector,
+        &payment_id,
+        &101,
+        &RefundReason::Mistaken,
+        &note(&s.env),
+    );
+    assert_eq!(too_much, Err(Ok(PaymentError::RefundExceedsPayment)));
+
+    s.client.refund_payment(
+        &s.collector,
+        &payment_id,
+        &100,
+        &RefundReason::Mistaken,
+        &note(&s.env),
+    );
+    let again = s.client.try_refund_payment(
+        &s.collector,
+        &payment_id,
+        &1,
+        &RefundReason::Mistaken,
+        &note(&s.env),
+    );
+    assert_eq!(again, Err(Ok(PaymentError::RefundExceedsPayment)));
+
+    let stranger = Address::generate(&s.env);
+    let unauthorized = s.client.try_refund_payment(
+        &stranger,
+        &payment_id,
+        &1,
+        &RefundReason::Other,
+        &note(&s.env),
+    );
+    assert_eq!(unauthorized, Err(Ok(PaymentError::NotAuthorizedRefunder)));
+}
+
+#[test]
+fn test_full_reversal_reopens_latest_period() {
+    let s = setup();
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    let payment_id = String::from_str(&s.env, "0");
+
+    s.client.refund_payment(
+        &s.landlord,
+        &payment_id,
+        &900,
+        &RefundReason::DuplicateCharge,
+        &note(&s.env),
+    );
+    s.client.refund_payment(
+        &s.collector,
+        &payment_id,
+        &100,
+        &RefundReason::DuplicateCharge,
+        &note(&s.env),
+    );
+
+    let agreement = get_agreement(&s);
+    assert_eq!(agreement.total_rent_paid, 0);
+    assert_eq!(agreement.payment_history.len(), 0);
+    assert_eq!(agreement.next_payment_due, s.env.ledger().timestamp());
+    assert_eq!(s.token.balance(&s.tenant), 100_000);
+
+    // The reopened period can be paid again
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    assert_eq!(get_agreement(&s).payment_history.len(), 1);
+
+    let trail = s.client.get_payment_refunds(&payment_id);
+    assert_eq!(trail.len(), 2);
+    assert_eq!(trail.get(0).unwrap().party, RefundParty::Landlord);
+    assert_eq!(trail.get(1).unwrap().party, RefundParty::Platform);
+    assert_eq!(s.client.get_agreement_refunds(&s.agreement_id).len(), 2);
+
+    let refunded = s.client.get_refunded_amounts(&payment_id);
+    assert_eq!(refunded.landlord_amount, 900);
+    assert_eq!(refunded.platform_amount, 100);
+}
+
+#[test]
+fn test_refund_of_open_period_contribution() {
+    let s = setup();
+    let employer = Address::generate(&s.env);
+    TokenAdminClient::new(&s.env, &s.token.address).mint(&employer, &10_000);
+    s.client.add_co_payer(
+        &s.agreement_id,
+        &employer,
+        &PayerRole::Employer,
+        &5000,
+        &None,
+        &0,
+    );
+
+    s.client.pay_rent(&employer, &s.agreement_id, &500);
+    s.client.refund_payment(
+        &s.landlord,
+        &String::from_str(&s.env, "0"),
+        &450,
+        &RefundReason::Mistaken,
+        &note(&s.env),
+    );
+
+    let progress = s.client.get_period_contributions(&s.agreement_id);
+    assert_eq!(progress.amount_paid, 50);
+    assert_eq!(progress.contributions.get(employer.clone()), Some(50));
+    assert_eq!(s.token.balance(&employer), 10_000 - 50);
+}
+
+#[test]
+fn test_refund_unknown_payment() {
+    let s = setup();
+    let result = s.client.try_refund_payment(
+        &s.landlord,
+        &String::from_str(&s.env, "42"),
+        &1,
+        &RefundReason::Other,
+        &note(&s.env),
+    );
+    assert_eq!(result, Err(Ok(PaymentError::PaymentNotFound)));
+    assert_eq!(
+        s.client.try_get_refund(&String::from_str(&s.env, "0")),
+        Err(Ok(PaymentError::RefundNotFound))
+    );
+}
+
+#[test]
+fn test_refund_of_payment_recorded_before_periods() {
+    let s = setup();
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    let payment_id = String::from_str(&s.env, "0");
+
+    let legacy = PaymentRecordV1 {
+        agreement_id: s.agreement_id.clone(),
+        payment_number: 1,
+        amount: RENT,
+        landlord_amount: 900,
+        agent_amount: 0,
+        timestamp: 100,
+        tenant: s.tenant.clone(),
+    };
+    s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Payment(payment_id.clone()), &legacy);
+    });
+
+    let record = s.client.get_payment(&payment_id);
+    assert_eq!(record.period, 0);
+
+    s.client.refund_payment(
+        &s.landlord,
+        &payment_id,
+        &900,
+        &RefundReason::DuplicateCharge,
+        &note(&s.env),
+    );
+    assert_eq!(s.token.balance(&s.tenant), 100_000 - 100);
+    assert_eq!(get_agreement(&s).total_rent_paid, 100);
+}
+
+#[test]
+fn test_partial_refund_reopens_period_for_the_shortfall() {
+    let s = setup();
+    let employer = Address::generate(&s.env);
+    TokenAdminClient::new(&s.env, &s.token.address).mint(&employer, &10_000);
+    s.client.add_co_payer(
+        &s.agreement_id,
+        &employer,
+        &PayerRole::Employer,
+        &5000,
+        &None,
+        &0,
+    );
+
+    s.client.pay_rent(&employer, &s.agreement_id, &500);
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &500);
+    assert_eq!(get_agreement(&s).payment_history.len(), 1);
+
+    // Refund part of the employer's share of the closed period
+    s.client.refund_payment(
+        &s.landlord,
+        &String::from_str(&s.env, "0"),
+        &300,
+        &RefundReason::Mistaken,
+        &note(&s.env),
+    );
+
+    let progress = s.client.get_period_contributions(&s.agreement_id);
+    assert_eq!(progress.amount_paid, 700);
+    assert_eq!(progress.contributions.get(employer.clone()), Some(200));
+    assert_eq!(progress.contributions.get(s.tenant.clone()), Some(500));
+
+    // Only the shortfall is due again
+    let over_share = s.client.try_pay_rent(&employer, &s.agreement_id, &301);
+    assert_eq!(over_share, Err(Ok(PaymentError::InvalidPaymentAmount)));
+    s.client.pay_rent(&employer, &s.agreement_id, &300);
+
+    let agreement = get_agreement(&s);
+    assert_eq!(agreement.payment_history.len(), 1);
+    assert_eq!(agreement.total_rent_paid, RENT);
+}
+
+#[test]
+fn test_refund_of_older_period_rejected() {
+    let s = setup();
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    s.env.ledger().with_mut(|li| {
+        li.timestamp += 2_592_000;
+    });
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    assert_eq!(get_agreement(&s).payment_history.len(), 2);
+
+    let older = s.client.try_refund_payment(
+        &s.landlord,
+        &String::from_str(&s.env, "0"),
+        &200,
+        &RefundReason::Overcharge,
+        &note(&s.env),
+    );
+    assert_eq!(older, Err(Ok(PaymentError::RefundPeriodClosed)));
+    assert_eq!(s.token.balance(&s.tenant), 100_000 - 2 * RENT);
+
+    // The latest period can still be corrected, and is reopened
+    s.client.refund_payment(
+        &s.landlord,
+        &String::from_str(&s.env, "1"),
+        &200,
+        &RefundReason::Overcharge,
+        &note(&s.env),
+    );
+    let agreement = get_agreement(&s);
+    assert_eq!(agreement.payment_history.len(), 1);
+    assert_eq!(agreement.total_rent_paid, 2 * RENT - 200);
+    assert_eq!(s.client.get_period_contributions(&s.agreement_id).period, 1);
+}
@@ -0,0 +1,261 @@
+//! Tests for continuous rent streaming
+#![allow(unused_results)]
+
+use crate::errors::PaymentError;
+use crate::storage::DataKey;
+use crate::types::*;
+use crate::PaymentContract;
+use soroban_sdk::token::{Client as TokenClient, StellarAssetClient as TokenAdminClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Map, String,
+};
+
+/// One token unit per second over a 30-day period
+const RENT: i128 = 2_592_000;
+
+struct Setup<'a> {
+    env: Env,
+    client: crate::PaymentContractClient<'a>,
+    agreement_id: String,
+    tenant: Address,
+    landlord: Address,
+    collector: Address,
+    token: TokenClient<'a>,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PaymentContract, ());
+    let client = crate::PaymentContractClient::new(&env, &contract_id);
+    let tenant = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+
+    TokenAdminClient::new(&env, &token).mint(&tenant, &(10 * RENT));
+    client.set_platform_fee_collector(&collector);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    let agreement_id = String::from_str(&env, "stream_001");
+    let agreement = RentAgreement {
+        agreement_id: agreement_id.clone(),
+        tenant: tenant.clone(),
+        landlord: landlord.clone(),
+        agent: None,
+        monthly_rent: RENT,
+        agent_commission_rate: 0,
+        status: AgreementStatus::Active,
+        total_rent_paid: 0,
+        payment_count: 0,
+        security_deposit: 0,
+        start_date: 0,
+        end_date: 1_000 + 12 * 2_592_000,
+        signed_at: None,
+        payment_token: token.clone(),
+        next_payment_due: 0,
+        payment_history: Map::new(&env),
+    };
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Agreement(agreement_id.clone()), &agreement);
+    });
+
+    let token = TokenClient::new(&env, &token);
+    Setup {
+        env,
+        client,
+        agreement_id,
+        tenant,
+        landlord,
+        collector,
+        token,
+    }
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+fn get_agreement(s: &Setup) -> RentAgreement {
+    s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .get(&DataKey::Agreement(s.agreement_id.clone()))
+            .unwrap()
+    })
+}
+
+#[test]
+fn test_stream_accrues_linearly_and_landlord_withdraws() {
+    let s = setup();
+
+    s.client.start_rent_stream(&s.agreement_id, &RENT);
+    assert_eq!(s.token.balance(&s.client.address), RENT);
+
+    advance(&s.env, 1_000);
+    assert_eq!(s.client.get_stream_withdrawable(&s.agreement_id), 1_000);
+
+    let withdrawn = s.client.withdraw_rent_stream(&s.agreement_id);
+    assert_eq!(withdrawn, 1_000);
+    assert_eq!(s.token.balance(&s.landlord), 900);
+    assert_eq!(s.token.balance(&s.collector), 100);
+    assert_eq!(s.client.get_stream_withdrawable(&s.agreement_id), 0);
+
+    let result = s.client.try_withdraw_rent_stream(&s.agreement_id);
+    assert_eq!(result, Err(Ok(PaymentError::NothingToWithdraw)));
+}
+
+#[test]
+fn test_stop_stream_settles_and_refunds() {
+    let s = setup();
+
+    s.client.start_rent_stream(&s.agreement_id, &RENT);
+    advance(&s.env, 10_000);
+    s.client.withdraw_rent_stream(&s.agreement_id);
+    advance(&s.env, 5_000);
+
+    s.client.stop_rent_stream(&s.agreement_id);
+
+    let stream = s.client.get_rent_stream(&s.agreement_id);
+    assert_eq!(stream.status, StreamStatus::Stopped);
+    assert_eq!(stream.withdrawn, 15_000);
+    assert_eq!(s.token.balance(&s.landlord), 13_500);
+    assert_eq!(s.token.balance(&s.tenant), 10 * RENT - 15_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    advance(&s.env, 5_000);
+    assert_eq!(s.client.get_stream_withdrawable(&s.agreement_id), 0);
+    let result = s.client.try_top_up_rent_stream(&s.agreement_id, &100);
+    assert_eq!(result, Err(Ok(PaymentError::StreamNotActive)));
+}
+
+#[test]
+fn test_accrual_capped_at_deposit_and_insolvency_detected() {
+    let s = setup();
+
+    // Half a period funded: the balance runs out before the period ends
+    s.client.start_rent_stream(&s.agreement_id, &(RENT / 2));
+    assert_eq!(
+        s.client.get_rent_stream(&s.agreement_id).status,
+        StreamStatus::Insolvent
+    );
+    assert!(!s.client.check_stream_solvency(&s.agreement_id));
+
+    advance(&s.env, 2_000_000);
+    assert_eq!(s.client.get_stream_withdrawable(&s.agreement_id), RENT / 2);
+
+    // Topping up covers the period again and back-pays the landlord
+    s.client.top_up_rent_stream(&s.agreement_id, &RENT);
+    assert_eq!(
+        s.client.get_rent_stream(&s.agreement_id).status,
+        StreamStatus::Active
+    );
+    assert_eq!(s.client.get_stream_withdrawable(&s.agreement_id), 2_000_000);
+    assert!(s.client.check_stream_solvency(&s.agreement_id));
+}
+
+#[test]
+fn test_solvency_check_flags_next_period_shortfall() {
+    let s = setup();
+
+    s.client
+        .start_rent_stream(&s.agreement_id, &(RENT + RENT / 2));
+    assert!(s.client.check_stream_solvency(&s.agreement_id));
+
+    advance(&s.env, 2_592_000 + 1);
+    assert!(!s.client.check_stream_solvency(&s.agreement_id));
+    assert_eq!(
+        s.client.get_rent_stream(&s.agreement_id).status,
+        StreamStatus::Insolvent
+    );
+}
+
+#[test]
+fn test_pay_rent_blocked_while_streaming() {
+    let s = setup();
+
+    s.client.start_rent_stream(&s.agreement_id, &RENT);
+    let result = s.client.try_pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    assert_eq!(result, Err(Ok(PaymentError::StreamAlreadyActive)));
+
+    let again = s.client.try_start_rent_stream(&s.agreement_id, &RENT);
+    assert_eq!(again, Err(Ok(PaymentError::StreamAlreadyActive)));
+
+    s.client.stop_rent_stream(&s.agreement_id);
+    assert!(s
+        .client
+        .try_pay_rent(&s.tenant, &s.agreement_id, &RENT)
+        .is_ok());
+}
+
+#[test]
+fn test_streamed_periods_advance_payment_schedule() {
+    let s = setup();
+
+    s.client.start_rent_stream(&s.agreement_id, &(3 * RENT));
+    advance(&s.env, 2_592_000 / 2);
+    s.client.withdraw_rent_stream(&s.agreement_id);
+    assert_eq!(get_agreement(&s).payment_history.len(), 0);
+
+    advance(&s.env, 2_592_000 + 2_592_000 / 2);
+    s.client.withdraw_rent_stream(&s.agreement_id);
+
+    let agreement = get_agreement(&s);
+    assert_eq!(agreement.payment_history.len(), 2);
+    assert_eq!(agreement.next_payment_due, 1_000 + 2 * 2_592_000);
+    assert_eq!(agreement.total_rent_paid, 2 * RENT);
+    let split = agreement.payment_history.get(1).unwrap();
+    assert_eq!(split.landlord_amount + split.platform_amount, RENT);
+    assert_eq!(
+        s.client.get_rent_stream(&s.agreement_id).periods_recorded,
+        2
+    );
+
+    assert_eq!(s.client.get_payment_count(), 2);
+    assert_eq!(agreement.payment_count, 2);
+    let record = s.client.get_payment(&String::from_str(&s.env, "1"));
+    assert_eq!(record.amount, RENT);
+    assert_eq!(record.payer, s.tenant);
+    assert_eq!(record.period, 1);
+    assert_eq!(record.payment_number, 2);
+}
+
+#[test]
+fn test_stream_rejected_for_paid_period() {
+    let s = setup();
+
+    s.client.pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    let result = s.client.try_start_rent_stream(&s.agreement_id, &RENT);
+    assert_eq!(result, Err(Ok(PaymentError::PaymentNotDue)));
+
+    advance(&s.env, 2_592_000);
+    s.client.start_rent_stream(&s.agreement_id, &RENT);
+}
+
+#[test]
+fn test_stopped_stream_records_part_period() {
+    let s = setup();
+
+    s.client.start_rent_stream(&s.agreement_id, &RENT);
+    advance(&s.env, 2_592_000 / 4);
+    s.client.stop_rent_stream(&s.agreement_id);
+
+    // The quarter already streamed counts towards the open period
+    let result = s.client.try_pay_rent(&s.tenant, &s.agreement_id, &RENT);
+    assert_eq!(result, Err(Ok(PaymentError::InvalidPaymentAmount)));
+    s.client
+        .pay_rent(&s.tenant, &s.agreement_id, &(RENT - RENT / 4));
+
+    let agreement = get_agreement(&s);
+    assert_eq!(agreement.payment_history.len(), 1);
+    assert_eq!(agreement.total_rent_paid, RENT);
+}
@@ -1,5 +1,5 @@
 //! Data structures for the Payment contract.
-use soroban_sdk::{contracttype, Address, Map, String};
+use soroban_sdk::{contracttype, Address, Map, String, Vec};
 
 /// Escalation type for programmable rent increases
 #[contracttype]
@@ -63,6 +63,42 @@ pub struct PaymentRecord {
     pub agent_amount: i128,
     pub timestamp: u64,
     pub tenant: Address,
+    /// Address that actually paid (tenant or an authorized co-payer)
+    pub payer: Address,
+    /// Index of the rent period in `payment_history` this payment counts towards
+    pub period: u32,
+}
+
+/// Payment record layout stored before co-payers and period tracking were
+/// added. Only read back
+/// from storage, and upgraded to `PaymentRecord` on load.
+#[contracttype(export = false)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentRecordV1 {
+    pub agreement_id: String,
+    pub payment_number: u32,
+    pub amount: i128,
+    pub landlord_amount: i128,
+    pub agent_amount: i128,
+    pub timestamp: u64,
+    pub tenant: Address,
+}
+
+impl From<PaymentRecordV1> for PaymentRecord {
+    /// Only the tenant could pay, and every payment closed its own period.
+    fn from(old: PaymentRecordV1) -> Self {
+        PaymentRecord {
+            period: old.payment_number.saturating_sub(1),
+            payer: old.tenant.clone(),
+            agreement_id: old.agreement_id,
+            payment_number: old.payment_number,
+            amount: old.amount,
+            landlord_amount: old.landlord_amount,
+            agent_amount: old.agent_amount,
+            timestamp: old.timestamp,
+            tenant: old.tenant,
+        }
+    }
 }
 
 /// Payment split information for rent payments
@@ -75,6 +111,158 @@ pub struct PaymentSplit {
     pub payment_date: u64,
 }
 
+/// Role of a third party authorized to pay rent on a tenant's behalf
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayerRole {
+    /// Backs the tenant and may be charged once arrears exceed a threshold
+    Guarantor,
+    /// Employer covering part of the rent
+    Employer,
+    /// Housing-voucher or subsidy program
+    SubsidyProgram,
+}
+
+/// A third party authorized to contribute to an agreement's rent
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoPayer {
+    pub payer: Address,
+    pub role: PayerRole,
+    /// Share of each period's rent this payer covers, in basis points
+    pub share_bps: u32,
+    /// Optional maximum this payer contributes in a single period
+    pub cap_per_period: Option<i128>,
+    /// Arrears above which a guarantor may be called (guarantors only)
+    pub call_threshold: i128,
+    pub added_at: u64,
+}
+
+/// Contributions towards the rent period currently being paid
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeriodContributions {
+    /// Index into `RentAgreement.payment_history` this period will occupy
+    pub period: u32,
+    pub amount_paid: i128,
+    pub landlord_amount: i128,
+    pub platform_amount: i128,
+    pub contributions: Map<Address, i128>,
+}
+
+/// Lifecycle of a continuous rent stream
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StreamStatus {
+    Active,
+    /// The deposited balance runs out before the current rent period ends
+    Insolvent,
+    /// Stopped by the tenant and settled
+    Stopped,
+}
+
+/// Rent that accrues linearly per second from a tenant-funded balance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RentStream {
+    pub agreement_id: String,
+    pub tenant: Address,
+    pub landlord: Address,
+    pub token: Address,
+    /// Rent accrued over one full `period_seconds`
+    pub rent_per_period: i128,
+    pub period_seconds: u64,
+    pub start_time: u64,
+    /// Accrual stops at this time even if the stream is not stopped
+    pub end_time: u64,
+    pub deposited: i128,
+    pub withdrawn: i128,
+    pub status: StreamStatus,
+    pub stopped_at: Option<u64>,
+    /// Full rent periods already closed into the agreement's `payment_history`
+    pub periods_recorded: u32,
+}
+
+/// Which share of a payment a refund is drawn from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RefundParty {
+    Landlord,
+    Platform,
+}
+
+/// Why a payment is being refunded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RefundReason {
+    /// Payment made in error
+    Mistaken,
+    /// Amount charged above what was owed
+    Overcharge,
+    /// Same rent charged twice
+    DuplicateCharge,
+    Other,
+}
+
+/// Immutable audit entry for a refund issued against a `PaymentRecord`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundRecord {
+    pub refund_id: String,
+    pub payment_id: String,
+    pub agreement_id: String,
+    pub party: RefundParty,
+    pub issuer: Address,
+    /// Recipient of the refund (the original payer)
+    pub recipient: Address,
+    pub amount: i128,
+    pub reason: RefundReason,
+    pub note: String,
+    pub timestamp: u64,
+}
+
+/// Running totals refunded from each share of a payment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundedAmounts {
+    pub landlord_amount: i128,
+    pub platform_amount: i128,
+}
+
+/// Outcome of a single item in a batch rent payment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BatchItemStatus {
+    /// Item was validated and paid
+    Paid,
+    /// Item passed validation but was not paid because another item failed
+    Skipped,
+    /// Item failed validation with the given `PaymentError` code
+    Failed(u32),
+}
+
+/// Per-item entry of a `BatchPaymentReport`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchItemOutcome {
+    pub agreement_id: String,
+    pub amount: i128,
+    pub status: BatchItemStatus,
+    /// ID of the `PaymentRecord` written for this item, if it was paid
+    pub payment_id: Option<String>,
+}
+
+/// Result of `pay_rent_batch`. The batch is all-or-nothing: `executed` is
+/// false and no funds move unless every item validates.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchPaymentReport {
+    pub payer: Address,
+    pub executed: bool,
+    pub total_amount: i128,
+    pub items: Vec<BatchItemOutcome>,
+}
+
 /// Agreement status enum (needed for payment validation)
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -138,6 +138,14 @@ impl UserProfileContract {
         env.storage().persistent().has(&key)
     }
 
+    /// Check if an account has a verified profile
+    /// Public read access; false when no profile exists
+    pub fn is_verified(env: Env, account_id: Address) -> bool {
+        Self::get_profile(env, account_id)
+            .map(|profile| profile.is_verified)
+            .unwrap_or(false)
+    }
+
     /// Verify a user profile (admin only)
     /// Sets is_verified flag to true
     pub fn verify_profile(
@@ -503,6 +511,44 @@ mod test {
         assert!(!unverified.is_verified);
     }
 
+    #[test]
+    fn test_is_verified() {
+        let env = Env::default();
+        let contract_id = env.register(UserProfileContract, ());
+        let client = UserProfileContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+        let data_hash = Bytes::from_array(&env, &[0u8; 32]);
+
+        env.mock_all_auths();
+
+        client.initialize(&admin);
+        client.create_profile(&user, &AccountType::Tenant, &data_hash);
+        assert!(!client.is_verified(&user));
+
+        client.verify_profile(&admin, &user);
+        assert!(client.is_verified(&user));
+
+        client.unverify_profile(&admin, &user);
+        assert!(!client.is_verified(&user));
+    }
+
+    #[test]
+    fn test_is_verified_without_profile() {
+        let env = Env::default();
+        let contract_id = env.register(UserProfileContract, ());
+        let client = UserProfileContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+
+        env.mock_all_auths();
+
+        client.initialize(&admin);
+
+        assert!(!client.is_verified(&Address::generate(&env)));
+    }
+
     #[test]
     fn test_delete_profile() {
         let env = Env::default();